@@ -0,0 +1,268 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+};
+
+use bevy::prelude::*;
+use bevy_replicon::bincode;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::renet::transport::NetcodeServerTransport;
+
+/// Prefix of probe packets.
+const PROBE_MAGIC: [u8; 4] = *b"RPLP";
+
+/// Prefix of response packets.
+const RESPONSE_MAGIC: [u8; 4] = *b"RPLR";
+
+/// Maximum size of a discovery packet.
+pub(crate) const MAX_PACKET_SIZE: usize = 1200;
+
+/**
+Discovers servers in the local network.
+
+On server insert [`LanResponder`] next to [`NetcodeServerTransport`] to answer probes
+with public addresses of the transport. On client insert [`LanDiscovery`] and call
+[`LanDiscovery::probe`] to broadcast a probe. Answers from servers with a matching protocol ID
+will be emitted as [`DiscoveredServer`] events.
+
+Not added by default.
+
+# Examples
+
+```no_run
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    lan_discovery::{DiscoveredServer, LanDiscovery, LanDiscoveryPlugin},
+    RepliconRenetPlugins,
+};
+
+const PROTOCOL_ID: u64 = 0;
+const DISCOVERY_PORT: u16 = 5001;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    RepliconRenetPlugins,
+    LanDiscoveryPlugin,
+));
+
+let discovery = LanDiscovery::new(DISCOVERY_PORT, PROTOCOL_ID).unwrap();
+discovery.probe().unwrap();
+app.insert_resource(discovery)
+    .add_systems(Update, list_servers);
+
+fn list_servers(mut discovered_events: EventReader<DiscoveredServer>) {
+    for event in discovered_events.read() {
+        info!("found server at {:?}", event.addresses);
+    }
+}
+```
+**/
+pub struct LanDiscoveryPlugin;
+
+impl Plugin for LanDiscoveryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DiscoveredServer>().add_systems(
+            PreUpdate,
+            (
+                Self::respond.run_if(
+                    resource_exists::<LanResponder>
+                        .and_then(resource_exists::<NetcodeServerTransport>),
+                ),
+                Self::receive_responses.run_if(resource_exists::<LanDiscovery>),
+            ),
+        );
+    }
+}
+
+impl LanDiscoveryPlugin {
+    fn respond(responder: Res<LanResponder>, transport: Res<NetcodeServerTransport>) {
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        while let Some((size, source)) = recv_from(&responder.socket, &mut buffer) {
+            let Some(protocol_id) = decode::<u64>(PROBE_MAGIC, &buffer[..size]) else {
+                debug!("ignoring invalid probe from {source}");
+                continue;
+            };
+            if protocol_id != responder.protocol_id {
+                debug!("ignoring probe from {source} with protocol ID {protocol_id}");
+                continue;
+            }
+
+            let response = (
+                responder.protocol_id,
+                transport.max_clients() as u32,
+                transport.connected_clients() as u32,
+                transport.addresses(),
+            );
+            let message = encode(RESPONSE_MAGIC, &response);
+            trace!("answering probe from {source}");
+            if let Err(e) = responder.socket.send_to(&message, source) {
+                debug!("unable to answer probe from {source}: {e}");
+            }
+        }
+    }
+
+    fn receive_responses(
+        discovery: Res<LanDiscovery>,
+        mut discovered_events: EventWriter<DiscoveredServer>,
+    ) {
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        while let Some((size, source)) = recv_from(&discovery.socket, &mut buffer) {
+            let Some((protocol_id, max_clients, connected_clients, mut addresses)) =
+                decode::<(u64, u32, u32, Vec<SocketAddr>)>(RESPONSE_MAGIC, &buffer[..size])
+            else {
+                debug!("ignoring invalid response from {source}");
+                continue;
+            };
+            if protocol_id != discovery.protocol_id {
+                debug!("ignoring response from {source} with protocol ID {protocol_id}");
+                continue;
+            }
+
+            // Servers that listen on all interfaces are reachable from the response source.
+            for addr in &mut addresses {
+                if addr.ip().is_unspecified() {
+                    addr.set_ip(source.ip());
+                }
+            }
+
+            trace!("discovered server at {addresses:?}");
+            discovered_events.send(DiscoveredServer {
+                addresses,
+                max_clients: max_clients as usize,
+                connected_clients: connected_clients as usize,
+            });
+        }
+    }
+}
+
+/// Binds a non-blocking socket with enabled broadcast.
+pub(crate) fn bind_socket(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = UdpSocket::bind(addr)?;
+    socket.set_nonblocking(true)?;
+    socket.set_broadcast(true)?;
+
+    Ok(socket)
+}
+
+/// Serializes a packet using the default [`bincode`] encoding prefixed with the magic.
+pub(crate) fn encode<T: Serialize>(magic: [u8; 4], packet: &T) -> Vec<u8> {
+    bincode::serialize(&(magic, packet)).expect("packet should be serializable")
+}
+
+/// Deserializes a packet serialized with [`encode`].
+///
+/// Returns [`None`] if the datagram is invalid or has a different magic.
+pub(crate) fn decode<T: DeserializeOwned>(magic: [u8; 4], message: &[u8]) -> Option<T> {
+    let (received_magic, packet) = bincode::deserialize::<([u8; 4], T)>(message).ok()?;
+    if received_magic != magic {
+        return None;
+    }
+
+    Some(packet)
+}
+
+/// Reads a single datagram from a non-blocking socket.
+///
+/// Returns [`None`] if there are no more datagrams.
+pub(crate) fn recv_from(socket: &UdpSocket, buffer: &mut [u8]) -> Option<(usize, SocketAddr)> {
+    match socket.recv_from(buffer) {
+        Ok(received) => Some(received),
+        Err(e) if e.kind() == ErrorKind::WouldBlock => None,
+        Err(e) => {
+            debug!("unable to receive packet: {e}");
+            None
+        }
+    }
+}
+
+/// Answers LAN discovery probes on server.
+///
+/// See [`LanDiscoveryPlugin`] for details.
+#[derive(Resource)]
+pub struct LanResponder {
+    protocol_id: u64,
+    socket: UdpSocket,
+}
+
+impl LanResponder {
+    /// Creates a responder that listens for probes on the specified port on all interfaces.
+    ///
+    /// Protocol ID should match the ID used for the transport.
+    pub fn new(port: u16, protocol_id: u64) -> io::Result<Self> {
+        Self::bind(
+            SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port),
+            protocol_id,
+        )
+    }
+
+    /// Same as [`Self::new`], but listens on the specified address.
+    pub fn bind(addr: SocketAddr, protocol_id: u64) -> io::Result<Self> {
+        let socket = bind_socket(addr)?;
+
+        Ok(Self {
+            protocol_id,
+            socket,
+        })
+    }
+
+    /// Returns the address on which the responder listens.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+}
+
+/// Sends LAN discovery probes on client.
+///
+/// See [`LanDiscoveryPlugin`] for details.
+#[derive(Resource)]
+pub struct LanDiscovery {
+    /// Address to which probes are sent.
+    ///
+    /// Broadcast address by default.
+    pub probe_addr: SocketAddr,
+
+    protocol_id: u64,
+    socket: UdpSocket,
+}
+
+impl LanDiscovery {
+    /// Creates a discovery that sends probes to the broadcast address with the specified port.
+    ///
+    /// Protocol ID should match the ID used for the transport.
+    pub fn new(port: u16, protocol_id: u64) -> io::Result<Self> {
+        let socket = bind_socket(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0))?;
+
+        Ok(Self {
+            probe_addr: SocketAddr::new(Ipv4Addr::BROADCAST.into(), port),
+            protocol_id,
+            socket,
+        })
+    }
+
+    /// Sends a probe.
+    ///
+    /// All servers that receive it will answer with [`DiscoveredServer`].
+    pub fn probe(&self) -> io::Result<()> {
+        let message = encode(PROBE_MAGIC, &self.protocol_id);
+        self.socket.send_to(&message, self.probe_addr)?;
+
+        Ok(())
+    }
+}
+
+/// An event that indicates a server answered a probe from [`LanDiscovery`].
+#[derive(Clone, Debug, Event)]
+pub struct DiscoveredServer {
+    /// Public addresses of the server transport.
+    pub addresses: Vec<SocketAddr>,
+
+    /// Maximum number of clients.
+    pub max_clients: usize,
+
+    /// Number of connected clients at the moment of the response.
+    pub connected_clients: usize,
+}
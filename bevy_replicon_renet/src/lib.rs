@@ -56,8 +56,43 @@ let connection_config = ConnectionConfig {
 
 For a full example of how to initialize a server or client see the example in the
 repository.
+
+## LAN discovery
+
+If the `renet_transport` feature is enabled, [`lan_discovery::LanDiscoveryPlugin`] can be used to find
+servers in the local network.
+
+## Server browser
+
+If the `renet_transport` feature is enabled, [`server_browser::ServerBrowserPlugin`] can be used to publish
+server metadata to a master server or a LAN broadcast address and list joinable servers.
+
+## NAT punch-through
+
+For peer-hosted games behind NAT, [`nat_punch::NatPunchPlugin`] can be used to obtain a socket
+and a peer address for the transport using a user-hosted coordinator.
+
+## Multiple endpoints
+
+If the `renet_transport` feature is enabled, [`multi_endpoint::MultiEndpointServerPlugin`] can be used
+to listen on several sockets in a single server, for example IPv4 and IPv6.
+
+## Matchmaking
+
+If the `renet_transport` feature is enabled, [`matchmaking::MatchmakingPlugin`] can be used to verify
+tickets from matchmaking services passed inside the netcode user data.
 */
 
+#[cfg(feature = "renet_transport")]
+pub mod lan_discovery;
+#[cfg(feature = "renet_transport")]
+pub mod matchmaking;
+#[cfg(feature = "renet_transport")]
+pub mod multi_endpoint;
+pub mod nat_punch;
+#[cfg(feature = "renet_transport")]
+pub mod server_browser;
+
 pub use bevy_renet::renet;
 #[cfg(feature = "renet_transport")]
 pub use bevy_renet::transport;
@@ -86,6 +121,7 @@ impl Plugin for RepliconRenetServerPlugin {
                         Self::set_running.run_if(resource_added::<RenetServer>),
                         Self::set_stopped.run_if(resource_removed::<RenetServer>()),
                         Self::receive_packets.run_if(resource_exists::<RenetServer>),
+                        Self::disconnect_clients.run_if(resource_exists::<RenetServer>),
                     )
                         .chain()
                         .in_set(ServerSet::ReceivePackets),
@@ -151,6 +187,16 @@ impl RepliconRenetServerPlugin {
         }
     }
 
+    /// Applies disconnects requested before the messages sent on the previous frame.
+    fn disconnect_clients(
+        mut renet_server: ResMut<RenetServer>,
+        mut replicon_server: ResMut<RepliconServer>,
+    ) {
+        for client_id in replicon_server.drain_disconnects() {
+            renet_server.disconnect(renet::ClientId::from_raw(client_id.get()));
+        }
+    }
+
     fn send_packets(
         mut renet_server: ResMut<RenetServer>,
         mut replicon_server: ResMut<RepliconServer>,
@@ -0,0 +1,216 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_replicon::prelude::*;
+
+use crate::renet::{
+    self,
+    transport::{NetcodeServerTransport, NETCODE_USER_DATA_BYTES},
+    RenetServer,
+};
+
+/// Maximum size of a ticket that fits into netcode user data.
+pub const MAX_TICKET_LEN: usize = NETCODE_USER_DATA_BYTES - 2;
+
+/**
+Verifies matchmaking tickets passed by clients inside the netcode user data.
+
+Clients obtain a ticket or a session token from a matchmaking service and pass
+[`ticket_user_data`] as `user_data` of [`ClientAuthentication`](crate::renet::transport::ClientAuthentication)
+or the connect token. When a client connects, server calls [`TicketVerifier::verify`] with the ticket.
+On success the returned [`Allocation`] is stored in [`Allocations`] until the client disconnects.
+On failure the client is disconnected.
+
+Server emits [`TicketEvent`] after verification. Game logic should wait for [`TicketEvent::Verified`]
+instead of [`ServerEvent::ClientConnected`] to ensure that the client is allowed to join.
+
+Verification runs synchronously, so tickets should be verifiable locally, for example signed tokens.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    matchmaking::{Allocation, MatchmakingPlugin, TicketVerifier},
+    RepliconRenetPlugins,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    RepliconRenetPlugins,
+    MatchmakingPlugin,
+))
+.insert_resource(TicketVerifier {
+    verify: verify_ticket,
+});
+
+fn verify_ticket(client_id: ClientId, ticket: &[u8]) -> Result<Allocation, String> {
+    // Verify the ticket signature and extract the player info.
+    let player_id = String::from_utf8(ticket.to_vec()).map_err(|e| e.to_string())?;
+    Ok(Allocation {
+        player_id,
+        ..Default::default()
+    })
+}
+```
+**/
+pub struct MatchmakingPlugin;
+
+impl Plugin for MatchmakingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Allocations>()
+            .add_event::<TicketEvent>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::verify_tickets
+                        .after(ServerSet::Receive)
+                        .run_if(server_running)
+                        .run_if(resource_exists::<TicketVerifier>)
+                        .run_if(resource_exists::<NetcodeServerTransport>),
+                    Self::reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+impl MatchmakingPlugin {
+    fn verify_tickets(
+        mut server_events: EventReader<ServerEvent>,
+        mut ticket_events: EventWriter<TicketEvent>,
+        mut allocations: ResMut<Allocations>,
+        mut server: ResMut<RenetServer>,
+        transport: Res<NetcodeServerTransport>,
+        verifier: Res<TicketVerifier>,
+    ) {
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    let renet_client_id = renet::ClientId::from_raw(client_id.get());
+                    let result = transport
+                        .user_data(renet_client_id)
+                        .ok_or_else(|| "client has no user data".to_string())
+                        .and_then(|user_data| {
+                            ticket_from_user_data(&user_data)
+                                .ok_or_else(|| "user data contains invalid ticket".to_string())
+                                .and_then(|ticket| (verifier.verify)(client_id, ticket))
+                        });
+
+                    match result {
+                        Ok(allocation) => {
+                            debug!("verified ticket from {client_id:?}");
+                            allocations.0.insert(client_id, allocation);
+                            ticket_events.send(TicketEvent::Verified { client_id });
+                        }
+                        Err(reason) => {
+                            debug!("rejecting ticket from {client_id:?}: {reason}");
+                            server.disconnect(renet_client_id);
+                            ticket_events.send(TicketEvent::Rejected { client_id, reason });
+                        }
+                    }
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    allocations.0.remove(&client_id);
+                }
+            }
+        }
+    }
+
+    fn reset(mut allocations: ResMut<Allocations>) {
+        allocations.0.clear();
+    }
+}
+
+/// Encodes the ticket into netcode user data.
+///
+/// Returns [`None`] if the ticket is longer than [`MAX_TICKET_LEN`].
+pub fn ticket_user_data(ticket: &[u8]) -> Option<[u8; NETCODE_USER_DATA_BYTES]> {
+    if ticket.len() > MAX_TICKET_LEN {
+        return None;
+    }
+
+    let mut user_data = [0; NETCODE_USER_DATA_BYTES];
+    user_data[..2].copy_from_slice(&(ticket.len() as u16).to_le_bytes());
+    user_data[2..2 + ticket.len()].copy_from_slice(ticket);
+
+    Some(user_data)
+}
+
+/// Decodes the ticket from netcode user data encoded with [`ticket_user_data`].
+///
+/// Returns [`None`] if the encoded length is invalid.
+pub fn ticket_from_user_data(user_data: &[u8; NETCODE_USER_DATA_BYTES]) -> Option<&[u8]> {
+    let len = u16::from_le_bytes([user_data[0], user_data[1]]) as usize;
+    user_data.get(2..2 + len)
+}
+
+/// Verification callback for [`MatchmakingPlugin`].
+///
+/// Should be inserted on server, tickets are not verified without it.
+#[derive(Clone, Copy, Resource)]
+pub struct TicketVerifier {
+    /// Checks the ticket and returns allocation metadata for the client.
+    ///
+    /// Returned error is used as the reason in [`TicketEvent::Rejected`].
+    pub verify: fn(ClientId, &[u8]) -> Result<Allocation, String>,
+}
+
+/// Matchmaking metadata of a verified client.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allocation {
+    /// Player ID from the matchmaking service.
+    pub player_id: String,
+
+    /// Service-specific data, such as the match ID, the team or the rating.
+    pub metadata: HashMap<String, String>,
+}
+
+/// Allocations of verified clients.
+///
+/// Clients are automatically removed on disconnect.
+#[derive(Default, Resource)]
+pub struct Allocations(HashMap<ClientId, Allocation>);
+
+impl Allocations {
+    /// Returns the allocation of a verified client.
+    pub fn get(&self, client_id: ClientId) -> Option<&Allocation> {
+        self.0.get(&client_id)
+    }
+
+    /// Returns iterator over verified clients and their allocations.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &Allocation)> {
+        self.0
+            .iter()
+            .map(|(&client_id, allocation)| (client_id, allocation))
+    }
+}
+
+/// An event that indicates the result of a ticket verification.
+#[derive(Clone, Debug, Event, PartialEq, Eq)]
+pub enum TicketEvent {
+    /// The client is allowed to join, its allocation is available in [`Allocations`].
+    Verified { client_id: ClientId },
+    /// The client is disconnected.
+    Rejected { client_id: ClientId, reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_data() {
+        let ticket = b"ticket";
+        let user_data = ticket_user_data(ticket).unwrap();
+        assert_eq!(ticket_from_user_data(&user_data), Some(&ticket[..]));
+
+        assert!(ticket_user_data(&[0; MAX_TICKET_LEN + 1]).is_none());
+
+        let mut invalid_data = [0; NETCODE_USER_DATA_BYTES];
+        invalid_data[..2].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(ticket_from_user_data(&invalid_data).is_none());
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use bevy::{app::AppExit, prelude::*, utils::HashMap};
+use bevy_renet::{RenetReceive, RenetSend, RenetServerPlugin};
+use renetcode::{NetcodeServer, ServerResult, NETCODE_MAX_PACKET_BYTES};
+
+use crate::renet::{
+    transport::{NetcodeTransportError, ServerConfig, NETCODE_USER_DATA_BYTES},
+    ClientId, RenetServer,
+};
+
+/**
+Netcode server transport that listens on multiple sockets at once.
+
+Useful for dual-stack deployments or separate ports for WAN and LAN in a single server process.
+All sockets share a single netcode server, so client IDs are unique across endpoints,
+and [`ServerConfig::public_addresses`] should contain public addresses of all endpoints.
+Packets are sent to each client from the socket on which it connected.
+
+Insert [`MultiEndpointServerTransport`] instead of [`NetcodeServerTransport`](crate::renet::transport::NetcodeServerTransport)
+along with [`RenetServer`].
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```no_run
+use std::{
+    net::UdpSocket,
+    time::SystemTime,
+};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    multi_endpoint::{MultiEndpointServerPlugin, MultiEndpointServerTransport},
+    renet::transport::{ServerAuthentication, ServerConfig},
+    RepliconRenetPlugins,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    RepliconRenetPlugins,
+    MultiEndpointServerPlugin,
+));
+
+let ipv4_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+let ipv6_socket = UdpSocket::bind("[::1]:0").unwrap();
+let server_config = ServerConfig {
+    current_time: SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap(),
+    max_clients: 32,
+    protocol_id: 0,
+    public_addresses: vec![
+        ipv4_socket.local_addr().unwrap(),
+        ipv6_socket.local_addr().unwrap(),
+    ],
+    authentication: ServerAuthentication::Unsecure,
+};
+let transport = MultiEndpointServerTransport::new(server_config, vec![ipv4_socket, ipv6_socket]).unwrap();
+// Insert the transport along with `RenetServer`.
+```
+**/
+pub struct MultiEndpointServerPlugin;
+
+impl Plugin for MultiEndpointServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NetcodeTransportError>()
+            .add_systems(
+                PreUpdate,
+                Self::update
+                    .in_set(RenetReceive)
+                    .after(RenetServerPlugin::update_system)
+                    .before(RenetServerPlugin::emit_server_events_system)
+                    .run_if(resource_exists::<MultiEndpointServerTransport>)
+                    .run_if(resource_exists::<RenetServer>),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::send_packets.in_set(RenetSend),
+                    Self::disconnect_on_exit,
+                )
+                    .run_if(resource_exists::<MultiEndpointServerTransport>)
+                    .run_if(resource_exists::<RenetServer>),
+            );
+    }
+}
+
+impl MultiEndpointServerPlugin {
+    fn update(
+        mut transport: ResMut<MultiEndpointServerTransport>,
+        mut server: ResMut<RenetServer>,
+        mut transport_errors: EventWriter<NetcodeTransportError>,
+        time: Res<Time>,
+    ) {
+        if let Err(e) = transport.update(time.delta(), &mut server) {
+            transport_errors.send(e);
+        }
+    }
+
+    fn send_packets(
+        mut transport: ResMut<MultiEndpointServerTransport>,
+        mut server: ResMut<RenetServer>,
+    ) {
+        transport.send_packets(&mut server);
+    }
+
+    fn disconnect_on_exit(
+        exit_events: EventReader<AppExit>,
+        mut transport: ResMut<MultiEndpointServerTransport>,
+        mut server: ResMut<RenetServer>,
+    ) {
+        if !exit_events.is_empty() {
+            transport.disconnect_all(&mut server);
+        }
+    }
+}
+
+/// Netcode transport with multiple sockets.
+///
+/// See [`MultiEndpointServerPlugin`] for details.
+#[derive(Resource)]
+pub struct MultiEndpointServerTransport {
+    sockets: Vec<UdpSocket>,
+    netcode_server: NetcodeServer,
+
+    /// Indices of sockets on which clients connected.
+    peer_sockets: HashMap<SocketAddr, usize>,
+
+    buffer: [u8; NETCODE_MAX_PACKET_BYTES],
+}
+
+impl MultiEndpointServerTransport {
+    /// Creates a transport from the sockets.
+    ///
+    /// Sockets will be switched into non-blocking mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no sockets are passed.
+    pub fn new(server_config: ServerConfig, sockets: Vec<UdpSocket>) -> io::Result<Self> {
+        assert!(
+            !sockets.is_empty(),
+            "transport should have at least one socket"
+        );
+        for socket in &sockets {
+            socket.set_nonblocking(true)?;
+        }
+
+        Ok(Self {
+            sockets,
+            netcode_server: NetcodeServer::new(server_config),
+            peer_sockets: Default::default(),
+            buffer: [0; NETCODE_MAX_PACKET_BYTES],
+        })
+    }
+
+    /// Returns public addresses from the server config.
+    pub fn addresses(&self) -> Vec<SocketAddr> {
+        self.netcode_server.addresses()
+    }
+
+    /// Returns local addresses of all sockets.
+    pub fn local_addresses(&self) -> io::Result<Vec<SocketAddr>> {
+        self.sockets.iter().map(UdpSocket::local_addr).collect()
+    }
+
+    /// Returns the maximum number of clients that can be connected.
+    pub fn max_clients(&self) -> usize {
+        self.netcode_server.max_clients()
+    }
+
+    /// Returns the current number of connected clients across all endpoints.
+    pub fn connected_clients(&self) -> usize {
+        self.netcode_server.connected_clients()
+    }
+
+    /// Returns the user data of a connected client.
+    pub fn user_data(&self, client_id: ClientId) -> Option<[u8; NETCODE_USER_DATA_BYTES]> {
+        self.netcode_server.user_data(client_id.raw())
+    }
+
+    /// Returns the address of a connected client.
+    pub fn client_addr(&self, client_id: ClientId) -> Option<SocketAddr> {
+        self.netcode_server.client_addr(client_id.raw())
+    }
+
+    /// Returns the local address of the socket to which the client is connected.
+    pub fn client_endpoint(&self, client_id: ClientId) -> Option<SocketAddr> {
+        let addr = self.client_addr(client_id)?;
+        let &index = self.peer_sockets.get(&addr)?;
+        self.sockets[index].local_addr().ok()
+    }
+
+    /// Disconnects all clients instantly.
+    ///
+    /// Use it when closing the server, otherwise use [`RenetServer::disconnect_all`].
+    pub fn disconnect_all(&mut self, server: &mut RenetServer) {
+        for client_id in self.netcode_server.clients_id() {
+            let server_result = self.netcode_server.disconnect(client_id);
+            handle_server_result(
+                server_result,
+                &self.sockets,
+                &mut self.peer_sockets,
+                None,
+                server,
+            );
+        }
+    }
+
+    /// Advances the transport by the duration and receives packets from all sockets.
+    pub fn update(
+        &mut self,
+        duration: Duration,
+        server: &mut RenetServer,
+    ) -> Result<(), NetcodeTransportError> {
+        self.netcode_server.update(duration);
+
+        for (index, socket) in self.sockets.iter().enumerate() {
+            loop {
+                let (len, addr) = match socket.recv_from(&mut self.buffer) {
+                    Ok(received) => received,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => break,
+                    Err(e) if e.kind() == ErrorKind::ConnectionReset => continue,
+                    Err(e) => return Err(e.into()),
+                };
+
+                let server_result = self
+                    .netcode_server
+                    .process_packet(addr, &mut self.buffer[..len]);
+                handle_server_result(
+                    server_result,
+                    &self.sockets,
+                    &mut self.peer_sockets,
+                    Some(index),
+                    server,
+                );
+            }
+        }
+
+        for client_id in self.netcode_server.clients_id() {
+            let server_result = self.netcode_server.update_client(client_id);
+            handle_server_result(
+                server_result,
+                &self.sockets,
+                &mut self.peer_sockets,
+                None,
+                server,
+            );
+        }
+
+        for client_id in server.disconnections_id() {
+            let server_result = self.netcode_server.disconnect(client_id.raw());
+            handle_server_result(
+                server_result,
+                &self.sockets,
+                &mut self.peer_sockets,
+                None,
+                server,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Sends packets to connected clients.
+    pub fn send_packets(&mut self, server: &mut RenetServer) {
+        'clients: for client_id in server.clients_id() {
+            let packets = server
+                .get_packets_to_send(client_id)
+                .expect("client from the server should be connected");
+            for packet in packets {
+                match self
+                    .netcode_server
+                    .generate_payload_packet(client_id.raw(), &packet)
+                {
+                    Ok((addr, payload)) => {
+                        let socket = socket_for(&self.sockets, &self.peer_sockets, None, addr);
+                        if let Err(e) = socket.send_to(payload, addr) {
+                            error!("unable to send packet to {client_id} ({addr}): {e}");
+                            continue 'clients;
+                        }
+                    }
+                    Err(e) => {
+                        error!("unable to encrypt payload packet for {client_id}: {e}");
+                        continue 'clients;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the socket for replies to the peer.
+///
+/// Prefers the socket on which the packet was received, then the socket on which the peer connected.
+fn socket_for<'a>(
+    sockets: &'a [UdpSocket],
+    peer_sockets: &HashMap<SocketAddr, usize>,
+    source: Option<usize>,
+    addr: SocketAddr,
+) -> &'a UdpSocket {
+    let index = source
+        .or_else(|| peer_sockets.get(&addr).copied())
+        .unwrap_or_default();
+    &sockets[index]
+}
+
+fn handle_server_result(
+    server_result: ServerResult,
+    sockets: &[UdpSocket],
+    peer_sockets: &mut HashMap<SocketAddr, usize>,
+    source: Option<usize>,
+    server: &mut RenetServer,
+) {
+    let send_packet = |peer_sockets: &HashMap<_, _>, packet: &[u8], addr: SocketAddr| {
+        let socket = socket_for(sockets, peer_sockets, source, addr);
+        if let Err(e) = socket.send_to(packet, addr) {
+            error!("unable to send packet to {addr}: {e}");
+        }
+    };
+
+    match server_result {
+        ServerResult::None => (),
+        ServerResult::PacketToSend { payload, addr } => send_packet(peer_sockets, payload, addr),
+        ServerResult::Payload { client_id, payload } => {
+            let client_id = ClientId::from_raw(client_id);
+            if let Err(e) = server.process_packet_from(payload, client_id) {
+                error!("unable to process payload from {client_id}: {e}");
+            }
+        }
+        ServerResult::ClientConnected {
+            client_id,
+            addr,
+            payload,
+            ..
+        } => {
+            server.add_connection(ClientId::from_raw(client_id));
+            if let Some(index) = source {
+                peer_sockets.insert(addr, index);
+            }
+            send_packet(peer_sockets, payload, addr);
+        }
+        ServerResult::ClientDisconnected {
+            client_id,
+            addr,
+            payload,
+        } => {
+            server.remove_connection(ClientId::from_raw(client_id));
+            if let Some(payload) = payload {
+                send_packet(peer_sockets, payload, addr);
+            }
+            peer_sockets.remove(&addr);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bevy_replicon::bincode;
+use serde::{Deserialize, Serialize};
+
+/// Maximum size of a rendezvous packet.
+const MAX_PACKET_SIZE: usize = 1200;
+
+/**
+Performs UDP hole punching between two peers using a user-hosted coordinator.
+
+Insert [`NatPunch`] with the socket that will be used for the transport on both peers.
+Each peer registers on the coordinator with the same session. Once the coordinator
+receives both registrations, it should send [`RendezvousPacket::Peer`] to each peer
+with the public address of the other peer. After that peers send punches to each other until
+one of them arrives.
+
+On success [`NatPunchEvent::Punched`] is emitted. Take the socket using [`NatPunch::take_socket`]
+and pass it to [`NetcodeServerTransport`](crate::renet::transport::NetcodeServerTransport) or
+[`NetcodeClientTransport`](crate::renet::transport::NetcodeClientTransport) with the punched address.
+Hole punching doesn't work for all NAT types, so a fallback to a relay is advised.
+
+To serve multiple clients, the host should finish punching for all of them before creating
+the transport using clones of the same socket, since the transport will receive all packets after its creation.
+
+Not added by default.
+
+# Examples
+
+```no_run
+use std::net::UdpSocket;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    nat_punch::{NatPunch, NatPunchEvent, NatPunchPlugin},
+    RepliconRenetPlugins,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    RepliconRenetPlugins,
+    NatPunchPlugin,
+));
+
+let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+let coordinator_addr = "203.0.113.1:6000".parse().unwrap();
+let nat_punch = NatPunch::new(socket, coordinator_addr, 42).unwrap();
+app.insert_resource(nat_punch)
+    .add_systems(Update, connect);
+
+fn connect(mut nat_punch: ResMut<NatPunch>, mut punch_events: EventReader<NatPunchEvent>) {
+    for event in punch_events.read() {
+        match *event {
+            NatPunchEvent::Punched { peer_addr } => {
+                let socket = nat_punch.take_socket().unwrap();
+                // Create the transport with the socket and the peer address.
+            }
+            NatPunchEvent::TimedOut => {
+                // Connect using a relay.
+            }
+        }
+    }
+}
+```
+**/
+pub struct NatPunchPlugin;
+
+impl Plugin for NatPunchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NatPunchEvent>()
+            .add_systems(PreUpdate, Self::update.run_if(resource_exists::<NatPunch>));
+    }
+}
+
+impl NatPunchPlugin {
+    fn update(
+        mut nat_punch: ResMut<NatPunch>,
+        mut punch_events: EventWriter<NatPunchEvent>,
+        time: Res<Time<Real>>,
+    ) {
+        let nat_punch = &mut *nat_punch;
+        if nat_punch.status.is_finished() {
+            return;
+        }
+        let Some(socket) = &nat_punch.socket else {
+            return;
+        };
+
+        let now = time.elapsed();
+        let started = *nat_punch.started.get_or_insert(now);
+        let session = nat_punch.session;
+
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        let mut status = nat_punch.status;
+        loop {
+            let (size, source) = match socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    debug!("unable to receive rendezvous packet: {e}");
+                    break;
+                }
+            };
+
+            match bincode::deserialize(&buffer[..size]) {
+                Ok(RendezvousPacket::Peer { addr })
+                    if source == nat_punch.coordinator_addr
+                        && status == NatPunchStatus::Registering =>
+                {
+                    debug!("received peer address {addr} from coordinator");
+                    status = NatPunchStatus::Punching { peer_addr: addr };
+                    nat_punch.next_send = None;
+                }
+                Ok(RendezvousPacket::Punch {
+                    session: punch_session,
+                }) if punch_session == session => {
+                    // Answer to let the peer know that its punch arrived.
+                    send(socket, &RendezvousPacket::Punch { session }, source);
+                    debug!("punched through to {source}");
+                    status = NatPunchStatus::Punched { peer_addr: source };
+                    break;
+                }
+                Ok(_) => trace!("ignoring unexpected rendezvous packet from {source}"),
+                Err(e) => debug!("ignoring invalid rendezvous packet from {source}: {e}"),
+            }
+        }
+
+        if let NatPunchStatus::Punched { peer_addr } = status {
+            punch_events.send(NatPunchEvent::Punched { peer_addr });
+        } else if now.saturating_sub(started) > nat_punch.timeout {
+            debug!("hole punching timed out");
+            status = NatPunchStatus::TimedOut;
+            punch_events.send(NatPunchEvent::TimedOut);
+        } else if nat_punch.next_send.is_none_or(|next_send| now >= next_send) {
+            match status {
+                NatPunchStatus::Registering => send(
+                    socket,
+                    &RendezvousPacket::Register { session },
+                    nat_punch.coordinator_addr,
+                ),
+                NatPunchStatus::Punching { peer_addr } => {
+                    send(socket, &RendezvousPacket::Punch { session }, peer_addr)
+                }
+                NatPunchStatus::Punched { .. } | NatPunchStatus::TimedOut => (),
+            }
+            nat_punch.next_send = Some(now + nat_punch.retry_interval);
+        }
+
+        nat_punch.status = status;
+    }
+}
+
+fn send(socket: &UdpSocket, packet: &RendezvousPacket, addr: SocketAddr) {
+    let message = bincode::serialize(packet).expect("rendezvous packet should be serializable");
+    if let Err(e) = socket.send_to(&message, addr) {
+        debug!("unable to send rendezvous packet to {addr}: {e}");
+    }
+}
+
+/// Hole punching state.
+///
+/// See [`NatPunchPlugin`] for details.
+#[derive(Resource)]
+pub struct NatPunch {
+    /// Time between repeated registrations and punches.
+    ///
+    /// By default set to 100 ms.
+    pub retry_interval: Duration,
+
+    /// Time after which the punching fails.
+    ///
+    /// By default set to 10 seconds.
+    pub timeout: Duration,
+
+    socket: Option<UdpSocket>,
+    coordinator_addr: SocketAddr,
+    session: u64,
+    status: NatPunchStatus,
+
+    /// Real time when the punching started.
+    started: Option<Duration>,
+
+    /// Real time when the next packet should be sent.
+    next_send: Option<Duration>,
+}
+
+impl NatPunch {
+    /// Creates a new punching for the specified session.
+    ///
+    /// The socket will be switched into non-blocking mode.
+    /// Session is an arbitrary ID that should be the same on both peers, such as a lobby ID.
+    pub fn new(socket: UdpSocket, coordinator_addr: SocketAddr, session: u64) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            retry_interval: Duration::from_millis(100),
+            timeout: Duration::from_secs(10),
+            socket: Some(socket),
+            coordinator_addr,
+            session,
+            status: NatPunchStatus::Registering,
+            started: None,
+            next_send: None,
+        })
+    }
+
+    /// Returns the current state.
+    pub fn status(&self) -> NatPunchStatus {
+        self.status
+    }
+
+    /// Takes the socket to pass to the transport.
+    ///
+    /// Returns [`None`] if the socket was already taken.
+    /// Punching stops after this call.
+    pub fn take_socket(&mut self) -> Option<UdpSocket> {
+        self.socket.take()
+    }
+}
+
+/// State of [`NatPunch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NatPunchStatus {
+    /// Waiting for the peer address from the coordinator.
+    Registering,
+    /// Sending punches to the peer.
+    Punching { peer_addr: SocketAddr },
+    /// Punch from the peer arrived.
+    Punched { peer_addr: SocketAddr },
+    /// Peer wasn't reached in time.
+    TimedOut,
+}
+
+impl NatPunchStatus {
+    /// Returns `true` if the punching succeeded or failed.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Punched { .. } | Self::TimedOut)
+    }
+}
+
+/// An event that indicates that [`NatPunch`] finished.
+#[derive(Clone, Copy, Debug, Event, PartialEq, Eq)]
+pub enum NatPunchEvent {
+    /// Socket can be used to connect to the peer.
+    Punched { peer_addr: SocketAddr },
+    /// Peer wasn't reached in time.
+    TimedOut,
+}
+
+/// Packet format used by [`NatPunchPlugin`].
+///
+/// Public to allow implementing coordinators.
+/// Packets are serialized using [`bincode::serialize`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum RendezvousPacket {
+    /// Sent by peers to the coordinator until they receive [`Self::Peer`].
+    Register { session: u64 },
+    /// Sent by the coordinator to each peer of a session with the public address of the other peer.
+    Peer { addr: SocketAddr },
+    /// Sent by peers to each other.
+    Punch { session: u64 },
+}
@@ -0,0 +1,338 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_replicon::{
+    prelude::*,
+    server::{connected_clients::ConnectedClients, ServerSet},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::lan_discovery::{self, MAX_PACKET_SIZE};
+
+/// Prefix of browser packets.
+const BROWSER_MAGIC: [u8; 4] = *b"RPLB";
+
+/**
+Publishes server metadata and discovers joinable servers.
+
+On server insert [`Heartbeat`] to periodically send [`ServerInfo`] to a master server or a LAN broadcast address.
+The player count is filled automatically from [`ConnectedClients`].
+
+On client insert [`ServerBrowser`] to receive heartbeats. For LAN the browser should be bound to
+the broadcast port, for a master server call [`ServerBrowser::query`] and the master server should answer
+with [`BrowserPacket::Announce`] for each known server.
+
+Packets are sent using separate UDP sockets, but share socket setup and packet framing with
+[`LanDiscoveryPlugin`](crate::lan_discovery::LanDiscoveryPlugin).
+
+Not added by default.
+
+# Examples
+
+```no_run
+use std::{net::SocketAddr, time::Duration};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_replicon_renet::{
+    server_browser::{Heartbeat, ServerBrowser, ServerBrowserPlugin, ServerInfo},
+    RepliconRenetPlugins,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    RepliconRenetPlugins,
+    ServerBrowserPlugin,
+));
+
+// On server.
+let broadcast_addr: SocketAddr = "255.255.255.255:5001".parse().unwrap();
+let heartbeat = Heartbeat::new(
+    ServerInfo {
+        name: "My server".to_string(),
+        map: "Island".to_string(),
+        port: 5000,
+        max_players: 4,
+        protocol: 1,
+        ..Default::default()
+    },
+    vec![broadcast_addr],
+    Duration::from_secs(1),
+)
+.unwrap();
+app.insert_resource(heartbeat);
+
+// On client.
+let server_browser = ServerBrowser::bind("0.0.0.0:5001".parse().unwrap(), 1).unwrap();
+app.insert_resource(server_browser)
+    .add_systems(Update, list_servers);
+
+fn list_servers(server_browser: Res<ServerBrowser>) {
+    for server in server_browser.iter_joinable() {
+        info!("{} at {}", server.info.name, server.addr);
+    }
+}
+```
+**/
+pub struct ServerBrowserPlugin;
+
+impl Plugin for ServerBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            Self::receive.run_if(resource_exists::<ServerBrowser>),
+        )
+        .add_systems(
+            PostUpdate,
+            Self::send_heartbeat
+                .in_set(ServerSet::Send)
+                .run_if(server_running)
+                .run_if(resource_exists::<Heartbeat>),
+        );
+    }
+}
+
+impl ServerBrowserPlugin {
+    fn send_heartbeat(
+        mut heartbeat: ResMut<Heartbeat>,
+        connected_clients: Res<ConnectedClients>,
+        time: Res<Time<Real>>,
+    ) {
+        let now = time.elapsed();
+        if heartbeat.next_send.is_some_and(|next_send| now < next_send) {
+            return;
+        }
+        heartbeat.next_send = Some(now + heartbeat.interval);
+
+        heartbeat.info.players = connected_clients.len() as u32;
+        let packet = BrowserPacket::Announce {
+            addr: None,
+            info: heartbeat.info.clone(),
+        };
+        let message = packet.to_bytes();
+
+        for &target in &heartbeat.targets {
+            if let Err(e) = heartbeat.socket.send_to(&message, target) {
+                debug!("unable to send heartbeat to {target}: {e}");
+            }
+        }
+    }
+
+    fn receive(mut server_browser: ResMut<ServerBrowser>, time: Res<Time<Real>>) {
+        let now = time.elapsed();
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        while let Some((size, source)) =
+            lan_discovery::recv_from(&server_browser.socket, &mut buffer)
+        {
+            let Some(packet) = BrowserPacket::from_bytes(&buffer[..size]) else {
+                debug!("ignoring invalid browser packet from {source}");
+                continue;
+            };
+
+            match packet {
+                BrowserPacket::Announce { addr, info } => {
+                    let addr = addr.unwrap_or_else(|| SocketAddr::new(source.ip(), info.port));
+                    trace!("received heartbeat from {addr}");
+                    server_browser.servers.insert(
+                        addr,
+                        BrowsedServer {
+                            addr,
+                            info,
+                            last_seen: now,
+                        },
+                    );
+                }
+                BrowserPacket::Query => trace!("ignoring query from {source}"),
+            }
+        }
+
+        let timeout = server_browser.timeout;
+        server_browser
+            .servers
+            .retain(|_, server| now.saturating_sub(server.last_seen) <= timeout);
+    }
+}
+
+/// Metadata about a server that is published by [`Heartbeat`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ServerInfo {
+    /// Displayed server name.
+    pub name: String,
+
+    /// Current map.
+    pub map: String,
+
+    /// Port on which the server accepts game connections.
+    ///
+    /// Used together with the heartbeat source IP to get the connection address.
+    pub port: u16,
+
+    /// Number of connected clients.
+    ///
+    /// Updated automatically before each heartbeat.
+    pub players: u32,
+
+    /// Maximum number of clients.
+    pub max_players: u32,
+
+    /// Hash of the game protocol.
+    ///
+    /// Clients consider servers with a different hash as not joinable.
+    /// Should change when replication rules or network events change.
+    pub protocol: u64,
+}
+
+/// Periodically publishes [`ServerInfo`] while the server is running.
+///
+/// See [`ServerBrowserPlugin`] for details.
+#[derive(Resource)]
+pub struct Heartbeat {
+    /// Published metadata.
+    pub info: ServerInfo,
+
+    /// Master server or broadcast addresses.
+    pub targets: Vec<SocketAddr>,
+
+    /// Time between heartbeats.
+    pub interval: Duration,
+
+    socket: UdpSocket,
+
+    /// Time when the next heartbeat should be sent.
+    ///
+    /// [`None`] if the heartbeat was never sent.
+    next_send: Option<Duration>,
+}
+
+impl Heartbeat {
+    /// Creates a new heartbeat with a socket bound to any available port.
+    ///
+    /// Broadcast is enabled on the socket.
+    pub fn new(info: ServerInfo, targets: Vec<SocketAddr>, interval: Duration) -> io::Result<Self> {
+        let socket = lan_discovery::bind_socket(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0))?;
+
+        Ok(Self {
+            info,
+            targets,
+            interval,
+            socket,
+            next_send: None,
+        })
+    }
+}
+
+/// Receives heartbeats and stores discovered servers.
+///
+/// See [`ServerBrowserPlugin`] for details.
+#[derive(Resource)]
+pub struct ServerBrowser {
+    /// Protocol hash of this client.
+    ///
+    /// See [`ServerInfo::protocol`].
+    pub protocol: u64,
+
+    /// Time after which a server without a heartbeat is removed.
+    ///
+    /// By default set to 5 seconds.
+    pub timeout: Duration,
+
+    socket: UdpSocket,
+    servers: HashMap<SocketAddr, BrowsedServer>,
+}
+
+impl ServerBrowser {
+    /// Creates a new browser with a socket bound to the specified address.
+    pub fn bind(addr: SocketAddr, protocol: u64) -> io::Result<Self> {
+        let socket = lan_discovery::bind_socket(addr)?;
+
+        Ok(Self {
+            protocol,
+            timeout: Duration::from_secs(5),
+            socket,
+            servers: Default::default(),
+        })
+    }
+
+    /// Returns the address of the browser socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Requests the list of servers from a master server.
+    pub fn query(&self, master_addr: SocketAddr) -> io::Result<()> {
+        let message = BrowserPacket::Query.to_bytes();
+        self.socket.send_to(&message, master_addr)?;
+
+        Ok(())
+    }
+
+    /// Returns iterator over all discovered servers.
+    pub fn iter(&self) -> impl Iterator<Item = &BrowsedServer> {
+        self.servers.values()
+    }
+
+    /// Returns iterator over servers with matching protocol and free slots.
+    pub fn iter_joinable(&self) -> impl Iterator<Item = &BrowsedServer> {
+        self.iter().filter(|server| {
+            server.info.protocol == self.protocol && server.info.players < server.info.max_players
+        })
+    }
+
+    /// Removes all discovered servers.
+    pub fn clear(&mut self) {
+        self.servers.clear();
+    }
+}
+
+/// Server discovered by [`ServerBrowser`].
+#[derive(Clone, Debug)]
+pub struct BrowsedServer {
+    /// Address for game connections.
+    pub addr: SocketAddr,
+
+    /// Last received metadata.
+    pub info: ServerInfo,
+
+    /// Real time of the last received heartbeat.
+    pub last_seen: Duration,
+}
+
+/// Packet format used by [`ServerBrowserPlugin`].
+///
+/// Public to allow implementing master servers.
+/// Use [`Self::to_bytes`] and [`Self::from_bytes`] to get the datagram representation.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BrowserPacket {
+    /// Server metadata.
+    ///
+    /// Sent by servers with [`None`] address, the address is taken from the packet source.
+    /// Master servers should specify the address when they answer [`Self::Query`].
+    Announce {
+        addr: Option<SocketAddr>,
+        info: ServerInfo,
+    },
+    /// Request from a client to a master server for announces of all known servers.
+    Query,
+}
+
+impl BrowserPacket {
+    /// Serializes the packet into a datagram.
+    ///
+    /// Uses the default [`bincode`](bevy_replicon::bincode) encoding prefixed with a magic.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        lan_discovery::encode(BROWSER_MAGIC, self)
+    }
+
+    /// Deserializes the packet from a datagram.
+    ///
+    /// Returns [`None`] if the datagram is not a valid browser packet.
+    pub fn from_bytes(message: &[u8]) -> Option<Self> {
+        lan_discovery::decode(BROWSER_MAGIC, message)
+    }
+}
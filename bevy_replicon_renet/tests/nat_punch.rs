@@ -0,0 +1,99 @@
+use std::{
+    io::ErrorKind,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bevy_replicon::bincode;
+use bevy_replicon_renet::nat_punch::{NatPunch, NatPunchPlugin, NatPunchStatus, RendezvousPacket};
+
+#[test]
+fn punch() {
+    const SESSION: u64 = 42;
+
+    let coordinator = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    coordinator.set_nonblocking(true).unwrap();
+    let coordinator_addr = coordinator.local_addr().unwrap();
+
+    let mut peer_apps = [App::new(), App::new()];
+    let mut peer_addrs = Vec::new();
+    for app in &mut peer_apps {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        peer_addrs.push(socket.local_addr().unwrap());
+        app.add_plugins((MinimalPlugins, NatPunchPlugin))
+            .insert_resource(NatPunch::new(socket, coordinator_addr, SESSION).unwrap());
+    }
+
+    let mut registered = Vec::new();
+    for _ in 0..100 {
+        for app in &mut peer_apps {
+            app.update();
+        }
+
+        let mut buffer = [0; 1200];
+        loop {
+            let (size, source) = match coordinator.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => panic!("coordinator should receive packets: {e}"),
+            };
+            let packet: RendezvousPacket = bincode::deserialize(&buffer[..size]).unwrap();
+            assert_eq!(packet, RendezvousPacket::Register { session: SESSION });
+            if !registered.contains(&source) {
+                registered.push(source);
+            }
+        }
+
+        if let [first, second] = registered[..] {
+            for (addr, peer_addr) in [(first, second), (second, first)] {
+                let message =
+                    bincode::serialize(&RendezvousPacket::Peer { addr: peer_addr }).unwrap();
+                coordinator.send_to(&message, addr).unwrap();
+            }
+            registered.clear();
+        }
+
+        if peer_apps
+            .iter()
+            .all(|app| app.world.resource::<NatPunch>().status().is_finished())
+        {
+            break;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    for (app, peer_addr) in peer_apps.iter_mut().zip(peer_addrs.iter().rev()) {
+        let mut nat_punch = app.world.resource_mut::<NatPunch>();
+        assert_eq!(
+            nat_punch.status(),
+            NatPunchStatus::Punched {
+                peer_addr: *peer_addr
+            }
+        );
+        assert!(nat_punch.take_socket().is_some());
+    }
+}
+
+#[test]
+fn timeout() {
+    let coordinator = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let coordinator_addr: SocketAddr = coordinator.local_addr().unwrap();
+
+    let mut nat_punch = NatPunch::new(socket, coordinator_addr, 0).unwrap();
+    nat_punch.timeout = Duration::ZERO;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, NatPunchPlugin))
+        .insert_resource(nat_punch);
+
+    app.update();
+    thread::sleep(Duration::from_millis(10));
+    app.update();
+
+    let nat_punch = app.world.resource::<NatPunch>();
+    assert_eq!(nat_punch.status(), NatPunchStatus::TimedOut);
+}
@@ -0,0 +1,98 @@
+use std::{net::SocketAddr, thread, time::Duration};
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use bevy_replicon_renet::server_browser::{
+    BrowserPacket, Heartbeat, ServerBrowser, ServerBrowserPlugin, ServerInfo,
+};
+
+#[test]
+fn heartbeat() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ServerBrowserPlugin));
+    }
+
+    let server_browser = ServerBrowser::bind(SocketAddr::from(([127, 0, 0, 1], 0)), 1).unwrap();
+    let browser_addr = server_browser.local_addr().unwrap();
+    client_app.insert_resource(server_browser);
+
+    let info = ServerInfo {
+        name: "Dummy".to_string(),
+        port: 5000,
+        max_players: 2,
+        protocol: 1,
+        ..Default::default()
+    };
+    let heartbeat =
+        Heartbeat::new(info.clone(), vec![browser_addr], Duration::from_secs(1)).unwrap();
+    server_app.insert_resource(heartbeat);
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+
+    // Give the loopback some time to deliver the datagram.
+    thread::sleep(Duration::from_millis(50));
+    client_app.update();
+
+    let server_browser = client_app.world.resource::<ServerBrowser>();
+    let server = server_browser
+        .iter_joinable()
+        .next()
+        .expect("server should be discovered");
+    assert_eq!(server.addr, SocketAddr::from(([127, 0, 0, 1], info.port)));
+    assert_eq!(server.info.name, info.name);
+    assert_eq!(server.info.players, 1);
+}
+
+#[test]
+fn not_joinable() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ServerBrowserPlugin));
+    }
+
+    let server_browser = ServerBrowser::bind(SocketAddr::from(([127, 0, 0, 1], 0)), 1).unwrap();
+    let browser_addr = server_browser.local_addr().unwrap();
+    client_app.insert_resource(server_browser);
+
+    let info = ServerInfo {
+        port: 5000,
+        max_players: 1,
+        protocol: 2,
+        ..Default::default()
+    };
+    let heartbeat = Heartbeat::new(info, vec![browser_addr], Duration::from_secs(1)).unwrap();
+    server_app.insert_resource(heartbeat);
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+
+    thread::sleep(Duration::from_millis(50));
+    client_app.update();
+
+    let server_browser = client_app.world.resource::<ServerBrowser>();
+    assert_eq!(server_browser.iter().count(), 1);
+    assert_eq!(
+        server_browser.iter_joinable().count(),
+        0,
+        "server with different protocol shouldn't be joinable"
+    );
+}
+
+#[test]
+fn packet_framing() {
+    let packet = BrowserPacket::Announce {
+        addr: None,
+        info: ServerInfo::default(),
+    };
+    let message = packet.to_bytes();
+    assert_eq!(BrowserPacket::from_bytes(&message), Some(packet));
+    assert_eq!(&message[..4], b"RPLB");
+
+    let mut invalid = message.clone();
+    invalid[0] = 0;
+    assert_eq!(BrowserPacket::from_bytes(&invalid), None);
+}
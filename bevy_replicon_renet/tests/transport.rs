@@ -1,18 +1,24 @@
 use std::{
     net::{Ipv4Addr, SocketAddr, UdpSocket},
-    time::SystemTime,
+    thread,
+    time::{Duration, SystemTime},
 };
 
-use bevy::prelude::*;
+use bevy::{ecs::event::Events, prelude::*};
 use bevy_renet::renet::{
     transport::{
         ClientAuthentication, NetcodeClientTransport, NetcodeServerTransport, ServerAuthentication,
-        ServerConfig,
+        ServerConfig, NETCODE_USER_DATA_BYTES,
     },
     ConnectionConfig, RenetClient, RenetServer,
 };
 use bevy_replicon::prelude::*;
-use bevy_replicon_renet::{RenetChannelsExt, RepliconRenetPlugins};
+use bevy_replicon_renet::{
+    lan_discovery::{DiscoveredServer, LanDiscovery, LanDiscoveryPlugin, LanResponder},
+    matchmaking::{self, Allocation, Allocations, MatchmakingPlugin, TicketEvent, TicketVerifier},
+    multi_endpoint::{MultiEndpointServerPlugin, MultiEndpointServerTransport},
+    RenetChannelsExt, RepliconRenetPlugins,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -133,6 +139,174 @@ fn client_event() {
     assert_eq!(client_events.len(), 1);
 }
 
+#[test]
+fn lan_discovery() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            RepliconRenetPlugins,
+            LanDiscoveryPlugin,
+        ));
+    }
+
+    let server_port = setup_server(&mut server_app, 1);
+    let responder =
+        LanResponder::bind(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0), PROTOCOL_ID).unwrap();
+    let mut discovery = LanDiscovery::new(0, PROTOCOL_ID).unwrap();
+    discovery.probe_addr = responder.local_addr().unwrap();
+    server_app.insert_resource(responder);
+
+    discovery.probe().unwrap();
+    client_app.insert_resource(discovery);
+
+    // Give the loopback some time to deliver datagrams.
+    thread::sleep(Duration::from_millis(50));
+    server_app.update();
+    thread::sleep(Duration::from_millis(50));
+    client_app.update();
+
+    let mut discovered_events = client_app.world.resource_mut::<Events<DiscoveredServer>>();
+    let event = discovered_events
+        .drain()
+        .next()
+        .expect("server should answer the probe");
+    assert_eq!(
+        event.addresses,
+        [SocketAddr::new(Ipv4Addr::LOCALHOST.into(), server_port)]
+    );
+    assert_eq!(event.max_clients, 1);
+    assert_eq!(event.connected_clients, 0);
+}
+
+#[test]
+fn matchmaking_ticket() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, RepliconRenetPlugins));
+    }
+    server_app
+        .add_plugins(MatchmakingPlugin)
+        .insert_resource(TicketVerifier {
+            verify: |_, ticket| {
+                if ticket == b"valid" {
+                    Ok(Allocation {
+                        player_id: "player".to_string(),
+                        ..Default::default()
+                    })
+                } else {
+                    Err("invalid ticket".to_string())
+                }
+            },
+        });
+
+    let port = setup_server(&mut server_app, 2);
+    let mut events = Vec::new();
+    for (client_id, client_app, ticket) in [
+        (1, &mut client_app1, &b"valid"[..]),
+        (2, &mut client_app2, &b"invalid"[..]),
+    ] {
+        setup_client(client_app, client_id, port);
+        let user_data = matchmaking::ticket_user_data(ticket).unwrap();
+        client_app.insert_resource(create_client_transport_with_data(
+            client_id,
+            port,
+            Some(user_data),
+        ));
+        // Drain after each update since events are cleared after two updates.
+        loop {
+            client_app.update();
+            server_app.update();
+            events.extend(
+                server_app
+                    .world
+                    .resource_mut::<Events<TicketEvent>>()
+                    .drain(),
+            );
+            if events.len() == client_id as usize {
+                break;
+            }
+        }
+    }
+
+    let allocations = server_app.world.resource::<Allocations>();
+    let allocation = allocations.get(ClientId::new(1)).unwrap();
+    assert_eq!(allocation.player_id, "player");
+    assert!(allocations.get(ClientId::new(2)).is_none());
+
+    assert_eq!(
+        events,
+        [
+            TicketEvent::Verified {
+                client_id: ClientId::new(1)
+            },
+            TicketEvent::Rejected {
+                client_id: ClientId::new(2),
+                reason: "invalid ticket".to_string()
+            }
+        ]
+    );
+}
+
+#[test]
+fn multiple_endpoints() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, RepliconRenetPlugins));
+    }
+    server_app.add_plugins(MultiEndpointServerPlugin);
+
+    let channels = server_app.world.resource::<RepliconChannels>();
+    let server = RenetServer::new(ConnectionConfig {
+        server_channels_config: channels.get_server_configs(),
+        client_channels_config: channels.get_client_configs(),
+        ..Default::default()
+    });
+    let sockets: Vec<_> = (0..2)
+        .map(|_| UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).expect("localhost should be bindable"))
+        .collect();
+    let public_addresses = sockets
+        .iter()
+        .map(|socket| socket.local_addr().unwrap())
+        .collect::<Vec<_>>();
+    let server_config = ServerConfig {
+        current_time: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap(),
+        max_clients: 2,
+        protocol_id: PROTOCOL_ID,
+        public_addresses: public_addresses.clone(),
+        authentication: ServerAuthentication::Unsecure,
+    };
+    let transport = MultiEndpointServerTransport::new(server_config, sockets).unwrap();
+    server_app
+        .insert_resource(server)
+        .insert_resource(transport);
+
+    for (client_id, client_app, addr) in [
+        (1, &mut client_app1, public_addresses[0]),
+        (2, &mut client_app2, public_addresses[1]),
+    ] {
+        setup_client(client_app, client_id, addr.port());
+        wait_for_connection(&mut server_app, client_app);
+    }
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(connected_clients.len(), 2);
+
+    let transport = server_app.world.resource::<MultiEndpointServerTransport>();
+    for (client_id, addr) in [(1, public_addresses[0]), (2, public_addresses[1])] {
+        let client_id = bevy_renet::renet::ClientId::from_raw(client_id);
+        assert_eq!(transport.client_endpoint(client_id), Some(addr));
+    }
+}
+
 fn setup(server_app: &mut App, client_app: &mut App) {
     const CLIENT_ID: u64 = 1;
     let port = setup_server(server_app, 1);
@@ -198,6 +372,14 @@ fn create_server_transport(max_clients: usize) -> NetcodeServerTransport {
 }
 
 fn create_client_transport(client_id: u64, port: u16) -> NetcodeClientTransport {
+    create_client_transport_with_data(client_id, port, None)
+}
+
+fn create_client_transport_with_data(
+    client_id: u64,
+    port: u16,
+    user_data: Option<[u8; NETCODE_USER_DATA_BYTES]>,
+) -> NetcodeClientTransport {
     let current_time = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap();
@@ -208,7 +390,7 @@ fn create_client_transport(client_id: u64, port: u16) -> NetcodeClientTransport
         client_id,
         protocol_id: PROTOCOL_ID,
         server_addr,
-        user_data: None,
+        user_data,
     };
 
     NetcodeClientTransport::new(current_time, authentication, socket).unwrap()
@@ -0,0 +1,25 @@
+//! Replays a corpus recorded with `FuzzCorpusPlugin`.
+//!
+//! Replace registrations with rules from your game and record a corpus into `fuzz/corpus/replay`.
+
+#![no_main]
+
+use bevy::prelude::*;
+use bevy_replicon::{client::fuzz_corpus, prelude::*};
+use libfuzzer_sys::fuzz_target;
+use serde::{Deserialize, Serialize};
+
+fuzz_target!(|data: &[u8]| {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Transform>()
+        .replicate::<Inventory>();
+
+    let _ = fuzz_corpus::replay(&mut app, data);
+});
+
+#[derive(Component, Deserialize, Serialize)]
+struct Inventory {
+    items: Vec<u16>,
+    gold: u32,
+}
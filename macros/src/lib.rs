@@ -0,0 +1,351 @@
+//! Derive macros for [`bevy_replicon`](https://docs.rs/bevy_replicon).
+//!
+//! Re-exported by `bevy_replicon`, don't depend on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, LitStr, Member, Path, Type};
+
+/// Implements `Replicate` from `#[replicate(...)]` attributes.
+///
+/// See the trait documentation in `bevy_replicon` for the list of attributes.
+#[proc_macro_derive(Replicate, attributes(replicate))]
+pub fn derive_replicate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_replicate(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_replicate(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = ReplicateAttrs::parse(&input)?;
+    let fields = ReplicatedField::parse_all(&input)?;
+    let partial = fields.iter().any(|field| field.kind != FieldKind::Regular);
+    if partial && (attrs.once || attrs.compact || attrs.with.is_some()) {
+        return Err(Error::new(
+            Span::call_site(),
+            "field attributes can't be combined with `once`, `compact` or `with`",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let register = if attrs.once {
+        quote! {
+            ::bevy_replicon::core::replication_rules::AppRuleExt::replicate_once::<Self>(app);
+        }
+    } else {
+        let mut rule_fns = if partial {
+            attrs.partial_rule_fns(&fields)
+        } else {
+            attrs.rule_fns()
+        };
+        if let Some(name) = &attrs.name {
+            rule_fns = quote! { #rule_fns.with_wire_name(#name) };
+        }
+        quote! {
+            let rule_fns = #rule_fns;
+            ::bevy_replicon::core::replication_rules::AppRuleExt::replicate_with::<Self>(app, rule_fns);
+        }
+    };
+
+    let priority = attrs.priority.map(|priority| {
+        quote! {
+            ::bevy_replicon::server::stale_priority::AppPriorityExt::set_replication_priority::<Self>(app, #priority);
+        }
+    });
+
+    let send_rate = attrs.send_rate.map(|send_rate| {
+        quote! {
+            ::bevy_replicon::server::send_rate::AppSendRateExt::set_send_rate::<Self>(app, #send_rate);
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_replicon::core::replication_rules::Replicate for #ident #ty_generics #where_clause {
+            fn register(app: &mut ::bevy::app::App) {
+                #register
+                #priority
+                #send_rate
+            }
+        }
+    })
+}
+
+/// Parsed `#[replicate(...)]` attributes.
+#[derive(Default)]
+struct ReplicateAttrs {
+    mapped: bool,
+    once: bool,
+    compact: bool,
+    codec: Option<Type>,
+    with: Option<Path>,
+    priority: Option<Expr>,
+    send_rate: Option<Expr>,
+    name: Option<LitStr>,
+}
+
+impl ReplicateAttrs {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("replicate"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("mapped") {
+                    attrs.mapped = true;
+                } else if meta.path.is_ident("once") {
+                    attrs.once = true;
+                } else if meta.path.is_ident("compact") {
+                    attrs.compact = true;
+                } else if meta.path.is_ident("codec") {
+                    attrs.codec = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("with") {
+                    attrs.with = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("priority") {
+                    attrs.priority = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("send_rate") {
+                    attrs.send_rate = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("name") {
+                    attrs.name = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error(
+                        "expected `mapped`, `once`, `compact`, `codec`, `with`, `priority`, `send_rate` or `name`",
+                    ));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        let serializers = [attrs.compact, attrs.codec.is_some(), attrs.with.is_some()]
+            .into_iter()
+            .filter(|&set| set)
+            .count();
+        if serializers > 1 {
+            return Err(Error::new(
+                Span::call_site(),
+                "only one of `compact`, `codec` or `with` can be specified",
+            ));
+        }
+        if attrs.mapped && (attrs.compact || attrs.with.is_some()) {
+            return Err(Error::new(
+                Span::call_site(),
+                "`mapped` can't be combined with `compact` or `with`, map entities inside the functions instead",
+            ));
+        }
+        if attrs.once && (attrs.mapped || serializers > 0 || attrs.name.is_some()) {
+            return Err(Error::new(
+                Span::call_site(),
+                "`once` can't be combined with other serialization attributes or `name`",
+            ));
+        }
+
+        Ok(attrs)
+    }
+
+    /// Returns an expression that creates `RuleFns` for the component.
+    fn rule_fns(&self) -> TokenStream2 {
+        let rule_fns = quote! { ::bevy_replicon::core::replication_fns::rule_fns::RuleFns::<Self> };
+        let replication_fns = quote! { ::bevy_replicon::core::replication_fns::ReplicationFns };
+        if let Some(with) = &self.with {
+            quote! { #with() }
+        } else if self.compact {
+            quote! { #rule_fns::compact() }
+        } else if let Some(codec) = &self.codec {
+            if self.mapped {
+                quote! { #rule_fns::with_codec_mapped::<#codec>() }
+            } else {
+                quote! { #rule_fns::with_codec::<#codec>() }
+            }
+        } else if self.mapped {
+            quote! { app.world.resource::<#replication_fns>().default_codec.rule_fns_mapped::<Self>() }
+        } else {
+            quote! { app.world.resource::<#replication_fns>().default_codec.rule_fns::<Self>() }
+        }
+    }
+
+    /// Returns an expression that creates `RuleFns` that skip fields marked with `skip`
+    /// and write fields marked with `once` only on insertion.
+    ///
+    /// If there are fields marked with `once`, a flag whether they are present is written first.
+    /// Without `codec` the fields are serialized with the default codec from `ReplicationFns`.
+    fn partial_rule_fns(&self, fields: &[ReplicatedField]) -> TokenStream2 {
+        let (init_codec, serialize, deserialize) = match &self.codec {
+            Some(codec) => {
+                let codec_trait =
+                    quote! { ::bevy_replicon::core::replication_fns::rule_fns::Codec };
+                (
+                    TokenStream2::new(),
+                    quote! { <#codec as #codec_trait>::serialize },
+                    quote! { <#codec as #codec_trait>::deserialize },
+                )
+            }
+            None => (
+                quote! {
+                    #[allow(unused_variables)]
+                    let codec = app
+                        .world
+                        .resource::<::bevy_replicon::core::replication_fns::ReplicationFns>()
+                        .default_codec;
+                },
+                quote! { codec.serialize },
+                quote! { codec.deserialize },
+            ),
+        };
+
+        let has_once = fields.iter().any(|field| field.kind == FieldKind::Once);
+        let (serialize_flag, deserialize_flag) = if has_once {
+            (
+                quote! { #serialize(cursor, &ctx.is_insertion)?; },
+                quote! { let with_once: bool = #deserialize(cursor)?; },
+            )
+        } else {
+            (TokenStream2::new(), TokenStream2::new())
+        };
+
+        let serialize_fields = fields.iter().map(|field| {
+            let member = &field.member;
+            match field.kind {
+                FieldKind::Regular => {
+                    quote! { #serialize(cursor, &component.#member)?; }
+                }
+                FieldKind::Skip => TokenStream2::new(),
+                FieldKind::Once => quote! {
+                    if ctx.is_insertion {
+                        #serialize(cursor, &component.#member)?;
+                    }
+                },
+            }
+        });
+
+        let deserialize_fields = fields.iter().map(|field| {
+            let member = &field.member;
+            match field.kind {
+                FieldKind::Regular => {
+                    quote! { #member: #deserialize(cursor)? }
+                }
+                FieldKind::Skip => quote! { #member: ::core::default::Default::default() },
+                FieldKind::Once => quote! {
+                    #member: if with_once {
+                        #deserialize(cursor)?
+                    } else {
+                        ::core::default::Default::default()
+                    }
+                },
+            }
+        });
+
+        let (ctx, map_entities) = if self.mapped {
+            (
+                quote! { ctx },
+                quote! { ::bevy::ecs::entity::MapEntities::map_entities(&mut component, ctx); },
+            )
+        } else {
+            (quote! { _ctx }, TokenStream2::new())
+        };
+
+        let update_fields = fields
+            .iter()
+            .filter(|field| field.kind == FieldKind::Regular)
+            .map(|field| {
+                let member = &field.member;
+                quote! { component.#member = received.#member; }
+            });
+
+        quote! {{
+            #init_codec
+            ::bevy_replicon::core::replication_fns::rule_fns::RuleFns::<Self>::new(
+                move |#[allow(unused_variables)] ctx: &::bevy_replicon::core::replication_fns::ctx::SerializeCtx,
+                 component: &Self,
+                 cursor: &mut ::std::io::Cursor<::std::vec::Vec<u8>>| {
+                    #serialize_flag
+                    #(#serialize_fields)*
+                    ::core::result::Result::Ok(())
+                },
+                move |#ctx: &mut ::bevy_replicon::core::replication_fns::ctx::WriteCtx,
+                 cursor: &mut ::std::io::Cursor<&[u8]>| {
+                    #deserialize_flag
+                    #[allow(unused_mut)]
+                    let mut component = Self { #(#deserialize_fields),* };
+                    #map_entities
+                    ::core::result::Result::Ok(component)
+                },
+            )
+            .with_in_place(
+                |deserialize: &::bevy_replicon::core::replication_fns::rule_fns::DeserializeFn<Self>,
+                 ctx: &mut ::bevy_replicon::core::replication_fns::ctx::WriteCtx,
+                 component: &mut Self,
+                 cursor: &mut ::std::io::Cursor<&[u8]>| {
+                    #[allow(unused_variables)]
+                    let received = (deserialize)(ctx, cursor)?;
+                    #(#update_fields)*
+                    ::core::result::Result::Ok(())
+                },
+            )
+        }}
+    }
+}
+
+/// A field of the component with its `#[replicate(...)]` attribute.
+struct ReplicatedField {
+    member: Member,
+    kind: FieldKind,
+}
+
+impl ReplicatedField {
+    fn parse_all(input: &DeriveInput) -> syn::Result<Vec<Self>> {
+        let fields = match &input.data {
+            Data::Struct(data) => &data.fields,
+            Data::Enum(_) | Data::Union(_) => {
+                // Field attributes are supported only for structs.
+                return Ok(Vec::new());
+            }
+        };
+
+        let mut replicated_fields = Vec::new();
+        for (field, member) in fields.iter().zip(fields.members()) {
+            let mut kind = FieldKind::Regular;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("replicate"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if kind != FieldKind::Regular {
+                        return Err(meta.error("only one field attribute can be specified"));
+                    }
+
+                    if meta.path.is_ident("skip") {
+                        kind = FieldKind::Skip;
+                    } else if meta.path.is_ident("once") {
+                        kind = FieldKind::Once;
+                    } else {
+                        return Err(meta.error("expected `skip` or `once`"));
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            replicated_fields.push(ReplicatedField { member, kind });
+        }
+
+        Ok(replicated_fields)
+    }
+}
+
+/// How a field is replicated.
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind {
+    /// Sent with every change.
+    Regular,
+    /// Never sent, initialized with [`Default`] on client.
+    Skip,
+    /// Sent only on insertion, initialized with [`Default`] on client if the component is missing.
+    Once,
+}
@@ -1,39 +1,81 @@
+pub mod async_connection;
 pub mod confirmed;
+pub mod deferred_spawn;
+pub mod despawn_grace;
 pub mod diagnostics;
+pub mod entity_allocator;
+pub mod fuzz_corpus;
+pub mod interpolation_delay;
+pub mod prediction;
+pub mod replication_ops;
 pub mod replicon_client;
+pub mod rollback_scope;
 pub mod server_entity_map;
+pub mod snapshot_interpolation;
 
-use std::{io::Cursor, mem};
+use std::{collections::VecDeque, io::Cursor, mem, time::Duration};
 
-use bevy::{ecs::system::CommandQueue, prelude::*};
-use bincode::{DefaultOptions, Options};
+use bevy::{core::FrameCount, ecs::system::CommandQueue, prelude::*, utils::HashMap};
+use bincode::Options;
 use bytes::Bytes;
-use varint_rs::VarintReader;
-
-use crate::core::{
-    command_markers::{CommandMarkers, EntityMarkers},
-    common_conditions::{client_connected, client_just_connected, client_just_disconnected},
-    replication_fns::{
-        ctx::{DespawnCtx, RemoveCtx, WriteCtx},
-        ReplicationFns,
+use varint_rs::{VarintReader, VarintWriter};
+
+use crate::{
+    core::{
+        command_markers::{CommandMarkers, EntityMarkers},
+        common_conditions::{client_connected, client_just_connected, client_just_disconnected},
+        component_encryption::{self, ClientEncryptionKey, EncryptedComponents},
+        replication_fns::{
+            ctx::{DespawnCtx, RemoveCtx, WriteCtx},
+            FnsId, ReplicationFns,
+        },
+        replicon_channels::{ReplicationChannel, RepliconChannels},
+        replicon_tick::RepliconTick,
+        wire_format, Replicated,
     },
-    replicon_channels::{ReplicationChannel, RepliconChannels},
-    replicon_tick::RepliconTick,
-    Replicated,
+    server::TickJumped,
 };
 use confirmed::Confirmed;
-use diagnostics::ClientStats;
+use deferred_spawn::{DeferredSpawn, PendingEntities, PendingEntity, ReplicationPending};
+use diagnostics::{ClientLatency, ClientStats};
+use entity_allocator::{ClientEntityAllocator, EntityAllocator};
+use replication_ops::{ReplicationOp, ReplicationOpKind};
 use replicon_client::RepliconClient;
 use server_entity_map::ServerEntityMap;
 
-pub struct ClientPlugin;
+#[derive(Default)]
+pub struct ClientPlugin {
+    /// How long received update messages are held before applying.
+    ///
+    /// Entity state never goes back in ticks, even if multiple messages arrive in one frame or out of
+    /// order at the transport level. Buffered updates are sorted by tick and the latest one is applied
+    /// first, older data is dropped per entity unless history is requested with
+    /// [`MarkerConfig::need_history`](crate::core::command_markers::MarkerConfig::need_history).
+    /// Data for ticks older than the latest applied for an entity is also dropped when it arrives
+    /// in a later frame.
+    ///
+    /// A non-zero window holds all received updates until the oldest of them has waited for the window
+    /// and then applies them together from the oldest tick to the latest. This way updates that arrive
+    /// out of order within the window are applied in tick order instead of being dropped as outdated,
+    /// at the cost of additional latency.
+    ///
+    /// By default it's zero, so updates are applied in the same frame they were received.
+    ///
+    /// Can be changed at runtime via [`ClientSettings`].
+    pub reorder_window: Duration,
+}
 
 impl Plugin for ClientPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<RepliconClient>()
+            .insert_resource(ClientSettings {
+                reorder_window: self.reorder_window,
+            })
             .init_resource::<ServerEntityMap>()
             .init_resource::<ServerInitTick>()
+            .init_resource::<ServerUpdateTick>()
             .init_resource::<BufferedUpdates>()
+            .init_resource::<PendingEntities>()
             .configure_sets(
                 PreUpdate,
                 (
@@ -53,8 +95,10 @@ impl Plugin for ClientPlugin {
             .add_systems(Startup, Self::setup_channels)
             .add_systems(
                 PreUpdate,
-                Self::receive_replication
-                    .map(Result::unwrap)
+                (
+                    Self::receive_replication.map(Result::unwrap),
+                    Self::receive_tick_jumps,
+                )
                     .in_set(ClientSet::Receive)
                     .run_if(client_connected),
             )
@@ -67,6 +111,21 @@ impl ClientPlugin {
         client.setup_server_channels(channels.server_channels().len());
     }
 
+    /// Emits [`TickJumped`] events received over [`ReplicationChannel::TickJumps`].
+    fn receive_tick_jumps(
+        mut client: ResMut<RepliconClient>,
+        mut jumped_events: EventWriter<TickJumped>,
+    ) {
+        for message in client.receive(ReplicationChannel::TickJumps) {
+            match wire_format::varint().deserialize(&message) {
+                Ok(event) => {
+                    jumped_events.send(event);
+                }
+                Err(e) => error!("unable to deserialize tick jump: {e}"),
+            }
+        }
+    }
+
     /// Receives and applies replication messages from the server.
     ///
     /// Tick init messages are sent over the [`ReplicationChannel::Init`] and are applied first to ensure valid state
@@ -91,30 +150,78 @@ impl ClientPlugin {
         world.resource_scope(|world, mut client: Mut<RepliconClient>| {
             world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
                 world.resource_scope(|world, mut buffered_updates: Mut<BufferedUpdates>| {
-                    world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
-                        world.resource_scope(|world, replication_fns: Mut<ReplicationFns>| {
-                            let mut stats = world.remove_resource::<ClientStats>();
-                            let mut params = ReceiveParams {
-                                queue: &mut queue,
-                                entity_markers: &mut entity_markers,
-                                entity_map: &mut entity_map,
-                                stats: stats.as_mut(),
-                                command_markers: &command_markers,
-                                replication_fns: &replication_fns,
-                            };
-
-                            apply_replication(
-                                world,
-                                &mut params,
-                                &mut client,
-                                &mut buffered_updates,
-                            )?;
-
-                            if let Some(stats) = stats {
-                                world.insert_resource(stats);
-                            }
-
-                            Ok(())
+                    world.resource_scope(|world, mut pending_entities: Mut<PendingEntities>| {
+                        world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
+                            world.resource_scope(|world, replication_fns: Mut<ReplicationFns>| {
+                                let mut stats = world.remove_resource::<ClientStats>();
+                                let mut latency = world.remove_resource::<ClientLatency>();
+                                let mut ops = world.remove_resource::<Events<ReplicationOp>>();
+                                let encrypted_components =
+                                    world.remove_resource::<EncryptedComponents>();
+                                let mut allocator =
+                                    world.remove_resource::<ClientEntityAllocator>();
+                                let encryption_key = world
+                                    .get_resource::<ClientEncryptionKey>()
+                                    .and_then(|key| **key);
+                                let now = world
+                                    .get_resource::<Time<Real>>()
+                                    .map(|time| time.elapsed())
+                                    .unwrap_or_default();
+                                let frame = world
+                                    .get_resource::<FrameCount>()
+                                    .map(|frame_count| frame_count.0)
+                                    .unwrap_or_default();
+                                let deferred_spawn = world.get_resource::<DeferredSpawn>().copied();
+                                let reorder_window = world
+                                    .get_resource::<ClientSettings>()
+                                    .map(|settings| settings.reorder_window)
+                                    .unwrap_or_default();
+                                let mut params = ReceiveParams {
+                                    queue: &mut queue,
+                                    entity_markers: &mut entity_markers,
+                                    entity_map: &mut entity_map,
+                                    pending_entities: &mut pending_entities,
+                                    stats: stats.as_mut(),
+                                    latency: latency.as_mut(),
+                                    ops: ops.as_mut(),
+                                    encrypted_components: encrypted_components.as_ref(),
+                                    encryption_key,
+                                    now,
+                                    frame,
+                                    deferred_spawn,
+                                    reorder_window,
+                                    allocator: allocator
+                                        .as_mut()
+                                        .map(|allocator| &mut *allocator.0 as _),
+                                    command_markers: &command_markers,
+                                    replication_fns: &replication_fns,
+                                };
+
+                                apply_replication(
+                                    world,
+                                    &mut params,
+                                    &mut client,
+                                    &mut buffered_updates,
+                                )?;
+
+                                if let Some(stats) = stats {
+                                    world.insert_resource(stats);
+                                }
+                                if let Some(latency) = latency {
+                                    world.insert_resource(latency);
+                                }
+                                if let Some(ops) = ops {
+                                    world.insert_resource(ops);
+                                }
+                                if let Some(encrypted_components) = encrypted_components {
+                                    world.insert_resource(encrypted_components);
+                                }
+                                if let Some(allocator) = allocator {
+                                    world.insert_resource(allocator);
+                                }
+
+                                Ok(())
+                            })
                         })
                     })
                 })
@@ -124,12 +231,56 @@ impl ClientPlugin {
 
     fn reset(
         mut init_tick: ResMut<ServerInitTick>,
+        mut update_tick: ResMut<ServerUpdateTick>,
         mut entity_map: ResMut<ServerEntityMap>,
         mut buffered_updates: ResMut<BufferedUpdates>,
+        mut pending_entities: ResMut<PendingEntities>,
+        latency: Option<ResMut<ClientLatency>>,
     ) {
         *init_tick = Default::default();
+        *update_tick = Default::default();
         entity_map.clear();
         buffered_updates.clear();
+        pending_entities.clear();
+        if let Some(mut latency) = latency {
+            *latency = Default::default();
+        }
+    }
+
+    /// Despawns all replicated entities and discards received data, keeping the connection and ticks.
+    ///
+    /// Used to receive the world from scratch, see [`ProtocolReloadPlugin`](crate::protocol_reload::ProtocolReloadPlugin).
+    pub(crate) fn despawn_replicated(world: &mut World) {
+        let entities: Vec<_> = world
+            .resource::<ServerEntityMap>()
+            .to_client()
+            .values()
+            .copied()
+            .collect();
+        world.resource_mut::<ServerEntityMap>().clear();
+        world.resource_mut::<BufferedUpdates>().clear();
+        world.resource_mut::<PendingEntities>().clear();
+
+        let ctx = DespawnCtx {
+            message_tick: **world.resource::<ServerInitTick>(),
+        };
+        let despawn = world.resource::<ReplicationFns>().despawn;
+        debug!("despawning {} replicated entities", entities.len());
+        for entity in entities {
+            // Could be already despawned together with the parent.
+            if let Some(client_entity) = world.get_entity_mut(entity) {
+                (despawn)(&ctx, client_entity);
+            }
+        }
+    }
+
+    /// Same as [`Self::despawn_replicated`], but also resets received ticks.
+    ///
+    /// Used to receive the world from another server, see [`MatchHostPlugin`](crate::server::match_host::MatchHostPlugin).
+    pub(crate) fn reset_replicated(world: &mut World) {
+        Self::despawn_replicated(world);
+        *world.resource_mut::<ServerInitTick>() = Default::default();
+        *world.resource_mut::<ServerUpdateTick>() = Default::default();
     }
 }
 
@@ -142,6 +293,10 @@ fn apply_replication(
     client: &mut RepliconClient,
     buffered_updates: &mut BufferedUpdates,
 ) -> bincode::Result<()> {
+    if let Some(deferred_spawn) = params.deferred_spawn {
+        apply_pending_entities(world, params, deferred_spawn.max_bytes_per_frame)?;
+    }
+
     for message in client.receive(ReplicationChannel::Init) {
         apply_init_message(world, params, &message)?;
     }
@@ -156,7 +311,12 @@ fn apply_replication(
     let mut acks = Vec::with_capacity(acks_size);
     for message in client.receive(ReplicationChannel::Update) {
         let update_index = read_update_message(params, buffered_updates, message)?;
-        bincode::serialize_into(&mut acks, &update_index)?;
+        acks.write_u16_varint(update_index)?;
+    }
+    for message in client.receive(ReplicationChannel::UpdateChunks) {
+        if let Some(update_index) = read_update_chunk(params, buffered_updates, message)? {
+            acks.write_u16_varint(update_index)?;
+        }
     }
     client.send(ReplicationChannel::Init, acks);
 
@@ -176,9 +336,12 @@ fn apply_init_message(
         stats.bytes += end_pos;
     }
 
-    let message_tick = bincode::deserialize_from(&mut cursor)?;
+    let message_tick = wire_format::fixint().deserialize_from(&mut cursor)?;
     trace!("applying init message for {message_tick:?}");
     world.resource_mut::<ServerInitTick>().0 = message_tick;
+    world
+        .resource_mut::<ServerUpdateTick>()
+        .record(message_tick, params.frame);
     debug_assert!(cursor.position() < end_pos, "init message can't be empty");
 
     apply_entity_mappings(world, params, &mut cursor)?;
@@ -228,34 +391,121 @@ fn read_update_message(
         stats.bytes += end_pos;
     }
 
-    let (init_tick, message_tick, update_index) = bincode::deserialize_from(&mut cursor)?;
+    let (init_tick, message_tick, send_time, update_index) = read_update_header(&mut cursor)?;
     trace!("received update message for {message_tick:?}");
     buffered_updates.insert(BufferedUpdate {
         init_tick,
         message_tick,
+        send_time,
+        received_at: params.now,
         message: message.slice(cursor.position() as usize..),
     });
 
     Ok(update_index)
 }
 
+/// Reads and buffers a chunk of [`UpdateMessage`](crate::server::replication_messages::UpdateMessage).
+///
+/// Returns update index to be used for acknowledgment if all chunks were received.
+fn read_update_chunk(
+    params: &mut ReceiveParams,
+    buffered_updates: &mut BufferedUpdates,
+    message: Bytes,
+) -> bincode::Result<Option<u16>> {
+    let end_pos: u64 = message.len().try_into().unwrap();
+    let mut cursor = Cursor::new(&*message);
+    if let Some(stats) = &mut params.stats {
+        stats.packets += 1;
+        stats.bytes += end_pos;
+    }
+
+    let (init_tick, message_tick, send_time, update_index) = read_update_header(&mut cursor)?;
+    let chunk_index = cursor.read_u16_varint()?;
+    let chunks_count = cursor.read_u16_varint()?;
+    trace!("received update chunk {chunk_index}/{chunks_count} for {message_tick:?}");
+    let Some(message) = buffered_updates.insert_chunk(
+        message_tick,
+        update_index,
+        chunk_index,
+        chunks_count,
+        message.slice(cursor.position() as usize..),
+    ) else {
+        return Ok(None);
+    };
+
+    buffered_updates.insert(BufferedUpdate {
+        init_tick,
+        message_tick,
+        send_time,
+        received_at: params.now,
+        message,
+    });
+
+    Ok(Some(update_index))
+}
+
+/// Reads header of [`UpdateMessage`](crate::server::replication_messages::UpdateMessage).
+///
+/// Returns init tick, message tick, send time if present and update index.
+fn read_update_header(
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<(RepliconTick, RepliconTick, Option<u32>, u16)> {
+    let message_tick: RepliconTick = wire_format::fixint().deserialize_from(&mut *cursor)?;
+    let tick_diff = cursor.read_u64_varint()?;
+    let init_tick = RepliconTick::new(message_tick.get().wrapping_sub((tick_diff >> 1) as u32));
+    let send_time = if tick_diff & 1 != 0 {
+        Some(wire_format::fixint().deserialize_from(&mut *cursor)?)
+    } else {
+        None
+    };
+    let update_index = cursor.read_u16_varint()?;
+
+    Ok((init_tick, message_tick, send_time, update_index))
+}
+
 /// Applies updates from [`BufferedUpdates`].
 ///
 /// If the update message can't be applied yet (because the init message with the
 /// corresponding tick hasn't arrived), it will be kept in the buffer.
+/// All other updates are kept until [`ClientSettings::reorder_window`] elapses for the oldest of them.
 fn apply_update_messages(
     world: &mut World,
     params: &mut ReceiveParams,
     buffered_updates: &mut BufferedUpdates,
     init_tick: ServerInitTick,
 ) -> bincode::Result<()> {
-    let mut result = Ok(());
-    buffered_updates.0.retain(|update| {
-        if update.init_tick > *init_tick {
-            return true;
-        }
+    // Updates are released together once the oldest applicable one has waited for the window,
+    // so updates that arrived later but have older ticks are applied in the same pass.
+    let oldest_received = buffered_updates
+        .updates
+        .iter()
+        .filter(|update| update.init_tick <= *init_tick)
+        .map(|update| update.received_at)
+        .min();
+    if oldest_received
+        .is_none_or(|received_at| params.now.saturating_sub(received_at) < params.reorder_window)
+    {
+        return Ok(());
+    }
+
+    let (mut ready, pending): (Vec<_>, _) = mem::take(&mut buffered_updates.updates)
+        .into_iter()
+        .partition(|update| update.init_tick <= *init_tick);
+    buffered_updates.updates = pending;
+    if !params.reorder_window.is_zero() {
+        // Apply from the oldest tick, so updates that arrived out of order aren't dropped as outdated.
+        ready.reverse();
+    }
 
+    let mut result = Ok(());
+    for update in ready {
         trace!("applying update message for {:?}", update.message_tick);
+        world
+            .resource_mut::<ServerUpdateTick>()
+            .record(update.message_tick, params.frame);
+        if let (Some(latency), Some(send_time)) = (&mut params.latency, update.send_time) {
+            latency.record(send_time, params.now);
+        }
         if let Err(e) = apply_update_components(
             world,
             params,
@@ -264,20 +514,37 @@ fn apply_update_messages(
         ) {
             result = Err(e);
         }
-
-        false
-    });
+    }
 
     result
 }
 
+/// Returns a new client entity from [`ClientEntityAllocator`] or spawns an empty one if not set.
+fn allocate_entity<'w>(
+    world: &'w mut World,
+    queue: &mut CommandQueue,
+    allocator: Option<&mut dyn EntityAllocator>,
+) -> EntityWorldMut<'w> {
+    if let Some(allocator) = allocator {
+        let mut commands = Commands::new_from_entities(queue, world.entities());
+        let entity = allocator.allocate(&mut commands);
+        queue.apply(world);
+        if world.get_entity(entity).is_some() {
+            return world.entity_mut(entity);
+        }
+        warn!("allocated {entity:?} doesn't exist, spawning a new entity instead");
+    }
+
+    world.spawn_empty()
+}
+
 /// Applies received server mappings from client's pre-spawned entities.
 fn apply_entity_mappings(
     world: &mut World,
     params: &mut ReceiveParams,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<()> {
-    let mappings_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    let mappings_len = cursor.read_u16_varint()?;
     if let Some(stats) = &mut params.stats {
         stats.mappings += mappings_len as u32;
     }
@@ -305,14 +572,40 @@ fn apply_init_components(
     cursor: &mut Cursor<&[u8]>,
     message_tick: RepliconTick,
 ) -> bincode::Result<()> {
-    let entities_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    let entities_len = cursor.read_u16_varint()?;
     for _ in 0..entities_len {
         let server_entity = deserialize_entity(cursor)?;
-        let data_size: u16 = bincode::deserialize_from(&mut *cursor)?;
+        let data_size = cursor.read_u16_varint()?;
 
+        let defer = matches!(components_kind, ComponentsKind::Insert)
+            && params
+                .deferred_spawn
+                .is_some_and(|deferred_spawn| data_size as usize >= deferred_spawn.size_threshold);
+        let mut spawned = false;
         let client_entity = params
             .entity_map
-            .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
+            .get_by_server_or_insert(server_entity, || {
+                spawned = true;
+                let mut entity =
+                    allocate_entity(world, params.queue, params.allocator.as_deref_mut());
+                if defer {
+                    entity.insert((Replicated, ReplicationPending));
+                } else {
+                    entity.insert(Replicated);
+                }
+                entity.id()
+            });
+        if spawned {
+            record_op(
+                params.ops.as_deref_mut(),
+                message_tick,
+                ReplicationOpKind::Spawn {
+                    entity: client_entity,
+                },
+            );
+        } else {
+            flush_pending_entity(world, params, client_entity)?;
+        }
 
         let world_cell = world.as_unsafe_world_cell();
         // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
@@ -332,26 +625,73 @@ fn apply_init_components(
         }
 
         let end_pos = cursor.position() + data_size as u64;
+        if defer && spawned {
+            let data = &cursor.get_ref()[cursor.position() as usize..end_pos as usize];
+            params.pending_entities.push(PendingEntity {
+                entity: client_entity.id(),
+                message_tick,
+                data: data.to_vec(),
+                position: 0,
+            });
+            cursor.set_position(end_pos);
+
+            if let Some(stats) = &mut params.stats {
+                stats.entities_changed += 1;
+            }
+
+            params.queue.apply(world);
+            continue;
+        }
+
         let mut components_len = 0u32;
         while cursor.position() < end_pos {
-            let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            let fns_id = wire_format::varint().deserialize_from(&mut *cursor)?;
             let (component_fns, rule_fns) = params.replication_fns.get(fns_id);
             match components_kind {
                 ComponentsKind::Insert => {
-                    let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick);
-
-                    // SAFETY: `rule_fns` and `component_fns` were created for the same type.
-                    unsafe {
-                        component_fns.write(
-                            &mut ctx,
-                            rule_fns,
-                            params.entity_markers,
-                            &mut client_entity,
-                            cursor,
-                        )?;
-                    }
+                    let decrypted = match decrypt_component(
+                        params.replication_fns,
+                        params.encrypted_components,
+                        params.encryption_key,
+                        fns_id,
+                        cursor,
+                    )? {
+                        ComponentData::Plain => None,
+                        ComponentData::Decrypted(data) => Some(data),
+                        ComponentData::Undecryptable => continue,
+                    };
+                    record_write(
+                        params.ops.as_deref_mut(),
+                        params.replication_fns,
+                        message_tick,
+                        &client_entity,
+                        fns_id,
+                    );
+                    let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick)
+                        .with_allocator(params.allocator.as_deref_mut());
+
+                    with_component_cursor(cursor, decrypted, |cursor| {
+                        // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+                        unsafe {
+                            component_fns.write(
+                                &mut ctx,
+                                rule_fns,
+                                params.entity_markers,
+                                &mut client_entity,
+                                cursor,
+                            )
+                        }
+                    })?;
                 }
                 ComponentsKind::Removal => {
+                    record_op(
+                        params.ops.as_deref_mut(),
+                        message_tick,
+                        ReplicationOpKind::Remove {
+                            entity: client_entity.id(),
+                            component_id: params.replication_fns.component_id(fns_id),
+                        },
+                    );
                     let mut ctx = RemoveCtx::new(&mut commands, message_tick);
                     component_fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
                 }
@@ -377,10 +717,11 @@ fn apply_despawns(
     cursor: &mut Cursor<&[u8]>,
     message_tick: RepliconTick,
 ) -> bincode::Result<()> {
-    let entities_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    let entities_len = cursor.read_u16_varint()?;
     if let Some(stats) = &mut params.stats {
         stats.despawns += entities_len as u32;
     }
+    let mut descendants = Vec::new();
     for _ in 0..entities_len {
         // The entity might have already been despawned because of hierarchy or
         // with the last replication message, but the server might not yet have received confirmation
@@ -389,16 +730,50 @@ fn apply_despawns(
         if let Some(client_entity) = params
             .entity_map
             .remove_by_server(server_entity)
+            .inspect(|&entity| {
+                params.pending_entities.take(entity);
+            })
             .and_then(|entity| world.get_entity_mut(entity))
         {
+            record_op(
+                params.ops.as_deref_mut(),
+                message_tick,
+                ReplicationOpKind::Despawn {
+                    entity: client_entity.id(),
+                },
+            );
+
+            descendants.clear();
+            collect_descendants(client_entity.world(), client_entity.id(), &mut descendants);
+
             let ctx = DespawnCtx { message_tick };
             (params.replication_fns.despawn)(&ctx, client_entity);
+
+            // Remove mappings of descendants that were despawned together with the entity,
+            // since their despawns might never arrive.
+            for &entity in &descendants {
+                if world.get_entity(entity).is_none()
+                    && params.entity_map.remove_by_client(entity).is_some()
+                {
+                    params.pending_entities.take(entity);
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Collects all children of the entity recursively.
+fn collect_descendants(world: &World, entity: Entity, descendants: &mut Vec<Entity>) {
+    if let Some(children) = world.get::<Children>(entity) {
+        for &child in children {
+            descendants.push(child);
+            collect_descendants(world, child, descendants);
+        }
+    }
+}
+
 ///  Deserializes replicated component updates and applies them to the `world`.
 ///
 /// Consumes all remaining bytes in the cursor.
@@ -411,7 +786,7 @@ fn apply_update_components(
     let message_end = cursor.get_ref().len() as u64;
     while cursor.position() < message_end {
         let server_entity = deserialize_entity(cursor)?;
-        let data_size: u16 = bincode::deserialize_from(&mut *cursor)?;
+        let data_size = cursor.read_u16_varint()?;
 
         let Some(client_entity) = params.entity_map.get_by_server(server_entity) else {
             // Update could arrive after a despawn from init message.
@@ -419,6 +794,10 @@ fn apply_update_components(
             cursor.set_position(cursor.position() + data_size as u64);
             continue;
         };
+        flush_pending_entity(world, params, client_entity)?;
+        if let Some(stats) = &mut params.stats {
+            stats.entity_updates += 1;
+        }
 
         let world_cell = world.as_unsafe_world_cell();
         // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
@@ -436,11 +815,19 @@ fn apply_update_components(
         if new_entity {
             confirmed.set_last_tick(message_tick);
         } else {
-            if !params.entity_markers.need_history() {
+            let duplicate = message_tick == confirmed.last_tick();
+            if !params.entity_markers.need_history() || duplicate {
                 trace!(
                     "ignoring outdated update for client's {:?}",
                     client_entity.id()
                 );
+                if let Some(stats) = &mut params.stats {
+                    if duplicate {
+                        stats.duplicate_updates += 1;
+                    } else {
+                        stats.outdated_updates += 1;
+                    }
+                }
                 cursor.set_position(cursor.position() + data_size as u64);
                 continue;
             }
@@ -451,6 +838,9 @@ fn apply_update_components(
                     "discarding update {ago} ticks old for client's {:?}",
                     client_entity.id()
                 );
+                if let Some(stats) = &mut params.stats {
+                    stats.outdated_updates += 1;
+                }
                 cursor.set_position(cursor.position() + data_size as u64);
                 continue;
             }
@@ -461,31 +851,54 @@ fn apply_update_components(
         let end_pos = cursor.position() + data_size as u64;
         let mut components_count = 0u32;
         while cursor.position() < end_pos {
-            let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            let fns_id = wire_format::varint().deserialize_from(&mut *cursor)?;
             let (component_fns, rule_fns) = params.replication_fns.get(fns_id);
-            let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick);
+            let decrypted = match decrypt_component(
+                params.replication_fns,
+                params.encrypted_components,
+                params.encryption_key,
+                fns_id,
+                cursor,
+            )? {
+                ComponentData::Plain => None,
+                ComponentData::Decrypted(data) => Some(data),
+                ComponentData::Undecryptable => continue,
+            };
+            if new_entity {
+                record_write(
+                    params.ops.as_deref_mut(),
+                    params.replication_fns,
+                    message_tick,
+                    &client_entity,
+                    fns_id,
+                );
+            }
+            let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick)
+                .with_allocator(params.allocator.as_deref_mut());
 
-            // SAFETY: `rule_fns` and `component_fns` were created for the same type.
-            unsafe {
-                if new_entity {
-                    component_fns.write(
-                        &mut ctx,
-                        rule_fns,
-                        params.entity_markers,
-                        &mut client_entity,
-                        cursor,
-                    )?;
-                } else {
-                    component_fns.consume_or_write(
-                        &mut ctx,
-                        rule_fns,
-                        params.entity_markers,
-                        params.command_markers,
-                        &mut client_entity,
-                        cursor,
-                    )?;
+            with_component_cursor(cursor, decrypted, |cursor| {
+                // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+                unsafe {
+                    if new_entity {
+                        component_fns.write(
+                            &mut ctx,
+                            rule_fns,
+                            params.entity_markers,
+                            &mut client_entity,
+                            cursor,
+                        )
+                    } else {
+                        component_fns.consume_or_write(
+                            &mut ctx,
+                            rule_fns,
+                            params.entity_markers,
+                            params.command_markers,
+                            &mut client_entity,
+                            cursor,
+                        )
+                    }
                 }
-            }
+            })?;
 
             components_count += 1;
         }
@@ -501,6 +914,188 @@ fn apply_update_components(
     Ok(())
 }
 
+/// Inserts components of entities from [`PendingEntities`] until `max_bytes` are applied.
+fn apply_pending_entities(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    max_bytes: usize,
+) -> bincode::Result<()> {
+    let mut budget = max_bytes;
+    while budget > 0 {
+        let Some(mut pending_entity) = params.pending_entities.pop_front() else {
+            break;
+        };
+
+        let applied = apply_pending_components(world, params, &mut pending_entity, Some(budget))?;
+        budget = budget.saturating_sub(applied);
+        if pending_entity.position < pending_entity.data.len() as u64 {
+            params.pending_entities.push_front(pending_entity);
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts all remaining components if the entity is pending.
+///
+/// Used to preserve the ordering when a new message for the entity arrives.
+fn flush_pending_entity(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    entity: Entity,
+) -> bincode::Result<()> {
+    if let Some(mut pending_entity) = params.pending_entities.take(entity) {
+        apply_pending_components(world, params, &mut pending_entity, None)?;
+    }
+
+    Ok(())
+}
+
+/// Inserts components from a pending entity.
+///
+/// If `budget` is specified, stops after applying the specified number of bytes,
+/// but applies at least one component.
+/// Removes [`ReplicationPending`] if all components were inserted.
+///
+/// Returns the number of applied bytes.
+fn apply_pending_components(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    pending_entity: &mut PendingEntity,
+    budget: Option<usize>,
+) -> bincode::Result<usize> {
+    let end_pos = pending_entity.data.len() as u64;
+    if world.get_entity(pending_entity.entity).is_none() {
+        // Entity could be despawned on client already.
+        pending_entity.position = end_pos;
+        return Ok(0);
+    }
+
+    let world_cell = world.as_unsafe_world_cell();
+    // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
+    let mut client_entity: EntityMut = unsafe {
+        world_cell
+            .world_mut()
+            .entity_mut(pending_entity.entity)
+            .into()
+    };
+    let mut commands = Commands::new_from_entities(params.queue, world_cell.entities());
+    params
+        .entity_markers
+        .read(params.command_markers, &client_entity);
+
+    let mut cursor = Cursor::new(&*pending_entity.data);
+    cursor.set_position(pending_entity.position);
+    let mut components_count = 0u32;
+    while cursor.position() < end_pos
+        && budget.is_none_or(|budget| cursor.position() - pending_entity.position < budget as u64)
+    {
+        let fns_id = wire_format::varint().deserialize_from(&mut cursor)?;
+        let (component_fns, rule_fns) = params.replication_fns.get(fns_id);
+        let decrypted = match decrypt_component(
+            params.replication_fns,
+            params.encrypted_components,
+            params.encryption_key,
+            fns_id,
+            &mut cursor,
+        )? {
+            ComponentData::Plain => None,
+            ComponentData::Decrypted(data) => Some(data),
+            ComponentData::Undecryptable => continue,
+        };
+        record_write(
+            params.ops.as_deref_mut(),
+            params.replication_fns,
+            pending_entity.message_tick,
+            &client_entity,
+            fns_id,
+        );
+        let mut ctx = WriteCtx::new(
+            &mut commands,
+            params.entity_map,
+            pending_entity.message_tick,
+        )
+        .with_allocator(params.allocator.as_deref_mut());
+
+        with_component_cursor(&mut cursor, decrypted, |cursor| {
+            // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+            unsafe {
+                component_fns.write(
+                    &mut ctx,
+                    rule_fns,
+                    params.entity_markers,
+                    &mut client_entity,
+                    cursor,
+                )
+            }
+        })?;
+
+        components_count += 1;
+    }
+
+    let applied = cursor.position() - pending_entity.position;
+    pending_entity.position = cursor.position();
+    if pending_entity.position == end_pos {
+        commands
+            .entity(pending_entity.entity)
+            .remove::<ReplicationPending>();
+    }
+
+    if let Some(stats) = &mut params.stats {
+        stats.components_changed += components_count;
+    }
+
+    params.queue.apply(world);
+
+    Ok(applied as usize)
+}
+
+/// Component data after decryption.
+enum ComponentData {
+    /// Component isn't encrypted, its data should be read from the message.
+    Plain,
+    Decrypted(Vec<u8>),
+    /// Component was encrypted with a different key or tampered and should be skipped.
+    Undecryptable,
+}
+
+/// Reads and decrypts the component if it's marked as encrypted.
+///
+/// See also [`ComponentEncryptionPlugin`](crate::core::component_encryption::ComponentEncryptionPlugin).
+fn decrypt_component(
+    replication_fns: &ReplicationFns,
+    encrypted_components: Option<&EncryptedComponents>,
+    encryption_key: Option<[u8; 32]>,
+    fns_id: FnsId,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<ComponentData> {
+    let component_id = replication_fns.component_id(fns_id);
+    if !encrypted_components.is_some_and(|components| components.contains(component_id)) {
+        return Ok(ComponentData::Plain);
+    }
+
+    let encrypted: Vec<u8> = wire_format::varint().deserialize_from(&mut *cursor)?;
+    match encryption_key.and_then(|key| component_encryption::decrypt(&key, fns_id, &encrypted)) {
+        Some(data) => Ok(ComponentData::Decrypted(data)),
+        None => {
+            debug!("skipping undecryptable {component_id:?}");
+            Ok(ComponentData::Undecryptable)
+        }
+    }
+}
+
+/// Calls `f` with a cursor over the decrypted data or with the message cursor if the component isn't encrypted.
+fn with_component_cursor(
+    cursor: &mut Cursor<&[u8]>,
+    decrypted: Option<Vec<u8>>,
+    f: impl FnOnce(&mut Cursor<&[u8]>) -> bincode::Result<()>,
+) -> bincode::Result<()> {
+    match decrypted {
+        Some(data) => f(&mut Cursor::new(&data)),
+        None => f(cursor),
+    }
+}
+
 /// Deserializes `entity` from compressed index and generation.
 ///
 /// For details see
@@ -526,11 +1121,57 @@ struct ReceiveParams<'a> {
     queue: &'a mut CommandQueue,
     entity_markers: &'a mut EntityMarkers,
     entity_map: &'a mut ServerEntityMap,
+    pending_entities: &'a mut PendingEntities,
     stats: Option<&'a mut ClientStats>,
+    latency: Option<&'a mut ClientLatency>,
+    ops: Option<&'a mut Events<ReplicationOp>>,
+    encrypted_components: Option<&'a EncryptedComponents>,
+    encryption_key: Option<[u8; 32]>,
+    now: Duration,
+    frame: u32,
+    deferred_spawn: Option<DeferredSpawn>,
+    reorder_window: Duration,
+    allocator: Option<&'a mut dyn EntityAllocator>,
     command_markers: &'a CommandMarkers,
     replication_fns: &'a ReplicationFns,
 }
 
+/// Emits [`ReplicationOp`] if [`ReplicationOpsPlugin`](replication_ops::ReplicationOpsPlugin) is added.
+fn record_op(ops: Option<&mut Events<ReplicationOp>>, tick: RepliconTick, kind: ReplicationOpKind) {
+    if let Some(ops) = ops {
+        ops.send(ReplicationOp { tick, kind });
+    }
+}
+
+/// Emits an insertion or a change of the component depending on its presence on the entity.
+fn record_write(
+    ops: Option<&mut Events<ReplicationOp>>,
+    replication_fns: &ReplicationFns,
+    tick: RepliconTick,
+    entity: &EntityMut,
+    fns_id: FnsId,
+) {
+    let Some(ops) = ops else {
+        return;
+    };
+
+    let component_id = replication_fns.component_id(fns_id);
+    let contains = entity.contains_id(component_id);
+    let entity = entity.id();
+    let kind = if contains {
+        ReplicationOpKind::Change {
+            entity,
+            component_id,
+        }
+    } else {
+        ReplicationOpKind::Insert {
+            entity,
+            component_id,
+        }
+    };
+    ops.send(ReplicationOp { tick, kind });
+}
+
 /// Type of components replication.
 ///
 /// Parameter for [`apply_components`].
@@ -595,25 +1236,156 @@ pub enum ClientSet {
 #[derive(Clone, Copy, Debug, Default, Deref, Resource)]
 pub struct ServerInitTick(RepliconTick);
 
+/// Latest server tick of applied replication, including component changes.
+///
+/// Unlike [`ServerInitTick`], also updated by update messages. Updated during [`ClientSet::Receive`]
+/// together with the applied data, so systems after it see the world state for this tick, which is
+/// useful for input reconciliation.
+///
+/// Also stores local frames from [`FrameCount`] in which recent ticks were applied.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct ServerUpdateTick {
+    tick: RepliconTick,
+
+    /// Recently applied ticks with their frames, sorted by tick.
+    frames: VecDeque<(RepliconTick, u32)>,
+}
+
+impl ServerUpdateTick {
+    /// Maximum number of stored frames.
+    pub const MAX_FRAMES: usize = 64;
+
+    /// Returns the latest applied tick.
+    pub fn get(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the local frame in which the tick was applied for the first time.
+    ///
+    /// Returns [`None`] if no data for the tick was applied or it's too old.
+    pub fn frame(&self, tick: RepliconTick) -> Option<u32> {
+        self.frames
+            .iter()
+            .find(|&&(frame_tick, _)| frame_tick == tick)
+            .map(|&(_, frame)| frame)
+    }
+
+    /// Records the frame for an applied tick and updates the latest tick if it's newer.
+    fn record(&mut self, tick: RepliconTick, frame: u32) {
+        if tick > self.tick {
+            self.tick = tick;
+        }
+
+        let index = self
+            .frames
+            .iter()
+            .rposition(|&(frame_tick, _)| frame_tick <= tick);
+        match index {
+            Some(index) if self.frames[index].0 == tick => (),
+            Some(index) => self.frames.insert(index + 1, (tick, frame)),
+            None => self.frames.push_front((tick, frame)),
+        }
+
+        if self.frames.len() > Self::MAX_FRAMES {
+            self.frames.pop_front();
+        }
+    }
+}
+
 /// All cached buffered updates, used by the replicon client to align replication updates with initialization
 /// messages.
 ///
+/// Also stores chunks of updates that don't fit into a single packet until all chunks arrive.
+///
 /// If [`ClientSet::Reset`] is disabled, then this needs to be cleaned up manually with [`Self::clear`].
 #[derive(Default, Resource)]
-pub struct BufferedUpdates(Vec<BufferedUpdate>);
+pub struct BufferedUpdates {
+    updates: Vec<BufferedUpdate>,
+    chunks: HashMap<(RepliconTick, u16), ChunkedUpdate>,
+}
 
 impl BufferedUpdates {
+    /// Number of ticks after which incomplete chunked updates will be discarded.
+    const MAX_CHUNKS_AGE: u32 = 64;
+
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.updates.clear();
+        self.chunks.clear();
     }
 
     /// Inserts a new update, maintaining sorting by their message tick in descending order.
     fn insert(&mut self, update: BufferedUpdate) {
         let index = self
-            .0
+            .updates
             .partition_point(|other_update| update.message_tick < other_update.message_tick);
-        self.0.insert(index, update);
+        self.updates.insert(index, update);
     }
+
+    /// Stores a chunk of an update and returns the reassembled update data if all chunks were received.
+    ///
+    /// Discards incomplete updates that are too old since the missing chunks won't arrive.
+    /// The server will send the data again with a new update index until it will be acknowledged.
+    fn insert_chunk(
+        &mut self,
+        message_tick: RepliconTick,
+        update_index: u16,
+        chunk_index: u16,
+        chunks_count: u16,
+        chunk: Bytes,
+    ) -> Option<Bytes> {
+        self.chunks.retain(|&(tick, _), _| {
+            tick >= message_tick || message_tick - tick < Self::MAX_CHUNKS_AGE
+        });
+
+        let key = (message_tick, update_index);
+        let chunked_update = self.chunks.entry(key).or_insert_with(|| ChunkedUpdate {
+            chunks: vec![None; chunks_count as usize],
+            remaining: chunks_count,
+        });
+
+        let Some(slot) = chunked_update.chunks.get_mut(chunk_index as usize) else {
+            debug!(
+                "ignoring chunk {chunk_index} for update {update_index} with {chunks_count} chunks"
+            );
+            return None;
+        };
+        if slot.is_none() {
+            *slot = Some(chunk);
+            chunked_update.remaining -= 1;
+        }
+
+        if chunked_update.remaining != 0 {
+            return None;
+        }
+
+        let chunked_update = self.chunks.remove(&key).unwrap();
+        let message: Vec<u8> = chunked_update
+            .chunks
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+
+        Some(message.into())
+    }
+}
+
+/// Chunks of an update message that were received so far.
+struct ChunkedUpdate {
+    /// Received chunks by their index.
+    chunks: Vec<Option<Bytes>>,
+
+    /// Number of chunks that haven't been received yet.
+    remaining: u16,
+}
+
+/// Client configuration that can be changed at runtime.
+///
+/// Initialized from [`ClientPlugin`] fields.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct ClientSettings {
+    /// See [`ClientPlugin::reorder_window`].
+    pub reorder_window: Duration,
 }
 
 /// Caches a partially-deserialized entity update message that is waiting for its tick to appear in an init message.
@@ -626,6 +1398,14 @@ pub(super) struct BufferedUpdate {
     /// The tick this update corresponds to.
     message_tick: RepliconTick,
 
+    /// Server real time in milliseconds when this update was sent.
+    ///
+    /// Present only if enabled with [`ServerPlugin::send_time`](crate::server::ServerPlugin::send_time).
+    send_time: Option<u32>,
+
+    /// Client real time when this update was received.
+    received_at: Duration,
+
     /// Update data.
     message: Bytes,
 }
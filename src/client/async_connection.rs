@@ -0,0 +1,297 @@
+use std::{
+    collections::VecDeque,
+    future,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Poll, Waker},
+};
+
+use bevy::prelude::*;
+
+use super::{
+    replicon_client::{RepliconClient, RepliconClientStatus},
+    ClientSet,
+};
+use crate::core::ClientId;
+
+/**
+Provides `async` API for connection management on client.
+
+Clone [`AsyncConnection`] from the world and pass it to the async code,
+for example to sequence matchmaking, connection and joining in a single task.
+Futures are runtime-agnostic and driven by the app updates, so the app should keep updating while they are awaited.
+
+Since connection depends on the messaging backend, the plugin calls [`Self::connect`] and [`Self::disconnect`]
+inside [`PreUpdate`] to perform the actual work, such as creating the transport.
+Futures resolve after the backend changes [`RepliconClientStatus`].
+
+Not added by default.
+
+# Examples
+
+```
+use std::net::SocketAddr;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    AsyncConnectionPlugin {
+        connect: create_transport,
+        disconnect: remove_transport,
+    },
+));
+
+let connection = app.world.resource::<AsyncConnection>().clone();
+let task = async move {
+    let server_addr = "127.0.0.1:5000".parse().unwrap();
+    let client_id = connection.connect(server_addr).await?;
+    info!("connected with {client_id:?}");
+
+    while let ConnectionEvent::Connected { .. } = connection.next_event().await {}
+    info!("disconnected");
+
+    Ok::<_, ConnectError>(())
+};
+// Spawn the task on your runtime.
+# drop(task);
+
+fn create_transport(world: &mut World, server_addr: SocketAddr) -> Result<(), String> {
+    // Insert transport resources for your messaging backend.
+    Ok(())
+}
+
+fn remove_transport(world: &mut World) {
+    // Remove transport resources.
+}
+```
+**/
+pub struct AsyncConnectionPlugin {
+    /// Starts connection to the server.
+    ///
+    /// Returned error resolves the connection future with [`ConnectError::Backend`].
+    pub connect: fn(&mut World, SocketAddr) -> Result<(), String>,
+
+    /// Closes the connection.
+    pub disconnect: fn(&mut World),
+}
+
+impl Plugin for AsyncConnectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AsyncConnection>()
+            .insert_resource(ConnectionBackend {
+                connect: self.connect,
+                disconnect: self.disconnect,
+            })
+            .add_systems(
+                PreUpdate,
+                (Self::process_requests, Self::update_status)
+                    .chain()
+                    .after(ClientSet::ReceivePackets)
+                    .before(ClientSet::Receive),
+            );
+    }
+}
+
+impl AsyncConnectionPlugin {
+    fn process_requests(world: &mut World) {
+        let connection = world.resource::<AsyncConnection>().clone();
+        let requests: Vec<_> = connection.lock().requests.drain(..).collect();
+        if requests.is_empty() {
+            return;
+        }
+
+        let backend = *world.resource::<ConnectionBackend>();
+        for request in requests {
+            match request {
+                ConnectionRequest::Connect(server_addr) => {
+                    let client = world.resource::<RepliconClient>();
+                    if client.is_connected() {
+                        debug!("ignoring connection to {server_addr} while already connected");
+                        connection.lock().connect_result = Some(Ok(client.id()));
+                        continue;
+                    }
+
+                    debug!("connecting to {server_addr}");
+                    if let Err(e) = (backend.connect)(world, server_addr) {
+                        debug!("unable to connect to {server_addr}: {e}");
+                        connection.lock().connect_result = Some(Err(ConnectError::Backend(e)));
+                    }
+                }
+                ConnectionRequest::Disconnect => {
+                    debug!("disconnecting");
+                    (backend.disconnect)(world);
+                }
+            }
+        }
+
+        // Wake futures that wait for processed requests.
+        connection.lock().wake();
+    }
+
+    fn update_status(connection: Res<AsyncConnection>, client: Res<RepliconClient>) {
+        let mut shared = connection.lock();
+        let status = client.status();
+        if shared.status == status {
+            return;
+        }
+
+        match (shared.status, status) {
+            (_, RepliconClientStatus::Connected { client_id }) => {
+                shared.connect_result = Some(Ok(client_id));
+                shared
+                    .events
+                    .push_back(ConnectionEvent::Connected { client_id });
+            }
+            (RepliconClientStatus::Connecting, RepliconClientStatus::Disconnected) => {
+                shared.connect_result = Some(Err(ConnectError::Disconnected));
+            }
+            (RepliconClientStatus::Connected { .. }, _) => {
+                shared.events.push_back(ConnectionEvent::Disconnected);
+            }
+            _ => (),
+        }
+
+        shared.status = status;
+        shared.wake();
+    }
+}
+
+/// Backend functions from [`AsyncConnectionPlugin`].
+#[derive(Clone, Copy, Resource)]
+struct ConnectionBackend {
+    connect: fn(&mut World, SocketAddr) -> Result<(), String>,
+    disconnect: fn(&mut World),
+}
+
+/// Handle for async connection management.
+///
+/// Cheap to clone and can be sent to other threads.
+/// See [`AsyncConnectionPlugin`] for details.
+#[derive(Clone, Default, Resource)]
+pub struct AsyncConnection(Arc<Mutex<SharedConnection>>);
+
+impl AsyncConnection {
+    /// Connects to the server and returns the assigned client ID if the backend provides it.
+    ///
+    /// Resolves with an error if the backend fails to start the connection
+    /// or if the client disconnects while connecting.
+    pub async fn connect(&self, server_addr: SocketAddr) -> Result<Option<ClientId>, ConnectError> {
+        {
+            let mut shared = self.lock();
+            shared.connect_result = None;
+            shared
+                .requests
+                .push_back(ConnectionRequest::Connect(server_addr));
+        }
+
+        future::poll_fn(|cx| {
+            let mut shared = self.lock();
+            if let Some(result) = shared.connect_result.take() {
+                return Poll::Ready(result);
+            }
+            shared.register(cx.waker());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Disconnects from the server.
+    ///
+    /// Resolves when the client becomes disconnected.
+    pub async fn disconnect(&self) {
+        self.lock()
+            .requests
+            .push_back(ConnectionRequest::Disconnect);
+
+        future::poll_fn(|cx| {
+            let mut shared = self.lock();
+            if shared.requests.is_empty() && shared.status == RepliconClientStatus::Disconnected {
+                return Poll::Ready(());
+            }
+            shared.register(cx.waker());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Waits for the next connection status change.
+    ///
+    /// Events are queued, so changes between calls are not missed.
+    pub async fn next_event(&self) -> ConnectionEvent {
+        future::poll_fn(|cx| {
+            let mut shared = self.lock();
+            if let Some(event) = shared.events.pop_front() {
+                return Poll::Ready(event);
+            }
+            shared.register(cx.waker());
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SharedConnection> {
+        self.0
+            .lock()
+            .expect("connection mutex shouldn't be poisoned")
+    }
+}
+
+/// State shared between [`AsyncConnection`] handles and the app.
+#[derive(Default)]
+struct SharedConnection {
+    requests: VecDeque<ConnectionRequest>,
+    events: VecDeque<ConnectionEvent>,
+
+    /// Last observed status of [`RepliconClient`].
+    status: RepliconClientStatus,
+
+    /// Result of the last connection attempt, taken by [`AsyncConnection::connect`].
+    connect_result: Option<Result<Option<ClientId>, ConnectError>>,
+
+    wakers: Vec<Waker>,
+}
+
+impl SharedConnection {
+    fn register(&mut self, waker: &Waker) {
+        if !self
+            .wakers
+            .iter()
+            .any(|registered| registered.will_wake(waker))
+        {
+            self.wakers.push(waker.clone());
+        }
+    }
+
+    fn wake(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+enum ConnectionRequest {
+    Connect(SocketAddr),
+    Disconnect,
+}
+
+/// Connection status change returned by [`AsyncConnection::next_event`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionEvent {
+    /// Client connected with the assigned ID if the backend provides it.
+    Connected { client_id: Option<ClientId> },
+    /// Client disconnected after being connected.
+    Disconnected,
+}
+
+/// Error returned by [`AsyncConnection::connect`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectError {
+    /// Error from [`AsyncConnectionPlugin::connect`].
+    Backend(String),
+    /// Client disconnected before the connection was established.
+    Disconnected,
+}
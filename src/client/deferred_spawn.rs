@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+Enables deferred insertion of components for newly spawned entities with large data.
+
+When a new entity arrives with data larger than [`Self::size_threshold`],
+it will be spawned with only [`Replicated`](crate::core::Replicated) and [`ReplicationPending`]
+and its components will be inserted on later frames in the order they were serialized
+(which corresponds to their rule priority). At most [`Self::max_bytes_per_frame`] bytes
+are applied per frame, but at least one component.
+
+If any replication message for a pending entity arrives, all its remaining
+components will be inserted immediately to preserve the ordering.
+
+Not inserted by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{client::deferred_spawn::DeferredSpawn, prelude::*};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins))
+    .insert_resource(DeferredSpawn::default());
+```
+*/
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct DeferredSpawn {
+    /// Minimum size of entity data in bytes to defer its insertion.
+    ///
+    /// By default set to `4096`.
+    pub size_threshold: usize,
+
+    /// Maximum number of bytes from pending entities to apply per frame.
+    ///
+    /// By default set to `4096`.
+    pub max_bytes_per_frame: usize,
+}
+
+impl Default for DeferredSpawn {
+    fn default() -> Self {
+        Self {
+            size_threshold: 4096,
+            max_bytes_per_frame: 4096,
+        }
+    }
+}
+
+/// Marks an entity whose components are still being inserted.
+///
+/// Removed after all components are inserted. See [`DeferredSpawn`] for details.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ReplicationPending;
+
+/// Entities with deferred insertion of components in arrival order.
+#[derive(Default, Resource)]
+pub(super) struct PendingEntities(VecDeque<PendingEntity>);
+
+impl PendingEntities {
+    pub(super) fn push(&mut self, pending_entity: PendingEntity) {
+        self.0.push_back(pending_entity);
+    }
+
+    /// Removes a pending entity and returns its data if it was pending.
+    pub(super) fn take(&mut self, entity: Entity) -> Option<PendingEntity> {
+        let index = self
+            .0
+            .iter()
+            .position(|pending_entity| pending_entity.entity == entity)?;
+
+        self.0.remove(index)
+    }
+
+    /// Removes the first pending entity.
+    pub(super) fn pop_front(&mut self) -> Option<PendingEntity> {
+        self.0.pop_front()
+    }
+
+    /// Returns a partially applied entity back to the front.
+    pub(super) fn push_front(&mut self, pending_entity: PendingEntity) {
+        self.0.push_front(pending_entity);
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Components data that haven't been inserted yet.
+pub(super) struct PendingEntity {
+    /// Client entity.
+    pub(super) entity: Entity,
+
+    /// Tick of the init message with this data.
+    pub(super) message_tick: RepliconTick,
+
+    /// Serialized components.
+    pub(super) data: Vec<u8>,
+
+    /// Position of the first component that wasn't applied yet.
+    pub(super) position: u64,
+}
@@ -0,0 +1,136 @@
+use std::{mem, time::Duration};
+
+use bevy::prelude::*;
+
+use super::ClientSet;
+use crate::core::{
+    replication_fns::{ctx::DespawnCtx, DespawnFn, ReplicationFns},
+    replicon_tick::RepliconTick,
+};
+
+/**
+Delays despawns of replicated entities on client.
+
+Instead of despawning immediately, entities despawned by server receive [`Despawned`]
+and are actually despawned after [`Self::grace_period`]. This gives dependent systems,
+like ragdolls, loot spawners or UI, a window to react with full access to the components.
+
+Marked entities are removed from [`ServerEntityMap`](super::server_entity_map::ServerEntityMap),
+so they no longer receive updates from server. Expired entities are despawned in [`PreUpdate`]
+before [`ClientSet::Receive`], so even with zero grace period the marker is visible for the entire frame.
+
+Wraps [`ReplicationFns::despawn`]. A custom despawn function set with
+[`AppMarkerExt::set_despawn_fn`](crate::core::command_markers::AppMarkerExt::set_despawn_fn)
+before or after adding this plugin will be called for the entity after the grace period.
+
+Not added by default. Needed only on client.
+
+# Examples
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    DespawnGracePlugin {
+        grace_period: Duration::from_millis(500),
+    },
+))
+.add_systems(Update, spawn_loot);
+
+fn spawn_loot(despawned: Query<&Transform, Added<Despawned>>) {
+    for transform in &despawned {
+        info!("dropping loot at {}", transform.translation);
+    }
+}
+```
+**/
+#[derive(Default)]
+pub struct DespawnGracePlugin {
+    /// Time the marked entities stay alive.
+    ///
+    /// By default set to zero, which keeps the entities for one frame.
+    pub grace_period: Duration,
+}
+
+impl Plugin for DespawnGracePlugin {
+    fn build(&self, app: &mut App) {
+        let mut replication_fns = app.world.resource_mut::<ReplicationFns>();
+        let despawn = mem::replace(&mut replication_fns.despawn, mark_despawned);
+
+        app.insert_resource(DespawnGrace {
+            grace_period: self.grace_period,
+            despawn,
+        })
+        .add_systems(PreUpdate, despawn_expired.before(ClientSet::Receive));
+    }
+}
+
+/// Inserts [`Despawned`] instead of despawning the entity.
+fn mark_despawned(ctx: &DespawnCtx, mut entity: EntityWorldMut) {
+    let despawned_at = entity.world().resource::<Time>().elapsed();
+    entity.insert(Despawned {
+        tick: ctx.message_tick,
+        despawned_at,
+    });
+}
+
+fn despawn_expired(world: &mut World, mut expired: Local<Vec<(Entity, RepliconTick)>>) {
+    let grace = world.resource::<DespawnGrace>();
+    let elapsed = world.resource::<Time>().elapsed();
+    let grace_period = grace.grace_period;
+    let despawn = grace.despawn;
+
+    expired.extend(
+        world
+            .query::<(Entity, &Despawned)>()
+            .iter(world)
+            .filter(|(_, despawned)| elapsed - despawned.despawned_at >= grace_period)
+            .map(|(entity, despawned)| (entity, despawned.tick)),
+    );
+
+    for (entity, tick) in expired.drain(..) {
+        // The entity could be already despawned as part of the hierarchy.
+        if let Some(entity) = world.get_entity_mut(entity) {
+            let ctx = DespawnCtx { message_tick: tick };
+            (despawn)(&ctx, entity);
+        }
+    }
+}
+
+/// Settings for [`DespawnGracePlugin`].
+///
+/// Can be changed at runtime.
+#[derive(Resource)]
+pub struct DespawnGrace {
+    /// Time the marked entities stay alive.
+    pub grace_period: Duration,
+
+    /// Despawn function called after the grace period.
+    despawn: DespawnFn,
+}
+
+impl DespawnGrace {
+    /// Replaces the function called after the grace period.
+    pub(crate) fn set_despawn(&mut self, despawn: DespawnFn) {
+        self.despawn = despawn;
+    }
+}
+
+/// Marks an entity that was despawned by server.
+///
+/// The entity will be despawned after [`DespawnGrace::grace_period`].
+/// See [`DespawnGracePlugin`] for details.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Despawned {
+    /// Tick of the message with the despawn.
+    pub tick: RepliconTick,
+
+    /// Elapsed time at the moment of marking.
+    despawned_at: Duration,
+}
@@ -23,6 +23,137 @@ pub struct ClientStats {
     pub packets: u32,
     /// Replication bytes received in packet payloads (without internal messaging plugin data).
     pub bytes: u64,
+    /// Incremented per entity in received update messages, including dropped ones.
+    pub entity_updates: u32,
+    /// Incremented per entity update dropped because a newer tick was already applied.
+    pub outdated_updates: u32,
+    /// Incremented per entity update dropped because its tick was already applied.
+    ///
+    /// Expected to stay zero, otherwise the messaging backend duplicates
+    /// messages or they are replayed.
+    pub duplicate_updates: u32,
+}
+
+impl ClientStats {
+    /// Returns the fraction of dropped entity updates.
+    pub fn drop_rate(&self) -> f32 {
+        if self.entity_updates == 0 {
+            0.0
+        } else {
+            (self.outdated_updates + self.duplicate_updates) as f32 / self.entity_updates as f32
+        }
+    }
+}
+
+/// Fraction of dropped entity updates per second above which [`DropRateExceeded`] is emitted.
+///
+/// Updates dropped as outdated are expected on unreliable channels with reordering,
+/// but a high rate indicates misconfigured channels or replayed messages.
+///
+/// By default it's 0.5.
+#[derive(Clone, Copy, Debug, Deref, DerefMut, Resource)]
+pub struct DropRateThreshold(pub f32);
+
+impl Default for DropRateThreshold {
+    fn default() -> Self {
+        Self(0.5)
+    }
+}
+
+/// An event emitted by [`ClientDiagnosticsPlugin`] when [`ClientStats::drop_rate`] for the last second
+/// exceeds [`DropRateThreshold`].
+#[derive(Clone, Copy, Debug, Event, PartialEq)]
+pub struct DropRateExceeded {
+    /// Fraction of dropped entity updates.
+    pub rate: f32,
+
+    /// Number of entity updates dropped as outdated.
+    pub outdated: u32,
+
+    /// Number of entity updates dropped as duplicates.
+    pub duplicates: u32,
+}
+
+/// End-to-end latency of update messages, including buffering on client.
+///
+/// Measured from the moment the server sent an update message until it was applied on client.
+/// Since server and client clocks are not synchronized, latency is relative to the fastest
+/// update received in the current session: it includes buffering and transit time above the
+/// minimum one. Jitter doesn't depend on clocks and can be used to tune interpolation delay.
+///
+/// Both values are smoothed with the gain of 1/16, like in RFC 3550.
+///
+/// Measured only if the server includes send time into update messages,
+/// see [`ServerPlugin::send_time`](crate::server::ServerPlugin::send_time).
+///
+/// Reset on disconnect. Flushed to Diagnostics system periodically.
+#[derive(Default, Resource, Debug)]
+pub struct ClientLatency {
+    /// Smoothed latency in milliseconds.
+    latency: f64,
+
+    /// Smoothed jitter in milliseconds.
+    jitter: f64,
+
+    /// Smallest difference between local and server time.
+    min_offset: Option<i32>,
+
+    /// Last measured latency in milliseconds.
+    last_sample: Option<f64>,
+
+    /// Number of measured updates.
+    samples: u32,
+}
+
+impl ClientLatency {
+    /// Smoothing gain for both latency and jitter.
+    const GAIN: f64 = 1.0 / 16.0;
+
+    /// Records an update with the specified server send time in milliseconds applied at `now`.
+    pub(super) fn record(&mut self, send_time: u32, now: Duration) {
+        // Both times wrap, but the difference between them is still valid.
+        let offset = (now.as_millis() as u32).wrapping_sub(send_time) as i32;
+        let min_offset = match self.min_offset {
+            Some(min_offset) if min_offset <= offset => min_offset,
+            _ => {
+                // Previous samples were measured against a larger offset, shift them.
+                if let Some(min_offset) = self.min_offset {
+                    let shift = (min_offset - offset) as f64;
+                    self.latency += shift;
+                    if let Some(last_sample) = &mut self.last_sample {
+                        *last_sample += shift;
+                    }
+                }
+                self.min_offset = Some(offset);
+                offset
+            }
+        };
+
+        let sample = (offset - min_offset) as f64;
+        if let Some(last_sample) = self.last_sample {
+            self.latency += (sample - self.latency) * Self::GAIN;
+            self.jitter += ((sample - last_sample).abs() - self.jitter) * Self::GAIN;
+        } else {
+            self.latency = sample;
+        }
+        self.last_sample = Some(sample);
+        self.samples += 1;
+    }
+
+    /// Returns smoothed latency.
+    pub fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.latency / 1000.0)
+    }
+
+    /// Returns smoothed variation of latency between updates.
+    pub fn jitter(&self) -> Duration {
+        Duration::from_secs_f64(self.jitter / 1000.0)
+    }
+
+    /// Returns the number of measured updates.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
 }
 
 /// Plugin to write Diagnostics every second.
@@ -37,6 +168,9 @@ impl Plugin for ClientDiagnosticsPlugin {
             Self::add_measurements.run_if(on_timer(Duration::from_secs(1))),
         )
         .init_resource::<ClientStats>()
+        .init_resource::<ClientLatency>()
+        .init_resource::<DropRateThreshold>()
+        .add_event::<DropRateExceeded>()
         .register_diagnostic(
             Diagnostic::new(Self::ENTITY_CHANGES)
                 .with_suffix("entities changed per second")
@@ -66,6 +200,26 @@ impl Plugin for ClientDiagnosticsPlugin {
             Diagnostic::new(Self::BYTES)
                 .with_suffix("bytes per second")
                 .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::OUTDATED_UPDATES)
+                .with_suffix("outdated updates per second")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::DUPLICATE_UPDATES)
+                .with_suffix("duplicate updates per second")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::LATENCY)
+                .with_suffix("ms")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::JITTER)
+                .with_suffix("ms")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
         );
     }
 }
@@ -85,11 +239,27 @@ impl ClientDiagnosticsPlugin {
     pub const PACKETS: DiagnosticPath = DiagnosticPath::const_new("replication.client.packets");
     /// How many bytes of replication packets payloads per second.
     pub const BYTES: DiagnosticPath = DiagnosticPath::const_new("replication.client.bytes");
+    /// How many entity updates dropped per second because a newer tick was already applied.
+    pub const OUTDATED_UPDATES: DiagnosticPath =
+        DiagnosticPath::const_new("replication.client.outdated_updates");
+    /// How many entity updates dropped per second because their tick was already applied.
+    pub const DUPLICATE_UPDATES: DiagnosticPath =
+        DiagnosticPath::const_new("replication.client.duplicate_updates");
+    /// Smoothed latency of update messages in milliseconds, see [`ClientLatency`].
+    pub const LATENCY: DiagnosticPath = DiagnosticPath::const_new("replication.client.latency");
+    /// Smoothed jitter of update messages in milliseconds, see [`ClientLatency`].
+    pub const JITTER: DiagnosticPath = DiagnosticPath::const_new("replication.client.jitter");
 
     /// Max diagnostic history length.
     pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
 
-    fn add_measurements(mut stats: ResMut<ClientStats>, mut diagnostics: Diagnostics) {
+    fn add_measurements(
+        mut drop_events: EventWriter<DropRateExceeded>,
+        mut stats: ResMut<ClientStats>,
+        latency: Res<ClientLatency>,
+        threshold: Res<DropRateThreshold>,
+        mut diagnostics: Diagnostics,
+    ) {
         diagnostics.add_measurement(&Self::ENTITY_CHANGES, || {
             if stats.packets == 0 {
                 0_f64
@@ -126,6 +296,43 @@ impl ClientDiagnosticsPlugin {
             }
         });
         diagnostics.add_measurement(&Self::PACKETS, || stats.packets as f64);
+        diagnostics.add_measurement(&Self::OUTDATED_UPDATES, || stats.outdated_updates as f64);
+        diagnostics.add_measurement(&Self::DUPLICATE_UPDATES, || stats.duplicate_updates as f64);
+        let rate = stats.drop_rate();
+        if rate > **threshold {
+            warn!("dropped {:.0}% of entity updates", rate * 100.0);
+            drop_events.send(DropRateExceeded {
+                rate,
+                outdated: stats.outdated_updates,
+                duplicates: stats.duplicate_updates,
+            });
+        }
+        if latency.samples() != 0 {
+            diagnostics.add_measurement(&Self::LATENCY, || latency.latency);
+            diagnostics.add_measurement(&Self::JITTER, || latency.jitter);
+        }
         *stats = ClientStats::default();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency() {
+        let mut latency = ClientLatency::default();
+        latency.record(1000, Duration::from_millis(50));
+        assert_eq!(latency.latency(), Duration::ZERO);
+        assert_eq!(latency.jitter(), Duration::ZERO);
+
+        latency.record(1016, Duration::from_millis(82));
+        assert_eq!(latency.latency, 1.0);
+        assert_eq!(latency.jitter, 1.0);
+
+        // Faster update shifts previous samples.
+        latency.record(1100, Duration::from_millis(134));
+        assert_eq!(latency.min_offset, Some(-966));
+        assert_eq!(latency.samples(), 3);
+    }
+}
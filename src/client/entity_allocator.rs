@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+/// Allocates client entities for server entities that the client sees for the first time.
+///
+/// Useful for object pooling or to spawn entities with a specific set of components,
+/// like a world partition marker. Implemented for closures that take [`Commands`] and return [`Entity`].
+///
+/// The returned entity could be reserved via [`Commands`] or already exist in the world.
+/// [`Replicated`](crate::core::Replicated) will be inserted into it automatically.
+///
+/// See [`AppEntityAllocatorExt::set_entity_allocator`].
+pub trait EntityAllocator: Send + Sync + 'static {
+    /// Returns an entity to map to a new server entity.
+    fn allocate(&mut self, commands: &mut Commands) -> Entity;
+}
+
+impl<F> EntityAllocator for F
+where
+    F: FnMut(&mut Commands) -> Entity + Send + Sync + 'static,
+{
+    fn allocate(&mut self, commands: &mut Commands) -> Entity {
+        (self)(commands)
+    }
+}
+
+/// Custom entity allocation for [`App`].
+pub trait AppEntityAllocatorExt {
+    /**
+    Allocates client entities for incoming server entities with the allocator instead of spawning new ones.
+
+    Used for entities from init messages and for entities that are referenced inside mapped components.
+    Entities that were pre-spawned on client and mapped by server are not affected.
+
+    Needed only on client.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .set_entity_allocator(|commands: &mut Commands| commands.spawn(Partition(0)).id());
+
+    #[derive(Component)]
+    struct Partition(u8);
+    ```
+    **/
+    fn set_entity_allocator(&mut self, allocator: impl EntityAllocator) -> &mut Self;
+}
+
+impl AppEntityAllocatorExt for App {
+    fn set_entity_allocator(&mut self, allocator: impl EntityAllocator) -> &mut Self {
+        self.insert_resource(ClientEntityAllocator(Box::new(allocator)))
+    }
+}
+
+/// Allocator set with [`AppEntityAllocatorExt::set_entity_allocator`].
+#[derive(Resource)]
+pub(crate) struct ClientEntityAllocator(pub(crate) Box<dyn EntityAllocator>);
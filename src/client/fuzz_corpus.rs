@@ -0,0 +1,212 @@
+/*!
+Recording of received replication messages into a fuzzing corpus.
+
+Each connection session is written into a separate file inside the corpus directory.
+A file is a sequence of frames, one for each update in which replication messages were received.
+A frame is a varint number of messages followed by messages, each message is its channel ID
+as a byte and its bytes prefixed with a varint length. All varints use [`wire_format::varint`].
+
+Files can be passed to [`replay`] as is, which makes them directly usable as inputs for
+`cargo-fuzz` targets. The repository contains a `fuzz` crate with a `replay` target that can be copied
+to fuzz your own replication rules.
+
+# Examples
+
+Record a corpus on client:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    FuzzCorpusPlugin {
+        directory: "fuzz/corpus/replay".into(),
+    },
+));
+```
+
+Replay a recorded file inside a fuzz target:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{client::fuzz_corpus, prelude::*};
+
+fn fuzz(data: &[u8]) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Health>(); // Same rules as in the game.
+
+    // Errors are expected for mutated inputs, only panics indicate bugs.
+    let _ = fuzz_corpus::replay(&mut app, data);
+}
+
+#[derive(Component, serde::Deserialize, serde::Serialize)]
+struct Health(u32);
+# fuzz(&[]);
+```
+*/
+
+use std::{
+    fs::{self, File},
+    io::{Cursor, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bincode::Options;
+
+use super::{
+    replicon_client::{RepliconClient, RepliconClientStatus},
+    ClientPlugin, ClientSet,
+};
+use crate::core::{
+    common_conditions::{client_connected, client_just_connected, client_just_disconnected},
+    replicon_channels::{ReplicationChannel, RepliconChannels},
+    wire_format,
+};
+
+/// Replication channels that are recorded and replayed.
+const CHANNELS: [ReplicationChannel; 3] = [
+    ReplicationChannel::Init,
+    ReplicationChannel::Update,
+    ReplicationChannel::UpdateChunks,
+];
+
+/**
+Records received replication messages into a corpus directory for fuzzing.
+
+Messages are recorded as they were received from the messaging backend, before any processing.
+See the [module](self) documentation for the format.
+
+Not added by default. Needed only on client.
+**/
+pub struct FuzzCorpusPlugin {
+    /// Directory for session files.
+    ///
+    /// Created if it doesn't exist.
+    pub directory: PathBuf,
+}
+
+impl Plugin for FuzzCorpusPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CorpusRecorder {
+            directory: self.directory.clone(),
+            file: None,
+        })
+        .add_systems(
+            PreUpdate,
+            (
+                Self::start_session.run_if(client_just_connected),
+                Self::finish_session.run_if(client_just_disconnected),
+                Self::record.run_if(client_connected),
+            )
+                .chain()
+                .after(ClientSet::ReceivePackets)
+                .before(ClientSet::Receive),
+        );
+    }
+}
+
+impl FuzzCorpusPlugin {
+    fn start_session(mut recorder: ResMut<CorpusRecorder>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = recorder.directory.join(format!("session-{timestamp:x}"));
+        match fs::create_dir_all(&recorder.directory).and_then(|_| File::create(&path)) {
+            Ok(file) => {
+                debug!("recording received messages into {path:?}");
+                recorder.file = Some(file);
+            }
+            Err(e) => error!("unable to create corpus file {path:?}: {e}"),
+        }
+    }
+
+    fn finish_session(mut recorder: ResMut<CorpusRecorder>) {
+        recorder.file = None;
+    }
+
+    fn record(mut recorder: ResMut<CorpusRecorder>, mut client: ResMut<RepliconClient>) {
+        let Some(file) = &mut recorder.file else {
+            return;
+        };
+
+        let mut messages = Vec::new();
+        for channel in CHANNELS {
+            client.retain_received(channel, |message| {
+                messages.push((channel as u8, message.clone()));
+                true
+            });
+        }
+        if messages.is_empty() {
+            return;
+        }
+
+        let mut frame = Cursor::new(Vec::new());
+        wire_format::varint()
+            .serialize_into(&mut frame, &messages.len())
+            .expect("message count should be serializable");
+        for (channel_id, message) in &messages {
+            wire_format::varint()
+                .serialize_into(&mut frame, &(channel_id, &message[..]))
+                .expect("message should be serializable");
+        }
+
+        if let Err(e) = file.write_all(frame.get_ref()) {
+            error!("unable to write corpus frame, stopping recording: {e}");
+            recorder.file = None;
+        }
+    }
+}
+
+/// Directory and file of the current session for [`FuzzCorpusPlugin`].
+#[derive(Resource)]
+struct CorpusRecorder {
+    directory: PathBuf,
+    file: Option<File>,
+}
+
+/// Applies replication messages recorded by [`FuzzCorpusPlugin`] to the app.
+///
+/// The app should contain [`ClientPlugin`] and the same replication rules as the recording client.
+/// It's marked as connected and each frame is applied immediately without running schedules.
+/// Messages for other channels are ignored and messages sent back to the server are discarded.
+///
+/// Returns an error if the data is malformed or if a message can't be applied.
+pub fn replay(app: &mut App, data: &[u8]) -> bincode::Result<()> {
+    let channels_count = app
+        .world
+        .resource::<RepliconChannels>()
+        .server_channels()
+        .len();
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.setup_server_channels(channels_count);
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    let mut cursor = Cursor::new(data);
+    while cursor.position() < data.len() as u64 {
+        let messages_count: usize = wire_format::varint().deserialize_from(&mut cursor)?;
+        let mut client = app.world.resource_mut::<RepliconClient>();
+        for _ in 0..messages_count {
+            let (channel_id, message): (u8, Vec<u8>) =
+                wire_format::varint().deserialize_from(&mut cursor)?;
+            if CHANNELS.iter().any(|&channel| channel as u8 == channel_id) {
+                client.insert_received(channel_id, message);
+            }
+        }
+
+        app.world
+            .run_system_once(ClientPlugin::receive_replication)?;
+        app.world
+            .resource_mut::<RepliconClient>()
+            .drain_sent()
+            .for_each(drop);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,303 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{diagnostics::ClientLatency, ClientSet};
+use crate::core::common_conditions::client_connected;
+
+/**
+Maintains [`InterpolationDelay`] for interpolation layers.
+
+In [`InterpolationDelayMode::Auto`] the delay follows the measured jitter from
+[`ClientLatency`] within the specified bounds. This trades latency for smoothness
+dynamically: on a stable connection the delay shrinks to the minimum and grows
+when updates start arriving unevenly.
+Jitter is measured only if [`ServerPlugin::send_time`](crate::server::ServerPlugin::send_time)
+is enabled on server, otherwise the minimum is used.
+
+Packet loss is not taken into account, use a larger minimum for lossy connections.
+
+After a client stall, like a GC pause or a window drag, a burst of buffered updates arrives at once.
+Interpolation layers that advance at normal speed replay this backlog in slow motion.
+With [`SpikeRecovery::FastForward`] the stall is detected from the frame duration, [`FastForwarded`]
+is emitted and [`InterpolationDelay::fast_forward_blend`] reports the progress of blending
+from the displayed state to the newest one.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, InterpolationDelayPlugin::default()))
+    .add_systems(Update, interpolate);
+
+fn interpolate(delay: Res<InterpolationDelay>) {
+    // Render remote entities `delay.get()` in the past.
+}
+```
+*/
+pub struct InterpolationDelayPlugin {
+    /// Initial mode for [`InterpolationDelay`].
+    ///
+    /// By default set to [`InterpolationDelayMode::Auto`] from 50 to 300 ms with jitter multiplier of 2.
+    pub mode: InterpolationDelayMode,
+
+    /// Initial recovery policy for [`InterpolationDelay`].
+    ///
+    /// By default set to [`SpikeRecovery::Replay`].
+    pub spike_recovery: SpikeRecovery,
+}
+
+impl Default for InterpolationDelayPlugin {
+    fn default() -> Self {
+        Self {
+            mode: InterpolationDelayMode::Auto {
+                min: Duration::from_millis(50),
+                max: Duration::from_millis(300),
+                jitter_multiplier: 2.0,
+            },
+            spike_recovery: SpikeRecovery::Replay,
+        }
+    }
+}
+
+impl Plugin for InterpolationDelayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClientLatency>()
+            .insert_resource(InterpolationDelay::new(self.mode, self.spike_recovery))
+            .add_event::<FastForwarded>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    (Self::update_delay, Self::update_recovery)
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            );
+    }
+}
+
+impl InterpolationDelayPlugin {
+    fn update_delay(mut delay: ResMut<InterpolationDelay>, latency: Res<ClientLatency>) {
+        let target = delay.mode.target(latency.jitter());
+        if delay.current != target {
+            trace!("changing interpolation delay to {target:?}");
+            delay.current = target;
+        }
+    }
+
+    pub(super) fn update_recovery(
+        mut delay: ResMut<InterpolationDelay>,
+        mut fast_forwarded: EventWriter<FastForwarded>,
+        time: Res<Time<Real>>,
+    ) {
+        let SpikeRecovery::FastForward { threshold, blend } = delay.spike_recovery else {
+            delay.blend = None;
+            return;
+        };
+
+        if time.delta() > threshold {
+            debug!("fast-forwarding after a stall of {:?}", time.delta());
+            fast_forwarded.send(FastForwarded {
+                stall: time.delta(),
+            });
+            delay.blend = (!blend.is_zero()).then_some(BlendProgress {
+                elapsed: Duration::ZERO,
+                duration: blend,
+            });
+        } else if let Some(progress) = &mut delay.blend {
+            progress.elapsed += time.delta();
+            if progress.elapsed >= progress.duration {
+                delay.blend = None;
+            }
+        }
+    }
+
+    fn reset(mut delay: ResMut<InterpolationDelay>) {
+        delay.current = delay.mode.target(Duration::ZERO);
+        delay.blend = None;
+    }
+}
+
+/// Delay behind the last received server state that interpolation layers should use.
+///
+/// See [`InterpolationDelayPlugin`] for details.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct InterpolationDelay {
+    /// How the delay is calculated.
+    ///
+    /// Can be changed at runtime.
+    pub mode: InterpolationDelayMode,
+
+    /// How interpolation layers should recover after a client stall.
+    ///
+    /// Can be changed at runtime.
+    pub spike_recovery: SpikeRecovery,
+
+    /// Current delay.
+    current: Duration,
+
+    /// Blending after the last fast-forward, if it's still in progress.
+    blend: Option<BlendProgress>,
+}
+
+impl InterpolationDelay {
+    fn new(mode: InterpolationDelayMode, spike_recovery: SpikeRecovery) -> Self {
+        Self {
+            mode,
+            spike_recovery,
+            current: mode.target(Duration::ZERO),
+            blend: None,
+        }
+    }
+
+    /// Returns the current delay.
+    pub fn get(&self) -> Duration {
+        self.current
+    }
+
+    /// Returns how far blending from the displayed state to the newest one progressed after [`FastForwarded`].
+    ///
+    /// Goes from 0 to 1. Returns [`None`] if there is no blending in progress.
+    pub fn fast_forward_blend(&self) -> Option<f32> {
+        self.blend
+            .map(|progress| progress.elapsed.as_secs_f32() / progress.duration.as_secs_f32())
+    }
+}
+
+/// Elapsed and total time of blending after [`FastForwarded`].
+#[derive(Clone, Copy, Debug)]
+struct BlendProgress {
+    elapsed: Duration,
+    duration: Duration,
+}
+
+/// Recovery policy for [`InterpolationDelay`] after a client stall.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpikeRecovery {
+    /// Interpolation layers play buffered states at normal speed.
+    Replay,
+    /// Interpolation layers jump to the newest state minus the delay after a frame longer than `threshold`.
+    FastForward {
+        /// Minimum frame duration considered a stall.
+        threshold: Duration,
+        /// Duration of blending from the displayed state to the newest one.
+        ///
+        /// If zero, the state should be snapped.
+        blend: Duration,
+    },
+}
+
+/// An event emitted on client by [`InterpolationDelayPlugin`] when a stall is detected
+/// with [`SpikeRecovery::FastForward`].
+///
+/// Interpolation layers should move their timelines to the newest received state minus [`InterpolationDelay::get`]
+/// and blend the displayed state using [`InterpolationDelay::fast_forward_blend`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct FastForwarded {
+    /// Duration of the stalled frame.
+    pub stall: Duration,
+}
+
+/// Calculation mode for [`InterpolationDelay`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterpolationDelayMode {
+    /// Constant delay.
+    Fixed(Duration),
+    /// Delay that adjusts to the measured jitter.
+    Auto {
+        /// Smallest allowed delay.
+        min: Duration,
+        /// Largest allowed delay.
+        ///
+        /// Takes precedence over `min` if it's smaller.
+        max: Duration,
+        /// How many jitters should fit into the delay.
+        ///
+        /// Negative and NaN values are treated as zero.
+        jitter_multiplier: f32,
+    },
+}
+
+impl InterpolationDelayMode {
+    /// Returns the delay for the specified jitter.
+    fn target(self, jitter: Duration) -> Duration {
+        match self {
+            InterpolationDelayMode::Fixed(delay) => delay,
+            InterpolationDelayMode::Auto {
+                min,
+                max,
+                jitter_multiplier,
+            } => {
+                let multiplier = f64::from(jitter_multiplier).max(0.0);
+                let delay = Duration::try_from_secs_f64(jitter.as_secs_f64() * multiplier)
+                    .unwrap_or(Duration::MAX);
+                delay.max(min).min(max)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target() {
+        let fixed = InterpolationDelayMode::Fixed(Duration::from_millis(100));
+        assert_eq!(
+            fixed.target(Duration::from_millis(200)),
+            Duration::from_millis(100)
+        );
+
+        let auto = InterpolationDelayMode::Auto {
+            min: Duration::from_millis(50),
+            max: Duration::from_millis(300),
+            jitter_multiplier: 2.0,
+        };
+        assert_eq!(auto.target(Duration::ZERO), Duration::from_millis(50));
+        assert_eq!(
+            auto.target(Duration::from_millis(40)),
+            Duration::from_millis(80)
+        );
+        assert_eq!(
+            auto.target(Duration::from_millis(500)),
+            Duration::from_millis(300)
+        );
+
+        let invalid = InterpolationDelayMode::Auto {
+            min: Duration::from_millis(300),
+            max: Duration::from_millis(50),
+            jitter_multiplier: f32::NAN,
+        };
+        assert_eq!(
+            invalid.target(Duration::from_millis(40)),
+            Duration::from_millis(50)
+        );
+
+        let negative = InterpolationDelayMode::Auto {
+            min: Duration::from_millis(50),
+            max: Duration::from_millis(300),
+            jitter_multiplier: -1.0,
+        };
+        assert_eq!(
+            negative.target(Duration::from_millis(40)),
+            Duration::from_millis(50)
+        );
+
+        let infinite = InterpolationDelayMode::Auto {
+            min: Duration::from_millis(50),
+            max: Duration::from_millis(300),
+            jitter_multiplier: f32::INFINITY,
+        };
+        assert_eq!(
+            infinite.target(Duration::from_millis(40)),
+            Duration::from_millis(300)
+        );
+    }
+}
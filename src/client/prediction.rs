@@ -0,0 +1,378 @@
+use std::{collections::VecDeque, io::Cursor};
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+use super::{
+    rollback_scope::{InRollbackScope, RollbackScope},
+    ClientSet, ServerInitTick,
+};
+use crate::core::{
+    command_markers::AppMarkerExt,
+    common_conditions::client_connected,
+    replication_fns::{
+        ctx::{RemoveCtx, WriteCtx},
+        rule_fns::RuleFns,
+    },
+    replicon_tick::RepliconTick,
+};
+
+/**
+Simulates entities with [`Predicted`] locally and corrects them with authoritative updates from server.
+
+Systems that simulate predicted entities should be added to [`PredictionSchedule`]. The plugin runs it once
+per [`FixedUpdate`], increments [`PredictionTick`] and stores the resulting values of components registered
+with [`AppPredictionExt::predict`] in [`PredictionHistory`].
+
+Received updates for these components are not written into the components directly. Instead, after
+[`ClientSet::Receive`] each received value is compared with the predicted value for the same tick.
+If they differ, all predicted components are restored to the state at that tick, the received value is applied,
+and [`PredictionSchedule`] runs again for every tick up to [`PredictionTick`]. [`Mispredicted`] is emitted for
+each rollback.
+
+The server sends only changed components, so predicted changes of values that didn't change on server
+are corrected only with the next change. If [`PredictionTick`] falls behind [`ServerInitTick`], it's moved
+forward without re-simulation.
+
+If [`RollbackScopePlugin`](super::rollback_scope::RollbackScopePlugin) is added, only entities with [`InRollbackScope`] are restored and re-simulated.
+Predicted entities outside of the scope keep their replicated state: received values are written directly
+and don't trigger rollback.
+
+Not added by default. Needed only on client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, PredictionPlugin))
+    .replicate::<Position>()
+    .predict::<Position>()
+    .add_systems(PredictionSchedule, move_players);
+
+fn move_players(mut players: Query<&mut Position, With<Predicted>>) {
+    for mut position in &mut players {
+        position.0 += 1.0;
+    }
+}
+
+#[derive(Component, Clone, Deserialize, PartialEq, Serialize)]
+struct Position(f32);
+```
+**/
+pub struct PredictionPlugin;
+
+impl Plugin for PredictionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PredictionTick>()
+            .init_resource::<PredictionRegistry>()
+            .init_schedule(PredictionSchedule)
+            .add_event::<Mispredicted>()
+            .register_marker::<Predicted>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::rollback
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(FixedUpdate, Self::advance.run_if(client_connected));
+    }
+}
+
+impl PredictionPlugin {
+    /// Compares received values with predictions and re-simulates from the earliest mispredicted tick.
+    fn rollback(world: &mut World) {
+        let init_tick = **world.resource::<ServerInitTick>();
+        let mut predicted_tick = world.resource_mut::<PredictionTick>();
+        if predicted_tick.0 < init_tick {
+            predicted_tick.0 = init_tick;
+        }
+        let current_tick = predicted_tick.0;
+
+        let registry = world.resource::<PredictionRegistry>().0.clone();
+        let rollback_tick = registry
+            .iter()
+            .filter_map(|fns| (fns.check)(world, current_tick))
+            .reduce(|min_tick, tick| if tick < min_tick { tick } else { min_tick });
+
+        let Some(rollback_tick) = rollback_tick else {
+            return;
+        };
+
+        debug!("re-simulating from {rollback_tick:?} to {current_tick:?}");
+        for fns in &registry {
+            (fns.restore)(world, rollback_tick);
+        }
+
+        let mut tick = rollback_tick;
+        while tick < current_tick {
+            tick += 1;
+            simulate(world, &registry, tick);
+        }
+
+        world.send_event(Mispredicted {
+            tick: rollback_tick,
+        });
+    }
+
+    /// Runs [`PredictionSchedule`] for the next tick.
+    fn advance(world: &mut World) {
+        let tick = world.resource::<PredictionTick>().0 + 1;
+        let registry = world.resource::<PredictionRegistry>().0.clone();
+        simulate(world, &registry, tick);
+    }
+
+    fn reset(mut predicted_tick: ResMut<PredictionTick>) {
+        *predicted_tick = Default::default();
+    }
+}
+
+/// Runs [`PredictionSchedule`] for the tick and records the results into history.
+fn simulate(world: &mut World, registry: &[PredictionFns], tick: RepliconTick) {
+    world.resource_mut::<PredictionTick>().0 = tick;
+    world.run_schedule(PredictionSchedule);
+    for fns in registry {
+        (fns.record)(world, tick);
+    }
+}
+
+/// Prediction for [`App`].
+pub trait AppPredictionExt {
+    /// Marks the component as predicted.
+    ///
+    /// On entities with [`Predicted`], received values of the component are compared with predictions
+    /// instead of being written directly.
+    ///
+    /// Should be called after adding [`PredictionPlugin`]. The component should also be registered for replication.
+    ///
+    /// See [`PredictionPlugin`] for details.
+    fn predict<C: Component + Clone + PartialEq>(&mut self) -> &mut Self;
+}
+
+impl AppPredictionExt for App {
+    fn predict<C: Component + Clone + PartialEq>(&mut self) -> &mut Self {
+        self.world
+            .resource_mut::<PredictionRegistry>()
+            .0
+            .push(PredictionFns {
+                check: check::<C>,
+                restore: restore::<C>,
+                record: record::<C>,
+            });
+
+        self.set_marker_fns::<Predicted, C>(write_received::<C>, remove_predicted::<C>)
+    }
+}
+
+/// Stores the received value in [`PredictionHistory`] to compare it with the prediction later.
+///
+/// Inserts the component as is if it's missing.
+fn write_received<C: Component + Clone + PartialEq>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    if let Some(mut history) = entity.get_mut::<PredictionHistory<C>>() {
+        history.received = Some((ctx.message_tick, component));
+    } else if entity.contains::<C>() {
+        ctx.commands.entity(entity.id()).insert(PredictionHistory {
+            received: Some((ctx.message_tick, component)),
+            ..Default::default()
+        });
+    } else {
+        let history = PredictionHistory::<C> {
+            values: [(ctx.message_tick, component.clone())].into(),
+            received: None,
+        };
+        ctx.commands
+            .entity(entity.id())
+            .insert((component, history));
+    }
+
+    Ok(())
+}
+
+/// Removes the component and its history.
+fn remove_predicted<C: Component>(ctx: &mut RemoveCtx, entity: &mut EntityMut) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<(C, PredictionHistory<C>)>();
+}
+
+/// Applies received values to history and returns the earliest mispredicted tick.
+fn check<C: Component + Clone + PartialEq>(
+    world: &mut World,
+    current_tick: RepliconTick,
+) -> Option<RepliconTick> {
+    let scoped = world.contains_resource::<RollbackScope>();
+    let mut rollback_tick: Option<RepliconTick> = None;
+    let mut predicted = world.query::<(&mut C, &mut PredictionHistory<C>, Has<InRollbackScope>)>();
+    for (mut component, mut history, in_scope) in predicted.iter_mut(world) {
+        let Some((tick, received)) = history.received.take() else {
+            continue;
+        };
+
+        if tick >= current_tick || (scoped && !in_scope) {
+            // Nothing was predicted for this tick yet or the entity isn't re-simulated.
+            history.values.clear();
+            history.values.push_back((tick, received.clone()));
+            *component = received;
+        } else if history.get(tick) == Some(&received) {
+            history.values.retain(|&(value_tick, _)| value_tick >= tick);
+        } else {
+            history.values.retain(|&(value_tick, _)| value_tick > tick);
+            history.values.push_front((tick, received));
+            if rollback_tick.is_none_or(|rollback_tick| tick < rollback_tick) {
+                rollback_tick = Some(tick);
+            }
+        }
+    }
+
+    rollback_tick
+}
+
+/// Restores components to their values at the tick and discards newer history.
+fn restore<C: Component + Clone>(world: &mut World, tick: RepliconTick) {
+    let scoped = world.contains_resource::<RollbackScope>();
+    let mut predicted = world.query::<(&mut C, &mut PredictionHistory<C>, Has<InRollbackScope>)>();
+    for (mut component, mut history, in_scope) in predicted.iter_mut(world) {
+        if scoped && !in_scope {
+            continue;
+        }
+
+        history.values.retain(|&(value_tick, _)| value_tick <= tick);
+        if let Some((_, value)) = history.values.back() {
+            *component = value.clone();
+        }
+    }
+}
+
+/// Stores current values of components in history.
+fn record<C: Component + Clone>(world: &mut World, tick: RepliconTick) {
+    let scoped = world.contains_resource::<RollbackScope>();
+    let mut untracked = world.query_filtered::<
+        (Entity, &C, Has<InRollbackScope>),
+        (With<Predicted>, Without<PredictionHistory<C>>),
+    >();
+    let entities: Vec<_> = untracked
+        .iter(world)
+        .filter(|&(_, _, in_scope)| !scoped || in_scope)
+        .map(|(entity, component, _)| (entity, component.clone()))
+        .collect();
+    for (entity, component) in entities {
+        world.entity_mut(entity).insert(PredictionHistory::<C> {
+            values: [(tick, component)].into(),
+            received: None,
+        });
+    }
+
+    let mut predicted = world
+        .query_filtered::<(&C, &mut PredictionHistory<C>, Has<InRollbackScope>), With<Predicted>>();
+    for (component, mut history, in_scope) in predicted.iter_mut(world) {
+        if scoped && !in_scope {
+            continue;
+        }
+        history.insert(tick, component.clone());
+    }
+}
+
+/// Functions for a component registered with [`AppPredictionExt::predict`].
+#[derive(Clone, Copy)]
+struct PredictionFns {
+    check: fn(&mut World, RepliconTick) -> Option<RepliconTick>,
+    restore: fn(&mut World, RepliconTick),
+    record: fn(&mut World, RepliconTick),
+}
+
+/// Components registered with [`AppPredictionExt::predict`].
+#[derive(Default, Resource)]
+pub(crate) struct PredictionRegistry(Vec<PredictionFns>);
+
+impl PredictionRegistry {
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Schedule with systems that simulate predicted entities.
+///
+/// Runs once per tick and again for each re-simulated tick.
+/// See [`PredictionPlugin`] for details.
+#[derive(ScheduleLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PredictionSchedule;
+
+/// Marks an entity whose registered components are predicted.
+///
+/// See [`PredictionPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Predicted;
+
+/// The tick that the local simulation reached.
+///
+/// During re-simulation it's set to the re-simulated tick.
+#[derive(Clone, Copy, Debug, Default, Deref, Resource)]
+pub struct PredictionTick(RepliconTick);
+
+/// Predicted values of component `C` for recent ticks.
+///
+/// Present only on client.
+#[derive(Component)]
+pub struct PredictionHistory<C> {
+    /// Values sorted by tick.
+    values: VecDeque<(RepliconTick, C)>,
+
+    /// The last received value that wasn't compared with the prediction yet.
+    received: Option<(RepliconTick, C)>,
+}
+
+impl<C> PredictionHistory<C> {
+    /// Maximum number of stored ticks.
+    pub const MAX_LEN: usize = 64;
+
+    /// Returns the value for the tick.
+    pub fn get(&self, tick: RepliconTick) -> Option<&C> {
+        self.values
+            .iter()
+            .find(|&&(value_tick, _)| value_tick == tick)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns stored values sorted by tick.
+    pub fn iter(&self) -> impl Iterator<Item = &(RepliconTick, C)> {
+        self.values.iter()
+    }
+
+    /// Stores the value for the tick, discarding values for this and later ticks.
+    fn insert(&mut self, tick: RepliconTick, value: C) {
+        self.values.retain(|&(value_tick, _)| value_tick < tick);
+        self.values.push_back((tick, value));
+        if self.values.len() > Self::MAX_LEN {
+            self.values.pop_front();
+        }
+    }
+}
+
+impl<C> Default for PredictionHistory<C> {
+    fn default() -> Self {
+        Self {
+            values: Default::default(),
+            received: None,
+        }
+    }
+}
+
+/// An event emitted on client when a received value differs from the prediction.
+///
+/// Emitted after re-simulation.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct Mispredicted {
+    /// Tick from which the state was re-simulated.
+    pub tick: RepliconTick,
+}
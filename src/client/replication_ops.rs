@@ -0,0 +1,82 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+Records applied replication as [`ReplicationOp`] events in the order of application.
+
+Useful for systems that should process changes in causal order, like building acceleration
+structures that depend on other entities, where change detection loses the ordering.
+Events are emitted during [`ClientSet::Receive`](super::ClientSet::Receive), so readers should run after it.
+Outdated updates that are only written into history are not recorded.
+
+Not added by default. Needed only on client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ReplicationOpsPlugin))
+    .add_systems(PreUpdate, rebuild.after(ClientSet::Receive));
+
+fn rebuild(mut op_events: EventReader<ReplicationOp>) {
+    for op in op_events.read() {
+        match op.kind {
+            ReplicationOpKind::Spawn { entity } => info!("{entity:?} spawned at {:?}", op.tick),
+            ReplicationOpKind::Despawn { entity } => info!("{entity:?} despawned at {:?}", op.tick),
+            _ => (),
+        }
+    }
+}
+```
+**/
+pub struct ReplicationOpsPlugin;
+
+impl Plugin for ReplicationOpsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ReplicationOp>();
+    }
+}
+
+/// An event for an applied replication operation.
+///
+/// See [`ReplicationOpsPlugin`] for details.
+#[derive(Clone, Copy, Debug, Event, PartialEq, Eq)]
+pub struct ReplicationOp {
+    /// Server tick of the message with the operation.
+    pub tick: RepliconTick,
+
+    /// Operation with client entities.
+    pub kind: ReplicationOpKind,
+}
+
+/// Kind of [`ReplicationOp`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplicationOpKind {
+    /// A new entity was spawned.
+    ///
+    /// Emitted before insertions for this entity.
+    Spawn { entity: Entity },
+    /// A component was inserted.
+    Insert {
+        entity: Entity,
+        component_id: ComponentId,
+    },
+    /// An existing component was changed.
+    Change {
+        entity: Entity,
+        component_id: ComponentId,
+    },
+    /// A component was removed.
+    Remove {
+        entity: Entity,
+        component_id: ComponentId,
+    },
+    /// An entity was despawned.
+    ///
+    /// The entity no longer exists when the event is read.
+    Despawn { entity: Entity },
+}
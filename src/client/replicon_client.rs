@@ -7,13 +7,13 @@ use crate::core::ClientId;
 ///
 /// The messaging backend is responsible for updating this resource:
 /// - When the messaging client changes its status (connected, connecting and disconnected),
-/// [`Self::set_status`] should be used to reflect this.
+///   [`Self::set_status`] should be used to reflect this.
 /// - For receiving messages, [`Self::insert_received`] should be to used.
-/// A system to forward backend messages to Replicon should run in
-/// [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
+///   A system to forward backend messages to Replicon should run in
+///   [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
-/// A system to forward Replicon messages to the backend should run in
-/// [`ClientSet::SendPackets`](super::ClientSet::SendPackets).
+///   A system to forward Replicon messages to the backend should run in
+///   [`ClientSet::SendPackets`](super::ClientSet::SendPackets).
 #[derive(Resource, Default)]
 pub struct RepliconClient {
     /// Client connection status.
@@ -66,6 +66,23 @@ impl RepliconClient {
         channel_messages.drain(..)
     }
 
+    /// Retains only the received messages on a channel specified by the predicate.
+    ///
+    /// Allows modifying messages in place before they are received.
+    pub(crate) fn retain_received<I, F>(&mut self, channel_id: I, f: F)
+    where
+        I: Into<u8>,
+        F: FnMut(&mut Bytes) -> bool,
+    {
+        let channel_id = channel_id.into();
+        let channel_messages = self
+            .received_messages
+            .get_mut(channel_id as usize)
+            .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
+
+        channel_messages.retain_mut(f)
+    }
+
     /// Sends a message to the server over a channel.
     pub fn send<I: Into<u8>, B: Into<Bytes>>(&mut self, channel_id: I, message: B) {
         if !self.is_connected() {
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use super::ClientSet;
+use crate::core::{common_conditions::client_connected, Replicated};
+
+/**
+Limits which entities and components participate in client-side re-simulation.
+
+Rollback cost usually scales with the number of restored entities, but only a small part of the world
+is predicted, like the local player and physics bodies around it. Insert [`RollbackAnchor`] on locally
+predicted entities and the plugin will maintain [`InRollbackScope`] on them and on replicated entities
+with [`Transform`] within [`RollbackScope::radius`] of any anchor.
+
+[`PredictionPlugin`](super::prediction::PredictionPlugin) restores and re-simulates components registered with
+[`AppPredictionExt::predict`](super::prediction::AppPredictionExt::predict) only on entities with
+[`InRollbackScope`], other entities keep their replicated state. Custom rollback implementations should
+filter by [`InRollbackScope`] too.
+
+The scope is updated after [`ClientSet::Receive`], so systems that re-simulate should run after it.
+
+Not added by default. Needed only on client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, RollbackScopePlugin))
+    .insert_resource(RollbackScope::new(Some(10.0)))
+    .add_systems(PreUpdate, resimulate.after(ClientSet::Receive));
+
+fn resimulate(bodies: Query<&Transform, With<InRollbackScope>>) {
+    for _transform in &bodies {
+        // Restore and re-simulate only entities in scope.
+    }
+}
+```
+**/
+pub struct RollbackScopePlugin;
+
+impl Plugin for RollbackScopePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RollbackScope>().add_systems(
+            PreUpdate,
+            Self::update_scope
+                .after(ClientSet::Receive)
+                .run_if(client_connected),
+        );
+    }
+}
+
+impl RollbackScopePlugin {
+    fn update_scope(
+        mut commands: Commands,
+        scope: Res<RollbackScope>,
+        anchors: Query<(Entity, Option<&Transform>), With<RollbackAnchor>>,
+        entities: Query<
+            (Entity, &Transform, Has<InRollbackScope>),
+            (With<Replicated>, Without<RollbackAnchor>),
+        >,
+        scoped_anchors: Query<(), (With<RollbackAnchor>, With<InRollbackScope>)>,
+    ) {
+        for (entity, _) in &anchors {
+            if !scoped_anchors.contains(entity) {
+                commands.entity(entity).insert(InRollbackScope);
+            }
+        }
+
+        for (entity, transform, in_scope) in &entities {
+            let nearby = scope.radius.is_some_and(|radius| {
+                anchors
+                    .iter()
+                    .filter_map(|(_, anchor_transform)| anchor_transform)
+                    .any(|anchor_transform| {
+                        anchor_transform
+                            .translation
+                            .distance_squared(transform.translation)
+                            <= radius * radius
+                    })
+            });
+
+            if nearby && !in_scope {
+                commands.entity(entity).insert(InRollbackScope);
+            } else if !nearby && in_scope {
+                commands.entity(entity).remove::<InRollbackScope>();
+            }
+        }
+    }
+}
+
+/// Configuration of the rollback scope.
+///
+/// See [`RollbackScopePlugin`] for details.
+#[derive(Default, Resource)]
+pub struct RollbackScope {
+    /// Distance from [`RollbackAnchor`] within which entities are included in the scope.
+    ///
+    /// [`None`] to include only anchors.
+    pub radius: Option<f32>,
+}
+
+impl RollbackScope {
+    /// Creates a scope with the specified radius.
+    pub fn new(radius: Option<f32>) -> Self {
+        Self { radius }
+    }
+}
+
+/// Marks an entity that is always re-simulated, like the locally predicted player.
+///
+/// Entities around anchors are included in the scope too.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct RollbackAnchor;
+
+/// Marks an entity that participates in re-simulation.
+///
+/// Maintained by [`RollbackScopePlugin`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct InRollbackScope;
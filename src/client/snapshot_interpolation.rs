@@ -0,0 +1,444 @@
+use std::{collections::VecDeque, io::Cursor, time::Duration};
+
+use bevy::prelude::*;
+
+use super::{
+    confirmed::Confirmed,
+    interpolation_delay::{FastForwarded, InterpolationDelay, InterpolationDelayPlugin},
+    ClientSet, ServerInitTick, ServerUpdateTick,
+};
+use crate::core::{
+    command_markers::AppMarkerExt,
+    common_conditions::client_connected,
+    replication_fns::{
+        ctx::{RemoveCtx, WriteCtx},
+        rule_fns::RuleFns,
+    },
+    replication_rules::AppHelperExt,
+    replicon_tick::RepliconTick,
+};
+
+/**
+Smooths received values of entities with [`Interpolated`] by rendering them in the past.
+
+Received values of components registered with [`AppInterpolationExt::interpolate`] are not written
+into the components directly. Instead, they are stored in [`SnapshotBuffer`] by replication tick.
+Snapshots are placed on the server timeline by their ticks multiplied by [`SnapshotInterpolation::tick_period`],
+so the network jitter doesn't affect the playback. After [`ClientSet::Receive`] the estimated server time advances
+with the client real time, but never falls behind the latest received tick. The components are set to the blend
+between the two snapshots around the estimated server time minus the delay. This hides choppiness at low send rates
+at the cost of additional latency.
+
+The delay is taken from [`InterpolationDelay`] if [`InterpolationDelayPlugin`]
+is added, otherwise from [`SnapshotInterpolation::delay`]. With [`SpikeRecovery::FastForward`](super::interpolation_delay::SpikeRecovery::FastForward)
+the components are blended from the displayed values to the interpolated ones after each [`FastForwarded`]
+using [`InterpolationDelay::fast_forward_blend`] instead of jumping.
+
+If the buffer runs out of snapshots, for example due to packet loss, components keep the last received value.
+Components with a function registered via [`AppInterpolationExt::set_extrapolation`] are extrapolated
+from the last two snapshots instead, for up to [`SnapshotInterpolation::max_extrapolation`]. When late snapshots
+arrive, components return to the interpolated values.
+
+Insert [`Interpolated`] when the entity is replicated. The value of the component at that moment becomes the first snapshot.
+
+Not added by default. Needed only on client.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, SnapshotInterpolationPlugin::default()))
+    .replicate::<Transform>()
+    .interpolate::<Transform>()
+    .set_extrapolation::<Transform>(extrapolate_linear)
+    .add_systems(PreUpdate, init_players.after(ClientSet::Receive));
+
+fn init_players(mut commands: Commands, players: Query<Entity, Added<Transform>>) {
+    for entity in &players {
+        commands.entity(entity).insert(Interpolated);
+    }
+}
+```
+**/
+pub struct SnapshotInterpolationPlugin {
+    /// Initial delay for [`SnapshotInterpolation`].
+    ///
+    /// By default set to 100 ms.
+    pub delay: Duration,
+
+    /// Initial extrapolation limit for [`SnapshotInterpolation`].
+    ///
+    /// By default set to 250 ms.
+    pub max_extrapolation: Duration,
+
+    /// Initial tick period for [`SnapshotInterpolation`].
+    ///
+    /// By default set to 1/30 of a second to match the default
+    /// [`TickPolicy`](crate::server::TickPolicy) of the server.
+    pub tick_period: Duration,
+}
+
+impl Default for SnapshotInterpolationPlugin {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(100),
+            max_extrapolation: Duration::from_millis(250),
+            tick_period: Duration::from_secs(1) / 30,
+        }
+    }
+}
+
+impl Plugin for SnapshotInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SnapshotInterpolation {
+            delay: self.delay,
+            max_extrapolation: self.max_extrapolation,
+            tick_period: self.tick_period,
+        })
+        .init_resource::<RenderTime>()
+        .add_event::<FastForwarded>()
+        .register_marker::<Interpolated>()
+        .add_systems(
+            PreUpdate,
+            (
+                Self::reset.in_set(ClientSet::Reset),
+                Self::advance
+                    .after(ClientSet::Receive)
+                    .after(InterpolationDelayPlugin::update_recovery)
+                    .run_if(client_connected),
+            ),
+        );
+    }
+}
+
+impl SnapshotInterpolationPlugin {
+    /// Advances the server clock and updates the render time.
+    fn advance(
+        mut render_time: ResMut<RenderTime>,
+        mut fast_forwarded: EventReader<FastForwarded>,
+        time: Res<Time<Real>>,
+        interpolation: Res<SnapshotInterpolation>,
+        interpolation_delay: Option<Res<InterpolationDelay>>,
+        init_tick: Res<ServerInitTick>,
+        update_tick: Res<ServerUpdateTick>,
+    ) {
+        let latest_tick = if update_tick.get() > **init_tick {
+            update_tick.get()
+        } else {
+            **init_tick
+        };
+        let latest_time = interpolation.tick_time(latest_tick);
+
+        render_time.fast_forwarded = fast_forwarded.read().count() != 0;
+        let clock = match render_time.clock {
+            Some(clock) if !render_time.fast_forwarded => (clock + time.delta()).max(latest_time),
+            _ => latest_time,
+        };
+
+        let delay = interpolation_delay.map_or(interpolation.delay, |delay| delay.get());
+        render_time.clock = Some(clock);
+        render_time.current = Some(clock.saturating_sub(delay));
+    }
+
+    fn reset(mut render_time: ResMut<RenderTime>) {
+        *render_time = Default::default();
+    }
+}
+
+/// Snapshot interpolation for [`App`].
+pub trait AppInterpolationExt {
+    /// Marks the component as interpolated.
+    ///
+    /// On entities with [`Interpolated`], received values of the component are buffered and blended
+    /// instead of being written directly.
+    ///
+    /// Should be called after adding [`SnapshotInterpolationPlugin`]. The component should also be registered for replication.
+    ///
+    /// See [`SnapshotInterpolationPlugin`] for details.
+    fn interpolate<C: Component + Interpolate + Clone>(&mut self) -> &mut Self;
+
+    /// Sets the function that extrapolates the component when the buffer runs out of snapshots.
+    ///
+    /// Without it the component keeps the last received value.
+    /// See [`extrapolate_linear`] for the function based on [`Interpolate`].
+    ///
+    /// See [`SnapshotInterpolationPlugin`] for details.
+    fn set_extrapolation<C: Component>(&mut self, extrapolate: ExtrapolateFn<C>) -> &mut Self;
+}
+
+impl AppInterpolationExt for App {
+    fn interpolate<C: Component + Interpolate + Clone>(&mut self) -> &mut Self {
+        self.set_marker_fns::<Interpolated, C>(write_snapshot::<C>, remove_interpolated::<C>);
+
+        if self.register_helper::<SnapshotBuffer<C>>() {
+            self.add_systems(
+                PreUpdate,
+                update_component::<C>
+                    .after(SnapshotInterpolationPlugin::advance)
+                    .run_if(client_connected),
+            );
+        }
+
+        self
+    }
+
+    fn set_extrapolation<C: Component>(&mut self, extrapolate: ExtrapolateFn<C>) -> &mut Self {
+        self.insert_resource(Extrapolation(extrapolate))
+    }
+}
+
+/// Stores the received value in [`SnapshotBuffer`].
+///
+/// Writes the component as is if the buffer is not created yet.
+fn write_snapshot<C: Component + Clone>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    if let Some(mut buffer) = entity.get_mut::<SnapshotBuffer<C>>() {
+        buffer.insert(ctx.message_tick, component);
+    } else if let Some(mut current) = entity.get_mut::<C>() {
+        *current = component;
+    } else {
+        ctx.commands.entity(entity.id()).insert(component);
+    }
+
+    Ok(())
+}
+
+/// Removes the component and its snapshots.
+fn remove_interpolated<C: Component>(ctx: &mut RemoveCtx, entity: &mut EntityMut) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<(C, SnapshotBuffer<C>)>();
+}
+
+/// Sets components to the blend between snapshots around the render time.
+fn update_component<C: Component + Interpolate + Clone>(
+    mut commands: Commands,
+    render_time: Res<RenderTime>,
+    interpolation: Res<SnapshotInterpolation>,
+    interpolation_delay: Option<Res<InterpolationDelay>>,
+    extrapolation: Option<Res<Extrapolation<C>>>,
+    mut buffered: Query<(&mut C, &mut SnapshotBuffer<C>), With<Interpolated>>,
+    untracked: Query<
+        (Entity, &C, Option<&Confirmed>),
+        (With<Interpolated>, Without<SnapshotBuffer<C>>),
+    >,
+) {
+    for (entity, component, confirmed) in &untracked {
+        let tick = confirmed.map(Confirmed::last_tick).unwrap_or_default();
+        commands.entity(entity).insert(SnapshotBuffer {
+            snapshots: [Snapshot {
+                tick,
+                value: component.clone(),
+            }]
+            .into(),
+            blend_from: None,
+        });
+    }
+
+    let Some(current_time) = render_time.current else {
+        return;
+    };
+    let blend = interpolation_delay.and_then(|delay| delay.fast_forward_blend());
+    for (mut component, mut buffer) in &mut buffered {
+        // Keep the last two snapshots before the render time for extrapolation and newer ones.
+        while buffer
+            .snapshots
+            .get(2)
+            .is_some_and(|snapshot| interpolation.tick_time(snapshot.tick) <= current_time)
+        {
+            buffer.snapshots.pop_front();
+        }
+
+        let snapshots = &buffer.snapshots;
+        let value = match snapshots
+            .iter()
+            .position(|snapshot| interpolation.tick_time(snapshot.tick) > current_time)
+        {
+            Some(0) => snapshots[0].value.clone(),
+            Some(index) => {
+                let from = &snapshots[index - 1];
+                let to = &snapshots[index];
+                let from_time = interpolation.tick_time(from.tick);
+                let elapsed = current_time - from_time;
+                let duration = interpolation.tick_time(to.tick) - from_time;
+                let t = elapsed.as_secs_f32() / duration.as_secs_f32();
+                from.value.interpolate(&to.value, t)
+            }
+            None => {
+                let Some(last) = snapshots.back() else {
+                    continue;
+                };
+                let previous = snapshots
+                    .len()
+                    .checked_sub(2)
+                    .map(|index| &snapshots[index]);
+                match (&extrapolation, previous) {
+                    (Some(extrapolation), Some(previous)) => {
+                        let last_time = interpolation.tick_time(last.tick);
+                        let elapsed =
+                            (current_time - last_time).min(interpolation.max_extrapolation);
+                        let interval = last_time - interpolation.tick_time(previous.tick);
+                        let t = elapsed.as_secs_f32() / interval.as_secs_f32();
+                        (extrapolation.0)(&previous.value, &last.value, t)
+                    }
+                    _ => last.value.clone(),
+                }
+            }
+        };
+
+        if render_time.fast_forwarded {
+            buffer.blend_from = Some(component.clone());
+        }
+        match (blend, &buffer.blend_from) {
+            (Some(t), Some(blend_from)) => *component = blend_from.interpolate(&value, t),
+            _ => {
+                buffer.blend_from = None;
+                *component = value;
+            }
+        }
+    }
+}
+
+/// Blending between two values.
+pub trait Interpolate {
+    /// Returns the value between `self` and `other` at `t` from 0 to 1.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for Transform {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+/// Signature of extrapolation functions.
+///
+/// Accepts the previous and the last received values and returns the predicted value
+/// `t` intervals between them after the last one.
+pub type ExtrapolateFn<C> = fn(&C, &C, f32) -> C;
+
+/// Continues the change between the previous and the last values using [`Interpolate`].
+pub fn extrapolate_linear<C: Interpolate>(previous: &C, last: &C, t: f32) -> C {
+    previous.interpolate(last, 1.0 + t)
+}
+
+/// Extrapolation function for component `C`.
+#[derive(Resource)]
+struct Extrapolation<C>(ExtrapolateFn<C>);
+
+/// Settings for [`SnapshotInterpolationPlugin`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SnapshotInterpolation {
+    /// How far in the past values are rendered.
+    ///
+    /// Ignored if [`InterpolationDelay`] is present.
+    pub delay: Duration,
+
+    /// How long components are extrapolated after the last snapshot.
+    ///
+    /// After that they stay at the extrapolated value until new snapshots arrive.
+    pub max_extrapolation: Duration,
+
+    /// Time between server ticks.
+    ///
+    /// Used to place snapshots on the server timeline, should match the tick rate of the server.
+    pub tick_period: Duration,
+}
+
+impl SnapshotInterpolation {
+    /// Returns the time of the tick on the server timeline.
+    fn tick_time(&self, tick: RepliconTick) -> Duration {
+        self.tick_period * tick.get()
+    }
+}
+
+/// Position on the server timeline at which interpolated components are rendered.
+#[derive(Default, Resource)]
+struct RenderTime {
+    /// Estimated server time.
+    ///
+    /// Advances with the client real time, but never falls behind the latest received tick.
+    /// [`None`] until the first update after connection.
+    clock: Option<Duration>,
+
+    /// Server time minus the delay.
+    current: Option<Duration>,
+
+    /// Whether the clock jumped to the latest received tick due to [`FastForwarded`] this frame.
+    fast_forwarded: bool,
+}
+
+/// Marks an entity whose registered components are interpolated.
+///
+/// See [`SnapshotInterpolationPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Interpolated;
+
+/// Received values of component `C` that are not fully rendered yet.
+///
+/// Present only on client.
+#[derive(Component)]
+pub struct SnapshotBuffer<C> {
+    /// Snapshots sorted by tick.
+    snapshots: VecDeque<Snapshot<C>>,
+
+    /// Displayed value at the moment of the last [`FastForwarded`] while blending is in progress.
+    blend_from: Option<C>,
+}
+
+impl<C> SnapshotBuffer<C> {
+    /// Maximum number of stored snapshots.
+    pub const MAX_LEN: usize = 32;
+
+    /// Returns the number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if there are no snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Returns the tick of the newest snapshot.
+    pub fn last_tick(&self) -> Option<RepliconTick> {
+        self.snapshots.back().map(|snapshot| snapshot.tick)
+    }
+
+    /// Stores a received value.
+    ///
+    /// Replaces the value for the same tick and ignores values older than the newest snapshot.
+    fn insert(&mut self, tick: RepliconTick, value: C) {
+        if let Some(last) = self.snapshots.back_mut() {
+            if tick == last.tick {
+                last.value = value;
+                return;
+            } else if tick < last.tick {
+                return;
+            }
+        }
+
+        self.snapshots.push_back(Snapshot { tick, value });
+        if self.snapshots.len() > Self::MAX_LEN {
+            self.snapshots.pop_front();
+        }
+    }
+}
+
+/// A received value.
+struct Snapshot<C> {
+    tick: RepliconTick,
+    value: C,
+}
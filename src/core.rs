@@ -1,13 +1,24 @@
 pub mod command_markers;
 pub mod common_conditions;
+pub mod component_encryption;
+pub mod delta_compression;
+pub mod message_signing;
+pub mod network_timer;
 pub mod replication_fns;
 pub mod replication_rules;
+pub mod replication_stages;
 pub mod replicon_channels;
 pub mod replicon_tick;
+pub mod server_health;
+pub mod shared_random;
+pub mod singleton;
+pub mod time_scale;
+pub mod wire_format;
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::server::TickJumped;
 use command_markers::CommandMarkers;
 use replication_fns::ReplicationFns;
 use replication_rules::ReplicationRules;
@@ -21,7 +32,8 @@ impl Plugin for RepliconCorePlugin {
             .init_resource::<RepliconChannels>()
             .init_resource::<ReplicationFns>()
             .init_resource::<ReplicationRules>()
-            .init_resource::<CommandMarkers>();
+            .init_resource::<CommandMarkers>()
+            .add_event::<TickJumped>();
     }
 }
 
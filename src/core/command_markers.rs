@@ -3,7 +3,10 @@ use std::cmp::Reverse;
 use bevy::{ecs::component::ComponentId, prelude::*};
 
 use super::replication_fns::command_fns::{RemoveFn, WriteFn};
-use crate::core::replication_fns::ReplicationFns;
+use crate::{
+    client::despawn_grace::DespawnGrace,
+    core::replication_fns::{DespawnFn, ReplicationFns},
+};
 
 /// Marker-based functions for [`App`].
 ///
@@ -119,6 +122,39 @@ pub trait AppMarkerExt {
     /// [`default_remove`](super::replication_fns::command_fns::default_remove).
     /// See also [`Self::set_marker_fns`].
     fn set_command_fns<C: Component>(&mut self, write: WriteFn<C>, remove: RemoveFn) -> &mut Self;
+
+    /**
+    Sets the function that will be called on client for entities despawned by server.
+
+    Replaces [`ReplicationFns::despawn`], which by default uses
+    [`despawn_recursive`](super::replication_fns::despawn_recursive).
+    Useful for playing death animations or fade-outs before removing the entity.
+    The entity is removed from [`ServerEntityMap`](crate::client::server_entity_map::ServerEntityMap)
+    before the call, so it no longer receives updates and should be despawned manually later.
+
+    If [`DespawnGracePlugin`](crate::client::despawn_grace::DespawnGracePlugin) is added, the function
+    is called after the grace period, regardless of whether it was set before or after adding the plugin.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::ctx::DespawnCtx, prelude::*};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .set_despawn_fn(start_dying);
+
+    fn start_dying(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
+        entity.insert(Dying(Timer::from_seconds(1.0, TimerMode::Once)));
+    }
+
+    /// Plays the animation and despawns the entity when the timer finishes.
+    #[derive(Component)]
+    struct Dying(Timer);
+    ```
+    **/
+    fn set_despawn_fn(&mut self, despawn: DespawnFn) -> &mut Self;
 }
 
 impl AppMarkerExt for App {
@@ -164,6 +200,16 @@ impl AppMarkerExt for App {
 
         self
     }
+
+    fn set_despawn_fn(&mut self, despawn: DespawnFn) -> &mut Self {
+        // The grace plugin replaces the function with its own and calls the wrapped one later.
+        if let Some(mut grace) = self.world.get_resource_mut::<DespawnGrace>() {
+            grace.set_despawn(despawn);
+        } else {
+            self.world.resource_mut::<ReplicationFns>().despawn = despawn;
+        }
+        self
+    }
 }
 
 /// Registered markers that override command functions if present.
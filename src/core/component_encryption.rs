@@ -0,0 +1,180 @@
+use bevy::{ecs::component::ComponentId, prelude::*, utils::HashMap};
+use bincode::Options;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+
+use super::{common_conditions::server_running, replication_fns::FnsId, wire_format, ClientId};
+use crate::{
+    client::ClientSet,
+    server::{ServerEvent, ServerSet},
+};
+
+/// Size of the random nonce prepended to each encrypted component.
+pub const NONCE_SIZE: usize = 24;
+
+/// Size of the authentication tag appended to each encrypted component.
+pub const TAG_SIZE: usize = 16;
+
+/**
+Encrypts selected replicated components separately for each recipient.
+
+Useful for hidden information intended for a single client, like hole cards in a card game,
+that shouldn't be revealed by packet capture or a compromised relay. Mark such components with
+[`AppEncryptionExt::encrypt_component`] on both server and client.
+
+Keys should be established for each session by the application, similar to [`MessageSigningPlugin`](super::message_signing::MessageSigningPlugin).
+Insert them into [`ServerEncryptionKeys`] on server and into [`ClientEncryptionKey`] on client.
+Encrypted components are serialized once and encrypted with XChaCha20-Poly1305 using the key of each client
+and a random nonce, clients without a key don't receive them at all. Components that fail authentication on client are skipped.
+
+Only components are encrypted, entities and other data are still visible. Combine with
+[`ClientVisibility`](crate::server::connected_clients::client_visibility::ClientVisibility)
+to hide the entities themselves.
+
+Not added by default. Should be added on both client and server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ComponentEncryptionPlugin))
+    .replicate::<HoleCards>()
+    .encrypt_component::<HoleCards>();
+
+#[derive(Component, Deserialize, Serialize)]
+struct HoleCards([u8; 2]);
+```
+**/
+pub struct ComponentEncryptionPlugin;
+
+impl Plugin for ComponentEncryptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EncryptedComponents>()
+            .init_resource::<ServerEncryptionKeys>()
+            .init_resource::<ClientEncryptionKey>()
+            .add_systems(
+                PreUpdate,
+                (
+                    reset.in_set(ClientSet::Reset),
+                    remove_disconnected
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                ),
+            );
+    }
+}
+
+fn remove_disconnected(
+    mut server_events: EventReader<ServerEvent>,
+    mut keys: ResMut<ServerEncryptionKeys>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+            keys.remove(&client_id);
+        }
+    }
+}
+
+/// Removes the session key after a disconnect, the next session should use a new one.
+fn reset(mut key: ResMut<ClientEncryptionKey>) {
+    **key = None;
+}
+
+/// Component encryption for [`App`].
+pub trait AppEncryptionExt {
+    /// Marks the component as encrypted for each recipient.
+    ///
+    /// Should be called after adding [`ComponentEncryptionPlugin`].
+    /// See it for details.
+    fn encrypt_component<C: Component>(&mut self) -> &mut Self;
+}
+
+impl AppEncryptionExt for App {
+    fn encrypt_component<C: Component>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let mut encrypted_components = self.world.resource_mut::<EncryptedComponents>();
+        if !encrypted_components.0.contains(&component_id) {
+            encrypted_components.0.push(component_id);
+        }
+
+        self
+    }
+}
+
+/// Components marked with [`AppEncryptionExt::encrypt_component`].
+#[derive(Default, Resource)]
+pub struct EncryptedComponents(Vec<ComponentId>);
+
+impl EncryptedComponents {
+    /// Returns `true` if the component is encrypted.
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.0.contains(&component_id)
+    }
+
+    /// Returns `true` if no components are encrypted.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Per-session encryption keys for connected clients.
+///
+/// Keys are automatically removed on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ServerEncryptionKeys(HashMap<ClientId, [u8; 32]>);
+
+/// Encryption key for the current session on client.
+///
+/// Automatically cleared on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ClientEncryptionKey(Option<[u8; 32]>);
+
+/// Encrypts serialized component data and returns the nonce followed by the ciphertext with the tag.
+///
+/// Uses XChaCha20-Poly1305 with a random nonce and the functions ID as associated data,
+/// so the ciphertext can't be substituted for another component.
+pub(crate) fn encrypt(key: &[u8; 32], fns_id: FnsId, data: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: data,
+        aad: &fns_bytes(fns_id),
+    };
+    let ciphertext = cipher
+        .encrypt(&nonce, payload)
+        .expect("component data should fit into the cipher limits");
+
+    let mut encrypted = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    encrypted.extend_from_slice(&nonce);
+    encrypted.extend_from_slice(&ciphertext);
+    encrypted
+}
+
+/// Verifies and decrypts data from [`encrypt`].
+///
+/// Returns [`None`] if the data was tampered or encrypted with a different key.
+pub(crate) fn decrypt(key: &[u8; 32], fns_id: FnsId, encrypted: &[u8]) -> Option<Vec<u8>> {
+    if encrypted.len() < NONCE_SIZE + TAG_SIZE {
+        return None;
+    }
+
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_SIZE);
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let payload = Payload {
+        msg: ciphertext,
+        aad: &fns_bytes(fns_id),
+    };
+    cipher.decrypt(XNonce::from_slice(nonce), payload).ok()
+}
+
+fn fns_bytes(fns_id: FnsId) -> Vec<u8> {
+    wire_format::varint()
+        .serialize(&fns_id)
+        .expect("functions ID should always be serializable")
+}
@@ -0,0 +1,405 @@
+use std::{collections::VecDeque, io::Cursor};
+
+use bevy::{
+    ecs::{
+        component::{Components, Tick},
+        entity::EntityHashMap,
+    },
+    prelude::*,
+    ptr::Ptr,
+};
+use bincode::{ErrorKind, Options};
+
+use super::{
+    common_conditions::server_running,
+    replication_fns::{
+        ctx::{SerializeCtx, WriteCtx},
+        rule_fns::{DeserializeFn, RuleFns},
+        FnsId,
+    },
+    replication_rules::{AppHelperExt, AppRuleExt},
+    replication_stages::{ComponentStage, ComponentStages},
+    wire_format,
+};
+use crate::server::ServerSet;
+
+/// Maximum number of sent values stored for each entity.
+///
+/// Clients whose baseline is older receive the full component.
+const MAX_HISTORY: usize = 32;
+
+/// Field-level delta compression for [`App`].
+pub trait AppDeltaExt {
+    /**
+    Replicates the component, sending only fields that changed since the value acknowledged by each client.
+
+    By default the whole component is serialized on every change. With this mode the server keeps recently
+    sent values and for each client writes a bitmask of changed fields followed by their values,
+    relative to the last value the client acknowledged. Clients patch the existing component in place.
+    Useful for large components like inventories where only a small part changes at a time.
+
+    Insertions and clients without a known baseline receive the full component. Deltas are serialized
+    separately for each client, so prefer it only for components where the saving outweighs the cost.
+    Clients shouldn't modify these components, otherwise patched values will diverge from the server.
+    Deltas are not applied to encrypted components, they are always sent in full.
+
+    Fields are described by [`FieldDelta`], which can be implemented with [`field_delta`](crate::field_delta).
+
+    Should be called on both client and server in the same order as other replication rules.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{field_delta, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_delta::<Inventory>();
+
+    #[derive(Component, Clone, Default)]
+    struct Inventory {
+        slots: Vec<Item>,
+        gold: u32,
+        weight: f32,
+    }
+
+    field_delta!(Inventory { slots, gold, weight });
+
+    #[derive(Clone, Deserialize, PartialEq, Serialize)]
+    struct Item(u16);
+    ```
+    **/
+    fn replicate_delta<C: Component + FieldDelta + Default>(&mut self) -> &mut Self;
+}
+
+impl AppDeltaExt for App {
+    fn replicate_delta<C: Component + FieldDelta + Default>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        self.world
+            .get_resource_or_insert_with(ComponentStages::default)
+            .insert(component_id, Box::<History<C>>::default());
+
+        self.replicate_with(
+            RuleFns::new(delta_serialize::<C>, delta_deserialize::<C>)
+                .with_in_place(delta_deserialize_in_place::<C>)
+                .with_consume(delta_consume::<C>),
+        );
+
+        if self.register_helper::<History<C>>() {
+            self.add_systems(
+                PostUpdate,
+                cleanup_removed::<C>
+                    .before(ServerSet::Send)
+                    .run_if(server_running),
+            );
+        }
+
+        self
+    }
+}
+
+fn cleanup_removed<C: Component>(
+    mut removed: RemovedComponents<C>,
+    mut stages: ResMut<ComponentStages>,
+    components: &Components,
+) {
+    let Some(history) = components
+        .component_id::<C>()
+        .and_then(|component_id| stages.get_mut(component_id))
+    else {
+        return;
+    };
+
+    for entity in removed.read() {
+        history.remove(entity);
+    }
+}
+
+/**
+Field access for [`AppDeltaExt::replicate_delta`].
+
+Each field is represented by a bit in a mask, so up to 64 fields are supported.
+Can be implemented with [`field_delta`](crate::field_delta).
+**/
+pub trait FieldDelta: Clone + Send + Sync + 'static {
+    /// Mask with bits of all fields.
+    const ALL_FIELDS: u64;
+
+    /// Returns a mask of fields that differ from `other`.
+    fn changed_fields(&self, other: &Self) -> u64;
+
+    /// Serializes fields whose bits are set in the mask in the order of their bits.
+    fn write_fields(&self, fields: u64, cursor: &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
+
+    /// Deserializes fields whose bits are set in the mask into the existing value.
+    fn read_fields(&mut self, fields: u64, cursor: &mut Cursor<&[u8]>) -> bincode::Result<()>;
+}
+
+/**
+Implements [`FieldDelta`] for a struct with named fields.
+
+All fields need to be listed. Each field should implement [`PartialEq`], [`Serialize`](serde::Serialize)
+and [`DeserializeOwned`](serde::de::DeserializeOwned). Bits are assigned in the listed order.
+
+See [`AppDeltaExt::replicate_delta`] for an example.
+**/
+#[macro_export]
+macro_rules! field_delta {
+    ($type:ty { $($field:ident),+ $(,)? }) => {
+        impl $crate::core::delta_compression::FieldDelta for $type {
+            const ALL_FIELDS: u64 = {
+                let mut fields = 0u64;
+                $(
+                    let _ = stringify!($field);
+                    fields = fields << 1 | 1;
+                )+
+                fields
+            };
+
+            fn changed_fields(&self, other: &Self) -> u64 {
+                let mut fields = 0;
+                let mut bit = 1;
+                $(
+                    if self.$field != other.$field {
+                        fields |= bit;
+                    }
+                    bit <<= 1;
+                )+
+                let _ = bit;
+                fields
+            }
+
+            fn write_fields(
+                &self,
+                fields: u64,
+                cursor: &mut ::std::io::Cursor<Vec<u8>>,
+            ) -> $crate::bincode::Result<()> {
+                let mut bit = 1;
+                $(
+                    if fields & bit != 0 {
+                        $crate::bincode::Options::serialize_into(
+                            $crate::core::wire_format::varint(),
+                            &mut *cursor,
+                            &self.$field,
+                        )?;
+                    }
+                    bit <<= 1;
+                )+
+                let _ = bit;
+                Ok(())
+            }
+
+            fn read_fields(
+                &mut self,
+                fields: u64,
+                cursor: &mut ::std::io::Cursor<&[u8]>,
+            ) -> $crate::bincode::Result<()> {
+                let mut bit = 1;
+                $(
+                    if fields & bit != 0 {
+                        self.$field = $crate::bincode::Options::deserialize_from(
+                            $crate::core::wire_format::varint(),
+                            &mut *cursor,
+                        )?;
+                    }
+                    bit <<= 1;
+                )+
+                let _ = bit;
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Like [`default_serialize`](super::replication_fns::rule_fns::default_serialize),
+/// but writes the mask of all fields followed by the fields.
+///
+/// Used for insertions and clients without a baseline.
+pub fn delta_serialize<C: Component + FieldDelta>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    write_delta(component, C::ALL_FIELDS, cursor)
+}
+
+/// Like [`default_deserialize`](super::replication_fns::rule_fns::default_deserialize),
+/// but reads the component written by [`delta_serialize`].
+///
+/// Returns an error if not all fields are present, since there is no value to patch.
+pub fn delta_deserialize<C: Component + FieldDelta + Default>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    let fields: u64 = wire_format::varint().deserialize_from(&mut *cursor)?;
+    if fields != C::ALL_FIELDS {
+        return Err(ErrorKind::Custom(format!(
+            "received delta with fields {fields:#b} for missing {}",
+            std::any::type_name::<C>()
+        ))
+        .into());
+    }
+
+    let mut component = C::default();
+    component.read_fields(fields, cursor)?;
+    Ok(component)
+}
+
+/// Patches the existing component with fields from the delta.
+pub fn delta_deserialize_in_place<C: Component + FieldDelta>(
+    _deserialize: &DeserializeFn<C>,
+    _ctx: &mut WriteCtx,
+    component: &mut C,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let fields = wire_format::varint().deserialize_from(&mut *cursor)?;
+    component.read_fields(fields, cursor)
+}
+
+/// Reads the delta and ignores it.
+pub fn delta_consume<C: Component + FieldDelta + Default>(
+    _deserialize: &DeserializeFn<C>,
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let fields = wire_format::varint().deserialize_from(&mut *cursor)?;
+    C::default().read_fields(fields, cursor)
+}
+
+fn write_delta<C: FieldDelta>(
+    component: &C,
+    fields: u64,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    wire_format::varint().serialize_into(&mut *cursor, &fields)?;
+    component.write_fields(fields, cursor)
+}
+
+/// Sent values of a component registered with [`AppDeltaExt::replicate_delta`].
+///
+/// Stored in [`ComponentStages`] and used on server to calculate deltas for each client.
+/// Values of entities that are not replicated to any client are periodically removed,
+/// see [`ServerPlugin::update_timeout`](crate::server::ServerPlugin::update_timeout).
+#[derive(Default)]
+struct History<C> {
+    values: EntityHashMap<VecDeque<(Tick, C)>>,
+}
+
+impl<C: Component + FieldDelta> ComponentStage for History<C> {
+    /// Writes fields that changed since the baseline of the client.
+    ///
+    /// The baseline is the last value sent not after the client's change limit.
+    /// Nothing is written if there is no baseline.
+    unsafe fn write_update(
+        &self,
+        entity: Entity,
+        ptr: Ptr,
+        fns_id: FnsId,
+        change_limit: Tick,
+        this_run: Tick,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<bool> {
+        let Some(values) = self.values.get(&entity) else {
+            return Ok(false);
+        };
+        let Some(baseline) = values
+            .iter()
+            .rposition(|(tick, _)| !tick.is_newer_than(change_limit, this_run))
+        else {
+            return Ok(false);
+        };
+
+        // Values sent after the baseline might be already applied on the client,
+        // so fields that changed in any of them need to be overwritten.
+        let component: &C = ptr.deref();
+        let fields = values.range(baseline..).fold(0, |fields, (_, value)| {
+            fields | component.changed_fields(value)
+        });
+
+        wire_format::varint().serialize_into(&mut *cursor, &fns_id)?;
+        write_delta(component, fields, cursor)?;
+
+        Ok(true)
+    }
+
+    /// Stores the sent value and removes values that are no longer needed as baselines.
+    ///
+    /// Values not after `oldest_limit` are kept only if they are the latest among them.
+    unsafe fn sent(
+        &mut self,
+        entity: Entity,
+        ptr: Ptr,
+        oldest_limit: Option<Tick>,
+        this_run: Tick,
+    ) {
+        let values = self.values.entry(entity).or_default();
+        if let Some(limit) = oldest_limit {
+            if let Some(baseline) = values
+                .iter()
+                .rposition(|(tick, _)| !tick.is_newer_than(limit, this_run))
+            {
+                values.drain(..baseline);
+            }
+        }
+        if values.len() >= MAX_HISTORY {
+            values.pop_front();
+        }
+
+        let component: &C = ptr.deref();
+        values.push_back((this_run, component.clone()));
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        self.values.remove(&entity);
+    }
+
+    fn retain(&mut self, f: &dyn Fn(Entity) -> bool) -> usize {
+        let len = self.values.len();
+        self.values.retain(|&entity, _| f(entity));
+        len - self.values.len()
+    }
+
+    fn len(&self) -> usize {
+        self.values.values().map(VecDeque::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields() {
+        let value = TestComponent {
+            a: 1,
+            b: "text".into(),
+            c: 2.0,
+        };
+        let changed = TestComponent {
+            b: "other".into(),
+            ..value.clone()
+        };
+        assert_eq!(TestComponent::ALL_FIELDS, 0b111);
+        assert_eq!(value.changed_fields(&changed), 0b010);
+
+        let mut cursor = Cursor::default();
+        write_delta(&changed, 0b010, &mut cursor).unwrap();
+
+        let mut cursor = Cursor::new(&**cursor.get_ref());
+        let fields = wire_format::varint().deserialize_from(&mut cursor).unwrap();
+        let mut patched = value.clone();
+        patched.read_fields(fields, &mut cursor).unwrap();
+        assert_eq!(patched, changed);
+    }
+
+    #[derive(Component, Clone, Debug, Default, PartialEq)]
+    struct TestComponent {
+        a: u32,
+        b: String,
+        c: f32,
+    }
+
+    field_delta!(TestComponent { a, b, c });
+}
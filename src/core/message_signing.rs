@@ -0,0 +1,397 @@
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+
+use super::{
+    common_conditions::{client_connected, server_running},
+    replication_stages,
+    replicon_channels::{ChannelKind, ReplicationChannel, RepliconChannels},
+    ClientId,
+};
+use crate::{
+    client::{replicon_client::RepliconClient, ClientPlugin, ClientSet},
+    server::{replicon_server::RepliconServer, ServerEvent, ServerPlugin, ServerSet},
+};
+
+/// Size of the signature appended to each signed message, including the sequence number.
+pub const SIGNATURE_SIZE: usize = SEQUENCE_SIZE + MAC_SIZE;
+
+const SEQUENCE_SIZE: usize = std::mem::size_of::<u64>();
+const MAC_SIZE: usize = 16;
+
+/// Number of sequence numbers behind the latest one that are still accepted.
+const REPLAY_WINDOW: u64 = u64::BITS as u64;
+
+const NONCE_SIZE: usize = 32;
+const CLIENT_SECRET_CONTEXT: &str = "bevy_replicon 2024-05 client signing secret";
+const SESSION_KEY_CONTEXT: &str = "bevy_replicon 2024-05 session signing key";
+
+/// Signs replication messages with application-provided keys and verifies them on clients.
+///
+/// Signing happens on top of the messaging backend, so relays and recorders
+/// between the server and clients can be untrusted. Messages are not encrypted,
+/// only authenticated.
+///
+/// Each message gets a sequence number that increases separately for each client and channel.
+/// The signature covers the channel, the sequence number and the message itself, so clients
+/// discard messages that were replayed, moved to a different replication channel or tampered.
+/// Since update messages are unreliable, out-of-order messages are accepted if they are within
+/// the last 64 sequence numbers and weren't received before.
+///
+/// Keys should be unique for each session. The plugin can derive them on connect:
+/// set [`ServerSigningSecret`] on server and pass [`client_secret`] for the client ID to the
+/// client during authentication, for example inside a connect token. On client insert it into
+/// [`ClientSigningSecret`] before connecting. On connect the server sends a random nonce
+/// and both sides derive the session key from the client secret and the nonce.
+/// Replication messages received before the nonce are held until it arrives.
+/// The client secret itself is never sent by the plugin, so the connection that delivers it
+/// to the client should be trusted.
+///
+/// Alternatively, keys could be established by the application and inserted directly into
+/// [`ServerSigningKeys`] on server and into [`ClientSigningKey`] on client.
+/// Sequence numbers start over on each connection, so reusing a key across sessions
+/// allows messages from a previous session to be replayed.
+///
+/// Messages for clients without a key will be sent unsigned.
+/// Client with a key will discard all unsigned or tampered messages.
+///
+/// Not added by default.
+pub struct MessageSigningPlugin;
+
+impl Plugin for MessageSigningPlugin {
+    fn build(&self, app: &mut App) {
+        let channel_id = app
+            .world
+            .resource_mut::<RepliconChannels>()
+            .create_server_channel(ChannelKind::Ordered.into());
+
+        app.insert_resource(HandshakeChannel(channel_id))
+            .init_resource::<ServerSigningSecret>()
+            .init_resource::<ServerSigningKeys>()
+            .init_resource::<SendSequences>()
+            .init_resource::<ClientSigningSecret>()
+            .init_resource::<ClientSigningKey>()
+            .init_resource::<PendingMessages>()
+            .init_resource::<ReplayWindows>()
+            .add_systems(
+                PreUpdate,
+                (
+                    reset.in_set(ClientSet::Reset),
+                    verify
+                        .before(ClientPlugin::receive_replication)
+                        .before(replication_stages::apply_receive_stages)
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    update_keys
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                sign.after(ServerPlugin::send_replication)
+                    .after(replication_stages::apply_send_stages)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+fn sign(
+    mut server: ResMut<RepliconServer>,
+    keys: Res<ServerSigningKeys>,
+    mut sequences: ResMut<SendSequences>,
+) {
+    for (client_id, channel_id, message) in server.iter_sent_mut() {
+        if !is_replication_channel(*channel_id) {
+            continue;
+        }
+
+        if let Some(key) = keys.get(client_id) {
+            let sequence = sequences.entry((*client_id, *channel_id)).or_default();
+            *sequence += 1;
+
+            let mac = mac(key, *channel_id, *sequence, message);
+            *message = Bytes::from([message, &sequence.to_le_bytes()[..], &mac[..]].concat());
+        }
+    }
+}
+
+fn verify(
+    mut client: ResMut<RepliconClient>,
+    mut key: ResMut<ClientSigningKey>,
+    mut pending: ResMut<PendingMessages>,
+    mut windows: ResMut<ReplayWindows>,
+    secret: Res<ClientSigningSecret>,
+    channel: Res<HandshakeChannel>,
+) {
+    for nonce in client.receive(**channel) {
+        let Some(secret) = **secret else {
+            debug!("ignoring session nonce without a client secret");
+            continue;
+        };
+        let Ok(nonce) = nonce[..].try_into() else {
+            error!("unable to derive session key: received nonce of invalid size");
+            continue;
+        };
+
+        debug!("deriving session key from the received nonce");
+        **key = Some(session_key(&secret, nonce));
+    }
+
+    if (key.is_none() && secret.is_some()) || !pending.is_empty() {
+        for channel_id in REPLICATION_CHANNELS {
+            let messages: Vec<_> = client.receive(channel_id).collect();
+            pending.extend(messages.into_iter().map(|message| (channel_id, message)));
+        }
+    }
+
+    let Some(key) = **key else {
+        if !pending.is_empty() {
+            trace!("holding {} messages until session nonce", pending.len());
+        }
+        return;
+    };
+
+    for (channel_id, message) in pending.drain(..) {
+        client.insert_received(channel_id, message);
+    }
+
+    for channel_id in REPLICATION_CHANNELS {
+        let window = windows.entry(channel_id).or_default();
+        client.retain_received(channel_id, |message| {
+            let Some(data_size) = message.len().checked_sub(SIGNATURE_SIZE) else {
+                debug!("discarding unsigned message from channel {channel_id}");
+                return false;
+            };
+
+            let (data, signature) = message.split_at(data_size);
+            let (sequence, received_mac) = signature.split_at(SEQUENCE_SIZE);
+            let sequence = u64::from_le_bytes(sequence.try_into().unwrap());
+            let expected = mac(&key, channel_id, sequence, data);
+            if !constant_time_eq(&expected, received_mac) {
+                debug!("discarding message with invalid signature from channel {channel_id}");
+                return false;
+            }
+
+            if !window.accept(sequence) {
+                debug!("discarding replayed message {sequence} from channel {channel_id}");
+                return false;
+            }
+
+            message.truncate(data_size);
+            true
+        });
+    }
+}
+
+/// Derives keys for connected clients if [`ServerSigningSecret`] is set and removes keys of disconnected clients.
+fn update_keys(
+    mut server: ResMut<RepliconServer>,
+    mut server_events: EventReader<ServerEvent>,
+    mut keys: ResMut<ServerSigningKeys>,
+    mut sequences: ResMut<SendSequences>,
+    secret: Res<ServerSigningSecret>,
+    channel: Res<HandshakeChannel>,
+) {
+    for event in server_events.read() {
+        match *event {
+            ServerEvent::ClientConnected { client_id } => {
+                let Some(secret) = **secret else {
+                    continue;
+                };
+
+                let mut nonce = [0; NONCE_SIZE];
+                OsRng.fill_bytes(&mut nonce);
+                let key = session_key(&client_secret(&secret, client_id), &nonce);
+                debug!("sending session nonce to client `{client_id:?}`");
+                keys.insert(client_id, key);
+                server.send(client_id, **channel, nonce.to_vec());
+            }
+            ServerEvent::ClientDisconnected { client_id, .. } => {
+                keys.remove(&client_id);
+                sequences.retain(|&(sequence_client_id, _), _| sequence_client_id != client_id);
+            }
+        }
+    }
+}
+
+/// Removes the session key after a disconnect, the next session should use a new one.
+fn reset(
+    mut key: ResMut<ClientSigningKey>,
+    mut pending: ResMut<PendingMessages>,
+    mut windows: ResMut<ReplayWindows>,
+) {
+    **key = None;
+    pending.clear();
+    windows.clear();
+}
+
+const REPLICATION_CHANNELS: [u8; 3] = [
+    ReplicationChannel::Init as u8,
+    ReplicationChannel::Update as u8,
+    ReplicationChannel::UpdateChunks as u8,
+];
+
+fn is_replication_channel(channel_id: u8) -> bool {
+    REPLICATION_CHANNELS.contains(&channel_id)
+}
+
+/// Derives a secret for the client from [`ServerSigningSecret`].
+///
+/// Should be passed to the client over a trusted connection and inserted into [`ClientSigningSecret`].
+pub fn client_secret(server_secret: &[u8; 32], client_id: ClientId) -> [u8; 32] {
+    blake3::Hasher::new_derive_key(CLIENT_SECRET_CONTEXT)
+        .update(server_secret)
+        .update(&client_id.get().to_le_bytes())
+        .finalize()
+        .into()
+}
+
+fn session_key(client_secret: &[u8; 32], nonce: &[u8; NONCE_SIZE]) -> [u8; 32] {
+    blake3::Hasher::new_derive_key(SESSION_KEY_CONTEXT)
+        .update(client_secret)
+        .update(nonce)
+        .finalize()
+        .into()
+}
+
+fn mac(key: &[u8; 32], channel_id: u8, sequence: u64, message: &[u8]) -> [u8; MAC_SIZE] {
+    let hash = blake3::Hasher::new_keyed(key)
+        .update(&[channel_id])
+        .update(&sequence.to_le_bytes())
+        .update(message)
+        .finalize();
+    let mut mac = [0; MAC_SIZE];
+    mac.copy_from_slice(&hash.as_bytes()[..MAC_SIZE]);
+    mac
+}
+
+/// Compares signatures without short-circuiting to avoid leaking timing information.
+pub(super) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Secret from which session keys for connecting clients are derived.
+///
+/// If set, a key is inserted into [`ServerSigningKeys`] for each connected client.
+/// See [`MessageSigningPlugin`] for details.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ServerSigningSecret(Option<[u8; 32]>);
+
+/// Per-session signing keys for connected clients.
+///
+/// Keys are automatically removed on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ServerSigningKeys(HashMap<ClientId, [u8; 32]>);
+
+/// Signing key for the current session on client.
+///
+/// If set, all replication messages from the server will be verified.
+/// Automatically cleared on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ClientSigningKey(Option<[u8; 32]>);
+
+/// Secret obtained from [`client_secret`] on server.
+///
+/// If set, [`ClientSigningKey`] will be derived from the nonce sent by the server on connect.
+/// Unlike the key, not cleared on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct ClientSigningSecret(Option<[u8; 32]>);
+
+/// Server channel for session nonces.
+#[derive(Clone, Copy, Deref, Resource)]
+struct HandshakeChannel(u8);
+
+/// Replication messages received before the session nonce.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct PendingMessages(Vec<(u8, Bytes)>);
+
+/// Last sent sequence numbers for each client and replication channel.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct SendSequences(HashMap<(ClientId, u8), u64>);
+
+/// Received sequence numbers for each replication channel on client.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct ReplayWindows(HashMap<u8, ReplayWindow>);
+
+/// Sliding window of received sequence numbers.
+#[derive(Default)]
+struct ReplayWindow {
+    /// Latest received sequence number, sequences start from 1.
+    latest: u64,
+
+    /// Received sequences behind the latest one, where bit `n` means `latest - n`.
+    received: u64,
+}
+
+impl ReplayWindow {
+    /// Marks the sequence as received.
+    ///
+    /// Returns `false` if it was already received or too old.
+    fn accept(&mut self, sequence: u64) -> bool {
+        if sequence > self.latest {
+            let shift = sequence - self.latest;
+            self.received = if shift < REPLAY_WINDOW {
+                self.received << shift
+            } else {
+                0
+            };
+            self.received |= 1;
+            self.latest = sequence;
+            return true;
+        }
+
+        let offset = self.latest - sequence;
+        if sequence == 0 || offset >= REPLAY_WINDOW || self.received & (1 << offset) != 0 {
+            return false;
+        }
+
+        self.received |= 1 << offset;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing() {
+        let key = [1; 32];
+        let message = [2; 10];
+        assert!(constant_time_eq(
+            &mac(&key, 0, 1, &message),
+            &mac(&key, 0, 1, &message)
+        ));
+        assert!(!constant_time_eq(
+            &mac(&key, 0, 1, &message),
+            &mac(&[3; 32], 0, 1, &message)
+        ));
+        assert!(!constant_time_eq(
+            &mac(&key, 0, 1, &message),
+            &mac(&key, 1, 1, &message)
+        ));
+        assert!(!constant_time_eq(
+            &mac(&key, 0, 1, &message),
+            &mac(&key, 0, 2, &message)
+        ));
+    }
+
+    #[test]
+    fn replay_window() {
+        let mut window = ReplayWindow::default();
+        assert!(!window.accept(0));
+        assert!(window.accept(1));
+        assert!(!window.accept(1));
+        assert!(window.accept(3));
+        assert!(window.accept(2), "reordered sequence should be accepted");
+        assert!(!window.accept(2));
+        assert!(window.accept(3 + REPLAY_WINDOW));
+        assert!(
+            !window.accept(3),
+            "sequence outside the window should be rejected"
+        );
+        assert!(window.accept(4));
+    }
+}
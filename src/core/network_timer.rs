@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use super::replicon_tick::RepliconTick;
+
+/**
+A timer that counts down in server ticks instead of wall time.
+
+Stores only the tick at which it started and its duration, so a component with it is replicated
+only when the timer is started and doesn't change on each tick. Since all calculations depend only
+on the passed tick, the same remaining time is displayed on server and clients without drift.
+
+Use [`ServerTick`](crate::server::server_tick::ServerTick) on server and the tick of the received state on client,
+like [`Confirmed::last_tick`](crate::client::confirmed::Confirmed::last_tick) of the entity
+or [`ServerInitTick`](crate::client::ServerInitTick).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::network_timer::NetworkTimer, prelude::*, server::server_tick::ServerTick};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins))
+    .replicate::<RespawnTimer>()
+    .add_systems(Update, respawn.run_if(server_running));
+
+fn respawn(
+    mut commands: Commands,
+    server_tick: Res<ServerTick>,
+    timers: Query<(Entity, &RespawnTimer)>,
+) {
+    for (entity, timer) in &timers {
+        if timer.is_finished(**server_tick) {
+            commands.entity(entity).remove::<RespawnTimer>();
+        }
+    }
+}
+
+#[derive(Component, Deref, Deserialize, Serialize)]
+struct RespawnTimer(NetworkTimer);
+```
+**/
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NetworkTimer {
+    start: RepliconTick,
+    duration: u32,
+}
+
+impl NetworkTimer {
+    /// Creates a new timer that starts at the tick and lasts for the number of ticks.
+    pub fn new(start: RepliconTick, duration: u32) -> Self {
+        Self { start, duration }
+    }
+
+    /// Restarts the timer from the tick with a new duration.
+    pub fn start(&mut self, start: RepliconTick, duration: u32) {
+        *self = Self::new(start, duration);
+    }
+
+    /// Returns the tick at which the timer was started.
+    pub fn start_tick(&self) -> RepliconTick {
+        self.start
+    }
+
+    /// Returns the tick at which the timer finishes.
+    pub fn end_tick(&self) -> RepliconTick {
+        self.start + self.duration
+    }
+
+    /// Returns the number of ticks the timer lasts.
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Returns the number of ticks elapsed at the tick, clamped to the duration.
+    ///
+    /// Returns 0 for ticks before the start.
+    pub fn elapsed(&self, tick: RepliconTick) -> u32 {
+        if tick > self.start {
+            (tick - self.start).min(self.duration)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the number of ticks remaining at the tick.
+    pub fn remaining(&self, tick: RepliconTick) -> u32 {
+        self.duration - self.elapsed(tick)
+    }
+
+    /// Returns the elapsed fraction from 0.0 to 1.0 at the tick.
+    ///
+    /// Timers with zero duration are always complete.
+    pub fn fraction(&self, tick: RepliconTick) -> f32 {
+        if self.duration == 0 {
+            1.0
+        } else {
+            self.elapsed(tick) as f32 / self.duration as f32
+        }
+    }
+
+    /// Returns `true` if the timer finished at the tick.
+    pub fn is_finished(&self, tick: RepliconTick) -> bool {
+        self.remaining(tick) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown() {
+        let timer = NetworkTimer::new(RepliconTick::new(u32::MAX - 1), 4);
+        assert_eq!(timer.end_tick(), RepliconTick::new(2));
+
+        assert_eq!(timer.remaining(RepliconTick::new(u32::MAX - 2)), 4);
+        assert_eq!(timer.remaining(RepliconTick::new(u32::MAX)), 3);
+        assert_eq!(timer.fraction(RepliconTick::new(0)), 0.5);
+        assert!(!timer.is_finished(RepliconTick::new(1)));
+        assert!(timer.is_finished(RepliconTick::new(2)));
+        assert_eq!(timer.elapsed(RepliconTick::new(10)), 4);
+    }
+}
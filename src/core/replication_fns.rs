@@ -1,17 +1,24 @@
 pub mod command_fns;
 pub mod component_fns;
 pub mod ctx;
+pub mod quantization;
 pub mod rule_fns;
 pub mod test_fns;
 
-use bevy::{ecs::component::ComponentId, prelude::*};
+use std::any;
+
+use bevy::{
+    ecs::component::{ComponentId, Components},
+    prelude::*,
+    utils::HashMap,
+};
 use serde::{Deserialize, Serialize};
 
 use super::command_markers::CommandMarkerIndex;
 use command_fns::{RemoveFn, UntypedCommandFns, WriteFn};
 use component_fns::ComponentFns;
 use ctx::DespawnCtx;
-use rule_fns::{RuleFns, UntypedRuleFns};
+use rule_fns::{ErasedCodec, RuleFns, UntypedRuleFns};
 
 /// Stores configurable replication functions.
 #[derive(Resource)]
@@ -22,6 +29,14 @@ pub struct ReplicationFns {
     /// Useful if you need to intercept despawns and handle them in a special way.
     pub despawn: DespawnFn,
 
+    /// Codec for components registered without an explicit codec.
+    ///
+    /// By default uses [`BincodeCodec`](rule_fns::BincodeCodec).
+    /// Changing it affects only components registered after the change.
+    ///
+    /// See also [`AppRuleExt::set_default_codec`](super::replication_rules::AppRuleExt::set_default_codec).
+    pub default_codec: ErasedCodec,
+
     /// Functions for replicated components.
     ///
     /// Unique for each component.
@@ -97,6 +112,15 @@ impl ReplicationFns {
         }
     }
 
+    /// Removes all component and rule functions.
+    ///
+    /// Keeps [`Self::despawn`], [`Self::default_codec`] and registered marker slots.
+    /// Used to register functions again, see [`ProtocolReloadPlugin`](crate::protocol_reload::ProtocolReloadPlugin).
+    pub(crate) fn clear(&mut self) {
+        self.components.clear();
+        self.rules.clear();
+    }
+
     /// Registers serialization/deserialization functions for a component.
     ///
     /// Returned data can be assigned to a
@@ -107,7 +131,17 @@ impl ReplicationFns {
         rule_fns: RuleFns<C>,
     ) -> FnsInfo {
         let (index, component_id) = self.init_component_fns::<C>(world);
-        self.rules.push((rule_fns.into(), index));
+        let rule_fns = UntypedRuleFns::from(rule_fns);
+        if let Some(wire_name) = rule_fns.wire_name() {
+            assert!(
+                self.rules.iter().all(|(other_fns, other_index)| {
+                    *other_index == index || other_fns.wire_name() != Some(wire_name)
+                }),
+                "wire name `{wire_name}` for `{}` should be unique across components",
+                any::type_name::<C>()
+            );
+        }
+        self.rules.push((rule_fns, index));
 
         FnsInfo {
             component_id,
@@ -148,12 +182,82 @@ impl ReplicationFns {
 
         (command_fns, rule_fns)
     }
+
+    /// Returns `true` if the ID belongs to registered rule functions.
+    pub(crate) fn contains(&self, fns_id: FnsId) -> bool {
+        fns_id.0 < self.rules.len()
+    }
+
+    /// Returns the ID of the component for which the rule functions were registered.
+    pub(crate) fn component_id(&self, fns_id: FnsId) -> ComponentId {
+        let &(_, index) = self
+            .rules
+            .get(fns_id.0)
+            .expect("serde function IDs should be obtained from the same instance");
+
+        // SAFETY: index obtained from `rules` is always valid.
+        let &(_, component_id) = unsafe { self.components.get_unchecked(index) };
+
+        component_id
+    }
+
+    /// Returns IDs of registered rule functions with their component names in registration order.
+    ///
+    /// Rule functions registered for the same component multiple times have repeated names.
+    /// Names set with [`RuleFns::with_wire_name`] are used instead of type names.
+    pub(crate) fn rule_names<'a>(
+        &'a self,
+        components: &'a Components,
+    ) -> impl Iterator<Item = (FnsId, &'a str)> + 'a {
+        self.rules
+            .iter()
+            .enumerate()
+            .map(|(fns_index, (rule_fns, index))| {
+                let name = rule_fns.wire_name().unwrap_or_else(|| {
+                    let (_, component_id) = self.components[*index];
+                    components
+                        .get_info(component_id)
+                        .expect("replicated components should be initialized")
+                        .name()
+                });
+                (FnsId(fns_index), name)
+            })
+    }
+
+    /// Maps IDs of registered rule functions to IDs from a different instance with the specified names.
+    ///
+    /// Names should be obtained from [`Self::rule_names`] of the other instance.
+    /// Functions are matched by the component name and the occurrence of the name, so other registrations
+    /// could be in a different order. Missing functions are mapped to [`None`].
+    /// Only IDs that differ are included.
+    pub(crate) fn map_rules(
+        &self,
+        components: &Components,
+        names: &[String],
+    ) -> HashMap<FnsId, Option<FnsId>> {
+        let mut occurrences = HashMap::<&str, usize>::new();
+        self.rule_names(components)
+            .filter_map(|(fns_id, name)| {
+                let occurrence = occurrences.entry(name).or_default();
+                let other_id = names
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, other_name)| *other_name == name)
+                    .nth(*occurrence)
+                    .map(|(other_index, _)| FnsId(other_index));
+                *occurrence += 1;
+
+                (other_id != Some(fns_id)).then_some((fns_id, other_id))
+            })
+            .collect()
+    }
 }
 
 impl Default for ReplicationFns {
     fn default() -> Self {
         Self {
             despawn: despawn_recursive,
+            default_codec: Default::default(),
             components: Default::default(),
             rules: Default::default(),
             marker_slots: 0,
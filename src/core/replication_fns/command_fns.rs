@@ -33,7 +33,7 @@ impl UntypedCommandFns {
             type_id: TypeId::of::<C>(),
             type_name: any::type_name::<C>(),
             // SAFETY: the function won't be called until the type is restored.
-            write: unsafe { mem::transmute(write) },
+            write: unsafe { mem::transmute::<WriteFn<C>, unsafe fn()>(write) },
             remove,
         }
     }
@@ -58,7 +58,7 @@ impl UntypedCommandFns {
             self.type_name,
         );
 
-        let write: WriteFn<C> = unsafe { mem::transmute(self.write) };
+        let write = unsafe { mem::transmute::<unsafe fn(), WriteFn<C>>(self.write) };
         (write)(ctx, rule_fns, entity, cursor)
     }
 
@@ -95,6 +95,26 @@ pub fn default_write<C: Component>(
     Ok(())
 }
 
+/// Component writing function for [`AppRuleExt::replicate_into`](crate::core::replication_rules::AppRuleExt::replicate_into).
+///
+/// Deserializes `S` with [`RuleFns::deserialize`] and writes `C` created from it.
+/// If `C` exists on the entity, it will be replaced directly on the entity, otherwise inserted via [`Commands`].
+pub fn convert_write<S: Component, C: Component + From<S>>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<S>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?.into();
+    if let Some(mut old_component) = entity.get_mut::<C>() {
+        *old_component = component;
+    } else {
+        ctx.commands.entity(entity.id()).insert(component);
+    }
+
+    Ok(())
+}
+
 /// Default component removal function.
 pub fn default_remove<C: Component>(ctx: &mut RemoveCtx, entity: &mut EntityMut) {
     ctx.commands.entity(entity.id()).remove::<C>();
@@ -213,7 +213,7 @@ unsafe fn untyped_write<C: Component>(
     entity: &mut EntityMut,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<()> {
-    command_fns.write::<C>(ctx, &rule_fns.typed::<C>(), entity, cursor)
+    command_fns.write::<C>(ctx, rule_fns.typed::<C>(), entity, cursor)
 }
 
 /// Resolves `rule_fns` to `C` and calls [`RuleFns::consume`](super::rule_fns::RuleFns) for `C`.
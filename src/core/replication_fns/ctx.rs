@@ -1,14 +1,61 @@
 use bevy::prelude::*;
 
 use crate::{
-    client::server_entity_map::ServerEntityMap, core::replicon_tick::RepliconTick, Replicated,
+    client::{entity_allocator::EntityAllocator, server_entity_map::ServerEntityMap},
+    core::{replicon_tick::RepliconTick, ClientId},
+    Replicated,
 };
 
 /// Replication context for serialization function.
 #[non_exhaustive]
-pub struct SerializeCtx {
+#[derive(Clone, Copy)]
+pub struct SerializeCtx<'a> {
     /// Current tick.
     pub server_tick: RepliconTick,
+
+    /// Client for which the component is serialized.
+    ///
+    /// Components are serialized once and the result is shared between clients, so it's set only for
+    /// components registered with [`RuleFns::per_client`](super::rule_fns::RuleFns::per_client).
+    pub client_id: Option<ClientId>,
+
+    /// Whether the component is serialized for an insertion.
+    ///
+    /// `false` for changes sent in update messages, which allows omitting data
+    /// that clients need only once. Always `true` outside of replication, like for saves.
+    pub is_insertion: bool,
+
+    world: &'a World,
+}
+
+impl<'a> SerializeCtx<'a> {
+    pub(crate) fn new(world: &'a World, server_tick: RepliconTick) -> Self {
+        Self {
+            server_tick,
+            client_id: None,
+            is_insertion: true,
+            world,
+        }
+    }
+
+    /// Sets [`Self::client_id`] for per-client serialization.
+    pub(crate) fn for_client(mut self, client_id: ClientId) -> Self {
+        self.client_id = Some(client_id);
+        self
+    }
+
+    /// Clears [`Self::is_insertion`] for serialization of changes.
+    pub(crate) fn for_update(mut self) -> Self {
+        self.is_insertion = false;
+        self
+    }
+
+    /// Returns a resource from the world if it exists.
+    ///
+    /// Useful for serialization that depends on configuration or on the state of the client from [`Self::client_id`].
+    pub fn get_resource<R: Resource>(&self) -> Option<&'a R> {
+        self.world.get_resource::<R>()
+    }
 }
 
 /// Replication context for writing and deserialization.
@@ -25,6 +72,9 @@ pub struct WriteCtx<'a, 'w, 's> {
 
     /// Disables mapping logic to avoid spawning entities for consume functions.
     pub(super) ignore_mapping: bool,
+
+    /// Allocates client entities for unknown server entities.
+    allocator: Option<&'a mut dyn EntityAllocator>,
 }
 
 impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
@@ -38,8 +88,21 @@ impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
             entity_map,
             message_tick,
             ignore_mapping: false,
+            allocator: None,
         }
     }
+
+    /// Disables mapping logic, so entities inside components are written as is.
+    pub(crate) fn ignoring_mapping(mut self) -> Self {
+        self.ignore_mapping = true;
+        self
+    }
+
+    /// Uses the allocator instead of spawning new entities when mapping unknown server entities.
+    pub(crate) fn with_allocator(mut self, allocator: Option<&'a mut dyn EntityAllocator>) -> Self {
+        self.allocator = allocator;
+        self
+    }
 }
 
 impl EntityMapper for WriteCtx<'_, '_, '_> {
@@ -49,7 +112,14 @@ impl EntityMapper for WriteCtx<'_, '_, '_> {
         }
 
         self.entity_map
-            .get_by_server_or_insert(entity, || self.commands.spawn(Replicated).id())
+            .get_by_server_or_insert(entity, || match self.allocator.as_deref_mut() {
+                Some(allocator) => {
+                    let entity = allocator.allocate(self.commands);
+                    self.commands.entity(entity).insert(Replicated);
+                    entity
+                }
+                None => self.commands.spawn(Replicated).id(),
+            })
     }
 }
 
@@ -0,0 +1,255 @@
+/*!
+Serialization functions that quantize floats to reduce the size of float-heavy components.
+
+Floats are converted into fixed-point integers with the given number of steps per unit
+and written as varints, so small values take fewer bytes. Rotations use the smallest-three
+encoding and always take 4 bytes.
+
+Functions for [`Transform`] can be passed directly into [`RuleFns::new`](super::rule_fns::RuleFns::new).
+For custom components use [`serialize_f32`], [`serialize_vec3`] and [`serialize_quat`]
+with the corresponding deserialization functions inside your own functions.
+
+# Examples
+
+Replicate [`Transform`] with millimeter precision:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication_fns::{
+        quantization::{self, MILLIMETERS},
+        rule_fns::RuleFns,
+    },
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_with(RuleFns::new(
+    quantization::quantized_transform_serialize::<MILLIMETERS>,
+    quantization::quantized_transform_deserialize::<MILLIMETERS>,
+));
+```
+
+Quantize a custom component:
+
+```
+use std::io::Cursor;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    bincode,
+    core::replication_fns::{
+        ctx::{SerializeCtx, WriteCtx},
+        quantization::{self, CENTIMETERS},
+        rule_fns::RuleFns,
+    },
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_with(RuleFns::new(serialize_position, deserialize_position));
+
+fn serialize_position(
+    _ctx: &SerializeCtx,
+    position: &Position,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    quantization::serialize_vec3::<CENTIMETERS>(cursor, position.0)
+}
+
+fn deserialize_position(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<Position> {
+    let translation = quantization::deserialize_vec3::<CENTIMETERS>(cursor)?;
+    Ok(Position(translation))
+}
+
+#[derive(Component)]
+struct Position(Vec3);
+```
+*/
+
+use std::{f32::consts::FRAC_1_SQRT_2, io::Cursor};
+
+use bevy::prelude::*;
+use bincode::Options;
+
+use super::ctx::{SerializeCtx, WriteCtx};
+use crate::core::wire_format;
+
+/// Steps per unit for centimeter precision if a unit is a meter.
+pub const CENTIMETERS: u32 = 100;
+
+/// Steps per unit for millimeter precision if a unit is a meter.
+pub const MILLIMETERS: u32 = 1000;
+
+/// Number of bits for each of the three smallest quaternion components.
+const QUAT_BITS: u32 = 10;
+
+/// Maximum quantized value of a quaternion component.
+const QUAT_MAX: u32 = (1 << QUAT_BITS) - 1;
+
+/// Serializes [`Transform`] with translation and scale quantized to `STEPS` per unit
+/// and rotation encoded with [`serialize_quat`].
+pub fn quantized_transform_serialize<const STEPS: u32>(
+    _ctx: &SerializeCtx,
+    transform: &Transform,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    serialize_vec3::<STEPS>(cursor, transform.translation)?;
+    serialize_quat(cursor, transform.rotation)?;
+    serialize_vec3::<STEPS>(cursor, transform.scale)
+}
+
+/// Deserializes [`Transform`] written by [`quantized_transform_serialize`] with the same `STEPS`.
+pub fn quantized_transform_deserialize<const STEPS: u32>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<Transform> {
+    let translation = deserialize_vec3::<STEPS>(cursor)?;
+    let rotation = deserialize_quat(cursor)?;
+    let scale = deserialize_vec3::<STEPS>(cursor)?;
+
+    Ok(Transform {
+        translation,
+        rotation,
+        scale,
+    })
+}
+
+/// Serializes a float as a fixed-point integer with `STEPS` per unit.
+///
+/// Values outside of the [`i32`] range after scaling are clamped.
+pub fn serialize_f32<const STEPS: u32>(
+    cursor: &mut Cursor<Vec<u8>>,
+    value: f32,
+) -> bincode::Result<()> {
+    let quantized = (value * STEPS as f32).round() as i32;
+    wire_format::varint().serialize_into(cursor, &quantized)
+}
+
+/// Deserializes a float written by [`serialize_f32`] with the same `STEPS`.
+pub fn deserialize_f32<const STEPS: u32>(cursor: &mut Cursor<&[u8]>) -> bincode::Result<f32> {
+    let quantized: i32 = wire_format::varint().deserialize_from(cursor)?;
+    Ok(quantized as f32 / STEPS as f32)
+}
+
+/// Serializes each component of a vector with [`serialize_f32`].
+pub fn serialize_vec3<const STEPS: u32>(
+    cursor: &mut Cursor<Vec<u8>>,
+    value: Vec3,
+) -> bincode::Result<()> {
+    for axis in value.to_array() {
+        serialize_f32::<STEPS>(cursor, axis)?;
+    }
+
+    Ok(())
+}
+
+/// Deserializes a vector written by [`serialize_vec3`] with the same `STEPS`.
+pub fn deserialize_vec3<const STEPS: u32>(cursor: &mut Cursor<&[u8]>) -> bincode::Result<Vec3> {
+    Ok(Vec3::new(
+        deserialize_f32::<STEPS>(cursor)?,
+        deserialize_f32::<STEPS>(cursor)?,
+        deserialize_f32::<STEPS>(cursor)?,
+    ))
+}
+
+/// Serializes a rotation into 4 bytes using the smallest-three encoding.
+///
+/// Writes the index of the largest component in 2 bits and the other three components in 10 bits each.
+/// The largest component is restored from the unit length, so the rotation is normalized before
+/// serialization. The error of each component is below 0.0007.
+pub fn serialize_quat(cursor: &mut Cursor<Vec<u8>>, rotation: Quat) -> bincode::Result<()> {
+    let components = rotation.normalize().to_array();
+    let (largest, &largest_value) = components
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+        .expect("quaternion should have components");
+
+    // `q` and `-q` represent the same rotation, so flip the sign to make the largest component positive.
+    let sign = largest_value.signum();
+    let mut bits = largest as u32;
+    for (_, &value) in components
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != largest)
+    {
+        let normalized = (value * sign / FRAC_1_SQRT_2 + 1.0) / 2.0;
+        let quantized = (normalized * QUAT_MAX as f32).round() as u32;
+        bits = bits << QUAT_BITS | quantized.min(QUAT_MAX);
+    }
+
+    wire_format::fixint().serialize_into(cursor, &bits)
+}
+
+/// Deserializes a rotation written by [`serialize_quat`].
+pub fn deserialize_quat(cursor: &mut Cursor<&[u8]>) -> bincode::Result<Quat> {
+    let bits: u32 = wire_format::fixint().deserialize_from(cursor)?;
+    let largest = (bits >> (3 * QUAT_BITS)) as usize;
+
+    let mut components = [0.0; 4];
+    let mut shift = 3 * QUAT_BITS;
+    for (index, component) in components.iter_mut().enumerate() {
+        if index == largest {
+            continue;
+        }
+        shift -= QUAT_BITS;
+        let quantized = bits >> shift & QUAT_MAX;
+        *component = (quantized as f32 / QUAT_MAX as f32 * 2.0 - 1.0) * FRAC_1_SQRT_2;
+    }
+
+    let sum: f32 = components
+        .iter()
+        .map(|component| component * component)
+        .sum();
+    components[largest] = (1.0 - sum).max(0.0).sqrt();
+
+    Ok(Quat::from_array(components).normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32() {
+        let mut cursor = Cursor::default();
+        serialize_f32::<MILLIMETERS>(&mut cursor, 1.2346).unwrap();
+        serialize_f32::<MILLIMETERS>(&mut cursor, -0.0004).unwrap();
+        assert_eq!(
+            cursor.get_ref().len(),
+            4,
+            "small values should take fewer bytes"
+        );
+
+        let mut cursor = Cursor::new(&**cursor.get_ref());
+        assert_eq!(deserialize_f32::<MILLIMETERS>(&mut cursor).unwrap(), 1.235);
+        assert_eq!(deserialize_f32::<MILLIMETERS>(&mut cursor).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn quat() {
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_x(-1.0),
+            Quat::from_euler(EulerRot::XYZ, 0.3, -2.0, 1.5),
+            -Quat::from_rotation_z(3.0),
+        ] {
+            let mut cursor = Cursor::default();
+            serialize_quat(&mut cursor, rotation).unwrap();
+            assert_eq!(cursor.get_ref().len(), 4);
+
+            let mut cursor = Cursor::new(&**cursor.get_ref());
+            let deserialized = deserialize_quat(&mut cursor).unwrap();
+            assert!(
+                deserialized.angle_between(rotation) < 0.01,
+                "{deserialized} should be close to {rotation}"
+            );
+        }
+    }
+}
@@ -1,87 +1,171 @@
 use std::{
-    any::{self, TypeId},
-    io::Cursor,
+    any::{self, Any, TypeId},
+    io::{Cursor, Read, Write},
+    marker::PhantomData,
     mem,
 };
 
 use bevy::{ecs::entity::MapEntities, prelude::*};
-use bincode::{DefaultOptions, Options};
-use serde::{de::DeserializeOwned, Serialize};
+use bincode::Options;
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, Deserializer},
+    Serialize,
+};
 
 use super::ctx::{SerializeCtx, WriteCtx};
+use crate::core::wire_format;
 
 /// Type-erased version of [`RuleFns`].
 ///
 /// Stored inside [`ReplicationFns`](super::ReplicationFns) after registration.
 pub(crate) struct UntypedRuleFns {
-    type_id: TypeId,
     type_name: &'static str,
 
-    serialize: unsafe fn(),
-    deserialize: unsafe fn(),
-    deserialize_in_place: unsafe fn(),
-    consume: unsafe fn(),
+    /// Boxed [`RuleFns`] with the original type.
+    fns: Box<dyn Any + Send + Sync>,
+
+    /// Functions for saves if they differ from replication.
+    save: Option<Box<UntypedRuleFns>>,
+
+    per_client: bool,
+
+    wire_name: Option<&'static str>,
 }
 
 impl UntypedRuleFns {
     /// Restores the original [`RuleFns`] from which this type was created.
     ///
-    /// # Safety
-    ///
-    /// The caller must ensure that the function is called with the same `C` with which this instance was created.
-    pub(super) unsafe fn typed<C: Component>(&self) -> RuleFns<C> {
-        debug_assert_eq!(
-            self.type_id,
-            TypeId::of::<C>(),
-            "trying to call rule functions with {}, but they were created with {}",
-            any::type_name::<C>(),
-            self.type_name,
-        );
-
-        RuleFns {
-            serialize: unsafe { mem::transmute(self.serialize) },
-            deserialize: unsafe { mem::transmute(self.deserialize) },
-            deserialize_in_place: unsafe { mem::transmute(self.deserialize_in_place) },
-            consume: unsafe { mem::transmute(self.consume) },
-        }
+    /// # Panics
+    ///
+    /// Panics if `C` differs from the type with which this instance was created.
+    pub(super) fn typed<C: Component>(&self) -> &RuleFns<C> {
+        self.fns.downcast_ref().unwrap_or_else(|| {
+            panic!(
+                "trying to call rule functions with {}, but they were created with {}",
+                any::type_name::<C>(),
+                self.type_name,
+            )
+        })
+    }
+
+    /// Returns functions for saves.
+    ///
+    /// See also [`RuleFns::with_save`].
+    pub(crate) fn for_save(&self) -> &Self {
+        self.save.as_deref().unwrap_or(self)
+    }
+
+    /// Returns `true` if the component should be serialized separately for each client.
+    ///
+    /// See also [`RuleFns::per_client`].
+    pub(crate) fn is_per_client(&self) -> bool {
+        self.per_client
+    }
+
+    /// Returns the explicit name used to match the component between server and clients.
+    ///
+    /// See also [`RuleFns::with_wire_name`].
+    pub(crate) fn wire_name(&self) -> Option<&'static str> {
+        self.wire_name
     }
 }
 
 impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
-    fn from(value: RuleFns<C>) -> Self {
-        // SAFETY: these functions won't be called until the type is restored.
+    fn from(mut value: RuleFns<C>) -> Self {
+        let save = value.save.take().map(|(serialize, deserialize)| {
+            Box::new(RuleFns::from_boxed(serialize, deserialize).into())
+        });
+
         Self {
-            type_id: TypeId::of::<C>(),
             type_name: any::type_name::<C>(),
-            serialize: unsafe { mem::transmute(value.serialize) },
-            deserialize: unsafe { mem::transmute(value.deserialize) },
-            deserialize_in_place: unsafe { mem::transmute(value.deserialize_in_place) },
-            consume: unsafe { mem::transmute(value.consume) },
+            per_client: value.per_client,
+            wire_name: value.wire_name,
+            fns: Box::new(value),
+            save,
         }
     }
 }
 
-/// Serialization and deserialization functions for a component.
-///
-/// See also [`AppRuleExt`](crate::core::replication_rules::AppRuleExt)
-/// and [`ReplicationRule`](crate::core::replication_rules::ReplicationRule).
+/**
+Serialization and deserialization functions for a component.
+
+Functions can be regular functions or closures that capture configuration, like a quantization range.
+
+# Examples
+
+```
+use std::io::Cursor;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    bincode::{self, Options},
+    core::{
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        wire_format,
+    },
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+let max_health = 500.0;
+app.replicate_with(RuleFns::new(
+    move |_ctx: &SerializeCtx, health: &Health, cursor: &mut Cursor<Vec<u8>>| {
+        let quantized = (health.0 / max_health * u8::MAX as f32) as u8;
+        wire_format::varint().serialize_into(cursor, &quantized)
+    },
+    move |_ctx: &mut WriteCtx, cursor: &mut Cursor<&[u8]>| {
+        let quantized: u8 = wire_format::varint().deserialize_from(cursor)?;
+        Ok(Health(quantized as f32 / u8::MAX as f32 * max_health))
+    },
+));
+
+#[derive(Component)]
+struct Health(f32);
+```
+
+See also [`AppRuleExt`](crate::core::replication_rules::AppRuleExt)
+and [`ReplicationRule`](crate::core::replication_rules::ReplicationRule).
+**/
 pub struct RuleFns<C> {
-    serialize: SerializeFn<C>,
-    deserialize: DeserializeFn<C>,
-    deserialize_in_place: DeserializeInPlaceFn<C>,
-    consume: ConsumeFn<C>,
+    serialize: Box<SerializeFn<C>>,
+    deserialize: Box<DeserializeFn<C>>,
+    deserialize_in_place: Box<DeserializeInPlaceFn<C>>,
+    consume: Box<ConsumeFn<C>>,
+    save: Option<(Box<SerializeFn<C>>, Box<DeserializeFn<C>>)>,
+    per_client: bool,
+    wire_name: Option<&'static str>,
 }
 
 impl<C: Component> RuleFns<C> {
     /// Creates a new instance.
     ///
     /// See also [`Self::with_in_place`] and [`Self::with_consume`].
-    pub fn new(serialize: SerializeFn<C>, deserialize: DeserializeFn<C>) -> Self {
+    pub fn new(
+        serialize: impl Fn(&SerializeCtx, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()>
+            + Send
+            + Sync
+            + 'static,
+        deserialize: impl Fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<C>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self::from_boxed(Box::new(serialize), Box::new(deserialize))
+    }
+
+    fn from_boxed(serialize: Box<SerializeFn<C>>, deserialize: Box<DeserializeFn<C>>) -> Self {
         Self {
             serialize,
             deserialize,
-            deserialize_in_place: in_place_as_deserialize::<C>,
-            consume: consume_as_deserialize,
+            deserialize_in_place: Box::new(in_place_as_deserialize::<C>),
+            consume: Box::new(consume_as_deserialize::<C>),
+            save: None,
+            per_client: false,
+            wire_name: None,
         }
     }
 
@@ -89,8 +173,14 @@ impl<C: Component> RuleFns<C> {
     ///
     /// This function will be called when a component is already present on an entity.
     /// For insertion [`Self::deserialize`] will be called instead.
-    pub fn with_in_place(mut self, deserialize_in_place: DeserializeInPlaceFn<C>) -> Self {
-        self.deserialize_in_place = deserialize_in_place;
+    pub fn with_in_place(
+        mut self,
+        deserialize_in_place: impl Fn(&DeserializeFn<C>, &mut WriteCtx, &mut C, &mut Cursor<&[u8]>) -> bincode::Result<()>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.deserialize_in_place = Box::new(deserialize_in_place);
         self
     }
 
@@ -107,8 +197,130 @@ impl<C: Component> RuleFns<C> {
     ///
     /// See [`MarkerConfig::need_history`](crate::core::command_markers::MarkerConfig::need_history)
     /// for details.
-    pub fn with_consume(mut self, consume: ConsumeFn<C>) -> Self {
-        self.consume = consume;
+    pub fn with_consume(
+        mut self,
+        consume: impl Fn(&DeserializeFn<C>, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.consume = Box::new(consume);
+        self
+    }
+
+    /**
+    Uses separate functions for saves created with [`save_game::capture_binary`](crate::server::save_game::capture_binary).
+
+    Replication keeps using the functions passed to [`Self::new`]. Useful to quantize components on the wire
+    while keeping saves lossless. For existing components saved values are applied with [`in_place_as_deserialize`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication_fns::{
+            quantization::{self, CENTIMETERS},
+            rule_fns::{self, RuleFns},
+        },
+        prelude::*,
+    };
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with(
+        RuleFns::new(
+            quantization::quantized_transform_serialize::<CENTIMETERS>,
+            quantization::quantized_transform_deserialize::<CENTIMETERS>,
+        )
+        .with_save(
+            rule_fns::default_serialize::<Transform>,
+            rule_fns::default_deserialize::<Transform>,
+        ),
+    );
+    ```
+    **/
+    pub fn with_save(
+        mut self,
+        serialize: impl Fn(&SerializeCtx, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()>
+            + Send
+            + Sync
+            + 'static,
+        deserialize: impl Fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<C>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.save = Some((Box::new(serialize), Box::new(deserialize)));
+        self
+    }
+
+    /**
+    Serializes the component separately for each client with [`SerializeCtx::client_id`] set.
+
+    By default a component is serialized once and the bytes are shared between all clients.
+    Use it to send different values to different clients, but keep in mind that serialization
+    will be performed for each client that receives the component.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        bincode::{self, Options},
+        core::{
+            replication_fns::{ctx::SerializeCtx, rule_fns::{self, RuleFns}},
+            wire_format,
+        },
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.init_resource::<Teams>().replicate_with(
+        RuleFns::new(serialize_health, rule_fns::default_deserialize::<Health>).per_client(),
+    );
+
+    /// Sends exact health only to allies and rounds it for enemies.
+    fn serialize_health(
+        ctx: &SerializeCtx,
+        health: &Health,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        let ally = ctx
+            .client_id
+            .zip(ctx.get_resource::<Teams>())
+            .is_some_and(|(client_id, teams)| teams.allies.contains(&client_id));
+        let value = if ally { health.0 } else { health.0 / 10 * 10 };
+        wire_format::varint().serialize_into(cursor, &Health(value))
+    }
+
+    #[derive(Default, Resource)]
+    struct Teams {
+        allies: Vec<ClientId>,
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+    ```
+    **/
+    pub fn per_client(mut self) -> Self {
+        self.per_client = true;
+        self
+    }
+
+    /// Sets an explicit name to match the component between server and clients.
+    ///
+    /// Used instead of the type name by [`ProtocolNegotiationPlugin`](crate::protocol_negotiation::ProtocolNegotiationPlugin).
+    /// Useful for generic components, whose type names depend on module paths of their parameters.
+    /// Names should be unique across components.
+    ///
+    /// See also [`AppRuleExt::replicate_as`](crate::core::replication_rules::AppRuleExt::replicate_as).
+    pub fn with_wire_name(mut self, name: &'static str) -> Self {
+        self.wire_name = Some(name);
         self
     }
 
@@ -142,7 +354,7 @@ impl<C: Component> RuleFns<C> {
         component: &mut C,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        (self.deserialize_in_place)(self.deserialize, ctx, component, cursor)
+        (self.deserialize_in_place)(&*self.deserialize, ctx, component, cursor)
     }
 
     /// Consumes a component from a cursor.
@@ -151,7 +363,7 @@ impl<C: Component> RuleFns<C> {
         ctx: &mut WriteCtx,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        (self.consume)(self.deserialize, ctx, cursor)
+        (self.consume)(&*self.deserialize, ctx, cursor)
     }
 }
 
@@ -167,46 +379,300 @@ impl<C: Component + Serialize + DeserializeOwned + MapEntities> RuleFns<C> {
     }
 }
 
+impl<C: Component + Serialize + DeserializeOwned> RuleFns<C> {
+    /// Like [`Self::default`], but serializes the component with a custom [`Codec`].
+    ///
+    /// See also [`codec_serialize`], [`codec_deserialize`] and [`in_place_as_deserialize`].
+    pub fn with_codec<K: Codec>() -> Self {
+        Self::new(codec_serialize::<C, K>, codec_deserialize::<C, K>)
+    }
+}
+
+impl<C: Component + Serialize + DeserializeOwned + MapEntities> RuleFns<C> {
+    /// Like [`Self::with_codec`], but also maps entities inside the component after deserialization.
+    ///
+    /// See also [`codec_serialize`], [`codec_deserialize_mapped`] and [`in_place_as_deserialize`].
+    pub fn with_codec_mapped<K: Codec>() -> Self {
+        Self::new(codec_serialize::<C, K>, codec_deserialize_mapped::<C, K>)
+    }
+}
+
+impl<C: Component + CompactTag> RuleFns<C> {
+    /// Creates a new instance that serializes the component as a single byte.
+    ///
+    /// See also [`compact_serialize`], [`compact_deserialize`] and [`in_place_as_deserialize`].
+    pub fn compact() -> Self {
+        Self::new(compact_serialize::<C>, compact_deserialize::<C>)
+    }
+}
+
 impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     /// Creates a new instance with default functions for a component.
     ///
     /// If your component contains any [`Entity`] inside, use [`Self::default_mapped`].
     ///
+    /// Zero-sized components that serialize into nothing, such as unit structs,
+    /// use [`marker_serialize`] to avoid calling the codec. It doesn't affect the message size,
+    /// the component is still written with its functions ID.
+    ///
+    /// Always uses [`BincodeCodec`], use [`ErasedCodec::rule_fns`] to create functions
+    /// for [`ReplicationFns::default_codec`](super::ReplicationFns::default_codec).
+    ///
     /// See also [`default_serialize`], [`default_deserialize`] and [`in_place_as_deserialize`].
     fn default() -> Self {
-        Self::new(default_serialize::<C>, default_deserialize::<C>)
+        if is_marker::<C>() {
+            Self::new(marker_serialize::<C>, default_deserialize::<C>)
+        } else {
+            Self::new(default_serialize::<C>, default_deserialize::<C>)
+        }
     }
 }
 
 /// Signature of component serialization functions.
-pub type SerializeFn<C> = fn(&SerializeCtx, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
+pub type SerializeFn<C> =
+    dyn Fn(&SerializeCtx, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()> + Send + Sync;
 
 /// Signature of component deserialization functions.
-pub type DeserializeFn<C> = fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<C>;
+pub type DeserializeFn<C> =
+    dyn Fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<C> + Send + Sync;
 
 /// Signature of component in-place deserialization functions.
-pub type DeserializeInPlaceFn<C> =
-    fn(DeserializeFn<C>, &mut WriteCtx, &mut C, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+pub type DeserializeInPlaceFn<C> = dyn Fn(&DeserializeFn<C>, &mut WriteCtx, &mut C, &mut Cursor<&[u8]>) -> bincode::Result<()>
+    + Send
+    + Sync;
 
 /// Signature of component consume functions.
-pub type ConsumeFn<C> =
-    fn(DeserializeFn<C>, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+pub type ConsumeFn<C> = dyn Fn(&DeserializeFn<C>, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>
+    + Send
+    + Sync;
+
+/// Wire format for serializing components.
+///
+/// By default components are serialized with [`BincodeCodec`].
+/// Implement this trait to use a different format, like postcard or MessagePack.
+/// Errors from other formats can be converted with [`bincode::ErrorKind::Custom`].
+///
+/// See also [`RuleFns::with_codec`],
+/// [`AppRuleExt::replicate_with_codec`](crate::core::replication_rules::AppRuleExt::replicate_with_codec)
+/// and [`AppRuleExt::set_default_codec`](crate::core::replication_rules::AppRuleExt::set_default_codec).
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use bevy_replicon::{bincode::{self, DefaultOptions, Options}, core::replication_fns::rule_fns::Codec};
+/// use serde::{de::DeserializeSeed, Serialize};
+///
+/// /// Bincode with fixed-size integers.
+/// struct FixintCodec;
+///
+/// impl Codec for FixintCodec {
+///     fn serialize<T: Serialize>(cursor: &mut Cursor<Vec<u8>>, value: &T) -> bincode::Result<()> {
+///         DefaultOptions::new()
+///             .with_fixint_encoding()
+///             .serialize_into(cursor, value)
+///     }
+///
+///     fn deserialize_seed<'de, T: DeserializeSeed<'de>>(
+///         cursor: &mut Cursor<&[u8]>,
+///         seed: T,
+///     ) -> bincode::Result<T::Value> {
+///         DefaultOptions::new()
+///             .with_fixint_encoding()
+///             .deserialize_from_seed(seed, cursor)
+///     }
+/// }
+/// ```
+pub trait Codec: 'static {
+    /// Serializes a value into a cursor.
+    fn serialize<T: Serialize>(cursor: &mut Cursor<Vec<u8>>, value: &T) -> bincode::Result<()>;
+
+    /// Deserializes a value from a cursor using a seed.
+    ///
+    /// The cursor should be advanced by the size of the value.
+    fn deserialize_seed<'de, T: DeserializeSeed<'de>>(
+        cursor: &mut Cursor<&[u8]>,
+        seed: T,
+    ) -> bincode::Result<T::Value>;
+
+    /// Deserializes a value from a cursor.
+    ///
+    /// The cursor should be advanced by the size of the value.
+    fn deserialize<T: DeserializeOwned>(cursor: &mut Cursor<&[u8]>) -> bincode::Result<T> {
+        Self::deserialize_seed(cursor, PhantomData)
+    }
+}
+
+/// Default [`Codec`] that uses [`bincode`] with [`wire_format::varint`].
+pub struct BincodeCodec;
+
+impl Codec for BincodeCodec {
+    fn serialize<T: Serialize>(cursor: &mut Cursor<Vec<u8>>, value: &T) -> bincode::Result<()> {
+        wire_format::varint().serialize_into(cursor, value)
+    }
+
+    fn deserialize_seed<'de, T: DeserializeSeed<'de>>(
+        cursor: &mut Cursor<&[u8]>,
+        seed: T,
+    ) -> bincode::Result<T::Value> {
+        wire_format::varint().deserialize_from_seed(seed, cursor)
+    }
+}
+
+/// Type-erased [`Codec`].
+///
+/// Used as [`ReplicationFns::default_codec`](super::ReplicationFns::default_codec)
+/// for components registered without an explicit codec.
+#[derive(Clone, Copy)]
+pub struct ErasedCodec {
+    type_id: TypeId,
+    serialize: fn(&mut Cursor<Vec<u8>>, &dyn erased_serde::Serialize) -> bincode::Result<()>,
+    deserialize: fn(&mut Cursor<&[u8]>, ErasedSeed<'_>) -> bincode::Result<()>,
+}
+
+impl ErasedCodec {
+    /// Erases the codec type.
+    pub fn new<K: Codec>() -> Self {
+        Self {
+            type_id: TypeId::of::<K>(),
+            serialize: |cursor, value| K::serialize(cursor, &value),
+            deserialize: |cursor, seed| K::deserialize_seed(cursor, seed),
+        }
+    }
+
+    /// Serializes a value into a cursor with the erased codec.
+    pub fn serialize<T: Serialize>(
+        &self,
+        cursor: &mut Cursor<Vec<u8>>,
+        value: &T,
+    ) -> bincode::Result<()> {
+        (self.serialize)(cursor, value)
+    }
+
+    /// Deserializes a value from a cursor with the erased codec.
+    pub fn deserialize<T: DeserializeOwned>(
+        &self,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<T> {
+        let mut value = None;
+        (self.deserialize)(
+            cursor,
+            ErasedSeed(&mut |deserializer| {
+                value = Some(erased_serde::deserialize(deserializer)?);
+                Ok(())
+            }),
+        )?;
+
+        Ok(value.expect("value should be assigned on successful deserialization"))
+    }
+
+    /// Creates [`RuleFns`] that serialize the component with this codec.
+    ///
+    /// Returns [`RuleFns::default`] for [`BincodeCodec`] to avoid dynamic dispatch.
+    pub fn rule_fns<C: Component + Serialize + DeserializeOwned>(self) -> RuleFns<C> {
+        if self.type_id == TypeId::of::<BincodeCodec>() {
+            return RuleFns::default();
+        }
+
+        RuleFns::new(
+            move |_ctx, component, cursor| self.serialize(cursor, component),
+            move |_ctx, cursor| self.deserialize(cursor),
+        )
+    }
+
+    /// Like [`Self::rule_fns`], but also maps entities inside the component after deserialization.
+    pub fn rule_fns_mapped<C>(self) -> RuleFns<C>
+    where
+        C: Component + Serialize + DeserializeOwned + MapEntities,
+    {
+        if self.type_id == TypeId::of::<BincodeCodec>() {
+            return RuleFns::default_mapped();
+        }
+
+        RuleFns::new(
+            move |_ctx, component, cursor| self.serialize(cursor, component),
+            move |ctx, cursor| {
+                let mut component: C = self.deserialize(cursor)?;
+                component.map_entities(ctx);
+                Ok(component)
+            },
+        )
+    }
+}
+
+impl Default for ErasedCodec {
+    fn default() -> Self {
+        Self::new::<BincodeCodec>()
+    }
+}
+
+/// Seed that passes the erased deserializer to a closure.
+struct ErasedSeed<'a>(
+    &'a mut dyn for<'de> FnMut(
+        &mut dyn erased_serde::Deserializer<'de>,
+    ) -> erased_serde::Result<()>,
+);
+
+impl<'de> DeserializeSeed<'de> for ErasedSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut deserializer).map_err(de::Error::custom)
+    }
+}
 
 /// Default component serialization function.
 pub fn default_serialize<C: Component + Serialize>(
+    ctx: &SerializeCtx,
+    component: &C,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    codec_serialize::<C, BincodeCodec>(ctx, component, cursor)
+}
+
+/// Component serialization function that uses the [`Codec`].
+pub fn codec_serialize<C: Component + Serialize, K: Codec>(
     _ctx: &SerializeCtx,
     component: &C,
     cursor: &mut Cursor<Vec<u8>>,
 ) -> bincode::Result<()> {
-    DefaultOptions::new().serialize_into(cursor, component)
+    K::serialize(cursor, component)
+}
+
+/// Component serialization function for markers that writes nothing.
+///
+/// Used by [`RuleFns::default`] for zero-sized components without payload.
+/// Produces the same bytes as [`default_serialize`] for them, but skips the codec call.
+pub fn marker_serialize<C: Component>(
+    _ctx: &SerializeCtx,
+    _component: &C,
+    _cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    Ok(())
+}
+
+/// Returns `true` if the component is zero-sized and its serialized form is empty.
+///
+/// Not all zero-sized types serialize into nothing, for example enums with a single variant write their index.
+fn is_marker<C: Component + Serialize + DeserializeOwned>() -> bool {
+    if mem::size_of::<C>() != 0 {
+        return false;
+    }
+
+    wire_format::varint()
+        .deserialize::<C>(&[])
+        .and_then(|component| wire_format::varint().serialized_size(&component))
+        .is_ok_and(|size| size == 0)
 }
 
 /// Default component deserialization function.
 pub fn default_deserialize<C: Component + DeserializeOwned>(
-    _ctx: &mut WriteCtx,
+    ctx: &mut WriteCtx,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<C> {
-    DefaultOptions::new().deserialize_from(cursor)
+    codec_deserialize::<C, BincodeCodec>(ctx, cursor)
 }
 
 /// Like [`default_deserialize`], but also maps entities before insertion.
@@ -214,7 +680,23 @@ pub fn default_deserialize_mapped<C: Component + DeserializeOwned + MapEntities>
     ctx: &mut WriteCtx,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<C> {
-    let mut component: C = DefaultOptions::new().deserialize_from(cursor)?;
+    codec_deserialize_mapped::<C, BincodeCodec>(ctx, cursor)
+}
+
+/// Component deserialization function that uses the [`Codec`].
+pub fn codec_deserialize<C: Component + DeserializeOwned, K: Codec>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    K::deserialize(cursor)
+}
+
+/// Like [`codec_deserialize`], but also maps entities before insertion.
+pub fn codec_deserialize_mapped<C: Component + DeserializeOwned + MapEntities, K: Codec>(
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    let mut component: C = K::deserialize(cursor)?;
     component.map_entities(ctx);
     Ok(component)
 }
@@ -223,7 +705,7 @@ pub fn default_deserialize_mapped<C: Component + DeserializeOwned + MapEntities>
 ///
 /// This implementation just assigns the value from the passed deserialization function.
 pub fn in_place_as_deserialize<C: Component>(
-    deserialize: DeserializeFn<C>,
+    deserialize: &DeserializeFn<C>,
     ctx: &mut WriteCtx,
     component: &mut C,
     cursor: &mut Cursor<&[u8]>,
@@ -236,12 +718,143 @@ pub fn in_place_as_deserialize<C: Component>(
 ///
 /// This implementation just calls deserialization function and ignores its result.
 pub fn consume_as_deserialize<C: Component>(
-    deserialize: DeserializeFn<C>,
+    deserialize: &DeserializeFn<C>,
     ctx: &mut WriteCtx,
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<()> {
-    ctx.ignore_mapping = true;
-    (deserialize)(ctx, cursor)?;
-    ctx.ignore_mapping = false;
+    let ignore_mapping = mem::replace(&mut ctx.ignore_mapping, true);
+    let result = (deserialize)(ctx, cursor);
+    ctx.ignore_mapping = ignore_mapping;
+    result.map(|_| ())
+}
+
+/// Like [`default_serialize`], but writes [`CompactTag::to_tag`] as a single byte.
+pub fn compact_serialize<C: Component + CompactTag>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    cursor.write_all(&[component.to_tag()])?;
     Ok(())
 }
+
+/// Like [`default_deserialize`], but reads a single byte and converts it using [`CompactTag::from_tag`].
+pub fn compact_deserialize<C: Component + CompactTag>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    let mut tag = [0];
+    cursor.read_exact(&mut tag)?;
+    C::from_tag(tag[0]).ok_or_else(|| {
+        bincode::ErrorKind::Custom(format!(
+            "received invalid tag {} for {}",
+            tag[0],
+            any::type_name::<C>()
+        ))
+        .into()
+    })
+}
+
+/**
+Conversion of a component into a single byte for [`RuleFns::compact`].
+
+Suitable for state-machine enums without fields and sets of up to 8 flags.
+For fieldless enums the trait can be implemented using [`compact_tag`](crate::compact_tag).
+
+# Examples
+
+Packing flags into bits:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication_fns::rule_fns::{CompactTag, RuleFns},
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_with(RuleFns::<Status>::compact());
+
+#[derive(Component)]
+struct Status {
+    stunned: bool,
+    burning: bool,
+    invisible: bool,
+}
+
+impl CompactTag for Status {
+    fn to_tag(&self) -> u8 {
+        self.stunned as u8 | (self.burning as u8) << 1 | (self.invisible as u8) << 2
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        if tag >> 3 != 0 {
+            return None;
+        }
+
+        Some(Self {
+            stunned: tag & 1 != 0,
+            burning: tag & 1 << 1 != 0,
+            invisible: tag & 1 << 2 != 0,
+        })
+    }
+}
+```
+**/
+pub trait CompactTag: Sized {
+    /// Converts the value into a tag.
+    fn to_tag(&self) -> u8;
+
+    /// Restores the value from a tag.
+    ///
+    /// Returns [`None`] if the tag is invalid.
+    fn from_tag(tag: u8) -> Option<Self>;
+}
+
+/**
+Implements [`CompactTag`] for a fieldless enum using variant discriminants as tags.
+
+All variants need to be listed and discriminants should fit into [`u8`].
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{compact_tag, prelude::*};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_compact::<State>();
+
+#[derive(Component)]
+enum State {
+    Idle,
+    Walking,
+    Running,
+}
+
+compact_tag!(State { Idle, Walking, Running });
+```
+**/
+#[macro_export]
+macro_rules! compact_tag {
+    ($type:ty { $($variant:ident),+ $(,)? }) => {
+        impl $crate::core::replication_fns::rule_fns::CompactTag for $type {
+            fn to_tag(&self) -> u8 {
+                match self {
+                    $(Self::$variant => Self::$variant as u8,)+
+                }
+            }
+
+            fn from_tag(tag: u8) -> Option<Self> {
+                $(
+                    if tag == Self::$variant as u8 {
+                        return Some(Self::$variant);
+                    }
+                )+
+                None
+            }
+        }
+    };
+}
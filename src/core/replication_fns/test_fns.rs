@@ -96,7 +96,7 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         let (component_fns, rule_fns) = replication_fns.get(fns_info.fns_id());
         let server_tick = **self.world().resource::<ServerTick>();
         let mut cursor = Cursor::default();
-        let ctx = SerializeCtx { server_tick };
+        let ctx = SerializeCtx::new(self.world(), server_tick);
         let ptr = self.get_by_id(fns_info.component_id()).unwrap_or_else(|| {
             let components = self.world().components();
             let component_name = components
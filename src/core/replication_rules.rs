@@ -1,13 +1,21 @@
-use std::cmp::Reverse;
+use std::{any::TypeId, cmp::Reverse};
 
 use bevy::{
     ecs::{archetype::Archetype, component::ComponentId, entity::MapEntities},
     prelude::*,
     utils::HashSet,
 };
+pub use bevy_replicon_macros::Replicate;
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::replication_fns::{rule_fns::RuleFns, FnsInfo, ReplicationFns};
+use super::{
+    command_markers::AppMarkerExt,
+    replication_fns::{
+        command_fns,
+        rule_fns::{Codec, CompactTag, ErasedCodec, RuleFns},
+        FnsInfo, ReplicationFns,
+    },
+};
 
 /// Replication functions for [`App`].
 pub trait AppRuleExt {
@@ -16,7 +24,8 @@ pub trait AppRuleExt {
     /// The component will be replicated if its entity contains the [`Replicated`](super::Replicated)
     /// marker component.
     ///
-    /// Component will be serialized and deserialized as-is using bincode.
+    /// Component will be serialized and deserialized as-is using [`ReplicationFns::default_codec`],
+    /// which is bincode unless changed with [`Self::set_default_codec`].
     /// To customize it, use [`Self::replicate_group`].
     ///
     /// If your component contains any [`Entity`] inside, use [`Self::replicate_mapped`].
@@ -24,11 +33,108 @@ pub trait AppRuleExt {
     /// See also [`Self::replicate_with`] and the section on [`components`](../../index.html#components)
     /// from the quick start guide.
     fn replicate<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned;
+
+    /// Same as [`Self::replicate`], but serializes the component with a custom [`Codec`].
+    ///
+    /// To use the codec for all components, see [`Self::set_default_codec`].
+    /// For components that contain entities, use [`RuleFns::with_codec_mapped`] with [`Self::replicate_with`].
+    fn replicate_with_codec<C, K>(&mut self) -> &mut Self
     where
         C: Component + Serialize + DeserializeOwned,
+        K: Codec,
     {
-        self.replicate_with::<C>(RuleFns::default())
+        self.replicate_with::<C>(RuleFns::with_codec::<K>())
+    }
+
+    /**
+    Sets [`ReplicationFns::default_codec`] that will be used for components registered without an explicit codec.
+
+    Applies to [`Self::replicate`], [`Self::replicate_as`], [`Self::replicate_mapped`], [`Self::replicate_group`],
+    [`Self::replicate_once`] and the [`Replicate`] derive. Components registered before the call are not affected.
+
+    Should be called on both server and client before registering components.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        bincode::{self, DefaultOptions, Options},
+        core::replication_fns::rule_fns::Codec,
+        prelude::*,
+    };
+    use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.set_default_codec::<FixintCodec>()
+        .replicate::<Health>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    /// Bincode with fixed-size integers.
+    struct FixintCodec;
+
+    impl Codec for FixintCodec {
+        fn serialize<T: Serialize>(cursor: &mut Cursor<Vec<u8>>, value: &T) -> bincode::Result<()> {
+            DefaultOptions::new()
+                .with_fixint_encoding()
+                .serialize_into(cursor, value)
+        }
+
+        fn deserialize_seed<'de, T: DeserializeSeed<'de>>(
+            cursor: &mut Cursor<&[u8]>,
+            seed: T,
+        ) -> bincode::Result<T::Value> {
+            DefaultOptions::new()
+                .with_fixint_encoding()
+                .deserialize_from_seed(seed, cursor)
+        }
     }
+    ```
+    **/
+    fn set_default_codec<K: Codec>(&mut self) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate`], but matches the component between server and clients by the specified name.
+
+    By default [`ProtocolNegotiationPlugin`](crate::protocol_negotiation::ProtocolNegotiationPlugin) matches
+    components by their type names, which for generic components include module paths of their parameters.
+    Explicit names stay stable across refactoring and distinguish instantiations of the same generic component.
+    Names should be unique across components.
+
+    See also [`RuleFns::with_wire_name`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_as::<Stockpile<Wood>>("wood")
+        .replicate_as::<Stockpile<Stone>>("stone");
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Stockpile<T>(T);
+
+    #[derive(Deserialize, Serialize)]
+    struct Wood(u32);
+
+    #[derive(Deserialize, Serialize)]
+    struct Stone(u32);
+    ```
+    **/
+    fn replicate_as<C>(&mut self, name: &'static str) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned;
 
     /**
     Same as [`Self::replicate`], but additionally maps server entities to client inside the component after receiving.
@@ -59,11 +165,59 @@ pub trait AppRuleExt {
     **/
     fn replicate_mapped<C>(&mut self) -> &mut Self
     where
-        C: Component + Serialize + DeserializeOwned + MapEntities,
+        C: Component + Serialize + DeserializeOwned + MapEntities;
+
+    /// Same as [`Self::replicate`], but serializes the component as a single byte using [`CompactTag`].
+    ///
+    /// Useful for fieldless enums and small flag sets that change often.
+    ///
+    /// See also [`RuleFns::compact`].
+    fn replicate_compact<C>(&mut self) -> &mut Self
+    where
+        C: Component + CompactTag,
     {
-        self.replicate_with::<C>(RuleFns::default_mapped())
+        self.replicate_with::<C>(RuleFns::compact())
     }
 
+    /**
+    Same as [`Self::replicate`], but inserts a different component on client.
+
+    Server serializes `S` and client deserializes it and inserts `C` created with [`From`].
+    The client entity won't contain `S`. Removals of `S` on server remove `C` on client.
+    Useful when server simulation components differ from client presentation components.
+
+    Should be called on both client and server. Overrides command functions for `S`,
+    see [`AppMarkerExt::set_command_fns`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_into::<Velocity, DisplayedSpeed>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Velocity(Vec3);
+
+    #[derive(Component)]
+    struct DisplayedSpeed(f32);
+
+    impl From<Velocity> for DisplayedSpeed {
+        fn from(velocity: Velocity) -> Self {
+            Self(velocity.0.length())
+        }
+    }
+    ```
+    **/
+    fn replicate_into<S, C>(&mut self) -> &mut Self
+    where
+        S: Component + Serialize + DeserializeOwned,
+        C: Component + From<S>;
+
     /**
     Same as [`Self::replicate`], but uses the specified functions for serialization and deserialization.
 
@@ -117,6 +271,50 @@ pub trait AppRuleExt {
     where
         C: Component;
 
+    /**
+    Same as [`Self::replicate_with`], but the rule applies only to entities with the marker component `M`.
+
+    The marker itself is not replicated by this rule. The rule has higher priority than a rule
+    for the component alone, so it can be used to register an alternative serialization
+    for specific entities. Client receives the functions used for each component, so markers
+    don't need to be present on client.
+
+    # Examples
+
+    Serialize [`Transform`] compactly by default, but use full precision for entities with `HighPrecision`:
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        prelude::*,
+    };
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with(RuleFns::new(
+        serialize_translation,
+        deserialize_translation,
+    ))
+    .replicate_with_marker::<HighPrecision, Transform>(RuleFns::default());
+
+    #[derive(Component)]
+    struct HighPrecision;
+
+    # fn serialize_translation(_: &SerializeCtx, _: &Transform, _: &mut Cursor<Vec<u8>>) -> bincode::Result<()> { unimplemented!() }
+    # fn deserialize_translation(_: &mut WriteCtx, _: &mut Cursor<&[u8]>) -> bincode::Result<Transform> { unimplemented!() }
+    ```
+    **/
+    fn replicate_with_marker<M, C>(&mut self, rule_fns: RuleFns<C>) -> &mut Self
+    where
+        M: Component,
+        C: Component;
+
     /**
     Creates a replication rule for a group of components.
 
@@ -124,6 +322,9 @@ pub trait AppRuleExt {
 
     If a group contains a single component, it will work the same as [`Self::replicate`].
 
+    Only changed components are sent, so changes to different components of a group could arrive
+    in different updates. To always send and apply them together, use [`Self::replicate_atomic_group`].
+
     If an entity matches multiple groups, functions from a group with higher priority
     will take precedence for overlapping components. For example, a rule with [`Transform`]
     and a `Player` marker will take precedence over a single [`Transform`] rule.
@@ -157,9 +358,144 @@ pub trait AppRuleExt {
     ```
     **/
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate_group`], but makes the group atomic.
+
+    Changing any component from an atomic group will send all its components,
+    so clients always apply them from the same tick. Useful for components that
+    should never be observed out of sync, like a weapon ID and its ammo count.
+
+    All group components are written into the same entity data of a single message,
+    so the client applies them together in one pass and never observes only a part of the group.
+    This also holds if some of the components are claimed by other rules with higher priority.
+    Overlapping atomic groups are merged into one.
+
+    This costs extra bandwidth, so prefer regular groups for components that
+    change independently.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_atomic_group::<(WeaponId, AmmoCount)>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct WeaponId(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct AmmoCount(u32);
+    ```
+    **/
+    fn replicate_atomic_group<C: GroupReplication>(&mut self) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate`], but sends the component only on insertion.
+
+    Changes of the component after insertion are never sent and the component is excluded
+    from change detection on server, which saves time for components that never change after spawn.
+    Newly connected clients and clients that gain visibility of the entity still receive the current value.
+    Removals are replicated as usual.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_once::<PlayerName>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct PlayerName(String);
+    ```
+    **/
+    fn replicate_once<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned;
+
+    /**
+    Makes clients write component `B` after component `A` when both are received for an entity in the same message.
+
+    By default components are written in the order of their rules, which depends on registration order and priority.
+    Use it for components that depend on each other on write, like a component that sets a parent
+    and a component that computes a transform relative to it. Dependencies are transitive.
+    If dependencies form a cycle, the order between components in the cycle is unspecified.
+
+    Should be called on server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<LocalOffset>()
+        .replicate::<Anchor>()
+        .apply_before::<Anchor, LocalOffset>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Anchor(Vec3);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct LocalOffset(Vec3);
+    ```
+    **/
+    fn apply_before<A: Component, B: Component>(&mut self) -> &mut Self;
 }
 
 impl AppRuleExt for App {
+    fn replicate<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        let rule_fns = self
+            .world
+            .resource::<ReplicationFns>()
+            .default_codec
+            .rule_fns();
+        self.replicate_with::<C>(rule_fns)
+    }
+
+    fn set_default_codec<K: Codec>(&mut self) -> &mut Self {
+        self.world.resource_mut::<ReplicationFns>().default_codec = ErasedCodec::new::<K>();
+        self
+    }
+
+    fn replicate_as<C>(&mut self, name: &'static str) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        let rule_fns = self
+            .world
+            .resource::<ReplicationFns>()
+            .default_codec
+            .rule_fns();
+        self.replicate_with::<C>(rule_fns.with_wire_name(name))
+    }
+
+    fn replicate_mapped<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned + MapEntities,
+    {
+        let rule_fns = self
+            .world
+            .resource::<ReplicationFns>()
+            .default_codec
+            .rule_fns_mapped();
+        self.replicate_with::<C>(rule_fns)
+    }
+
     fn replicate_with<C>(&mut self, rule_fns: RuleFns<C>) -> &mut Self
     where
         C: Component,
@@ -175,6 +511,34 @@ impl AppRuleExt for App {
         self
     }
 
+    fn replicate_with_marker<M, C>(&mut self, rule_fns: RuleFns<C>) -> &mut Self
+    where
+        M: Component,
+        C: Component,
+    {
+        let marker_id = self.world.init_component::<M>();
+        let rule = self
+            .world
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                let fns_info = replication_fns.register_rule_fns(world, rule_fns);
+                ReplicationRule::new(vec![fns_info]).with_marker(marker_id)
+            });
+
+        self.world.resource_mut::<ReplicationRules>().insert(rule);
+        self
+    }
+
+    fn replicate_into<S, C>(&mut self) -> &mut Self
+    where
+        S: Component + Serialize + DeserializeOwned,
+        C: Component + From<S>,
+    {
+        self.replicate::<S>().set_command_fns(
+            command_fns::convert_write::<S, C>,
+            command_fns::default_remove::<C>,
+        )
+    }
+
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self {
         let rule = self
             .world
@@ -185,11 +549,92 @@ impl AppRuleExt for App {
         self.world.resource_mut::<ReplicationRules>().insert(rule);
         self
     }
+
+    fn replicate_atomic_group<C: GroupReplication>(&mut self) -> &mut Self {
+        let mut rule =
+            self.world
+                .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                    C::register(world, &mut replication_fns)
+                });
+        rule.atomic = true;
+
+        self.world.resource_mut::<ReplicationRules>().insert(rule);
+        self
+    }
+
+    fn replicate_once<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        let mut rule =
+            self.world
+                .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                    let rule_fns = replication_fns.default_codec.rule_fns::<C>();
+                    let fns_info = replication_fns.register_rule_fns(world, rule_fns);
+                    ReplicationRule::new(vec![fns_info])
+                });
+        rule.once = true;
+
+        self.world.resource_mut::<ReplicationRules>().insert(rule);
+        self
+    }
+
+    fn apply_before<A: Component, B: Component>(&mut self) -> &mut Self {
+        let before_id = self.world.init_component::<A>();
+        let after_id = self.world.init_component::<B>();
+        self.world
+            .resource_mut::<ReplicationRules>()
+            .dependencies
+            .push((before_id, after_id));
+        self
+    }
 }
 
+/// Registration helpers with their own systems for [`App`].
+pub(crate) trait AppHelperExt {
+    /// Returns `true` if the helper identified by `K` is registered for the first time.
+    ///
+    /// Helpers can be called again by [`AppProtocolReloadExt::reload_protocol`](crate::protocol_reload::AppProtocolReloadExt::reload_protocol),
+    /// so they should add their systems only once.
+    fn register_helper<K: 'static>(&mut self) -> bool;
+}
+
+impl AppHelperExt for App {
+    fn register_helper<K: 'static>(&mut self) -> bool {
+        self.world
+            .get_resource_or_insert_with(RegisteredHelpers::default)
+            .0
+            .insert(TypeId::of::<K>())
+    }
+}
+
+/// Helpers registered with [`AppHelperExt::register_helper`].
+///
+/// Not cleared on reload, since systems of helpers stay in the schedules.
+#[derive(Default, Resource)]
+struct RegisteredHelpers(HashSet<TypeId>);
+
 /// All registered rules for components replication.
 #[derive(Default, Deref, Resource)]
-pub(crate) struct ReplicationRules(Vec<ReplicationRule>);
+pub struct ReplicationRules {
+    /// Rules sorted by priority.
+    #[deref]
+    rules: Vec<ReplicationRule>,
+
+    /// Components for which replication is temporarily disabled.
+    disabled: HashSet<TypeId>,
+
+    /// Components that were enabled back and need to be sent again on the next tick.
+    reenabled: Vec<TypeId>,
+
+    /// Pairs of components where the first should be written before the second.
+    ///
+    /// See [`AppRuleExt::apply_before`] for details.
+    dependencies: Vec<(ComponentId, ComponentId)>,
+
+    /// Incremented on each [`Self::clear`] to invalidate data cached from the rules.
+    generation: u32,
+}
 
 impl ReplicationRules {
     /// Inserts a new rule, maintaining sorting by their priority in descending order.
@@ -198,7 +643,103 @@ impl ReplicationRules {
             .binary_search_by_key(&Reverse(rule.priority), |rule| Reverse(rule.priority))
             .unwrap_or_else(|index| index);
 
-        self.0.insert(index, rule);
+        self.rules.insert(index, rule);
+    }
+
+    /// Enables or disables replication of component `C` at runtime.
+    ///
+    /// Should be called on server. While disabled, insertions and changes of the component
+    /// won't be sent to clients, all other components on the same entities will continue replicating.
+    /// Removals are still sent.
+    ///
+    /// After enabling it back, current values of the component on all replicated entities
+    /// are sent as insertions on the next tick to deliver the changes made while it was disabled.
+    /// The component is not marked as changed, so user change detection is not affected.
+    ///
+    /// Enabled by default for all registered components.
+    pub fn set_enabled<C: Component>(&mut self, enabled: bool) {
+        let type_id = TypeId::of::<C>();
+        if enabled {
+            if self.disabled.remove(&type_id) {
+                self.reenabled.push(type_id);
+            }
+        } else {
+            self.disabled.insert(type_id);
+            self.reenabled
+                .retain(|&reenabled_id| reenabled_id != type_id);
+        }
+    }
+
+    /// Returns `true` if replication of component `C` wasn't disabled by [`Self::set_enabled`].
+    pub fn is_enabled<C: Component>(&self) -> bool {
+        !self.disabled.contains(&TypeId::of::<C>())
+    }
+
+    /// Returns `true` if the component should be written after the other component.
+    ///
+    /// Checks dependencies transitively.
+    pub(crate) fn depends_on(&self, component_id: ComponentId, other_id: ComponentId) -> bool {
+        let mut visited = Vec::new();
+        let mut pending = vec![component_id];
+        while let Some(id) = pending.pop() {
+            for &(before_id, _) in self
+                .dependencies
+                .iter()
+                .filter(|&&(_, after_id)| after_id == id)
+            {
+                if before_id == other_id {
+                    return true;
+                }
+                if !visited.contains(&before_id) {
+                    visited.push(before_id);
+                    pending.push(before_id);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns `true` if any dependencies were declared with [`AppRuleExt::apply_before`].
+    pub(crate) fn has_dependencies(&self) -> bool {
+        !self.dependencies.is_empty()
+    }
+
+    /// Removes all rules, dependencies and disabled components.
+    ///
+    /// Used to register rules again, see [`ProtocolReloadPlugin`](crate::protocol_reload::ProtocolReloadPlugin).
+    pub(crate) fn clear(&mut self) {
+        self.rules.clear();
+        self.disabled.clear();
+        self.reenabled.clear();
+        self.dependencies.clear();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Returns the number of [`Self::clear`] calls.
+    pub(crate) fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Returns IDs of components that were enabled back and weren't sent yet.
+    pub(crate) fn reenabled_ids(&self, world: &World) -> Vec<ComponentId> {
+        self.reenabled
+            .iter()
+            .filter_map(|&type_id| world.components().get_id(type_id))
+            .collect()
+    }
+
+    /// Forgets components that were enabled back after sending their values.
+    pub(crate) fn clear_reenabled(&mut self) {
+        self.reenabled.clear();
+    }
+
+    /// Returns IDs of components with disabled replication.
+    pub(crate) fn disabled_ids(&self, world: &World) -> Vec<ComponentId> {
+        self.disabled
+            .iter()
+            .filter_map(|&type_id| world.components().get_id(type_id))
+            .collect()
     }
 }
 
@@ -212,6 +753,21 @@ pub struct ReplicationRule {
 
     /// Rule components and their serialization/deserialization/removal functions.
     pub components: Vec<FnsInfo>,
+
+    /// Components that should be present on an entity for the rule to match.
+    ///
+    /// Not replicated by this rule. See [`AppRuleExt::replicate_with_marker`] for details.
+    pub markers: Vec<ComponentId>,
+
+    /// Whether all components should be sent together if any of them changes.
+    ///
+    /// See [`AppRuleExt::replicate_atomic_group`] for details.
+    pub atomic: bool,
+
+    /// Whether components should be sent only on insertion.
+    ///
+    /// See [`AppRuleExt::replicate_once`] for details.
+    pub once: bool,
 }
 
 impl ReplicationRule {
@@ -220,20 +776,35 @@ impl ReplicationRule {
         Self {
             priority: components.len(),
             components,
+            markers: Vec::new(),
+            atomic: false,
+            once: false,
         }
     }
 
+    /// Adds a marker required for the rule to match and increases the priority by one.
+    pub fn with_marker(mut self, marker_id: ComponentId) -> Self {
+        self.markers.push(marker_id);
+        self.priority += 1;
+        self
+    }
+
     /// Determines whether an archetype contains all components required by the rule.
     pub(crate) fn matches(&self, archetype: &Archetype) -> bool {
         self.components
             .iter()
             .all(|fns_info| archetype.contains(fns_info.component_id()))
+            && self
+                .markers
+                .iter()
+                .all(|&marker_id| archetype.contains(marker_id))
     }
 
     /// Determines whether the rule is applicable to an archetype with removals included and contains at least one removal.
     ///
     /// Returns `true` if all components in this rule are found in either `removed_components` or the
     /// `post_removal_archetype`, and at least one component is found in `removed_components`.
+    /// Markers should be present in either of them too, but their removal alone doesn't count.
     /// Returning true means the entity with this archetype satisfied this
     /// rule in the previous tick, but then a component within this rule was removed from the entity.
     pub(crate) fn matches_removals(
@@ -241,6 +812,12 @@ impl ReplicationRule {
         post_removal_archetype: &Archetype,
         removed_components: &HashSet<ComponentId>,
     ) -> bool {
+        if self.markers.iter().any(|marker_id| {
+            !post_removal_archetype.contains(*marker_id) && !removed_components.contains(marker_id)
+        }) {
+            return false;
+        }
+
         let mut matches = false;
         for fns_info in &self.components {
             if removed_components.contains(&fns_info.component_id()) {
@@ -327,7 +904,8 @@ macro_rules! impl_registrations {
                 // TODO: initialize with capacity after stabilization: https://github.com/rust-lang/rust/pull/122808
                 let mut components = Vec::new();
                 $(
-                    let fns_info = replication_fns.register_rule_fns(world, RuleFns::<$type>::default());
+                    let rule_fns = replication_fns.default_codec.rule_fns::<$type>();
+                    let fns_info = replication_fns.register_rule_fns(world, rule_fns);
                     components.push(fns_info);
                 )*
 
@@ -339,6 +917,105 @@ macro_rules! impl_registrations {
 
 bevy::utils::all_tuples!(impl_registrations, 1, 15, B);
 
+/**
+Registration of a component with settings declared on its type.
+
+Usually derived with `#[derive(Replicate)]` and registered with [`register_replicated_components`](crate::register_replicated_components).
+The derive accepts the following `#[replicate(...)]` attributes:
+
+- `mapped` - maps entities inside the component, like [`AppRuleExt::replicate_mapped`].
+- `once` - sends only the initial value, like [`AppRuleExt::replicate_once`].
+- `compact` - serializes as a single byte, like [`AppRuleExt::replicate_compact`].
+- `codec = Type` - serializes with a custom [`Codec`], like [`AppRuleExt::replicate_with_codec`].
+  Can be combined with `mapped`.
+- `with = path` - uses [`RuleFns`] returned by the function at the path, like [`AppRuleExt::replicate_with`].
+- `priority = value` - sets the priority for [`StalePriorityPlugin`](crate::server::stale_priority::StalePriorityPlugin),
+  like [`AppPriorityExt::set_replication_priority`](crate::server::stale_priority::AppPriorityExt::set_replication_priority).
+- `send_rate = ticks` - sends changes only on every `ticks`-th server tick,
+  like [`AppSendRateExt::set_send_rate`](crate::server::send_rate::AppSendRateExt::set_send_rate).
+- `name = "name"` - matches the component between server and clients by the name, like [`AppRuleExt::replicate_as`].
+  Can't be combined with `once`.
+
+Without attributes the component is registered like with [`AppRuleExt::replicate`].
+
+Fields of structs accept the following `#[replicate(...)]` attributes:
+
+- `skip` - the field is never sent and initialized with [`Default`] on client.
+- `once` - the field is sent only on insertion and not included in updates.
+  If a change arrives for a missing component, the field is initialized with [`Default`].
+
+With field attributes only field types need to implement [`Serialize`] and [`DeserializeOwned`].
+They can't be combined with `once`, `compact` or `with` on the type.
+
+# Examples
+
+```
+use bevy::{ecs::entity::{EntityMapper, MapEntities}, prelude::*};
+use bevy_replicon::{compact_tag, prelude::*, register_replicated_components};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+register_replicated_components!(&mut app, Position, Target, Stance, PlayerName, Health);
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(priority = 10, send_rate = 2)]
+struct Position(Vec2);
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(mapped)]
+struct Target(Entity);
+
+impl MapEntities for Target {
+    fn map_entities<T: EntityMapper>(&mut self, mapper: &mut T) {
+        self.0 = mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Component, Replicate)]
+#[replicate(compact)]
+enum Stance {
+    Standing,
+    Crouching,
+}
+
+compact_tag!(Stance { Standing, Crouching });
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(once)]
+struct PlayerName(String);
+
+#[derive(Component, Replicate)]
+struct Health {
+    current: u32,
+    #[replicate(once)]
+    max: u32,
+    /// Server-only value.
+    #[replicate(skip)]
+    regeneration: f32,
+}
+```
+**/
+pub trait Replicate: Component {
+    /// Registers replication for the component.
+    fn register(app: &mut App);
+}
+
+/// Registers replication for multiple components that implement [`Replicate`].
+///
+/// Accepts `&mut App` followed by the component types.
+///
+/// Should be called on both client and server.
+///
+/// See [`Replicate`] for an example.
+#[macro_export]
+macro_rules! register_replicated_components {
+    ($app:expr, $($component:ty),+ $(,)?) => {{
+        let app: &mut ::bevy::app::App = $app;
+        $(<$component as $crate::core::replication_rules::Replicate>::register(app);)+
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -363,6 +1040,41 @@ mod tests {
         assert_eq!(priorities, [2, 2, 1, 1, 1, 1]);
     }
 
+    #[test]
+    #[should_panic]
+    fn duplicate_wire_names() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate_as::<ComponentA>("component")
+            .replicate_as::<ComponentB>("component");
+    }
+
+    #[test]
+    fn disabling() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate::<ComponentA>()
+            .replicate::<ComponentB>();
+
+        let mut replication_rules = app.world.resource_mut::<ReplicationRules>();
+        replication_rules.set_enabled::<ComponentA>(false);
+        assert!(!replication_rules.is_enabled::<ComponentA>());
+        assert!(replication_rules.is_enabled::<ComponentB>());
+
+        let component_id = app.world.component_id::<ComponentA>().unwrap();
+        let replication_rules = app.world.resource::<ReplicationRules>();
+        assert_eq!(replication_rules.disabled_ids(&app.world), [component_id]);
+
+        let mut replication_rules = app.world.resource_mut::<ReplicationRules>();
+        replication_rules.set_enabled::<ComponentA>(true);
+        assert!(replication_rules.is_enabled::<ComponentA>());
+
+        let replication_rules = app.world.resource::<ReplicationRules>();
+        assert_eq!(replication_rules.reenabled_ids(&app.world), [component_id]);
+    }
+
     #[derive(Serialize, Deserialize, Component)]
     struct ComponentA;
 
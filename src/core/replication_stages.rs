@@ -0,0 +1,330 @@
+use std::io::Cursor;
+
+use bevy::{
+    ecs::component::{ComponentId, Tick},
+    prelude::*,
+    ptr::Ptr,
+    utils::HashMap,
+};
+use bytes::Bytes;
+
+use super::{
+    common_conditions::{client_connected, server_running},
+    replication_fns::FnsId,
+    replicon_channels::ReplicationChannel,
+    replicon_tick::RepliconTick,
+    ClientId,
+};
+use crate::{
+    client::{replicon_client::RepliconClient, ClientPlugin, ClientSet},
+    server::{
+        connected_clients::ConnectedClient, replicon_server::RepliconServer, ServerPlugin,
+        ServerSet,
+    },
+};
+
+/// Custom stages of the replication pipeline for [`App`].
+///
+/// Allows implementing advanced filtering or compression without modifying the crate.
+/// Stages are called in the order of registration.
+pub trait AppStageExt {
+    /**
+    Adds a filter that is called after change collection and before serialization.
+
+    Called on server for each client and each replicated entity in a tick.
+    If the filter returns `false`, component changes of the entity are not sent to the client this tick.
+    Since the change limit of the entity for the client is not advanced, skipped changes
+    will be sent when the filter passes again. Useful for things like throttling updates
+    of distant entities.
+
+    Only changes from update messages are filtered. Spawns, insertions, removals and despawns
+    are always sent to keep client state consistent. To hide entities completely, use
+    [`ClientVisibility`](crate::server::connected_clients::client_visibility::ClientVisibility).
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_stages::FilterCtx, prelude::*};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_entity_filter(every_other_tick);
+
+    /// Sends changes for entities far from the origin only on even ticks.
+    fn every_other_tick(ctx: &FilterCtx, entity: Entity) -> bool {
+        let is_far = ctx
+            .world
+            .get::<Transform>(entity)
+            .is_some_and(|transform| transform.translation.length() > 100.0);
+
+        !is_far || ctx.server_tick.get() % 2 == 0
+    }
+    ```
+    **/
+    fn add_entity_filter(&mut self, filter: EntityFilterFn) -> &mut Self;
+
+    /**
+    Adds a stage that is called after serialization and before sending.
+
+    Called on server for each serialized replication message. The stage can replace the message,
+    for example to compress it. Use [`Self::add_receive_stage`] on client to reverse the transformation.
+
+    Runs in [`ServerSet::Send`] after replication messages are collected and before
+    [`MessageSigningPlugin`](super::message_signing::MessageSigningPlugin) signs them.
+    **/
+    fn add_send_stage(&mut self, stage: SendStageFn) -> &mut Self;
+
+    /**
+    Adds a stage that is called after receiving and before deserialization.
+
+    Called on client for each received replication message. If the stage returns `false`,
+    the message is discarded.
+
+    Runs in [`ClientSet::Receive`] before replication messages are applied and after
+    [`MessageSigningPlugin`](super::message_signing::MessageSigningPlugin) verifies them.
+
+    # Examples
+
+    Reverses all bytes of messages on server and restores them on client:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::{replicon_channels::ReplicationChannel, ClientId},
+        prelude::*,
+    };
+    use bytes::Bytes;
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_send_stage(reverse_sent)
+        .add_receive_stage(reverse_received);
+
+    fn reverse_sent(_client_id: ClientId, _channel: ReplicationChannel, message: &mut Bytes) {
+        *message = message.iter().rev().copied().collect();
+    }
+
+    fn reverse_received(_channel: ReplicationChannel, message: &mut Bytes) -> bool {
+        *message = message.iter().rev().copied().collect();
+        true
+    }
+    ```
+    **/
+    fn add_receive_stage(&mut self, stage: ReceiveStageFn) -> &mut Self;
+}
+
+impl AppStageExt for App {
+    fn add_entity_filter(&mut self, filter: EntityFilterFn) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(ReplicationStages::default)
+            .entity_filters
+            .push(filter);
+
+        self
+    }
+
+    fn add_send_stage(&mut self, stage: SendStageFn) -> &mut Self {
+        let mut stages = self
+            .world
+            .get_resource_or_insert_with(ReplicationStages::default);
+        stages.send_stages.push(stage);
+
+        if stages.send_stages.len() == 1 {
+            self.add_systems(
+                PostUpdate,
+                apply_send_stages
+                    .after(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+        }
+
+        self
+    }
+
+    fn add_receive_stage(&mut self, stage: ReceiveStageFn) -> &mut Self {
+        let mut stages = self
+            .world
+            .get_resource_or_insert_with(ReplicationStages::default);
+        stages.receive_stages.push(stage);
+
+        if stages.receive_stages.len() == 1 {
+            self.add_systems(
+                PreUpdate,
+                apply_receive_stages
+                    .before(ClientPlugin::receive_replication)
+                    .in_set(ClientSet::Receive)
+                    .run_if(client_connected),
+            );
+        }
+
+        self
+    }
+}
+
+pub(super) fn apply_send_stages(
+    mut server: ResMut<RepliconServer>,
+    stages: Res<ReplicationStages>,
+) {
+    for (client_id, channel_id, message) in server.iter_sent_mut() {
+        let Some(channel) = replication_channel(*channel_id) else {
+            continue;
+        };
+
+        for stage in &stages.send_stages {
+            (stage)(*client_id, channel, message);
+        }
+    }
+}
+
+pub(super) fn apply_receive_stages(
+    mut client: ResMut<RepliconClient>,
+    stages: Res<ReplicationStages>,
+) {
+    for channel in [
+        ReplicationChannel::Init,
+        ReplicationChannel::Update,
+        ReplicationChannel::UpdateChunks,
+    ] {
+        client.retain_received(channel, |message| {
+            stages
+                .receive_stages
+                .iter()
+                .all(|stage| (stage)(channel, message))
+        });
+    }
+}
+
+fn replication_channel(channel_id: u8) -> Option<ReplicationChannel> {
+    [
+        ReplicationChannel::Init,
+        ReplicationChannel::Update,
+        ReplicationChannel::UpdateChunks,
+    ]
+    .into_iter()
+    .find(|&channel| channel as u8 == channel_id)
+}
+
+/// Stages registered with [`AppStageExt`].
+#[derive(Default, Resource)]
+pub struct ReplicationStages {
+    entity_filters: Vec<EntityFilterFn>,
+    send_stages: Vec<SendStageFn>,
+    receive_stages: Vec<ReceiveStageFn>,
+}
+
+impl ReplicationStages {
+    /// Returns `true` if all entity filters pass for the client.
+    pub(crate) fn filter_entity(&self, ctx: &FilterCtx, entity: Entity) -> bool {
+        self.entity_filters
+            .iter()
+            .all(|filter| (filter)(ctx, entity))
+    }
+
+    /// Returns `true` if at least one entity filter is registered.
+    pub(crate) fn has_entity_filters(&self) -> bool {
+        !self.entity_filters.is_empty()
+    }
+}
+
+/// Per-component stages that replace serialization of changes in update messages.
+///
+/// Used on server by [`AppDeltaExt::replicate_delta`](super::delta_compression::AppDeltaExt::replicate_delta).
+#[derive(Default, Resource)]
+pub struct ComponentStages(HashMap<ComponentId, Box<dyn ComponentStage>>);
+
+impl ComponentStages {
+    /// Returns the number of values stored by all stages, such as delta compression baselines.
+    pub fn stored_values(&self) -> usize {
+        self.0.values().map(|stage| stage.len()).sum()
+    }
+
+    /// Removes state of entities for which `f` returns `false`.
+    ///
+    /// Returns the number of entities whose state was removed.
+    pub(crate) fn retain(&mut self, f: impl Fn(Entity) -> bool) -> usize {
+        self.0.values_mut().map(|stage| stage.retain(&f)).sum()
+    }
+
+    pub(crate) fn insert(&mut self, component_id: ComponentId, stage: Box<dyn ComponentStage>) {
+        self.0.insert(component_id, stage);
+    }
+
+    pub(crate) fn get_mut(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<&mut (dyn ComponentStage + 'static)> {
+        self.0.get_mut(&component_id).map(|stage| &mut **stage)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Stage of a single component stored in [`ComponentStages`].
+pub(crate) trait ComponentStage: Send + Sync {
+    /// Writes replication functions ID and the change of the component for a client.
+    ///
+    /// Returns `false` without writing anything if the component should be serialized as usual.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` points to the component of this stage.
+    unsafe fn write_update(
+        &self,
+        entity: Entity,
+        ptr: Ptr,
+        fns_id: FnsId,
+        change_limit: Tick,
+        this_run: Tick,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<bool>;
+
+    /// Called after the component was sent to at least one client.
+    ///
+    /// `oldest_limit` is the oldest change limit of the entity among clients that see it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` points to the component of this stage.
+    unsafe fn sent(&mut self, entity: Entity, ptr: Ptr, oldest_limit: Option<Tick>, this_run: Tick);
+
+    /// Removes all state of an entity.
+    fn remove(&mut self, entity: Entity);
+
+    /// Removes state of entities for which `f` returns `false`.
+    ///
+    /// Returns the number of removed entities.
+    fn retain(&mut self, f: &dyn Fn(Entity) -> bool) -> usize;
+
+    /// Returns the number of stored values.
+    fn len(&self) -> usize;
+}
+
+/// Signature of entity filters for [`AppStageExt::add_entity_filter`].
+pub type EntityFilterFn = fn(&FilterCtx, Entity) -> bool;
+
+/// Signature of send stages for [`AppStageExt::add_send_stage`].
+pub type SendStageFn = fn(ClientId, ReplicationChannel, &mut Bytes);
+
+/// Signature of receive stages for [`AppStageExt::add_receive_stage`].
+pub type ReceiveStageFn = fn(ReplicationChannel, &mut Bytes) -> bool;
+
+/// Context for [`EntityFilterFn`].
+#[non_exhaustive]
+pub struct FilterCtx<'a> {
+    /// World with replicated entities.
+    pub world: &'a World,
+
+    /// Client for which the entity is collected.
+    pub client: &'a ConnectedClient,
+
+    /// Current tick.
+    pub server_tick: RepliconTick,
+}
@@ -5,6 +5,7 @@ use bevy::prelude::*;
 /// ID of a server replication channel.
 ///
 /// See also [`RepliconChannels`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ReplicationChannel {
     /// For sending messages with entity mappings, inserts, removals and despawns.
@@ -15,13 +16,23 @@ pub enum ReplicationChannel {
     ///
     /// This is an unreliable channel.
     Update,
+    /// For sending component updates of a single entity that don't fit into a packet, split into chunks.
+    ///
+    /// This is an unreliable channel.
+    UpdateChunks,
+    /// For sending [`TickJumped`](crate::server::TickJumped) notifications.
+    ///
+    /// This is an ordered reliable channel. Server channels for events are created after it.
+    TickJumps,
 }
 
 impl From<ReplicationChannel> for RepliconChannel {
     fn from(value: ReplicationChannel) -> Self {
         match value {
-            ReplicationChannel::Init => ChannelKind::Ordered.into(),
-            ReplicationChannel::Update => ChannelKind::Unreliable.into(),
+            ReplicationChannel::Init | ReplicationChannel::TickJumps => ChannelKind::Ordered.into(),
+            ReplicationChannel::Update | ReplicationChannel::UpdateChunks => {
+                ChannelKind::Unreliable.into()
+            }
         }
     }
 }
@@ -55,6 +66,8 @@ impl Default for RepliconChannels {
             server: vec![
                 ReplicationChannel::Init.into(),
                 ReplicationChannel::Update.into(),
+                ReplicationChannel::UpdateChunks.into(),
+                ReplicationChannel::TickJumps.into(),
             ],
             client: vec![
                 ReplicationChannel::Init.into(),
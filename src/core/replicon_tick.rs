@@ -3,6 +3,7 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Like [`Tick`](bevy::ecs::component::Tick), but for replication.
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See also [`ServerInitTick`](crate::client::ServerInitTick) and
 /// [`ServerTick`](crate::server::server_tick::ServerTick).
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Reflect, Serialize)]
 pub struct RepliconTick(u32);
 
 impl RepliconTick {
@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer, utils::Instant};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common_conditions::{client_connected, server_running},
+    replicon_channels::ChannelKind,
+};
+use crate::{
+    client::ClientSet,
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+    server::{connected_clients::ConnectedClients, ServerSet},
+};
+
+/**
+Mirrors a compact server health sample to clients.
+
+Server measures how long its updates take and periodically broadcasts [`ServerHealth`]
+with the average update duration, load and the number of connected clients. Clients store the last
+received sample in the same resource, so games can display server performance without custom events.
+Samples are sent over an unreliable channel, a lost sample is replaced by the next one.
+
+Clients receive the first sample only after [`Self::interval`] passes on server.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ServerHealthPlugin::default()))
+    .add_systems(Update, display_health.run_if(client_connected));
+
+fn display_health(health: Res<ServerHealth>) {
+    if health.is_changed() {
+        info!(
+            "server load: {:.0}%, players: {}",
+            health.load() * 100.0,
+            health.players()
+        );
+    }
+}
+```
+**/
+pub struct ServerHealthPlugin {
+    /// Interval between samples.
+    ///
+    /// Uses [`Time`], so changes of [`Time<Virtual>`] affect it.
+    pub interval: Duration,
+}
+
+impl Default for ServerHealthPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for ServerHealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerHealth>()
+            .init_resource::<HealthMeasurement>()
+            .add_server_event::<HealthSample>(ChannelKind::Unreliable)
+            .add_systems(First, Self::begin_update.run_if(server_running))
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    Self::receive
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send
+                    .before(ServerSet::Send)
+                    .run_if(server_running)
+                    .run_if(on_timer(self.interval)),
+            )
+            .add_systems(Last, Self::end_update.run_if(server_running));
+    }
+}
+
+impl ServerHealthPlugin {
+    fn begin_update(mut measurement: ResMut<HealthMeasurement>) {
+        let now = Instant::now();
+        measurement.sample_start.get_or_insert(now);
+        measurement.update_start = Some(now);
+    }
+
+    fn end_update(mut measurement: ResMut<HealthMeasurement>) {
+        if let Some(update_start) = measurement.update_start.take() {
+            measurement.busy += update_start.elapsed();
+            measurement.updates += 1;
+        }
+    }
+
+    /// Broadcasts a sample of updates measured since the last sample.
+    ///
+    /// The current update is not finished yet, so it's included in the next sample.
+    fn send(
+        mut measurement: ResMut<HealthMeasurement>,
+        mut health: ResMut<ServerHealth>,
+        mut sample_events: EventWriter<ToClients<HealthSample>>,
+        connected_clients: Res<ConnectedClients>,
+    ) {
+        let now = Instant::now();
+        let elapsed = measurement
+            .sample_start
+            .map(|sample_start| now - sample_start)
+            .unwrap_or_default();
+
+        let update_duration = measurement
+            .busy
+            .checked_div(measurement.updates)
+            .unwrap_or_default();
+        let load = if elapsed.is_zero() {
+            0.0
+        } else {
+            measurement.busy.as_secs_f32() / elapsed.as_secs_f32()
+        };
+
+        *health = ServerHealth {
+            update_micros: update_duration.as_micros().try_into().unwrap_or(u32::MAX),
+            load_percent: (load.min(1.0) * 100.0).round() as u8,
+            players: connected_clients.len().try_into().unwrap_or(u16::MAX),
+        };
+        trace!("sending {:?}", *health);
+        sample_events.send(ToClients {
+            mode: SendMode::Broadcast,
+            event: HealthSample(*health),
+        });
+
+        *measurement = HealthMeasurement {
+            sample_start: Some(now),
+            update_start: measurement.update_start,
+            ..Default::default()
+        };
+    }
+
+    fn receive(mut health: ResMut<ServerHealth>, mut sample_events: EventReader<HealthSample>) {
+        if let Some(event) = sample_events.read().last() {
+            *health = event.0;
+        }
+    }
+
+    fn reset(mut health: ResMut<ServerHealth>) {
+        *health = Default::default();
+    }
+}
+
+/// Last server health sample.
+///
+/// Updated on server for each sample and contains the last received sample on clients.
+/// See [`ServerHealthPlugin`] for details.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+pub struct ServerHealth {
+    update_micros: u32,
+    load_percent: u8,
+    players: u16,
+}
+
+impl ServerHealth {
+    /// Returns the average wall time of a server update.
+    pub fn update_duration(&self) -> Duration {
+        Duration::from_micros(self.update_micros.into())
+    }
+
+    /// Returns the fraction of wall time the server spent inside updates, from `0.0` to `1.0`.
+    ///
+    /// Values close to `1.0` mean that the server can't keep up with its tick rate.
+    pub fn load(&self) -> f32 {
+        self.load_percent as f32 / 100.0
+    }
+
+    /// Returns the number of connected clients.
+    pub fn players(&self) -> usize {
+        self.players.into()
+    }
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct HealthSample(ServerHealth);
+
+/// Update timings on server since the last sample.
+#[derive(Default, Resource)]
+struct HealthMeasurement {
+    /// Start of the current sample, [`None`] before the first update.
+    sample_start: Option<Instant>,
+
+    /// Start of the current update.
+    update_start: Option<Instant>,
+
+    /// Total wall time of finished updates.
+    busy: Duration,
+
+    /// Number of finished updates.
+    updates: u32,
+}
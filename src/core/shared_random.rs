@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common_conditions::{client_connected, server_running},
+    replicon_channels::ChannelKind,
+    replicon_tick::RepliconTick,
+};
+use crate::{
+    client::ClientSet,
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+    server::{ServerEvent, ServerPlugin, ServerSet},
+};
+
+/**
+Replicates a random seed to let clients generate the same cosmetic randomness as server.
+
+Set [`SharedSeed`] on server, for example at the start of a match, and clients will receive it.
+Instead of replicating each outcome, both sides create [`SharedRng`] with [`SharedSeed::rng`]
+for a tick and a stream, so particles or displayed crit rolls match across machines.
+Since the sequence is derived from the tick and the stream, there are no positions to keep in sync.
+
+Use separate streams for unrelated random sources to keep them independent.
+Streams should be identical on all machines, so don't use client entities directly, use IDs that
+are the same everywhere, like server entities or your own identifiers.
+
+Randomness is predictable by clients, so it shouldn't be used for outcomes that affect gameplay.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::replicon_tick::RepliconTick, prelude::*};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, SharedRandomPlugin))
+    .add_systems(Update, spawn_sparks);
+
+const SPARKS_STREAM: u64 = 0;
+
+fn spawn_sparks(seed: Res<SharedSeed>, hits: Query<&Hit, Added<Hit>>) {
+    for hit in &hits {
+        let mut rng = seed.rng(hit.tick, SPARKS_STREAM);
+        for _ in 0..rng.range(3..8) {
+            let angle = rng.next_f32() * std::f32::consts::TAU;
+            // Spawn a spark in the direction.
+        }
+    }
+}
+
+/// Replicated component with the server tick of the hit.
+#[derive(Component)]
+struct Hit {
+    tick: RepliconTick,
+}
+```
+**/
+pub struct SharedRandomPlugin;
+
+impl Plugin for SharedRandomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SharedSeed>()
+            .add_server_event::<SeedChange>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    Self::receive
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl SharedRandomPlugin {
+    /// Sends the seed to all clients on change and to newly connected clients.
+    fn send(
+        seed: Res<SharedSeed>,
+        mut server_events: EventReader<ServerEvent>,
+        mut change_events: EventWriter<ToClients<SeedChange>>,
+    ) {
+        let changed = seed.is_changed();
+        if changed {
+            debug!("changing shared seed");
+            change_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: SeedChange(*seed),
+            });
+        }
+
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                // Already included in the broadcast.
+                if !changed {
+                    change_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: SeedChange(*seed),
+                    });
+                }
+            }
+        }
+    }
+
+    fn receive(mut seed: ResMut<SharedSeed>, mut change_events: EventReader<SeedChange>) {
+        if let Some(event) = change_events.read().last() {
+            trace!("received shared seed");
+            *seed = event.0;
+        }
+    }
+
+    fn reset(mut seed: ResMut<SharedSeed>) {
+        *seed = Default::default();
+    }
+}
+
+/// Random seed shared between server and clients.
+///
+/// Should be changed only on server. On clients it contains the last received value.
+/// See [`SharedRandomPlugin`] for details.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Resource, Serialize)]
+pub struct SharedSeed(u64);
+
+impl SharedSeed {
+    /// Creates a seed with the specified value.
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the seed value.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Creates a generator for the stream at the tick.
+    ///
+    /// Returns the same sequence for the same seed, tick and stream.
+    pub fn rng(self, tick: RepliconTick, stream: u64) -> SharedRng {
+        let tick_state = mix(self.0 ^ mix(tick.get().into()));
+        SharedRng {
+            state: mix(tick_state ^ stream),
+        }
+    }
+}
+
+/// A small deterministic generator created by [`SharedSeed::rng`].
+///
+/// Produces identical values on all platforms, but not suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct SharedRng {
+    state: u64,
+}
+
+impl SharedRng {
+    /// Returns the next random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix(self.state)
+    }
+
+    /// Returns the next random [`u32`].
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns the next random [`f32`] in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// Returns a random value in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn range(&mut self, range: std::ops::Range<u32>) -> u32 {
+        assert!(!range.is_empty(), "range should contain at least one value");
+        let len = range.end - range.start;
+        range.start + ((self.next_u32() as u64 * len as u64) >> 32) as u32
+    }
+
+    /// Returns `true` with the probability, where `0.0` is never and `1.0` is always.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
+
+/// Increment of the SplitMix64 generator.
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Finalizer of the SplitMix64 generator.
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct SeedChange(SharedSeed);
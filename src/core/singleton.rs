@@ -0,0 +1,145 @@
+use std::{any, marker::PhantomData};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    common_conditions::server_running,
+    replication_rules::{AppHelperExt, AppRuleExt},
+    Replicated,
+};
+use crate::{client::ClientSet, server::ServerSet};
+
+/// Singleton replication for [`App`].
+pub trait AppSingletonExt {
+    /**
+    Replicates the component and guarantees that only one entity with it is replicated.
+
+    Useful for global state that lives on an entity, like match state, world settings
+    or a day/night cycle. Access the entity with [`Singleton<C>`] on both server and client.
+
+    If server has multiple replicated entities with the component, only the first one stays replicated
+    and [`Replicated`] is removed from the rest with an error. The check runs in [`PostUpdate`]
+    before [`ServerSet::Send`] and [`Singleton<C>`] is updated in [`PreUpdate`] after [`ClientSet::Receive`].
+
+    Should be called on both client and server instead of [`AppRuleExt::replicate`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_singleton::<MatchState>()
+        .add_systems(Update, show_score);
+
+    fn show_score(singleton: Res<Singleton<MatchState>>, states: Query<&MatchState>) {
+        if let Some(state) = singleton.entity().and_then(|entity| states.get(entity).ok()) {
+            info!("score: {}:{}", state.red, state.blue);
+        }
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct MatchState {
+        red: u32,
+        blue: u32,
+    }
+    ```
+    **/
+    fn replicate_singleton<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned;
+}
+
+impl AppSingletonExt for App {
+    fn replicate_singleton<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        self.replicate::<C>().init_resource::<Singleton<C>>();
+
+        if self.register_helper::<Singleton<C>>() {
+            self.add_systems(PreUpdate, update_singleton::<C>.after(ClientSet::Receive))
+                .add_systems(
+                    PostUpdate,
+                    enforce_singleton::<C>
+                        .before(ServerSet::Send)
+                        .run_if(server_running),
+                );
+        }
+
+        self
+    }
+}
+
+/// Points [`Singleton<C>`] to the replicated entity with `C`.
+fn update_singleton<C: Component>(
+    mut singleton: ResMut<Singleton<C>>,
+    entities: Query<Entity, (With<C>, With<Replicated>)>,
+) {
+    if singleton
+        .entity
+        .is_some_and(|entity| entities.contains(entity))
+    {
+        return;
+    }
+
+    let entity = entities.iter().next();
+    if singleton.entity != entity {
+        singleton.entity = entity;
+    }
+}
+
+/// Keeps only one replicated entity with `C`.
+fn enforce_singleton<C: Component>(
+    mut commands: Commands,
+    mut singleton: ResMut<Singleton<C>>,
+    entities: Query<Entity, (With<C>, With<Replicated>)>,
+) {
+    let kept = singleton
+        .entity
+        .filter(|&entity| entities.contains(entity))
+        .or_else(|| entities.iter().next());
+
+    if let Some(kept) = kept {
+        for entity in entities.iter().filter(|&entity| entity != kept) {
+            error!(
+                "removing `Replicated` from `{entity:?}` because `{kept:?}` already replicates singleton `{}`",
+                any::type_name::<C>()
+            );
+            commands.entity(entity).remove::<Replicated>();
+        }
+    }
+
+    if singleton.entity != kept {
+        singleton.entity = kept;
+    }
+}
+
+/// Points to the replicated entity with `C` registered with [`AppSingletonExt::replicate_singleton`].
+///
+/// On client it's the mapped client entity.
+#[derive(Resource)]
+pub struct Singleton<C> {
+    entity: Option<Entity>,
+    marker: PhantomData<C>,
+}
+
+impl<C> Singleton<C> {
+    /// Returns the entity or [`None`] if it wasn't spawned or received yet.
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+impl<C> Default for Singleton<C> {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common_conditions::{client_connected, server_running},
+    replicon_channels::ChannelKind,
+    replicon_tick::RepliconTick,
+};
+use crate::{
+    client::ClientSet,
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+    server::{server_tick::ServerTick, ServerEvent, ServerPlugin, ServerSet},
+};
+
+/// Replicates server time scale changes to clients.
+///
+/// Set [`ServerTimeScale`] on server to pause or slow down the simulation and clients will receive
+/// the new scale together with the tick from which it applies. Interpolation or prediction layers
+/// can use it to adjust their timelines instead of interpreting the slowdown as lag.
+///
+/// The plugin doesn't change [`Time`] on its own. If the tick rate should follow the scale,
+/// adjust [`Time<Virtual>`] on the server too, [`TickPolicy::MaxTickRate`](crate::server::TickPolicy::MaxTickRate)
+/// uses it for its timer.
+///
+/// Not added by default.
+pub struct TimeScalePlugin;
+
+impl Plugin for TimeScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerTimeScale>()
+            .add_server_event::<TimeScaleChange>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    Self::receive
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl TimeScalePlugin {
+    /// Sends the scale to all clients on change and to newly connected clients.
+    fn send(
+        mut time_scale: ResMut<ServerTimeScale>,
+        mut server_events: EventReader<ServerEvent>,
+        mut change_events: EventWriter<ToClients<TimeScaleChange>>,
+        server_tick: Res<ServerTick>,
+    ) {
+        let changed = time_scale.is_changed();
+        if changed {
+            time_scale.bypass_change_detection().tick = **server_tick;
+            debug!(
+                "changing time scale to {} at {:?}",
+                time_scale.scale, time_scale.tick
+            );
+            change_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: TimeScaleChange(*time_scale),
+            });
+        }
+
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                // Already included in the broadcast.
+                if !changed {
+                    change_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: TimeScaleChange(*time_scale),
+                    });
+                }
+            }
+        }
+    }
+
+    fn receive(
+        mut time_scale: ResMut<ServerTimeScale>,
+        mut change_events: EventReader<TimeScaleChange>,
+    ) {
+        if let Some(event) = change_events.read().last() {
+            trace!(
+                "received time scale {} at {:?}",
+                event.0.scale,
+                event.0.tick
+            );
+            *time_scale = event.0;
+        }
+    }
+
+    fn reset(mut time_scale: ResMut<ServerTimeScale>) {
+        *time_scale = Default::default();
+    }
+}
+
+/// Time scale of the server simulation.
+///
+/// Should be changed only on server using [`Self::set`]. On clients it contains the last received value.
+/// See [`TimeScalePlugin`] for details.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Resource, Serialize)]
+pub struct ServerTimeScale {
+    scale: f32,
+    tick: RepliconTick,
+}
+
+impl ServerTimeScale {
+    /// Sets the scale, where `0.0` is a pause and `1.0` is the normal speed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scale is negative or not finite.
+    pub fn set(&mut self, scale: f32) {
+        assert!(
+            scale.is_finite() && scale >= 0.0,
+            "time scale should be finite and non-negative"
+        );
+        self.scale = scale;
+    }
+
+    /// Returns the current scale.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Returns the server tick from which the scale applies.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns `true` if the simulation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.scale == 0.0
+    }
+}
+
+impl Default for ServerTimeScale {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            tick: Default::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct TimeScaleChange(ServerTimeScale);
@@ -0,0 +1,73 @@
+use bincode::{DefaultOptions, Options};
+
+/// Returns options for values whose size depends on their magnitude, like component data,
+/// function IDs and events.
+///
+/// Integers are little-endian on every target and use bincode's variable-length encoding:
+/// values below 251 take a single byte, larger values are prefixed with a byte that specifies
+/// the width of the following integer. `usize` and `isize` are encoded as 64-bit integers,
+/// so the encoding doesn't depend on the target.
+///
+/// Equal to [`DefaultOptions`], but doesn't rely on bincode defaults. Should be used for all
+/// protocol data instead of [`DefaultOptions`] to keep the wire format stable.
+pub fn varint() -> impl Options + Copy {
+    DefaultOptions::new()
+        .with_little_endian()
+        .with_varint_encoding()
+        .reject_trailing_bytes()
+}
+
+/// Returns options for fixed-size fields of message headers, like ticks and send time.
+///
+/// Absolute ticks grow quickly and would take more bytes as varints. Array lengths, sizes,
+/// update indices and tick differences in replication messages are encoded as LEB128 varints instead.
+///
+/// Integers are little-endian on every target and encoded with their exact width,
+/// `usize` and `isize` as 64-bit integers.
+///
+/// Equal to options of [`bincode::serialize_into`], but doesn't rely on bincode defaults.
+pub fn fixint() -> impl Options + Copy {
+    DefaultOptions::new()
+        .with_little_endian()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint_layout() {
+        assert_eq!(varint().serialize(&7u32).unwrap(), [7]);
+        assert_eq!(varint().serialize(&0x1234u16).unwrap(), [251, 0x34, 0x12]);
+        assert_eq!(varint().serialize(&7usize).unwrap(), [7]);
+        assert_eq!(varint().serialize(&-1i32).unwrap(), [1]);
+    }
+
+    #[test]
+    fn fixint_layout() {
+        assert_eq!(fixint().serialize(&0x1234u16).unwrap(), [0x34, 0x12]);
+        assert_eq!(
+            fixint().serialize(&0x12345678u32).unwrap(),
+            [0x78, 0x56, 0x34, 0x12]
+        );
+        assert_eq!(
+            fixint().serialize(&1usize).unwrap(),
+            [1, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn defaults_match() {
+        let value = (u16::MAX, 300u32, -5i64, 1usize);
+        assert_eq!(
+            varint().serialize(&value).unwrap(),
+            DefaultOptions::new().serialize(&value).unwrap()
+        );
+        assert_eq!(
+            fixint().serialize(&value).unwrap(),
+            bincode::serialize(&value).unwrap()
+        );
+    }
+}
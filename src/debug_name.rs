@@ -0,0 +1,161 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::{
+    ecs::{archetype::Archetypes, component::ComponentId, entity::Entities},
+    prelude::*,
+    utils::get_short_name,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{common_conditions::server_running, replication_rules::AppRuleExt, Replicated},
+    server::ServerSet,
+};
+
+/**
+Replicates short debug labels of entities into [`ReplicatedDebugName`] on clients.
+
+Makes inspectors show meaningful names for server-spawned entities instead of bare IDs.
+On server the label is taken from [`Name`] if present, otherwise from the short type name of the first
+component registered with [`AppDebugNameExt::label_by_type`] that the entity contains.
+Labels are truncated to [`ReplicatedDebugName::MAX_LEN`] bytes.
+
+Does nothing in release builds, so it can be added unconditionally.
+Client and server should be built with the same profile, since the replication rule is registered only in debug builds.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, DebugNamePlugin))
+    .label_by_type::<Player>();
+
+#[derive(Component)]
+struct Player;
+```
+**/
+pub struct DebugNamePlugin;
+
+impl Plugin for DebugNamePlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        app.init_resource::<LabelTypes>()
+            .register_type::<ReplicatedDebugName>()
+            .replicate::<ReplicatedDebugName>()
+            .add_systems(
+                PostUpdate,
+                Self::update_labels
+                    .before(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl DebugNamePlugin {
+    /// Inserts [`ReplicatedDebugName`] for replicated entities whose labels changed.
+    fn update_labels(
+        mut commands: Commands,
+        label_types: Res<LabelTypes>,
+        archetypes: &Archetypes,
+        entities: &Entities,
+        replicated: Query<(Entity, Option<&Name>, Option<&ReplicatedDebugName>), With<Replicated>>,
+    ) {
+        for (entity, name, debug_name) in &replicated {
+            let label = name.map(Name::as_str).or_else(|| {
+                let location = entities.get(entity)?;
+                let archetype = archetypes.get(location.archetype_id)?;
+                label_types
+                    .0
+                    .iter()
+                    .find(|&&(component_id, _)| archetype.contains(component_id))
+                    .map(|(_, label)| label.as_str())
+            });
+
+            let Some(label) = label else {
+                if debug_name.is_some() {
+                    commands.entity(entity).remove::<ReplicatedDebugName>();
+                }
+                continue;
+            };
+
+            let label = truncate(label, ReplicatedDebugName::MAX_LEN);
+            if debug_name.map(|debug_name| debug_name.0.as_str()) != Some(label) {
+                commands
+                    .entity(entity)
+                    .insert(ReplicatedDebugName(label.into()));
+            }
+        }
+    }
+}
+
+/// Returns the longest prefix of the string that fits into `max_len` bytes without splitting characters.
+fn truncate(label: &str, max_len: usize) -> &str {
+    if label.len() <= max_len {
+        return label;
+    }
+
+    let mut end = max_len;
+    while !label.is_char_boundary(end) {
+        end -= 1;
+    }
+    &label[..end]
+}
+
+/// Debug labels for [`App`].
+pub trait AppDebugNameExt {
+    /// Labels entities that contain the component with its short type name.
+    ///
+    /// Used for entities without [`Name`]. If multiple registered components are present,
+    /// the one registered first is used.
+    ///
+    /// Needed only on server. See [`DebugNamePlugin`] for details.
+    fn label_by_type<C: Component>(&mut self) -> &mut Self;
+}
+
+impl AppDebugNameExt for App {
+    fn label_by_type<C: Component>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let label = get_short_name(std::any::type_name::<C>());
+        self.world
+            .get_resource_or_insert_with(LabelTypes::default)
+            .0
+            .push((component_id, label));
+
+        self
+    }
+}
+
+/// Components registered with [`AppDebugNameExt::label_by_type`] and their labels.
+#[derive(Default, Resource)]
+struct LabelTypes(Vec<(ComponentId, String)>);
+
+/// Debug label of a server entity.
+///
+/// See [`DebugNamePlugin`] for details.
+#[derive(Component, Clone, Debug, Default, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct ReplicatedDebugName(String);
+
+impl ReplicatedDebugName {
+    /// Maximum length of the label in bytes.
+    pub const MAX_LEN: usize = 32;
+
+    /// Returns the label.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for ReplicatedDebugName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
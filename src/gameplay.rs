@@ -0,0 +1,9 @@
+//! Game-level features built on top of replication and network events.
+//!
+//! Available only with the `gameplay` feature.
+
+pub mod ability;
+pub mod chat;
+pub mod lobby;
+pub mod scoreboard;
+pub mod state_machine;
@@ -0,0 +1,333 @@
+use bevy::{ecs::entity::MapEntities, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        common_conditions::has_authority, replication_rules::AppRuleExt,
+        replicon_channels::ChannelKind, replicon_tick::RepliconTick, ClientId,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        server_event::{SendMode, ServerEventAppExt, ToClients},
+    },
+    server::{server_tick::ServerTick, ServerSet},
+};
+
+/**
+Validates ability activations from clients on server and replicates cooldowns.
+
+Register abilities with [`AbilityAppExt::add_ability`] and insert [`AbilityOwner`] and [`Cooldowns`]
+on caster entities. Clients send [`ActivateAbility`] and server checks the owner, the cooldown,
+the range and the custom [`Ability::validate`] callback in this order. On success the cooldown is updated
+and [`AbilityActivated`] is emitted on server for the game logic. On failure the client receives [`AbilityRejected`].
+
+Mark casters with [`Replicated`](crate::core::Replicated) to let clients display remaining cooldowns.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    gameplay::ability::{Ability, AbilityActivated, AbilityAppExt, AbilityPlugin, ActivateAbility},
+    prelude::*,
+};
+
+const FIREBALL: u16 = 0;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, AbilityPlugin))
+    .add_ability(
+        FIREBALL,
+        Ability {
+            cooldown: 60,
+            range: Some(20.0),
+            validate: consume_mana,
+        },
+    )
+    .add_systems(Update, cast.run_if(server_running));
+
+fn consume_mana(world: &mut World, activation: &ActivateAbility) -> bool {
+    let Some(mut mana) = world.get_mut::<Mana>(activation.caster) else {
+        return false;
+    };
+    if mana.0 < 10 {
+        return false;
+    }
+    mana.0 -= 10;
+    true
+}
+
+fn cast(mut activated_events: EventReader<AbilityActivated>) {
+    for event in activated_events.read() {
+        // Spawn the fireball.
+    }
+}
+
+#[derive(Component)]
+struct Mana(u32);
+```
+**/
+pub struct AbilityPlugin;
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Abilities>()
+            .add_event::<AbilityActivated>()
+            .replicate::<Cooldowns>()
+            .add_mapped_client_event::<ActivateAbility>(ChannelKind::Ordered)
+            .add_server_event::<AbilityRejected>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                Self::apply_activations
+                    .after(ServerSet::Receive)
+                    .run_if(has_authority),
+            );
+    }
+}
+
+impl AbilityPlugin {
+    fn apply_activations(world: &mut World) {
+        let activations: Vec<_> = world
+            .resource_mut::<Events<FromClient<ActivateAbility>>>()
+            .drain()
+            .collect();
+
+        for FromClient { client_id, event } in activations {
+            match Self::validate(world, client_id, &event) {
+                Ok(()) => {
+                    world.send_event(AbilityActivated {
+                        client_id,
+                        activation: event,
+                    });
+                }
+                Err(reason) => {
+                    debug!(
+                        "rejecting ability {} for {:?} from {client_id:?}: {reason:?}",
+                        event.ability, event.caster
+                    );
+                    world.send_event(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: AbilityRejected {
+                            ability: event.ability,
+                            reason,
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Checks the activation and starts the cooldown on success.
+    fn validate(
+        world: &mut World,
+        client_id: ClientId,
+        activation: &ActivateAbility,
+    ) -> Result<(), AbilityError> {
+        let ability = *world
+            .resource::<Abilities>()
+            .get(&activation.ability)
+            .ok_or(AbilityError::UnknownAbility)?;
+
+        let caster = world
+            .get_entity(activation.caster)
+            .ok_or(AbilityError::NotOwner)?;
+        if caster
+            .get::<AbilityOwner>()
+            .is_none_or(|owner| **owner != client_id)
+        {
+            return Err(AbilityError::NotOwner);
+        }
+
+        let tick = **world.resource::<ServerTick>();
+        let cooldowns = caster.get::<Cooldowns>().ok_or(AbilityError::NotOwner)?;
+        if !cooldowns.is_ready(activation.ability, tick) {
+            return Err(AbilityError::OnCooldown);
+        }
+
+        if let Some(range) = ability.range {
+            let caster_position = caster
+                .get::<Transform>()
+                .ok_or(AbilityError::OutOfRange)?
+                .translation;
+            let target_position = match activation.target {
+                AbilityTarget::None => caster_position,
+                AbilityTarget::Entity(entity) => {
+                    world
+                        .get::<Transform>(entity)
+                        .ok_or(AbilityError::OutOfRange)?
+                        .translation
+                }
+                AbilityTarget::Position(position) => position,
+            };
+            if caster_position.distance(target_position) > range {
+                return Err(AbilityError::OutOfRange);
+            }
+        }
+
+        if !(ability.validate)(world, activation) {
+            return Err(AbilityError::Denied);
+        }
+
+        if let Some(mut cooldowns) = world.get_mut::<Cooldowns>(activation.caster) {
+            cooldowns.start(activation.ability, tick + ability.cooldown);
+        }
+
+        Ok(())
+    }
+}
+
+/// Ability registration for [`App`].
+pub trait AbilityAppExt {
+    /// Registers an ability with the specified ID.
+    ///
+    /// Registration is needed only on server, but it's fine to share it with clients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ID is already registered.
+    fn add_ability(&mut self, id: u16, ability: Ability) -> &mut Self;
+}
+
+impl AbilityAppExt for App {
+    fn add_ability(&mut self, id: u16, ability: Ability) -> &mut Self {
+        let mut abilities = self.world.resource_mut::<Abilities>();
+        assert!(
+            abilities.insert(id, ability).is_none(),
+            "ability {id} should be registered only once"
+        );
+
+        self
+    }
+}
+
+/// Validation rules of a registered ability.
+#[derive(Clone, Copy)]
+pub struct Ability {
+    /// Number of ticks before the ability can be activated again.
+    pub cooldown: u32,
+
+    /// Maximum distance between the caster and the target.
+    ///
+    /// Distance is calculated using [`Transform::translation`].
+    /// [`None`] if the range shouldn't be checked.
+    pub range: Option<f32>,
+
+    /// Custom check, such as a resource cost.
+    ///
+    /// Called last, so it's safe to consume resources inside. Returning `false` rejects the activation.
+    pub validate: fn(&mut World, &ActivateAbility) -> bool,
+}
+
+impl Default for Ability {
+    fn default() -> Self {
+        Self {
+            cooldown: 0,
+            range: None,
+            validate: |_, _| true,
+        }
+    }
+}
+
+/// Registered abilities by their IDs.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct Abilities(HashMap<u16, Ability>);
+
+/// Client that is allowed to activate abilities of the entity.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, PartialEq, Eq)]
+pub struct AbilityOwner(pub ClientId);
+
+/// Ticks at which abilities of the entity become ready.
+///
+/// Updated by server on activation.
+#[derive(Component, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Cooldowns(HashMap<u16, RepliconTick>);
+
+impl Cooldowns {
+    /// Returns `true` if the ability can be activated at the tick.
+    pub fn is_ready(&self, ability: u16, tick: RepliconTick) -> bool {
+        self.remaining(ability, tick) == 0
+    }
+
+    /// Returns number of ticks until the ability becomes ready.
+    pub fn remaining(&self, ability: u16, tick: RepliconTick) -> u32 {
+        match self.0.get(&ability) {
+            Some(&ready_tick) if ready_tick > tick => ready_tick - tick,
+            _ => 0,
+        }
+    }
+
+    /// Sets the tick at which the ability becomes ready.
+    pub fn start(&mut self, ability: u16, ready_tick: RepliconTick) {
+        self.0.insert(ability, ready_tick);
+    }
+}
+
+/// A client event to activate an ability.
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize)]
+pub struct ActivateAbility {
+    /// Entity with [`AbilityOwner`] of the sending client.
+    pub caster: Entity,
+
+    /// ID of the registered ability.
+    pub ability: u16,
+
+    /// Target of the ability.
+    pub target: AbilityTarget,
+}
+
+impl MapEntities for ActivateAbility {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.caster = entity_mapper.map_entity(self.caster);
+        if let AbilityTarget::Entity(entity) = &mut self.target {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+/// Target of [`ActivateAbility`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AbilityTarget {
+    /// The ability doesn't need a target.
+    None,
+    /// The ability targets an entity.
+    Entity(Entity),
+    /// The ability targets a point.
+    Position(Vec3),
+}
+
+/// An event that indicates a validated activation on server.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct AbilityActivated {
+    /// Client that activated the ability.
+    pub client_id: ClientId,
+
+    /// Activation with server entities.
+    pub activation: ActivateAbility,
+}
+
+/// A server event sent to the client whose [`ActivateAbility`] was rejected.
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize)]
+pub struct AbilityRejected {
+    /// ID of the ability from the activation.
+    pub ability: u16,
+
+    /// Why the activation was rejected.
+    pub reason: AbilityError,
+}
+
+/// Reason why [`ActivateAbility`] was rejected.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum AbilityError {
+    /// Ability ID is not registered.
+    UnknownAbility,
+    /// Caster doesn't exist, doesn't have [`Cooldowns`] or doesn't belong to the client.
+    NotOwner,
+    /// Ability is not ready yet.
+    OnCooldown,
+    /// Target is too far or doesn't have [`Transform`].
+    OutOfRange,
+    /// Rejected by [`Ability::validate`].
+    Denied,
+}
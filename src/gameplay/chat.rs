@@ -0,0 +1,378 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        common_conditions::{has_authority, server_running},
+        replicon_channels::ChannelKind,
+        ClientId,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        server_event::{SendMode, ServerEventAppExt, ToClients},
+    },
+    server::{connected_clients::ConnectedClients, ServerEvent, ServerSet},
+};
+
+/**
+Text chat with channels, direct messages and server announcements.
+
+Clients send [`ChatMessage`] and receive [`ChatReceived`]. Server validates messages according
+to [`ChatSettings`] and relays them to recipients. The sender also receives its own message
+as a confirmation. Rejected messages are answered with [`ChatRejected`].
+
+Messages sent on server or host are not validated and received by clients as announcements from [`ChatSender::Server`].
+Like other local client events, they are relayed on the next frame.
+
+Could be used as is or as a reference for custom network events.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    gameplay::chat::{ChatMessage, ChatPlugin, ChatReceived, ChatSettings, ChatTarget},
+    prelude::*,
+};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin))
+    .insert_resource(ChatSettings {
+        validate: |_, text| !text.contains("spam"),
+        ..Default::default()
+    })
+    .add_systems(Update, (greet.run_if(server_running), print_messages));
+
+fn greet(mut server_events: EventReader<ServerEvent>, mut chat_events: EventWriter<ChatMessage>) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            chat_events.send(ChatMessage {
+                target: ChatTarget::Direct(client_id),
+                text: "Welcome!".to_string(),
+            });
+        }
+    }
+}
+
+fn print_messages(mut chat_events: EventReader<ChatReceived>) {
+    for event in chat_events.read() {
+        info!("{:?}: {}", event.sender, event.text);
+    }
+}
+```
+**/
+pub struct ChatPlugin;
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChatSettings>()
+            .init_resource::<ChatChannels>()
+            .init_resource::<SentMessages>()
+            .add_client_event::<ChatMessage>(ChannelKind::Ordered)
+            .add_server_event::<ChatReceived>(ChannelKind::Ordered)
+            .add_server_event::<ChatRejected>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::remove_disconnected
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::relay.after(ServerSet::Receive).run_if(has_authority),
+                ),
+            );
+    }
+}
+
+impl ChatPlugin {
+    fn relay(
+        mut message_events: EventReader<FromClient<ChatMessage>>,
+        mut received_events: EventWriter<ToClients<ChatReceived>>,
+        mut rejected_events: EventWriter<ToClients<ChatRejected>>,
+        mut sent_messages: ResMut<SentMessages>,
+        settings: Res<ChatSettings>,
+        channels: Res<ChatChannels>,
+        connected_clients: Option<Res<ConnectedClients>>,
+        time: Res<Time<Real>>,
+    ) {
+        for FromClient { client_id, event } in message_events.read() {
+            let client_id = *client_id;
+            let mut text = event.text.clone();
+            let sender = if client_id == ClientId::SERVER {
+                ChatSender::Server
+            } else {
+                ChatSender::Client(client_id)
+            };
+
+            let recipients = match sender {
+                ChatSender::Server => Ok(()),
+                ChatSender::Client(_) => {
+                    Self::validate(&settings, &mut sent_messages, &time, client_id, &mut text)
+                }
+            }
+            .and_then(|()| {
+                Self::recipients(
+                    &channels,
+                    connected_clients.as_deref(),
+                    sender,
+                    event.target,
+                )
+            });
+
+            match recipients {
+                Ok(recipients) => {
+                    for mode in recipients {
+                        received_events.send(ToClients {
+                            mode,
+                            event: ChatReceived {
+                                sender,
+                                target: event.target,
+                                text: text.clone(),
+                            },
+                        });
+                    }
+                }
+                Err(reason) => {
+                    debug!("rejecting chat message from {client_id:?}: {reason:?}");
+                    rejected_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: ChatRejected { reason },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns send modes for all recipients of a message, including the author.
+    fn recipients(
+        channels: &ChatChannels,
+        connected_clients: Option<&ConnectedClients>,
+        sender: ChatSender,
+        target: ChatTarget,
+    ) -> Result<Vec<SendMode>, ChatError> {
+        match (target, sender) {
+            (ChatTarget::Global, _) => Ok(vec![SendMode::Broadcast]),
+            (ChatTarget::Channel(channel), ChatSender::Client(client_id))
+                if !channels.contains(channel, client_id) =>
+            {
+                Err(ChatError::NotInChannel)
+            }
+            (ChatTarget::Channel(channel), _) => Ok(channels
+                .iter_members(channel)
+                .map(SendMode::Direct)
+                .collect()),
+            (ChatTarget::Direct(recipient), _)
+                if recipient != ClientId::SERVER
+                    && connected_clients
+                        .and_then(|clients| clients.get_client(recipient))
+                        .is_none() =>
+            {
+                Err(ChatError::UnknownRecipient)
+            }
+            (ChatTarget::Direct(recipient), ChatSender::Client(client_id))
+                if recipient != client_id =>
+            {
+                Ok(vec![
+                    SendMode::Direct(recipient),
+                    SendMode::Direct(client_id),
+                ])
+            }
+            (ChatTarget::Direct(recipient), _) => Ok(vec![SendMode::Direct(recipient)]),
+        }
+    }
+
+    /// Applies [`ChatSettings`] to a client message.
+    fn validate(
+        settings: &ChatSettings,
+        sent_messages: &mut SentMessages,
+        time: &Time<Real>,
+        client_id: ClientId,
+        text: &mut String,
+    ) -> Result<(), ChatError> {
+        if text.chars().count() > settings.max_length {
+            return Err(ChatError::TooLong);
+        }
+
+        let now = time.elapsed();
+        let timestamps = sent_messages.entry(client_id).or_default();
+        while timestamps
+            .front()
+            .is_some_and(|&timestamp| now.saturating_sub(timestamp) >= settings.rate_period)
+        {
+            timestamps.pop_front();
+        }
+        if timestamps.len() >= settings.rate_limit {
+            return Err(ChatError::RateLimited);
+        }
+
+        if !(settings.validate)(client_id, text) {
+            return Err(ChatError::Invalid);
+        }
+
+        timestamps.push_back(now);
+
+        Ok(())
+    }
+
+    fn remove_disconnected(
+        mut server_events: EventReader<ServerEvent>,
+        mut channels: ResMut<ChatChannels>,
+        mut sent_messages: ResMut<SentMessages>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+                for members in channels.0.values_mut() {
+                    members.remove(&client_id);
+                }
+                sent_messages.remove(&client_id);
+            }
+        }
+    }
+}
+
+/// Validation rules for [`ChatMessage`] from clients.
+#[derive(Clone, Copy, Resource)]
+pub struct ChatSettings {
+    /// Maximum number of characters in a message.
+    ///
+    /// By default set to 256.
+    pub max_length: usize,
+
+    /// Maximum number of messages from a client per [`Self::rate_period`].
+    ///
+    /// By default set to 5.
+    pub rate_limit: usize,
+
+    /// Period for [`Self::rate_limit`].
+    ///
+    /// By default set to 5 seconds.
+    pub rate_period: Duration,
+
+    /// Custom validation, such as a profanity filter.
+    ///
+    /// Called after other checks. Can modify the text, returning `false` rejects the message.
+    /// By default accepts all messages.
+    pub validate: fn(ClientId, &mut String) -> bool,
+}
+
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            max_length: 256,
+            rate_limit: 5,
+            rate_period: Duration::from_secs(5),
+            validate: |_, _| true,
+        }
+    }
+}
+
+/// Members of chat channels on server.
+///
+/// Clients are automatically removed on disconnect.
+#[derive(Default, Resource)]
+pub struct ChatChannels(HashMap<u16, HashSet<ClientId>>);
+
+impl ChatChannels {
+    /// Adds the client to the channel.
+    pub fn join(&mut self, channel: u16, client_id: ClientId) {
+        self.0.entry(channel).or_default().insert(client_id);
+    }
+
+    /// Removes the client from the channel.
+    pub fn leave(&mut self, channel: u16, client_id: ClientId) {
+        if let Some(members) = self.0.get_mut(&channel) {
+            members.remove(&client_id);
+        }
+    }
+
+    /// Returns `true` if the client is a member of the channel.
+    pub fn contains(&self, channel: u16, client_id: ClientId) -> bool {
+        self.0
+            .get(&channel)
+            .is_some_and(|members| members.contains(&client_id))
+    }
+
+    /// Returns iterator over members of the channel.
+    pub fn iter_members(&self, channel: u16) -> impl Iterator<Item = ClientId> + '_ {
+        self.0.get(&channel).into_iter().flatten().copied()
+    }
+}
+
+/// Timestamps of recent messages from each client for rate limiting.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct SentMessages(HashMap<ClientId, VecDeque<Duration>>);
+
+/// A client event to send a chat message.
+///
+/// Sending it on server makes an announcement.
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct ChatMessage {
+    /// Recipients of the message.
+    pub target: ChatTarget,
+
+    /// Message text.
+    pub text: String,
+}
+
+/// Recipients of [`ChatMessage`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum ChatTarget {
+    /// All clients.
+    Global,
+    /// Members of the channel from [`ChatChannels`].
+    ///
+    /// Clients can send messages only to channels they joined.
+    Channel(u16),
+    /// A single client.
+    Direct(ClientId),
+}
+
+/// A server event with a relayed [`ChatMessage`].
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct ChatReceived {
+    /// Author of the message.
+    pub sender: ChatSender,
+
+    /// Recipients from the original message.
+    pub target: ChatTarget,
+
+    /// Message text after validation.
+    pub text: String,
+}
+
+/// Author of [`ChatReceived`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum ChatSender {
+    /// Announcement from the server.
+    Server,
+    /// Message from a client.
+    Client(ClientId),
+}
+
+/// A server event sent to the author of a rejected [`ChatMessage`].
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize)]
+pub struct ChatRejected {
+    /// Why the message was rejected.
+    pub reason: ChatError,
+}
+
+/// Reason why [`ChatMessage`] was rejected.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum ChatError {
+    /// Client sent too many messages, see [`ChatSettings::rate_limit`].
+    RateLimited,
+    /// Message exceeds [`ChatSettings::max_length`].
+    TooLong,
+    /// Message rejected by [`ChatSettings::validate`].
+    Invalid,
+    /// Client is not a member of the channel.
+    NotInChannel,
+    /// Recipient of a direct message is not connected.
+    UnknownRecipient,
+}
@@ -0,0 +1,343 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        common_conditions::{server_just_stopped, server_running},
+        replication_rules::AppRuleExt,
+        replicon_channels::ChannelKind,
+        ClientId, Replicated,
+    },
+    network_event::client_event::{ClientEventAppExt, FromClient},
+    server::{ServerEvent, ServerSet},
+};
+
+/**
+Assigns connected clients to a limited number of slots with teams and ready states.
+
+On connection server spawns a replicated slot entity with [`LobbySlot`], [`SlotTeam`] and [`SlotReady`]
+for the client. The client is assigned to the first free slot and to the team with
+the fewest members. Clients can change their team and ready state using [`LobbyRequest`].
+Server can change these components directly.
+
+Insert your own replicated components on slot entities for per-slot metadata,
+such as player names or selected characters. The entity is despawned on disconnect.
+
+Server emits [`LobbyEvent`] for slot changes. Clients can query the replicated components.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    gameplay::lobby::{Lobby, LobbyPlugin, LobbySlot, SlotReady},
+    prelude::*,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    LobbyPlugin {
+        max_slots: 4,
+        teams: 2,
+    },
+))
+.add_systems(Update, start_game.run_if(server_running));
+
+fn start_game(lobby: Res<Lobby>, slots: Query<&SlotReady, With<LobbySlot>>) {
+    if lobby.is_full() && slots.iter().all(|ready| **ready) {
+        // Start the game.
+    }
+}
+```
+**/
+pub struct LobbyPlugin {
+    /// Maximum number of clients in the lobby.
+    pub max_slots: usize,
+
+    /// Number of teams.
+    pub teams: u8,
+}
+
+impl Default for LobbyPlugin {
+    fn default() -> Self {
+        Self {
+            max_slots: 8,
+            teams: 2,
+        }
+    }
+}
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        assert_ne!(self.teams, 0, "lobby should have at least one team");
+
+        app.insert_resource(Lobby {
+            slots: vec![None; self.max_slots],
+            teams: self.teams,
+        })
+        .add_event::<LobbyEvent>()
+        .replicate::<LobbySlot>()
+        .replicate::<SlotTeam>()
+        .replicate::<SlotReady>()
+        .add_client_event::<LobbyRequest>(ChannelKind::Ordered)
+        .add_systems(
+            PreUpdate,
+            (
+                (Self::update_slots, Self::apply_requests)
+                    .chain()
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+                Self::reset.run_if(server_just_stopped),
+            ),
+        );
+    }
+}
+
+impl LobbyPlugin {
+    fn update_slots(
+        mut commands: Commands,
+        mut lobby: ResMut<Lobby>,
+        mut server_events: EventReader<ServerEvent>,
+        mut lobby_events: EventWriter<LobbyEvent>,
+        teams: Query<&SlotTeam, With<LobbySlot>>,
+    ) {
+        let mut members = vec![0; lobby.teams.into()];
+        for team in &teams {
+            if let Some(count) = members.get_mut(team.0 as usize) {
+                *count += 1;
+            }
+        }
+
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    let Some(index) = lobby.slots.iter().position(Option::is_none) else {
+                        debug!("no free lobby slots for {client_id:?}");
+                        lobby_events.send(LobbyEvent::Rejected { client_id });
+                        continue;
+                    };
+
+                    let team = smallest_team(&members);
+                    members[team as usize] += 1;
+
+                    let entity = commands
+                        .spawn((
+                            Replicated,
+                            LobbySlot { index, client_id },
+                            SlotTeam(team),
+                            SlotReady::default(),
+                        ))
+                        .id();
+                    lobby.slots[index] = Some((client_id, entity));
+
+                    debug!("assigning {client_id:?} to lobby slot {index}");
+                    lobby_events.send(LobbyEvent::Joined {
+                        client_id,
+                        slot: index,
+                        entity,
+                    });
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    let Some((index, entity)) = lobby.client_slot(client_id) else {
+                        continue;
+                    };
+
+                    if let Some(count) = teams
+                        .get(entity)
+                        .ok()
+                        .and_then(|team| members.get_mut(team.0 as usize))
+                    {
+                        *count -= 1;
+                    }
+
+                    debug!("freeing lobby slot {index} from {client_id:?}");
+                    commands.entity(entity).despawn_recursive();
+                    lobby.slots[index] = None;
+                    lobby_events.send(LobbyEvent::Left {
+                        client_id,
+                        slot: index,
+                    });
+                }
+            }
+        }
+    }
+
+    fn apply_requests(
+        lobby: Res<Lobby>,
+        mut request_events: EventReader<FromClient<LobbyRequest>>,
+        mut lobby_events: EventWriter<LobbyEvent>,
+        mut slots: Query<(&mut SlotTeam, &mut SlotReady)>,
+    ) {
+        for &FromClient { client_id, event } in request_events.read() {
+            let Some((mut team, mut ready)) = lobby
+                .client_slot(client_id)
+                .and_then(|(_, entity)| slots.get_mut(entity).ok())
+            else {
+                debug!("ignoring lobby request from {client_id:?} without a slot");
+                continue;
+            };
+
+            match event {
+                LobbyRequest::Team(requested) => {
+                    if requested >= lobby.teams {
+                        debug!("ignoring invalid team {requested} from {client_id:?}");
+                        continue;
+                    }
+                    if team.0 != requested {
+                        team.0 = requested;
+                        lobby_events.send(LobbyEvent::TeamChanged {
+                            client_id,
+                            team: requested,
+                        });
+                    }
+                }
+                LobbyRequest::Ready(requested) => {
+                    if ready.0 != requested {
+                        ready.0 = requested;
+                        lobby_events.send(LobbyEvent::ReadyChanged {
+                            client_id,
+                            ready: requested,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn reset(mut commands: Commands, mut lobby: ResMut<Lobby>) {
+        for (_, entity) in lobby.slots.iter_mut().filter_map(Option::take) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Slot assignments on server.
+///
+/// See [`LobbyPlugin`] for details.
+#[derive(Resource)]
+pub struct Lobby {
+    /// Clients and their slot entities by slot index.
+    slots: Vec<Option<(ClientId, Entity)>>,
+    teams: u8,
+}
+
+impl Lobby {
+    /// Returns the maximum number of clients.
+    pub fn max_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of teams.
+    pub fn teams(&self) -> u8 {
+        self.teams
+    }
+
+    /// Returns the entity of the slot if it's occupied.
+    pub fn slot_entity(&self, index: usize) -> Option<Entity> {
+        self.slots
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|(_, entity)| entity)
+    }
+
+    /// Returns the index and the entity of the client slot.
+    pub fn client_slot(&self, client_id: ClientId) -> Option<(usize, Entity)> {
+        self.iter_slots()
+            .find(|&(_, slot_client_id, _)| slot_client_id == client_id)
+            .map(|(index, _, entity)| (index, entity))
+    }
+
+    /// Returns iterator over indices, clients and entities of occupied slots.
+    pub fn iter_slots(&self) -> impl Iterator<Item = (usize, ClientId, Entity)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.map(|(client_id, entity)| (index, client_id, entity)))
+    }
+
+    /// Returns `true` if all slots are occupied.
+    pub fn is_full(&self) -> bool {
+        self.slots.iter().all(Option::is_some)
+    }
+}
+
+/// Returns the team with the fewest members.
+fn smallest_team(members: &[usize]) -> u8 {
+    let (team, _) = members
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &count)| count)
+        .expect("lobby should have at least one team");
+
+    team as u8
+}
+
+/// Client slot in the lobby.
+///
+/// Spawned by server on connection, see [`LobbyPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct LobbySlot {
+    index: usize,
+    client_id: ClientId,
+}
+
+impl LobbySlot {
+    /// Returns the slot index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the client that occupies the slot.
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+}
+
+/// Team of a lobby slot.
+#[derive(
+    Component, Clone, Copy, Debug, Default, Deref, DerefMut, Deserialize, PartialEq, Eq, Serialize,
+)]
+pub struct SlotTeam(pub u8);
+
+/// Ready state of a lobby slot.
+#[derive(
+    Component, Clone, Copy, Debug, Default, Deref, DerefMut, Deserialize, PartialEq, Eq, Serialize,
+)]
+pub struct SlotReady(pub bool);
+
+/// A client event to change its own slot.
+///
+/// Invalid requests are ignored.
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize)]
+pub enum LobbyRequest {
+    /// Switch to the team.
+    Team(u8),
+    /// Set the ready state.
+    Ready(bool),
+}
+
+/// An event for lobby slot changes on server.
+#[derive(Clone, Copy, Debug, Event, PartialEq, Eq)]
+pub enum LobbyEvent {
+    /// Client was assigned to a slot.
+    Joined {
+        client_id: ClientId,
+        slot: usize,
+        entity: Entity,
+    },
+    /// Client left the slot.
+    Left { client_id: ClientId, slot: usize },
+    /// Client connected, but all slots were occupied.
+    ///
+    /// The client should probably be disconnected.
+    Rejected { client_id: ClientId },
+    /// Client switched the team using [`LobbyRequest`].
+    TeamChanged { client_id: ClientId, team: u8 },
+    /// Client changed the ready state using [`LobbyRequest`].
+    ReadyChanged { client_id: ClientId, ready: bool },
+}
@@ -0,0 +1,298 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_just_stopped, server_running},
+        replicon_channels::ChannelKind,
+        ClientId,
+    },
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+    server::{ServerEvent, ServerPlugin, ServerSet},
+};
+
+/**
+Replicates per-client statistics as a single table at a low rate.
+
+Scoreboards contain a few values for every client, such as kills, score or ping, that change often
+and are displayed together. Replicating them as components requires an entity per client and sends each
+change separately, so this plugin keeps them in [`Scoreboard`] with a fixed number of columns instead.
+
+Server adds a row on connection and removes it on disconnect. Rows for other players, such as
+the listen server host, can be added with [`Scoreboard::set`]. The whole table is sent to all clients
+at most once per [`Self::interval`] if it changed and to newly connected clients immediately.
+
+The meaning of columns is up to the game, but the column count should be the same on all machines.
+
+Not added by default.
+
+# Examples
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    gameplay::scoreboard::{Scoreboard, ScoreboardPlugin},
+    prelude::*,
+};
+
+const KILLS: usize = 0;
+const SCORE: usize = 1;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    ScoreboardPlugin {
+        columns: 2,
+        interval: Duration::from_secs(1),
+    },
+))
+.add_systems(Update, (count_kills.run_if(server_running), display));
+
+fn count_kills(mut scoreboard: ResMut<Scoreboard>, mut kill_events: EventReader<Kill>) {
+    for kill in kill_events.read() {
+        scoreboard.add(kill.client_id, KILLS, 1);
+        scoreboard.add(kill.client_id, SCORE, 100);
+    }
+}
+
+fn display(scoreboard: Res<Scoreboard>) {
+    if scoreboard.is_changed() {
+        for (client_id, values) in scoreboard.sorted_by(SCORE) {
+            info!("{client_id:?}: {} kills, {} points", values[KILLS], values[SCORE]);
+        }
+    }
+}
+
+#[derive(Event)]
+struct Kill {
+    client_id: ClientId,
+}
+```
+**/
+pub struct ScoreboardPlugin {
+    /// Number of values in each row.
+    pub columns: usize,
+
+    /// Minimum time between table updates.
+    pub interval: Duration,
+}
+
+impl Default for ScoreboardPlugin {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for ScoreboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Scoreboard {
+            columns: self.columns,
+            rows: Default::default(),
+        })
+        .insert_resource(ScoreboardTimer(Timer::new(
+            self.interval,
+            TimerMode::Repeating,
+        )))
+        .add_server_event::<ScoreboardUpdate>(ChannelKind::Ordered)
+        .add_systems(
+            PreUpdate,
+            (
+                Self::update_rows
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+                (Self::reset, Self::reset_timer).run_if(server_just_stopped),
+                Self::reset.in_set(ClientSet::Reset),
+                Self::receive
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            Self::send
+                .after(ServerPlugin::increment_tick)
+                .before(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl ScoreboardPlugin {
+    fn update_rows(
+        mut server_events: EventReader<ServerEvent>,
+        mut scoreboard: ResMut<Scoreboard>,
+    ) {
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    scoreboard.row_mut(client_id);
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    scoreboard.remove(client_id);
+                }
+            }
+        }
+    }
+
+    /// Sends the table to all clients after the interval if it changed and to newly connected clients.
+    fn send(
+        mut changed: Local<bool>,
+        mut timer: ResMut<ScoreboardTimer>,
+        mut server_events: EventReader<ServerEvent>,
+        mut update_events: EventWriter<ToClients<ScoreboardUpdate>>,
+        scoreboard: Res<Scoreboard>,
+        time: Res<Time>,
+    ) {
+        *changed |= scoreboard.is_changed();
+        timer.tick(time.delta());
+        let broadcast = *changed && timer.finished();
+        if broadcast {
+            trace!(
+                "broadcasting scoreboard with {} rows",
+                scoreboard.rows.len()
+            );
+            *changed = false;
+            update_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: ScoreboardUpdate(scoreboard.rows.clone()),
+            });
+        }
+
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                // Already included in the broadcast.
+                if !broadcast {
+                    update_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: ScoreboardUpdate(scoreboard.rows.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    fn receive(
+        mut scoreboard: ResMut<Scoreboard>,
+        mut update_events: EventReader<ScoreboardUpdate>,
+    ) {
+        if let Some(event) = update_events.read().last() {
+            scoreboard.rows.clone_from(&event.0);
+        }
+    }
+
+    fn reset(mut scoreboard: ResMut<Scoreboard>) {
+        scoreboard.rows.clear();
+    }
+
+    fn reset_timer(mut timer: ResMut<ScoreboardTimer>) {
+        timer.reset();
+    }
+}
+
+/// Interval timer for broadcasting [`Scoreboard`].
+#[derive(Deref, DerefMut, Resource)]
+struct ScoreboardTimer(Timer);
+
+/// Per-client statistics with a fixed number of columns.
+///
+/// Should be changed only on server. On clients it contains the last received table.
+/// See [`ScoreboardPlugin`] for details.
+#[derive(Resource)]
+pub struct Scoreboard {
+    columns: usize,
+
+    /// Rows in the order of addition.
+    rows: Vec<(ClientId, Vec<i32>)>,
+}
+
+impl Scoreboard {
+    /// Returns the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns values of a client or [`None`] if it's not in the table.
+    pub fn get(&self, client_id: ClientId) -> Option<&[i32]> {
+        self.rows
+            .iter()
+            .find(|&&(id, _)| id == client_id)
+            .map(|(_, values)| &values[..])
+    }
+
+    /// Sets a value of a client, adding a row if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is out of bounds.
+    pub fn set(&mut self, client_id: ClientId, column: usize, value: i32) {
+        self.row_mut(client_id)[column] = value;
+    }
+
+    /// Adds the delta to a value of a client, adding a row if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is out of bounds.
+    pub fn add(&mut self, client_id: ClientId, column: usize, delta: i32) {
+        let value = &mut self.row_mut(client_id)[column];
+        *value = value.saturating_add(delta);
+    }
+
+    /// Removes the row of a client.
+    pub fn remove(&mut self, client_id: ClientId) {
+        self.rows.retain(|&(id, _)| id != client_id);
+    }
+
+    /// Returns an iterator over clients and their values in the order of addition.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &[i32])> {
+        self.rows
+            .iter()
+            .map(|(client_id, values)| (*client_id, &values[..]))
+    }
+
+    /// Returns clients and their values sorted by the column in descending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is out of bounds.
+    pub fn sorted_by(&self, column: usize) -> Vec<(ClientId, &[i32])> {
+        let mut rows: Vec<_> = self.iter().collect();
+        rows.sort_by(|(_, a), (_, b)| b[column].cmp(&a[column]));
+        rows
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn row_mut(&mut self, client_id: ClientId) -> &mut Vec<i32> {
+        let index = match self.rows.iter().position(|&(id, _)| id == client_id) {
+            Some(index) => index,
+            None => {
+                self.rows.push((client_id, vec![0; self.columns]));
+                self.rows.len() - 1
+            }
+        };
+
+        &mut self.rows[index].1
+    }
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct ScoreboardUpdate(Vec<(ClientId, Vec<i32>)>);
@@ -0,0 +1,311 @@
+use std::io::{Cursor, Read, Write};
+
+use bevy::prelude::*;
+use varint_rs::{VarintReader, VarintWriter};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::client_connected,
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::{CompactTag, DeserializeFn, RuleFns},
+        },
+        replication_rules::{AppHelperExt, AppRuleExt},
+        replicon_tick::RepliconTick,
+    },
+};
+
+/// State machine registration for [`App`].
+pub trait AppStateMachineExt {
+    /**
+    Replicates [`StateMachine<S>`] in a compact form and emits [`MachineTransition<S>`] on client.
+
+    Each state of the path is written as a single byte using [`CompactTag`] and each entered tick
+    as a varint difference from the message tick, so timers usually take a byte per state.
+
+    Transitions are detected from applied updates. If multiple updates are applied in one frame,
+    a single event from the path before the first update to the path after the last is emitted.
+    Re-entering the same state is also emitted since its entered tick changes.
+
+    Should be called on both client and server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        compact_tag,
+        gameplay::state_machine::{AppStateMachineExt, MachineTransition, StateMachine},
+        prelude::*,
+        server::server_tick::ServerTick,
+    };
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_state_machine::<EnemyState>()
+        .add_systems(Update, (
+            start_attack.run_if(server_running),
+            play_animations.run_if(client_connected),
+        ));
+
+    fn start_attack(server_tick: Res<ServerTick>, mut machines: Query<&mut StateMachine<EnemyState>>) {
+        for mut machine in &mut machines {
+            if machine.current() == &EnemyState::Chase {
+                machine.transition(1, EnemyState::Attack, **server_tick);
+                machine.push(EnemyState::Windup, **server_tick);
+            }
+        }
+    }
+
+    fn play_animations(mut transitions: EventReader<MachineTransition<EnemyState>>) {
+        for transition in transitions.read() {
+            info!("{:?} went from {:?} to {:?}", transition.entity, transition.from, transition.to);
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum EnemyState {
+        Combat,
+        Chase,
+        Attack,
+        Windup,
+    }
+
+    compact_tag!(EnemyState { Combat, Chase, Attack, Windup });
+    ```
+    **/
+    fn replicate_state_machine<S: MachineState>(&mut self) -> &mut Self;
+}
+
+impl AppStateMachineExt for App {
+    fn replicate_state_machine<S: MachineState>(&mut self) -> &mut Self {
+        self.add_event::<MachineTransition<S>>().replicate_with(
+            RuleFns::new(serialize_machine::<S>, deserialize_machine::<S>)
+                .with_in_place(deserialize_machine_in_place::<S>),
+        );
+
+        if self.register_helper::<StateMachine<S>>() {
+            self.add_systems(
+                PreUpdate,
+                emit_transitions::<S>
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            );
+        }
+
+        self
+    }
+}
+
+/// Emits [`MachineTransition`] for machines with paths changed by received updates.
+fn emit_transitions<S: MachineState>(
+    mut transitions: EventWriter<MachineTransition<S>>,
+    mut machines: Query<(Entity, &mut StateMachine<S>), Changed<StateMachine<S>>>,
+) {
+    for (entity, mut machine) in &mut machines {
+        let machine = machine.bypass_change_detection();
+        let Some(previous) = machine.previous.take() else {
+            continue;
+        };
+
+        if previous != machine.path {
+            transitions.send(MachineTransition {
+                entity,
+                from: previous.into_iter().map(|(state, _)| state).collect(),
+                to: machine.states().cloned().collect(),
+            });
+        }
+    }
+}
+
+/// Bounds for states of [`StateMachine`].
+pub trait MachineState: CompactTag + Clone + PartialEq + Send + Sync + 'static {}
+
+impl<S: CompactTag + Clone + PartialEq + Send + Sync + 'static> MachineState for S {}
+
+/// Current state path of a hierarchical state machine.
+///
+/// The path goes from the root state to the current leaf state. Each state stores the tick
+/// at which it was entered, so time spent in a state can be calculated on both server and clients.
+/// Use [`ServerTick`](crate::server::server_tick::ServerTick) on server for transitions.
+///
+/// See also [`AppStateMachineExt::replicate_state_machine`].
+#[derive(Component, Clone, Debug)]
+pub struct StateMachine<S> {
+    /// States from root to leaf with their entered ticks.
+    path: Vec<(S, RepliconTick)>,
+
+    /// Path before received updates that weren't processed into [`MachineTransition`] yet.
+    previous: Option<Vec<(S, RepliconTick)>>,
+}
+
+impl<S: MachineState> StateMachine<S> {
+    /// Creates a new instance with only the root state.
+    pub fn new(root: S, tick: RepliconTick) -> Self {
+        Self {
+            path: vec![(root, tick)],
+            previous: None,
+        }
+    }
+
+    /// Returns the current leaf state.
+    pub fn current(&self) -> &S {
+        let (state, _) = self.path.last().expect("path should contain the root");
+        state
+    }
+
+    /// Returns states from root to leaf.
+    pub fn states(&self) -> impl ExactSizeIterator<Item = &S> {
+        self.path.iter().map(|(state, _)| state)
+    }
+
+    /// Returns a state at the specified depth, where `0` is the root.
+    pub fn state(&self, depth: usize) -> Option<&S> {
+        self.path.get(depth).map(|(state, _)| state)
+    }
+
+    /// Returns the number of states in the path.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns `true` if the state is present at any depth of the path.
+    pub fn is_in(&self, state: &S) -> bool {
+        self.states().any(|path_state| path_state == state)
+    }
+
+    /// Replaces the state at the specified depth and removes all its children.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is greater than [`Self::depth`].
+    pub fn transition(&mut self, depth: usize, state: S, tick: RepliconTick) {
+        assert!(
+            depth <= self.path.len(),
+            "transition depth {depth} should be within the path of depth {}",
+            self.path.len()
+        );
+        self.path.truncate(depth);
+        self.path.push((state, tick));
+    }
+
+    /// Enters a child state of the current leaf.
+    pub fn push(&mut self, state: S, tick: RepliconTick) {
+        self.path.push((state, tick));
+    }
+
+    /// Leaves the current leaf state and returns it.
+    ///
+    /// Returns [`None`] if only the root is left, since it can be only replaced with [`Self::transition`].
+    pub fn pop(&mut self) -> Option<S> {
+        if self.path.len() > 1 {
+            self.path.pop().map(|(state, _)| state)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tick at which a state at the specified depth was entered.
+    pub fn entered_tick(&self, depth: usize) -> Option<RepliconTick> {
+        self.path.get(depth).map(|&(_, tick)| tick)
+    }
+
+    /// Returns the number of ticks elapsed since a state at the specified depth was entered.
+    pub fn elapsed_ticks(&self, depth: usize, tick: RepliconTick) -> Option<u32> {
+        self.entered_tick(depth)
+            .map(|entered_tick| tick - entered_tick)
+    }
+}
+
+/// An event for a [`StateMachine`] path change applied on client.
+///
+/// See [`AppStateMachineExt::replicate_state_machine`] for details.
+#[derive(Event, Clone, Debug)]
+pub struct MachineTransition<S> {
+    pub entity: Entity,
+
+    /// Path before the transition.
+    ///
+    /// Empty if the machine was just inserted.
+    pub from: Vec<S>,
+
+    /// Path after the transition.
+    pub to: Vec<S>,
+}
+
+/// Writes the path length and each state as a tag with its entered tick relative to the message tick.
+fn serialize_machine<S: MachineState>(
+    ctx: &SerializeCtx,
+    machine: &StateMachine<S>,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let depth: u16 = machine.path.len().try_into().map_err(|_| {
+        bincode::ErrorKind::Custom(format!(
+            "state machine depth {} exceeds the maximum of {}",
+            machine.path.len(),
+            u16::MAX
+        ))
+    })?;
+
+    cursor.write_u16_varint(depth)?;
+    for (state, entered_tick) in &machine.path {
+        cursor.write_all(&[state.to_tag()])?;
+        cursor.write_u32_varint(ctx.server_tick - *entered_tick)?;
+    }
+
+    Ok(())
+}
+
+/// Reads a machine written by [`serialize_machine`].
+fn deserialize_machine<S: MachineState>(
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<StateMachine<S>> {
+    let depth = cursor.read_u16_varint()?;
+    if depth == 0 {
+        return Err(bincode::ErrorKind::Custom(
+            "state machine path should contain at least the root".into(),
+        )
+        .into());
+    }
+
+    let mut path = Vec::with_capacity(depth.into());
+    for _ in 0..depth {
+        let mut tag = [0];
+        cursor.read_exact(&mut tag)?;
+        let state = S::from_tag(tag[0]).ok_or_else(|| {
+            bincode::ErrorKind::Custom(format!(
+                "received invalid tag {} for {}",
+                tag[0],
+                std::any::type_name::<S>()
+            ))
+        })?;
+        let elapsed = cursor.read_u32_varint()?;
+        path.push((state, ctx.message_tick - elapsed));
+    }
+
+    Ok(StateMachine {
+        path,
+        previous: Some(Vec::new()),
+    })
+}
+
+/// Replaces the machine and keeps the path before the first unprocessed update to emit [`MachineTransition`].
+fn deserialize_machine_in_place<S: MachineState>(
+    deserialize: &DeserializeFn<StateMachine<S>>,
+    ctx: &mut WriteCtx,
+    machine: &mut StateMachine<S>,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let mut new_machine = (deserialize)(ctx, cursor)?;
+    new_machine.previous = Some(
+        machine
+            .previous
+            .take()
+            .unwrap_or_else(|| machine.path.clone()),
+    );
+    *machine = new_machine;
+
+    Ok(())
+}
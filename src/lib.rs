@@ -41,6 +41,9 @@ app.add_plugins((MinimalPlugins, RepliconPlugins, MyMessagingPlugins));
 # }
 ```
 
+Additional plugins that register their own replication rules, network events or channels should be added
+after [`RepliconPlugins`] on both client and server in the same order.
+
 If you are planning to separate client and server you can use
 [`PluginGroupBuilder::disable()`] to disable [`ClientPlugin`] or [`ServerPlugin`] on [`RepliconPlugins`].
 You will need to disable similar plugins on your messaing library of choice too.
@@ -143,13 +146,14 @@ mapping. Therefore, to replicate such components properly, they need to implemen
 the [`MapEntities`](bevy::ecs::entity::MapEntities) trait and register
 using [`AppRuleExt::replicate_mapped()`].
 
-By default all components are serialized with [`bincode`] using [`DefaultOptions`](bincode::DefaultOptions).
+By default all components are serialized with [`bincode`] using [`wire_format::varint`](core::wire_format::varint).
 If your component doesn't implement serde traits or you want to serialize it partially
 (for example, only replicate the `translation` field from [`Transform`]),
 you can use [`AppRuleExt::replicate_with`].
 
 If you want a group of components to be replicated only if all of them are present on an entity,
-you can use [`AppRuleExt::replicate_group`].
+you can use [`AppRuleExt::replicate_group`]. Changes to its components are still sent independently,
+so if they should always arrive and be applied together, use [`AppRuleExt::replicate_atomic_group`].
 
 If you want to customize how the received component will be written or removed on clients based
 on some marker component (for example, write into a different component), see [`AppMarkerExt`].
@@ -222,6 +226,8 @@ struct Player;
 This pairs nicely with server state serialization and keeps saves clean.
 You can use [`replicate_into`](scene::replicate_into) to
 fill [`DynamicScene`] with replicated entities and their components.
+To replicate entities of spawned scenes and propagate scene hot reloads to clients
+(useful for live level editing), add [`SceneReplicationPlugin`](scene::SceneReplicationPlugin).
 
 **Performance note**: We used [`With<Player>`] and [`Without<GlobalTransform>`] to
 filter all non-initialized entities. It's possible to use [`Added`] / [`Changed`] too,
@@ -238,7 +244,8 @@ not only need to add it to the [`Children`] of the parent, but also remove it
 from the [`Children`] of the old one. In this case, you need to create a third
 component that correctly updates the other two when it changes, and only
 replicate that one. This crate provides [`ParentSync`] component that replicates
-Bevy hierarchy. For your custom components with relations you need to write your
+Bevy hierarchy and [`TransformSync`] component that replicates transforms relative to it
+or in world space. For your custom components with relations you need to write your
 own with a similar pattern.
 
 ## Network events
@@ -363,6 +370,25 @@ Just like with client events, if the event contains an entity, then
 For events that require special sending and receiving functions you can use
 [`ServerEventAppExt::add_server_event_with()`].
 
+### Bulk transfers
+
+For large occasional payloads, like a message of the day or a rule set, you can add
+[`BulkTransferPlugin`]. Use [`BulkTransfers`] on server to send a payload. It will be split into chunks
+and trickled to clients with a limited number of bytes per tick, so it won't compete with replication.
+Clients receive [`BulkProgress`] after each chunk and [`BulkReceived`] once the whole payload arrives.
+
+## Gameplay
+
+If the `gameplay` feature is enabled, the `gameplay` module provides ready-made game-level plugins
+built on top of replication and network events. They aren't included in the prelude.
+
+- `gameplay::chat::ChatPlugin` relays chat messages from clients to channels, direct recipients
+  or everyone with validation and rate limiting.
+- `gameplay::lobby::LobbyPlugin` assigns connected clients to replicated slots with teams and ready states.
+- `gameplay::scoreboard::ScoreboardPlugin` replicates per-client statistics as a single table.
+- `gameplay::ability::AbilityPlugin` validates ability activations from clients and tracks cooldowns.
+- `gameplay::state_machine::AppStateMachineExt` replicates hierarchical state machines.
+
 ## Client visibility
 
 You can control which parts of the world are visible for each client by setting visibility policy
@@ -410,6 +436,7 @@ fn update_visibility(
 struct Player(ClientId);
 ```
 
+For tag-based visibility see [`InterestTagsPlugin`].
 For a higher level API consider using [`bevy_replicon_attributes`](https://docs.rs/bevy_replicon_attributes).
 
 ## Eventual consistency
@@ -436,15 +463,27 @@ To reduce packet size there are the following limits per replication update:
 - Up to [`u16::MAX`] entities that have changed components with up to [`u16::MAX`] bytes of component data.
 - Up to [`u16::MAX`] entities that have removed components with up to [`u16::MAX`] bytes of component data.
 - Up to [`u16::MAX`] entities that were despawned.
+
+Changed components of a single entity that don't fit into a packet are split into chunks,
+sent over [`ReplicationChannel::UpdateChunks`](core::replicon_channels::ReplicationChannel::UpdateChunks)
+and reassembled on the client before deserialization.
 */
 
 pub mod client;
 pub mod core;
+pub mod debug_name;
+#[cfg(feature = "gameplay")]
+pub mod gameplay;
 pub mod network_event;
+pub mod packed_flags;
 pub mod parent_sync;
+pub mod protocol_negotiation;
+pub mod protocol_reload;
 pub mod scene;
 pub mod server;
+pub mod service_client;
 pub mod test_app;
+pub mod transform_sync;
 
 pub mod prelude {
     #[allow(deprecated)]
@@ -452,30 +491,120 @@ pub mod prelude {
 
     pub use super::{
         client::{
-            diagnostics::{ClientDiagnosticsPlugin, ClientStats},
+            async_connection::{
+                AsyncConnection, AsyncConnectionPlugin, ConnectError, ConnectionEvent,
+            },
+            despawn_grace::{DespawnGrace, DespawnGracePlugin, Despawned},
+            diagnostics::{
+                ClientDiagnosticsPlugin, ClientLatency, ClientStats, DropRateExceeded,
+                DropRateThreshold,
+            },
+            entity_allocator::{AppEntityAllocatorExt, EntityAllocator},
+            fuzz_corpus::FuzzCorpusPlugin,
+            interpolation_delay::{
+                FastForwarded, InterpolationDelay, InterpolationDelayMode,
+                InterpolationDelayPlugin, SpikeRecovery,
+            },
+            prediction::{
+                AppPredictionExt, Mispredicted, Predicted, PredictionHistory, PredictionPlugin,
+                PredictionSchedule, PredictionTick,
+            },
+            replication_ops::{ReplicationOp, ReplicationOpKind, ReplicationOpsPlugin},
             replicon_client::{RepliconClient, RepliconClientStatus},
-            ClientPlugin, ClientSet,
+            rollback_scope::{InRollbackScope, RollbackAnchor, RollbackScope, RollbackScopePlugin},
+            snapshot_interpolation::{
+                extrapolate_linear, AppInterpolationExt, ExtrapolateFn, Interpolate, Interpolated,
+                SnapshotBuffer, SnapshotInterpolation, SnapshotInterpolationPlugin,
+            },
+            ClientPlugin, ClientSet, ClientSettings,
         },
         core::{
             command_markers::AppMarkerExt,
             common_conditions::*,
-            replication_rules::AppRuleExt,
+            component_encryption::{
+                AppEncryptionExt, ClientEncryptionKey, ComponentEncryptionPlugin,
+                ServerEncryptionKeys,
+            },
+            delta_compression::{AppDeltaExt, FieldDelta},
+            message_signing::{
+                client_secret, ClientSigningKey, ClientSigningSecret, MessageSigningPlugin,
+                ServerSigningKeys, ServerSigningSecret,
+            },
+            network_timer::NetworkTimer,
+            replication_rules::{AppRuleExt, Replicate},
+            replication_stages::AppStageExt,
             replicon_channels::{ChannelKind, RepliconChannel, RepliconChannels},
+            server_health::{ServerHealth, ServerHealthPlugin},
+            shared_random::{SharedRandomPlugin, SharedRng, SharedSeed},
+            singleton::{AppSingletonExt, Singleton},
+            time_scale::{ServerTimeScale, TimeScalePlugin},
             ClientId, Replicated, RepliconCorePlugin,
         },
+        debug_name::{AppDebugNameExt, DebugNamePlugin, ReplicatedDebugName},
         network_event::{
+            bulk_transfer::{BulkProgress, BulkReceived, BulkTransferPlugin, BulkTransfers},
             client_event::{ClientEventAppExt, FromClient},
+            disconnect_reason::{AppDisconnectExt, DisconnectReason},
+            event_budget::{AppEventBudgetExt, EventSaturated, OverflowPolicy, ServerEventBudget},
+            live_tweak::{
+                LiveTweakPlugin, TweakError, TweakPermissions, TweakRequest, TweakResponse,
+            },
             server_event::{SendMode, ServerEventAppExt, ToClients},
         },
+        packed_flags::{AppFlagExt, PackedFlags, PackedFlagsPlugin},
         parent_sync::{ParentSync, ParentSyncPlugin},
+        protocol_negotiation::{
+            ClientRegistrations, MismatchPolicy, ProtocolDowngraded, ProtocolMismatch,
+            ProtocolNegotiationPlugin,
+        },
+        protocol_reload::{AppProtocolReloadExt, ProtocolReloadPlugin, ProtocolReloaded},
         server::{
+            accumulation::AppAccumulationExt,
+            bandwidth_simulation::{BandwidthSimulationPlugin, SimulatedBandwidth},
             client_entity_map::{ClientEntityMap, ClientMapping},
+            component_history::{AppHistoryExt, ComponentHistory},
+            concealed_components::{AppConcealExt, ConcealedComponents},
             connected_clients::{
-                client_visibility::ClientVisibility, ConnectedClient, ConnectedClients,
+                client_visibility::ClientVisibility, ClientPrivilege, ConnectedClient,
+                ConnectedClients,
+            },
+            connection_filter::{ConnectionDenied, ConnectionFilter, ConnectionFilterPlugin},
+            diagnostics::{ComponentSizeStats, ServerDiagnosticsPlugin},
+            hibernation::{
+                Hibernated, Hibernation, HibernationPlugin, HibernationRegion, RegionDigests,
+            },
+            ignored::{Ignored, IgnoredCommandsExt},
+            interest_radius::{InterestAnchor, InterestRadius, InterestRadiusPlugin},
+            interest_tags::{InterestSubscriptions, InterestTags, InterestTagsPlugin},
+            lag_compensation::{
+                HitCollider, LagCompensation, LagCompensationPlugin, LaunchProjectile, Projectile,
+                ProjectileHit,
             },
+            match_host::{MatchHost, MatchHostPlugin, MatchId, MatchMoved},
+            pending_from_client::{AppPendingExt, PendingFromClient},
+            replication_conditions::{AppConditionExt, ReplicationConditions},
+            replication_heatmap::{HeatmapSample, ReplicationHeatmap, ReplicationHeatmapPlugin},
             replicon_server::RepliconServer,
-            ServerEvent, ServerPlugin, ServerSet, TickPolicy, VisibilityPolicy,
+            save_game::AppSaveExt,
+            send_log::{SendLog, SendLogPlugin, SentComponent},
+            send_rate::AppSendRateExt,
+            server_commands::ServerCommands,
+            snapshots::{ServerSnapshots, ServerSnapshotsPlugin, SnapshotCommand, SnapshotInfo},
+            stale_priority::{AppPriorityExt, StalePriority, StalePriorityPlugin},
+            tick_source::{AppTickSourceExt, TickSource},
+            value_comparison::{AppComparisonExt, ApproxEq},
+            warm_standby::{WarmStandby, WarmStandbyPlugin},
+            world_digest::{
+                AppDigestExt, DigestCell, DigestPlane, DigestTeam, WorldDigest, WorldDigestPlugin,
+            },
+            ServerEvent, ServerPlugin, ServerSet, ServerSettings, StallPolicy, TickJumped,
+            TickPolicy, VisibilityPolicy,
+        },
+        service_client::{
+            ClientSummary, EntityOwner, ServiceApproved, ServiceClientPlugin, ServiceClients,
+            ServiceDenied, ServiceStats, ServiceToken,
         },
+        transform_sync::{TransformSync, TransformSyncPlugin, WorldSpaceTransform},
         RepliconPlugins,
     };
 }
@@ -492,8 +621,8 @@ impl PluginGroup for RepliconPlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(RepliconCorePlugin)
-            .add(ParentSyncPlugin)
-            .add(ClientPlugin)
+            .add(ParentSyncPlugin::default())
+            .add(ClientPlugin::default())
             .add(ServerPlugin::default())
     }
 }
@@ -1,4 +1,8 @@
+pub mod bulk_transfer;
 pub mod client_event;
+pub mod disconnect_reason;
+pub mod event_budget;
+pub mod live_tweak;
 pub mod server_event;
 
 use bevy::{ecs::entity::EntityHashMap, prelude::*};
@@ -0,0 +1,449 @@
+use std::{collections::VecDeque, io::Cursor};
+
+use bevy::{prelude::*, utils::HashMap};
+use bincode::Options;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use super::server_event::SendMode;
+use crate::{
+    client::{replicon_client::RepliconClient, ClientPlugin, ClientSet},
+    core::{
+        common_conditions::{client_connected, has_authority, server_just_stopped, server_running},
+        replicon_channels::{ChannelKind, RepliconChannels},
+        wire_format, ClientId,
+    },
+    server::{
+        connected_clients::ConnectedClients, replicon_server::RepliconServer,
+        server_tick::ServerTick, ServerEvent, ServerPlugin, ServerSet,
+    },
+};
+
+/**
+Low-priority channel for large occasional payloads.
+
+Payloads like a message of the day, rule sets or small files are split into chunks
+and trickled to clients with at most [`Self::max_bytes_per_tick`] bytes per client
+on each server tick. This way they don't compete with replication for bandwidth.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, BulkTransferPlugin::default()))
+    .add_systems(Update, (send_motd.run_if(server_running), read_motd));
+
+fn send_motd(mut bulk_transfers: ResMut<BulkTransfers>, mut server_events: EventReader<ServerEvent>) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            bulk_transfers.send(SendMode::Direct(client_id), "motd", "Welcome!");
+        }
+    }
+}
+
+fn read_motd(mut received_events: EventReader<BulkReceived>) {
+    for event in received_events.read() {
+        info!("received `{}` with {} bytes", event.name, event.data.len());
+    }
+}
+```
+**/
+pub struct BulkTransferPlugin {
+    /// Maximum number of payload bytes sent to each client per server tick.
+    ///
+    /// By default set to `4096`.
+    pub max_bytes_per_tick: usize,
+
+    /// Maximum size of a single chunk in bytes.
+    ///
+    /// By default set to `1024`.
+    pub chunk_size: usize,
+}
+
+impl Default for BulkTransferPlugin {
+    fn default() -> Self {
+        Self {
+            max_bytes_per_tick: 4096,
+            chunk_size: 1024,
+        }
+    }
+}
+
+impl Plugin for BulkTransferPlugin {
+    fn build(&self, app: &mut App) {
+        debug_assert!(
+            self.chunk_size > 0 && self.chunk_size <= self.max_bytes_per_tick,
+            "chunk size should be non-zero and fit into the per tick budget"
+        );
+
+        let channel_id = app
+            .world
+            .resource_mut::<RepliconChannels>()
+            .create_server_channel(ChannelKind::Ordered.into());
+
+        app.insert_resource(BulkTransfers::new(
+            channel_id,
+            self.max_bytes_per_tick,
+            self.chunk_size,
+        ))
+        .init_resource::<BulkBuffers>()
+        .add_event::<BulkProgress>()
+        .add_event::<BulkReceived>()
+        .add_systems(
+            PreUpdate,
+            (
+                Self::reset.in_set(ClientSet::Reset),
+                Self::receive
+                    .after(ClientPlugin::receive_replication)
+                    .in_set(ClientSet::Receive)
+                    .run_if(client_connected),
+                Self::remove_disconnected
+                    .in_set(ServerSet::Receive)
+                    .run_if(server_running),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                Self::start_transfers.run_if(server_running),
+                Self::resend_locally.run_if(has_authority),
+                Self::send_chunks
+                    .run_if(server_running)
+                    .run_if(resource_changed::<ServerTick>),
+            )
+                .chain()
+                .after(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send),
+        )
+        .add_systems(PostUpdate, Self::cancel_all.run_if(server_just_stopped));
+    }
+}
+
+impl BulkTransferPlugin {
+    /// Starts queued transfers for connected clients.
+    fn start_transfers(
+        mut bulk_transfers: ResMut<BulkTransfers>,
+        connected_clients: Res<ConnectedClients>,
+    ) {
+        let bulk_transfers = &mut *bulk_transfers;
+        for pending in &bulk_transfers.pending {
+            let clients: Vec<_> = connected_clients
+                .iter_client_ids()
+                .filter(|&client_id| pending.mode.includes(client_id))
+                .collect();
+
+            if clients.is_empty() {
+                trace!(
+                    "skipping bulk transfer {:?} without remote recipients",
+                    pending.id
+                );
+                continue;
+            }
+
+            debug!(
+                "starting bulk transfer {:?} with {} bytes",
+                pending.id,
+                pending.data.len()
+            );
+            bulk_transfers.active.push_back(ActiveTransfer {
+                id: pending.id,
+                name: pending.name.clone(),
+                data: pending.data.clone(),
+                clients,
+                offset: 0,
+            });
+        }
+    }
+
+    /// Sends next chunks of started transfers according to the budget.
+    fn send_chunks(mut server: ResMut<RepliconServer>, mut bulk_transfers: ResMut<BulkTransfers>) {
+        let bulk_transfers = &mut *bulk_transfers;
+        let mut budget = bulk_transfers.max_bytes_per_tick;
+        while budget > 0 {
+            let Some(transfer) = bulk_transfers.active.front_mut() else {
+                break;
+            };
+
+            let chunk_len = (transfer.data.len() - transfer.offset)
+                .min(bulk_transfers.chunk_size)
+                .min(budget);
+            let chunk = transfer.chunk(chunk_len);
+            for &client_id in &transfer.clients {
+                server.send(client_id, bulk_transfers.channel_id, chunk.clone());
+            }
+
+            transfer.offset += chunk_len;
+            budget -= chunk_len;
+            if transfer.offset == transfer.data.len() {
+                trace!("finished sending bulk transfer {:?}", transfer.id);
+                bulk_transfers.active.pop_front();
+            }
+        }
+    }
+
+    /// Emits [`BulkReceived`] for transfers that include [`ClientId::SERVER`]
+    /// to "emulate" receiving for offline mode or when server is also a player.
+    fn resend_locally(
+        mut bulk_transfers: ResMut<BulkTransfers>,
+        mut received_events: EventWriter<BulkReceived>,
+    ) {
+        for pending in bulk_transfers.pending.drain(..) {
+            if pending.mode.includes(ClientId::SERVER) {
+                received_events.send(BulkReceived {
+                    id: pending.id,
+                    name: pending.name,
+                    data: pending.data,
+                });
+            }
+        }
+    }
+
+    fn remove_disconnected(
+        mut server_events: EventReader<ServerEvent>,
+        mut bulk_transfers: ResMut<BulkTransfers>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+                for transfer in &mut bulk_transfers.active {
+                    transfer.clients.retain(|&id| id != client_id);
+                }
+                bulk_transfers
+                    .active
+                    .retain(|transfer| !transfer.clients.is_empty());
+            }
+        }
+    }
+
+    fn cancel_all(mut bulk_transfers: ResMut<BulkTransfers>) {
+        bulk_transfers.active.clear();
+    }
+
+    fn receive(
+        mut client: ResMut<RepliconClient>,
+        mut buffers: ResMut<BulkBuffers>,
+        mut progress_events: EventWriter<BulkProgress>,
+        mut received_events: EventWriter<BulkReceived>,
+        bulk_transfers: Res<BulkTransfers>,
+    ) {
+        for message in client.receive(bulk_transfers.channel_id) {
+            if let Err(e) = buffers.read_chunk(&message, &mut progress_events, &mut received_events)
+            {
+                error!("unable to read bulk chunk: {e}");
+            }
+        }
+    }
+
+    fn reset(mut buffers: ResMut<BulkBuffers>) {
+        buffers.0.clear();
+    }
+}
+
+/// Queue of bulk payloads to send.
+///
+/// Used only on the server.
+#[derive(Resource)]
+pub struct BulkTransfers {
+    channel_id: u8,
+    max_bytes_per_tick: usize,
+    chunk_size: usize,
+    next_id: BulkId,
+    pending: Vec<PendingTransfer>,
+    active: VecDeque<ActiveTransfer>,
+}
+
+impl BulkTransfers {
+    fn new(channel_id: u8, max_bytes_per_tick: usize, chunk_size: usize) -> Self {
+        Self {
+            channel_id,
+            max_bytes_per_tick,
+            chunk_size,
+            next_id: Default::default(),
+            pending: Default::default(),
+            active: Default::default(),
+        }
+    }
+
+    /// Queues a payload for sending and returns its ID.
+    ///
+    /// Recipients are resolved on the next server tick.
+    /// Transfers are sent one after another in the order they were queued.
+    pub fn send(
+        &mut self,
+        mode: SendMode,
+        name: impl Into<String>,
+        data: impl Into<Bytes>,
+    ) -> BulkId {
+        let id = self.next_id;
+        self.next_id.0 = self.next_id.0.wrapping_add(1);
+        self.pending.push(PendingTransfer {
+            id,
+            mode,
+            name: name.into(),
+            data: data.into(),
+        });
+
+        id
+    }
+
+    /// Returns the maximum number of bytes sent to each client per tick.
+    pub fn max_bytes_per_tick(&self) -> usize {
+        self.max_bytes_per_tick
+    }
+
+    /// Changes the maximum number of bytes sent to each client per tick.
+    ///
+    /// Applied starting from the next tick, allows tuning the budget on a live server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is less than [`BulkTransferPlugin::chunk_size`].
+    pub fn set_max_bytes_per_tick(&mut self, max_bytes_per_tick: usize) {
+        assert!(
+            max_bytes_per_tick >= self.chunk_size,
+            "budget should fit at least a single chunk"
+        );
+        self.max_bytes_per_tick = max_bytes_per_tick;
+    }
+
+    /// Returns the number of transfers that are queued or not fully sent.
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.active.len()
+    }
+
+    /// Returns `true` if there are no queued or unfinished transfers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+struct PendingTransfer {
+    id: BulkId,
+    mode: SendMode,
+    name: String,
+    data: Bytes,
+}
+
+struct ActiveTransfer {
+    id: BulkId,
+    name: String,
+    data: Bytes,
+    clients: Vec<ClientId>,
+    offset: usize,
+}
+
+impl ActiveTransfer {
+    /// Creates a message with the header and the next `len` bytes of the payload.
+    ///
+    /// The first chunk additionally includes the payload name.
+    fn chunk(&self, len: usize) -> Bytes {
+        let header = ChunkHeader {
+            id: self.id,
+            total: self.data.len() as u32,
+            offset: self.offset as u32,
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        wire_format::varint()
+            .serialize_into(&mut cursor, &header)
+            .expect("bulk header should be serializable");
+        if self.offset == 0 {
+            wire_format::varint()
+                .serialize_into(&mut cursor, &self.name)
+                .expect("bulk name should be serializable");
+        }
+
+        let mut message = cursor.into_inner();
+        message.extend_from_slice(&self.data[self.offset..self.offset + len]);
+        message.into()
+    }
+}
+
+/// Header of each chunk message.
+#[derive(Deserialize, Serialize)]
+struct ChunkHeader {
+    id: BulkId,
+    total: u32,
+    offset: u32,
+}
+
+/// Partially received payloads on client.
+#[derive(Default, Resource)]
+struct BulkBuffers(HashMap<BulkId, (String, Vec<u8>)>);
+
+impl BulkBuffers {
+    fn read_chunk(
+        &mut self,
+        message: &[u8],
+        progress_events: &mut EventWriter<BulkProgress>,
+        received_events: &mut EventWriter<BulkReceived>,
+    ) -> bincode::Result<()> {
+        let mut cursor = Cursor::new(message);
+        let header: ChunkHeader = wire_format::varint().deserialize_from(&mut cursor)?;
+        let (name, buffer) = if header.offset == 0 {
+            let name = wire_format::varint().deserialize_from(&mut cursor)?;
+            self.0
+                .entry(header.id)
+                .or_insert((name, Vec::with_capacity(header.total as usize)))
+        } else {
+            self.0
+                .get_mut(&header.id)
+                .ok_or(bincode::ErrorKind::Custom(format!(
+                    "received chunk for unknown {:?}",
+                    header.id
+                )))?
+        };
+
+        buffer.extend_from_slice(&message[cursor.position() as usize..]);
+        let received = buffer.len() as u32;
+        trace!("received {received}/{} for {:?}", header.total, header.id);
+        progress_events.send(BulkProgress {
+            id: header.id,
+            name: name.clone(),
+            received,
+            total: header.total,
+        });
+
+        if received >= header.total {
+            let (name, data) = self.0.remove(&header.id).unwrap();
+            received_events.send(BulkReceived {
+                id: header.id,
+                name,
+                data: data.into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Unique ID of a bulk transfer.
+///
+/// Can be obtained from [`BulkTransfers::send`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct BulkId(u32);
+
+/// Emitted on client after receiving each chunk of a bulk payload.
+#[derive(Clone, Debug, Event)]
+pub struct BulkProgress {
+    pub id: BulkId,
+    pub name: String,
+    /// Number of received bytes so far.
+    pub received: u32,
+    /// Total size of the payload.
+    pub total: u32,
+}
+
+/// Emitted on client after receiving the whole bulk payload.
+///
+/// Also emitted on server or in single-player if [`ClientId::SERVER`] is included in the send mode.
+#[derive(Clone, Debug, Event)]
+pub struct BulkReceived {
+    pub id: BulkId,
+    pub name: String,
+    pub data: Bytes,
+}
@@ -4,7 +4,7 @@ use bevy::{
     ecs::{entity::MapEntities, event::Event},
     prelude::*,
 };
-use bincode::{DefaultOptions, Options};
+use bincode::Options;
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::EventMapper;
@@ -13,7 +13,7 @@ use crate::{
     core::{
         common_conditions::{client_connected, has_authority, server_running},
         replicon_channels::{RepliconChannel, RepliconChannels},
-        ClientId,
+        wire_format, ClientId,
     },
     server::{replicon_server::RepliconServer, ServerSet},
 };
@@ -50,8 +50,10 @@ pub trait ClientEventAppExt {
 
     ```
     use bevy::{prelude::*, reflect::serde::{ReflectSerializer, UntypedReflectDeserializer}};
-    use bevy_replicon::{network_event::client_event::ClientEventChannel, prelude::*};
-    use bincode::{DefaultOptions, Options};
+    use bevy_replicon::{
+        core::wire_format, network_event::client_event::ClientEventChannel, prelude::*,
+    };
+    use bincode::Options;
     use serde::de::DeserializeSeed;
 
     let mut app = App::new();
@@ -71,7 +73,7 @@ pub trait ClientEventAppExt {
         let registry = registry.read();
         for event in reflect_events.read() {
             let serializer = ReflectSerializer::new(&*event.0, &registry);
-            let message = DefaultOptions::new()
+            let message = wire_format::varint()
                 .serialize(&serializer)
                 .expect("client event should be serializable");
 
@@ -87,7 +89,7 @@ pub trait ClientEventAppExt {
     ) {
         let registry = registry.read();
         for (client_id, message) in server.receive(*channel) {
-            let mut deserializer = bincode::Deserializer::from_slice(&message, DefaultOptions::new());
+            let mut deserializer = bincode::Deserializer::from_slice(&message, wire_format::varint());
             match UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer) {
                 Ok(reflect) => {
                     reflect_events.send(FromClient {
@@ -172,7 +174,7 @@ fn receive<T: Event + DeserializeOwned>(
     channel: Res<ClientEventChannel<T>>,
 ) {
     for (client_id, message) in server.receive(*channel) {
-        match DefaultOptions::new().deserialize(&message) {
+        match wire_format::varint().deserialize(&message) {
             Ok(event) => {
                 trace!(
                     "applying event `{}` from `{client_id:?}`",
@@ -191,7 +193,7 @@ fn send<T: Event + Serialize>(
     channel: Res<ClientEventChannel<T>>,
 ) {
     for event in events.read() {
-        let message = DefaultOptions::new()
+        let message = wire_format::varint()
             .serialize(&event)
             .expect("client event should be serializable");
 
@@ -208,7 +210,7 @@ fn map_and_send<T: Event + MapEntities + Serialize + Clone>(
 ) {
     for mut event in events.read().cloned() {
         event.map_entities(&mut EventMapper(entity_map.to_server()));
-        let message = DefaultOptions::new()
+        let message = wire_format::varint()
             .serialize(&event)
             .expect("mapped client event should be serializable");
 
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::server_event::ServerEventAppExt;
+use crate::core::replicon_channels::ChannelKind;
+
+/// Disconnect reasons for [`App`].
+pub trait AppDisconnectExt {
+    /**
+    Registers a reason that server can send to a client before disconnecting it.
+
+    Send it with [`ServerCommands::disconnect`](crate::server::server_commands::ServerCommands::disconnect)
+    and read [`DisconnectReason<D>`] on client to show it in the UI.
+    The reason is sent over [`ChannelKind::Ordered`].
+
+    Should be called on both client and server in the same order relative to other network events,
+    since it registers a server event.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_disconnect_reason::<KickReason>()
+        .add_systems(Update, show_reason);
+
+    fn show_reason(mut reason_events: EventReader<DisconnectReason<KickReason>>) {
+        for event in reason_events.read() {
+            info!("disconnected by server: {:?}", event.0);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    enum KickReason {
+        Banned,
+        ServerFull,
+    }
+    ```
+    **/
+    fn add_disconnect_reason<D>(&mut self) -> &mut Self
+    where
+        D: Send + Sync + Serialize + DeserializeOwned + 'static;
+}
+
+impl AppDisconnectExt for App {
+    fn add_disconnect_reason<D>(&mut self) -> &mut Self
+    where
+        D: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        self.add_server_event::<DisconnectReason<D>>(ChannelKind::Ordered)
+    }
+}
+
+/// A reason received from server before it disconnects the client.
+///
+/// See also [`AppDisconnectExt::add_disconnect_reason`].
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize)]
+pub struct DisconnectReason<D>(pub D);
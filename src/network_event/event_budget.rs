@@ -0,0 +1,205 @@
+use std::{collections::VecDeque, marker::PhantomData};
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::server_event::ServerEventChannel;
+use crate::{
+    core::{
+        common_conditions::{server_just_stopped, server_running},
+        ClientId,
+    },
+    server::{replicon_server::RepliconServer, ServerEvent, ServerSet},
+};
+
+/// Budgets for server events for [`App`].
+pub trait AppEventBudgetExt {
+    /**
+    Limits the number of sent [`ToClients<T>`](super::server_event::ToClients) messages for each client per frame.
+
+    Messages over the limit are queued and sent on the next frames in the same order.
+    If a client's queue reaches `queue_limit`, the `overflow` policy is applied and
+    [`EventSaturated<T>`] is emitted, so a burst of events can't grow memory or keep
+    the channel busy indefinitely.
+
+    Applied after [`ServerSet::Send`], so all messages of the event's channel are limited.
+    Limits can be changed at runtime via [`ServerEventBudget<T>`].
+
+    Should be called after the event is registered.
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_server_event::<DamageNumber>(ChannelKind::Ordered)
+        .set_server_event_budget::<DamageNumber>(32, 256, OverflowPolicy::DropOldest);
+
+    #[derive(Deserialize, Event, Serialize)]
+    struct DamageNumber(u32);
+    ```
+    **/
+    fn set_server_event_budget<T: Event>(
+        &mut self,
+        per_frame: usize,
+        queue_limit: usize,
+        overflow: OverflowPolicy,
+    ) -> &mut Self;
+}
+
+impl AppEventBudgetExt for App {
+    fn set_server_event_budget<T: Event>(
+        &mut self,
+        per_frame: usize,
+        queue_limit: usize,
+        overflow: OverflowPolicy,
+    ) -> &mut Self {
+        self.insert_resource(ServerEventBudget::<T> {
+            per_frame,
+            queue_limit,
+            overflow,
+            queues: Default::default(),
+            marker: PhantomData,
+        })
+        .add_event::<EventSaturated<T>>()
+        .add_systems(
+            PostUpdate,
+            (
+                limit::<T>
+                    .after(ServerSet::Send)
+                    .before(ServerSet::SendPackets)
+                    .run_if(server_running),
+                reset::<T>.run_if(server_just_stopped),
+            ),
+        )
+    }
+}
+
+/// Queues sent messages of the event's channel and sends the allowed number for each client.
+fn limit<T: Event>(
+    mut server_events: EventReader<ServerEvent>,
+    mut saturated_events: EventWriter<EventSaturated<T>>,
+    mut server: ResMut<RepliconServer>,
+    mut budget: ResMut<ServerEventBudget<T>>,
+    channel: Res<ServerEventChannel<T>>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+            budget.queues.remove(client_id);
+        }
+    }
+
+    let channel_id = u8::from(*channel);
+    let ServerEventBudget {
+        per_frame,
+        queue_limit,
+        overflow,
+        queues,
+        ..
+    } = &mut *budget;
+
+    let mut dropped = HashMap::<ClientId, usize>::new();
+    server.retain_sent(|&(client_id, message_channel, ref message)| {
+        if message_channel != channel_id {
+            return true;
+        }
+
+        let queue = queues.entry(client_id).or_default();
+        if queue.len() < *queue_limit {
+            queue.push_back(message.clone());
+        } else {
+            *dropped.entry(client_id).or_default() += 1;
+            match overflow {
+                OverflowPolicy::DropOldest => {
+                    queue.pop_front();
+                    queue.push_back(message.clone());
+                }
+                OverflowPolicy::DropNewest => (),
+                OverflowPolicy::Disconnect => queue.clear(),
+            }
+        }
+
+        false
+    });
+
+    for (client_id, dropped) in dropped {
+        warn!(
+            "`{}` queue for {client_id:?} is saturated, applying {overflow:?}",
+            std::any::type_name::<T>()
+        );
+        if *overflow == OverflowPolicy::Disconnect {
+            queues.remove(&client_id);
+            server.disconnect(client_id);
+        }
+        saturated_events.send(EventSaturated {
+            client_id,
+            dropped,
+            marker: PhantomData,
+        });
+    }
+
+    for (&client_id, queue) in queues.iter_mut() {
+        let count = queue.len().min(*per_frame);
+        for message in queue.drain(..count) {
+            server.send(client_id, channel_id, message);
+        }
+    }
+}
+
+fn reset<T: Event>(mut budget: ResMut<ServerEventBudget<T>>) {
+    budget.queues.clear();
+}
+
+/// Limits and queued messages for server event `T`.
+///
+/// See [`AppEventBudgetExt::set_server_event_budget`] for details.
+#[derive(Resource)]
+pub struct ServerEventBudget<T> {
+    /// Maximum number of messages sent to each client per frame.
+    pub per_frame: usize,
+
+    /// Maximum number of queued messages for each client.
+    pub queue_limit: usize,
+
+    /// What to do when a client's queue is full.
+    pub overflow: OverflowPolicy,
+
+    queues: HashMap<ClientId, VecDeque<Bytes>>,
+    marker: PhantomData<T>,
+}
+
+impl<T> ServerEventBudget<T> {
+    /// Returns the number of messages queued for the client.
+    pub fn queued(&self, client_id: ClientId) -> usize {
+        self.queues.get(&client_id).map_or(0, VecDeque::len)
+    }
+}
+
+/// Controls what happens when a client's queue in [`ServerEventBudget`] is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued message to fit the new one.
+    DropOldest,
+    /// Discard the new message.
+    DropNewest,
+    /// Discard all queued messages and disconnect the client.
+    Disconnect,
+}
+
+/// An event emitted on server when a client's queue in [`ServerEventBudget<T>`] overflows.
+#[derive(Debug, Event)]
+pub struct EventSaturated<T> {
+    pub client_id: ClientId,
+
+    /// Number of messages that didn't fit the queue during the frame.
+    ///
+    /// With [`OverflowPolicy::Disconnect`] only messages over the limit are counted.
+    pub dropped: usize,
+
+    marker: PhantomData<T>,
+}
@@ -0,0 +1,307 @@
+use std::any;
+
+use bevy::{
+    ecs::entity::MapEntities,
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        TypeRegistry,
+    },
+    utils::HashSet,
+};
+use bincode::Options;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use super::{
+    client_event::{ClientEventAppExt, FromClient},
+    server_event::{SendMode, ServerEventAppExt, ToClients},
+};
+use crate::{
+    core::{
+        common_conditions::{has_authority, server_running},
+        replication_rules::ReplicationRules,
+        replicon_channels::ChannelKind,
+        wire_format, ClientId, Replicated,
+    },
+    server::{ServerEvent, ServerSet},
+};
+
+/**
+Channel for editor or inspector clients to read and mutate replicated components on the server.
+
+Clients send [`TweakRequest`] and receive [`TweakResponse`] with the current component value.
+Mutated components are replicated to all clients as usual.
+Values are serialized using reflection, so components should be registered with [`App::register_type`]
+and have `#[reflect(Component)]`. Only components that have replication rules can be accessed
+and only on entities with [`Replicated`].
+
+Requests are applied only from clients in [`TweakPermissions`].
+Requests from [`ClientId::SERVER`] are always allowed.
+
+Intended for live tuning during development, so it's advised to add it only in development builds.
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins));
+#[cfg(debug_assertions)]
+app.add_plugins(LiveTweakPlugin)
+    .add_systems(Update, allow_tweaks.run_if(server_running));
+
+fn allow_tweaks(
+    mut permissions: ResMut<TweakPermissions>,
+    mut server_events: EventReader<ServerEvent>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            // Allow access only for trusted clients in real applications.
+            permissions.insert(client_id);
+        }
+    }
+}
+```
+**/
+pub struct LiveTweakPlugin;
+
+impl Plugin for LiveTweakPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TweakPermissions>()
+            .add_mapped_client_event::<TweakRequest>(ChannelKind::Ordered)
+            .add_server_event::<TweakResponse>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::remove_disconnected
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::apply_requests
+                        .after(ServerSet::Receive)
+                        .run_if(has_authority),
+                ),
+            );
+    }
+}
+
+impl LiveTweakPlugin {
+    fn apply_requests(world: &mut World) {
+        let requests: Vec<_> = world
+            .resource_mut::<Events<FromClient<TweakRequest>>>()
+            .drain()
+            .collect();
+
+        for FromClient { client_id, event } in requests {
+            let result = Self::apply_request(world, client_id, &event);
+            if let Err(e) = result {
+                debug!(
+                    "unable to apply tweak of `{}` for {:?} from {client_id:?}: {e:?}",
+                    event.type_path, event.entity
+                );
+            }
+
+            world.send_event(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: TweakResponse {
+                    id: event.id,
+                    type_path: event.type_path,
+                    result,
+                },
+            });
+        }
+    }
+
+    /// Applies the request and returns the serialized component value.
+    fn apply_request(
+        world: &mut World,
+        client_id: ClientId,
+        request: &TweakRequest,
+    ) -> Result<Vec<u8>, TweakError> {
+        if client_id != ClientId::SERVER
+            && !world.resource::<TweakPermissions>().contains(&client_id)
+        {
+            return Err(TweakError::PermissionDenied);
+        }
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let registration = registry
+            .get_with_type_path(&request.type_path)
+            .ok_or(TweakError::UnknownType)?;
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or(TweakError::UnknownType)?;
+
+        let component_id = world
+            .components()
+            .get_id(registration.type_id())
+            .ok_or(TweakError::NotReplicated)?;
+        let replicated = world
+            .resource::<ReplicationRules>()
+            .iter()
+            .flat_map(|rule| &rule.components)
+            .any(|fns_info| fns_info.component_id() == component_id);
+        if !replicated {
+            return Err(TweakError::NotReplicated);
+        }
+
+        let mut entity = world
+            .get_entity_mut(request.entity)
+            .filter(|entity| entity.contains::<Replicated>())
+            .ok_or(TweakError::NoEntity)?;
+        let mut component = reflect_component
+            .reflect_mut(&mut entity)
+            .ok_or(TweakError::NoComponent)?;
+
+        if let Some(value) = &request.value {
+            let mut deserializer = bincode::Deserializer::from_slice(value, wire_format::varint());
+            let value = TypedReflectDeserializer::new(registration, &registry)
+                .deserialize(&mut deserializer)
+                .map_err(|_| TweakError::InvalidValue)?;
+            component.apply(&*value);
+        }
+
+        let serializer = TypedReflectSerializer::new(component.as_reflect(), &registry);
+        wire_format::varint()
+            .serialize(&serializer)
+            .map_err(|_| TweakError::Unserializable)
+    }
+
+    fn remove_disconnected(
+        mut server_events: EventReader<ServerEvent>,
+        mut permissions: ResMut<TweakPermissions>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+                permissions.remove(&client_id);
+            }
+        }
+    }
+}
+
+/// Clients that are allowed to send [`TweakRequest`].
+///
+/// Clients are automatically removed on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct TweakPermissions(HashSet<ClientId>);
+
+/// A client event to read or mutate a replicated component on the server.
+///
+/// Server answers with [`TweakResponse`].
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct TweakRequest {
+    /// Arbitrary ID to match the response.
+    pub id: u32,
+
+    /// Entity with the component.
+    ///
+    /// Mapped to the server entity before sending.
+    pub entity: Entity,
+
+    /// Type path of the component.
+    pub type_path: String,
+
+    /// Serialized component value to apply or [`None`] to only read the current value.
+    pub value: Option<Vec<u8>>,
+}
+
+impl TweakRequest {
+    /// Creates a request to read component `C` from the entity.
+    pub fn get<C: Component + TypePath>(id: u32, entity: Entity) -> Self {
+        Self {
+            id,
+            entity,
+            type_path: C::type_path().to_string(),
+            value: None,
+        }
+    }
+
+    /// Creates a request to replace component `C` on the entity with the specified value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component can't be serialized using reflection.
+    pub fn set<C: Component + Reflect + TypePath>(
+        id: u32,
+        entity: Entity,
+        component: &C,
+        registry: &TypeRegistry,
+    ) -> Self {
+        let serializer = TypedReflectSerializer::new(component, registry);
+        let value = wire_format::varint()
+            .serialize(&serializer)
+            .unwrap_or_else(|e| panic!("`{}` should be serializable: {e}", any::type_name::<C>()));
+
+        Self {
+            id,
+            entity,
+            type_path: C::type_path().to_string(),
+            value: Some(value),
+        }
+    }
+}
+
+impl MapEntities for TweakRequest {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
+
+/// A server event with the result of [`TweakRequest`].
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct TweakResponse {
+    /// ID from the request.
+    pub id: u32,
+
+    /// Type path of the component from the request.
+    pub type_path: String,
+
+    /// Serialized component value after applying the request.
+    pub result: Result<Vec<u8>, TweakError>,
+}
+
+impl TweakResponse {
+    /// Deserializes the component value.
+    ///
+    /// Returns [`None`] if the request failed, the response is for a different component
+    /// or the value can't be deserialized.
+    pub fn component<C: Component + FromReflect + TypePath>(
+        &self,
+        registry: &TypeRegistry,
+    ) -> Option<C> {
+        if self.type_path != C::type_path() {
+            return None;
+        }
+
+        let value = self.result.as_ref().ok()?;
+        let registration = registry.get(any::TypeId::of::<C>())?;
+        let mut deserializer = bincode::Deserializer::from_slice(value, wire_format::varint());
+        let reflect = TypedReflectDeserializer::new(registration, registry)
+            .deserialize(&mut deserializer)
+            .ok()?;
+
+        C::from_reflect(&*reflect)
+    }
+}
+
+/// Reason why [`TweakRequest`] was rejected.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum TweakError {
+    /// Client is not in [`TweakPermissions`].
+    PermissionDenied,
+    /// Type path is not registered or the type is not a reflected component.
+    UnknownType,
+    /// Component is not registered for replication.
+    NotReplicated,
+    /// Entity doesn't exist or not replicated.
+    NoEntity,
+    /// Entity doesn't have the component.
+    NoComponent,
+    /// Value can't be deserialized into the component.
+    InvalidValue,
+    /// Component can't be serialized using reflection.
+    Unserializable,
+}
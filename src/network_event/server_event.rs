@@ -4,7 +4,7 @@ use bevy::{
     ecs::{entity::MapEntities, event::Event},
     prelude::*,
 };
-use bincode::{DefaultOptions, Options};
+use bincode::Options;
 use bytes::Bytes;
 use ordered_multimap::ListOrderedMultimap;
 use serde::{de::DeserializeOwned, Serialize};
@@ -19,7 +19,7 @@ use crate::{
         common_conditions::{client_connected, has_authority, server_running},
         replicon_channels::{RepliconChannel, RepliconChannels},
         replicon_tick::RepliconTick,
-        ClientId,
+        wire_format, ClientId,
     },
     prelude::{ClientPlugin, ServerPlugin},
     server::{
@@ -66,10 +66,11 @@ pub trait ServerEventAppExt {
     };
     use bevy_replicon::{
         client::ServerInitTick,
+        core::wire_format,
         network_event::server_event::{self, ServerEventChannel, ServerEventQueue},
         prelude::*,
     };
-    use bincode::{DefaultOptions, Options};
+    use bincode::Options;
     use serde::de::DeserializeSeed;
 
     let mut app = App::new();
@@ -91,7 +92,7 @@ pub trait ServerEventAppExt {
         for ToClients { event, mode } in reflect_events.read() {
             server_event::send_with(&mut server, &connected_clients, *channel, *mode, |cursor| {
                 let serializer = ReflectSerializer::new(&*event.0, &registry);
-                DefaultOptions::new().serialize_into(cursor, &serializer)
+                wire_format::varint().serialize_into(cursor, &serializer)
             })
             .expect("server event should be serializable");
         }
@@ -109,7 +110,7 @@ pub trait ServerEventAppExt {
         for message in client.receive(*channel) {
             let (tick, event) = server_event::deserialize_with(&message, |cursor| {
                 let mut deserializer =
-                    bincode::Deserializer::with_reader(cursor, DefaultOptions::new());
+                    bincode::Deserializer::with_reader(cursor, wire_format::varint());
                 let reflect = UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer)?;
                 Ok(ReflectEvent(reflect))
             })
@@ -216,7 +217,7 @@ fn receive<T: Event + DeserializeOwned>(
 ) {
     for message in client.receive(*channel) {
         let (tick, event) = deserialize_with(&message, |cursor| {
-            DefaultOptions::new().deserialize_from(cursor)
+            wire_format::varint().deserialize_from(cursor)
         })
         .expect("server should send valid events");
 
@@ -240,7 +241,7 @@ fn receive_and_map<T: Event + MapEntities + DeserializeOwned>(
 ) {
     for message in client.receive(*channel) {
         let (tick, mut event): (_, T) = deserialize_with(&message, |cursor| {
-            DefaultOptions::new().deserialize_from(cursor)
+            wire_format::varint().deserialize_from(cursor)
         })
         .expect("server should send valid events");
 
@@ -264,7 +265,7 @@ fn send<T: Event + Serialize>(
     for ToClients { event, mode } in server_events.read() {
         trace!("sending event `{}` with `{mode:?}`", any::type_name::<T>());
         send_with(&mut server, &connected_clients, *channel, *mode, |cursor| {
-            DefaultOptions::new().serialize_into(cursor, &event)
+            wire_format::varint().serialize_into(cursor, &event)
         })
         .expect("server event should be serializable");
     }
@@ -366,9 +367,9 @@ fn serialize_with(
             return Ok(previous_message);
         }
 
-        let tick_size = DefaultOptions::new().serialized_size(&client.change_tick())? as usize;
+        let tick_size = wire_format::varint().serialized_size(&client.change_tick())? as usize;
         let mut bytes = Vec::with_capacity(tick_size + previous_message.event_bytes().len());
-        DefaultOptions::new().serialize_into(&mut bytes, &client.change_tick())?;
+        wire_format::varint().serialize_into(&mut bytes, &client.change_tick())?;
         bytes.extend_from_slice(previous_message.event_bytes());
         let message = SerializedMessage {
             tick: client.change_tick(),
@@ -379,7 +380,7 @@ fn serialize_with(
         Ok(message)
     } else {
         let mut cursor = Cursor::new(Vec::new());
-        DefaultOptions::new().serialize_into(&mut cursor, &client.change_tick())?;
+        wire_format::varint().serialize_into(&mut cursor, &client.change_tick())?;
         let tick_size = cursor.get_ref().len();
         (serialize)(&mut cursor)?;
         let message = SerializedMessage {
@@ -411,7 +412,7 @@ pub fn deserialize_with<T>(
     deserialize: impl FnOnce(&mut Cursor<&[u8]>) -> bincode::Result<T>,
 ) -> bincode::Result<(RepliconTick, T)> {
     let mut cursor = Cursor::new(message);
-    let tick = DefaultOptions::new().deserialize_from(&mut cursor)?;
+    let tick = wire_format::varint().deserialize_from(&mut cursor)?;
     let event = (deserialize)(&mut cursor)?;
 
     Ok((tick, event))
@@ -462,6 +463,17 @@ pub enum SendMode {
     Direct(ClientId),
 }
 
+impl SendMode {
+    /// Returns `true` if the client with the specified ID is a recipient in this mode.
+    pub fn includes(self, client_id: ClientId) -> bool {
+        match self {
+            SendMode::Broadcast => true,
+            SendMode::BroadcastExcept(except_id) => except_id != client_id,
+            SendMode::Direct(target_id) => target_id == client_id,
+        }
+    }
+}
+
 /// Stores all received events from server that arrived earlier then replication message with their tick.
 ///
 /// Stores data sorted by ticks and maintains order of arrival.
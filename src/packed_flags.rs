@@ -0,0 +1,216 @@
+use bevy::{
+    ecs::{component::ComponentId, query::QueryState},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_rules::AppRuleExt,
+        Replicated,
+    },
+    server::ServerSet,
+};
+
+/// Maximum number of flags that can be registered with [`AppFlagExt::replicate_flag`].
+pub const MAX_FLAGS: usize = u32::BITS as usize;
+
+/**
+Replicates marker components of an entity as bits of a single component.
+
+Register markers with [`AppFlagExt::replicate_flag`]. Before sending, server stores presence
+of registered markers for each replicated entity in [`PackedFlags`], which is replicated instead of markers.
+After receiving, clients insert or remove markers according to the bits.
+
+Useful when entities have many small markers that change often, since each replicated
+component adds at least its ID to the message, while the whole set takes a few bytes.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, PackedFlagsPlugin))
+    .replicate_flag::<Stunned>()
+    .replicate_flag::<Burning>();
+
+#[derive(Component, Default)]
+struct Stunned;
+
+#[derive(Component, Default)]
+struct Burning;
+```
+**/
+pub struct PackedFlagsPlugin;
+
+impl Plugin for PackedFlagsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlagRegistry>()
+            .replicate::<PackedFlags>()
+            .add_systems(
+                PreUpdate,
+                Self::unpack
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::pack
+                    .after(ServerSet::StoreHierarchy)
+                    .before(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl PackedFlagsPlugin {
+    /// Updates [`PackedFlags`] of replicated entities from the present markers.
+    ///
+    /// Bits are calculated once per archetype, but compared per entity to avoid triggering changes.
+    fn pack(world: &mut World, mut updates: Local<Vec<(Entity, u32)>>) {
+        let Some(replicated_id) = world.component_id::<Replicated>() else {
+            return;
+        };
+        let packed_id = world.component_id::<PackedFlags>();
+        let registry = world.resource::<FlagRegistry>();
+
+        for archetype in world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(replicated_id))
+        {
+            let bits = registry
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| archetype.contains(flag.component_id))
+                .fold(0, |bits, (index, _)| bits | 1 << index);
+
+            let has_packed = packed_id.is_some_and(|id| archetype.contains(id));
+            if !has_packed && bits == 0 {
+                continue;
+            }
+
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                if world
+                    .get::<PackedFlags>(entity)
+                    .is_none_or(|flags| flags.0 != bits)
+                {
+                    updates.push((entity, bits));
+                }
+            }
+        }
+
+        for (entity, bits) in updates.drain(..) {
+            world.entity_mut(entity).insert(PackedFlags(bits));
+        }
+    }
+
+    /// Inserts or removes registered markers according to received [`PackedFlags`].
+    fn unpack(
+        world: &mut World,
+        mut changed_flags: Local<QueryState<(Entity, &PackedFlags), Changed<PackedFlags>>>,
+        mut updates: Local<Vec<(Entity, u32)>>,
+    ) {
+        updates.extend(
+            changed_flags
+                .iter(world)
+                .map(|(entity, flags)| (entity, flags.0)),
+        );
+        if updates.is_empty() {
+            return;
+        }
+
+        world.resource_scope(|world, registry: Mut<FlagRegistry>| {
+            for (entity, bits) in updates.drain(..) {
+                let mut entity = world.entity_mut(entity);
+                for (index, flag) in registry.iter().enumerate() {
+                    let present = entity.contains_id(flag.component_id);
+                    if bits & 1 << index != 0 {
+                        if !present {
+                            (flag.insert)(&mut entity);
+                        }
+                    } else if present {
+                        (flag.remove)(&mut entity);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Flag registration for [`App`].
+pub trait AppFlagExt {
+    /// Replicates the marker component as a bit of [`PackedFlags`].
+    ///
+    /// Don't register the marker using [`AppRuleExt`], it's replicated only through [`PackedFlags`].
+    /// The component is inserted on clients using [`Default`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is already registered or if more than [`MAX_FLAGS`] are registered.
+    fn replicate_flag<C: Component + Default>(&mut self) -> &mut Self;
+}
+
+impl AppFlagExt for App {
+    fn replicate_flag<C: Component + Default>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let mut registry = self.world.resource_mut::<FlagRegistry>();
+        assert!(
+            registry
+                .iter()
+                .all(|flag| flag.component_id != component_id),
+            "flag `{}` should be registered only once",
+            std::any::type_name::<C>(),
+        );
+        assert!(
+            registry.len() < MAX_FLAGS,
+            "only {MAX_FLAGS} flags can be registered"
+        );
+
+        registry.push(Flag {
+            component_id,
+            insert: insert_flag::<C>,
+            remove: remove_flag::<C>,
+        });
+
+        self
+    }
+}
+
+/// Registered flags in bit order.
+#[derive(Default, Deref, DerefMut, Resource)]
+struct FlagRegistry(Vec<Flag>);
+
+struct Flag {
+    component_id: ComponentId,
+    insert: fn(&mut EntityWorldMut),
+    remove: fn(&mut EntityWorldMut),
+}
+
+fn insert_flag<C: Component + Default>(entity: &mut EntityWorldMut) {
+    entity.insert(C::default());
+}
+
+fn remove_flag<C: Component>(entity: &mut EntityWorldMut) {
+    entity.remove::<C>();
+}
+
+/// Presence of markers registered with [`AppFlagExt::replicate_flag`].
+///
+/// Automatically updated on server and expanded on clients.
+#[derive(Component, Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct PackedFlags(u32);
+
+impl PackedFlags {
+    /// Returns bits in registration order.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
@@ -9,17 +9,43 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::ClientSet,
-    core::{common_conditions::has_authority, replication_rules::AppRuleExt},
+    core::{common_conditions::has_authority, replication_rules::AppRuleExt, Replicated},
     server::ServerSet,
 };
 
-pub struct ParentSyncPlugin;
+/**
+Automatically updates hierarchy on client if [`ParentSync`] component is present on entity.
+
+This allows to save / replicate hierarchy using only single component.
+If your system runs in [`PostUpdate`] and modifies hierarchy with [`ParentSync`],
+you need to run it before [`ServerSet::StoreHierarchy`].
+
+Despawning a replicated parent on server despawns its whole hierarchy on clients,
+including children spawned on client.
+
+# Examples
+
+Insert [`ParentSync`] automatically for all replicated children of replicated entities:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins.set(ParentSyncPlugin { automatic: true }),
+));
+```
+**/
+#[derive(Default)]
+pub struct ParentSyncPlugin {
+    /// Insert [`ParentSync`] on server for entities with [`Replicated`] whose parent is also replicated.
+    ///
+    /// Disabled by default.
+    pub automatic: bool,
+}
 
-/// Automatically updates hierarchy on client if [`ParentSync`] component is present on entity.
-///
-/// This allows to save / replicate hierarchy using only single component.
-/// If your system runs in [`PostUpdate`] and modifies hierarchy with [`ParentSync`],
-/// you need to run it before [`ServerSet::StoreHierarchy`].
 impl Plugin for ParentSyncPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Option<Entity>>()
@@ -32,6 +58,15 @@ impl Plugin for ParentSyncPlugin {
                     .run_if(has_authority)
                     .in_set(ServerSet::StoreHierarchy),
             );
+
+        if self.automatic {
+            app.add_systems(
+                PostUpdate,
+                Self::insert_missing
+                    .run_if(has_authority)
+                    .in_set(ServerSet::StoreHierarchy),
+            );
+        }
     }
 }
 
@@ -54,6 +89,19 @@ impl ParentSyncPlugin {
         }
     }
 
+    /// Inserts [`ParentSync`] for replicated children of replicated entities.
+    fn insert_missing(
+        mut commands: Commands,
+        children: Query<(Entity, &Parent), (With<Replicated>, Without<ParentSync>)>,
+        replicated: Query<(), With<Replicated>>,
+    ) {
+        for (entity, parent) in &children {
+            if replicated.get(**parent).is_ok() {
+                commands.entity(entity).insert(ParentSync(Some(**parent)));
+            }
+        }
+    }
+
     fn store_changes(mut hierarchy: Query<(&Parent, &mut ParentSync), Changed<Parent>>) {
         for (parent, mut parent_sync) in &mut hierarchy {
             parent_sync.0 = Some(**parent);
@@ -99,7 +147,7 @@ mod tests {
     #[test]
     fn update() {
         let mut app = App::new();
-        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin));
+        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin::default()));
 
         let child_entity = app.world.spawn_empty().id();
         app.world.spawn_empty().add_child(child_entity);
@@ -120,7 +168,7 @@ mod tests {
     #[test]
     fn removal() {
         let mut app = App::new();
-        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin));
+        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin::default()));
 
         let parent_entity = app.world.spawn_empty().id();
         let child_entity = app
@@ -146,7 +194,7 @@ mod tests {
     #[test]
     fn update_sync() {
         let mut app = App::new();
-        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin));
+        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin::default()));
 
         let parent_entity = app.world.spawn_empty().id();
         let child_entity = app.world.spawn(ParentSync(Some(parent_entity))).id();
@@ -162,7 +210,7 @@ mod tests {
     #[test]
     fn removal_sync() {
         let mut app = App::new();
-        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin));
+        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin::default()));
 
         let child_entity = app.world.spawn_empty().id();
         app.world.spawn_empty().add_child(child_entity);
@@ -180,6 +228,32 @@ mod tests {
         assert!(child_entity.get::<ParentSync>().unwrap().0.is_none());
     }
 
+    #[test]
+    fn automatic() {
+        let mut app = App::new();
+        app.add_plugins((RepliconCorePlugin, ParentSyncPlugin { automatic: true }));
+
+        let parent_entity = app.world.spawn(Replicated).id();
+        let child_entity = app.world.spawn(Replicated).set_parent(parent_entity).id();
+        let not_replicated_parent = app.world.spawn_empty().id();
+        let other_child_entity = app
+            .world
+            .spawn(Replicated)
+            .set_parent(not_replicated_parent)
+            .id();
+
+        app.update();
+
+        let parent_sync = app.world.get::<ParentSync>(child_entity).unwrap();
+        assert!(parent_sync.0.is_some_and(|entity| entity == parent_entity));
+        assert!(
+            !app.world
+                .entity(other_child_entity)
+                .contains::<ParentSync>(),
+            "children of non-replicated entities shouldn't be synced"
+        );
+    }
+
     #[test]
     fn scene_update_sync() {
         let mut app = App::new();
@@ -187,7 +261,7 @@ mod tests {
             AssetPlugin::default(),
             ScenePlugin,
             RepliconCorePlugin,
-            ParentSyncPlugin,
+            ParentSyncPlugin::default(),
         ));
 
         let mut scene_world = World::new();
@@ -0,0 +1,234 @@
+use bevy::{ecs::component::Components, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, client_just_connected, server_running},
+        replication_fns::ReplicationFns,
+        replicon_channels::ChannelKind,
+        ClientId,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        disconnect_reason::{AppDisconnectExt, DisconnectReason},
+    },
+    server::{
+        connected_clients::ConnectedClients, server_commands::ServerCommands, ServerEvent,
+        ServerSet,
+    },
+};
+
+/**
+Negotiates replication registrations on connect to tolerate clients with fewer replicated components.
+
+Without it server and clients should register the same replication rules in the same order.
+With this plugin clients send [`ClientRegistrations`] after connection and server
+pauses replication to the client until it receives them. Components that the client doesn't have
+are skipped for this client and other components are sent with the client's IDs,
+so optional feature plugins with replication rules can be added only on some clients.
+Server emits [`ProtocolDowngraded`] for clients with missing components.
+
+If registrations should match exactly, set [`MismatchPolicy::Disconnect`]. In this case server disconnects
+clients whose registrations differ and sends them [`ProtocolMismatch`] as [`DisconnectReason`]
+with the list of differences.
+
+Components are matched by their type names, so both sides should be built from the same sources.
+To match components by explicit names, use [`AppRuleExt::replicate_as`](crate::core::replication_rules::AppRuleExt::replicate_as).
+Only replication rules are negotiated, network events should still be registered in the same order.
+Since the plugin registers its own events, it should be added before other plugins that register events.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    ProtocolNegotiationPlugin,
+))
+.add_systems(Update, log_downgrades.run_if(server_running));
+
+fn log_downgrades(mut downgraded_events: EventReader<ProtocolDowngraded>) {
+    for event in downgraded_events.read() {
+        info!("{:?} doesn't replicate {:?}", event.client_id, event.missing);
+    }
+}
+```
+**/
+pub struct ProtocolNegotiationPlugin;
+
+impl Plugin for ProtocolNegotiationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MismatchPolicy>()
+            .add_event::<ProtocolDowngraded>()
+            .add_client_event::<ClientRegistrations>(ChannelKind::Ordered)
+            .add_disconnect_reason::<ProtocolMismatch>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::send_registrations
+                        .after(ClientSet::Receive)
+                        .run_if(client_just_connected),
+                    Self::log_mismatch
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::negotiate
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                ),
+            );
+    }
+}
+
+impl ProtocolNegotiationPlugin {
+    fn send_registrations(
+        mut registrations: EventWriter<ClientRegistrations>,
+        replication_fns: Res<ReplicationFns>,
+        components: &Components,
+    ) {
+        let rules = replication_fns
+            .rule_names(components)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        registrations.send(ClientRegistrations { rules });
+    }
+
+    fn log_mismatch(mut reason_events: EventReader<DisconnectReason<ProtocolMismatch>>) {
+        for DisconnectReason(mismatch) in reason_events.read() {
+            error!(
+                "server rejected replication registrations, missing on client: {:?}, unknown to server: {:?}",
+                mismatch.missing, mismatch.unknown
+            );
+        }
+    }
+
+    /// Pauses replication to new clients and resumes it with mapped registrations after receiving them.
+    ///
+    /// Disconnects clients with different registrations if the policy requires it.
+    #[allow(clippy::too_many_arguments)]
+    fn negotiate(
+        mut commands: ServerCommands,
+        mut server_events: EventReader<ServerEvent>,
+        mut registrations: EventReader<FromClient<ClientRegistrations>>,
+        mut downgraded_events: EventWriter<ProtocolDowngraded>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        replication_fns: Res<ReplicationFns>,
+        policy: Res<MismatchPolicy>,
+        components: &Components,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                connected_clients.client_mut(client_id).pause_replication();
+            }
+        }
+
+        for FromClient { client_id, event } in registrations.read() {
+            let Some(client) = connected_clients.get_client_mut(*client_id) else {
+                continue;
+            };
+            if !client.is_replication_paused() {
+                debug!("ignoring repeated registrations from {client_id:?}");
+                continue;
+            }
+
+            let fns_map = replication_fns.map_rules(components, &event.rules);
+            let missing: Vec<_> = replication_fns
+                .rule_names(components)
+                .filter(|(fns_id, _)| fns_map.get(fns_id).is_some_and(Option::is_none))
+                .map(|(_, name)| name.to_string())
+                .collect();
+
+            if *policy == MismatchPolicy::Disconnect {
+                let mut server_names: Vec<_> = replication_fns
+                    .rule_names(components)
+                    .map(|(_, name)| name)
+                    .collect();
+                let unknown: Vec<_> = event
+                    .rules
+                    .iter()
+                    .filter(|&name| {
+                        if let Some(index) = server_names.iter().position(|other| other == name) {
+                            server_names.swap_remove(index);
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .cloned()
+                    .collect();
+
+                if !missing.is_empty() || !unknown.is_empty() {
+                    error!(
+                        "disconnecting {client_id:?} with mismatched registrations, missing on client: {missing:?}, unknown to server: {unknown:?}"
+                    );
+                    commands.disconnect(*client_id, ProtocolMismatch { missing, unknown });
+                    continue;
+                }
+            }
+
+            debug!(
+                "resuming replication for {client_id:?} with {} remapped functions",
+                fns_map.len()
+            );
+            client.resume_replication(fns_map);
+            if !missing.is_empty() {
+                debug!("{client_id:?} is missing {missing:?}");
+                downgraded_events.send(ProtocolDowngraded {
+                    client_id: *client_id,
+                    missing,
+                });
+            }
+        }
+    }
+}
+
+/// A client event with component names of replication functions in registration order.
+///
+/// Sent automatically on connect.
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct ClientRegistrations {
+    pub rules: Vec<String>,
+}
+
+/// Controls how server handles clients with registrations that differ from its own.
+///
+/// See [`ProtocolNegotiationPlugin`] for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource)]
+pub enum MismatchPolicy {
+    /// Skip components that the client doesn't have and emit [`ProtocolDowngraded`].
+    ///
+    /// Components that only the client has are ignored.
+    #[default]
+    Downgrade,
+    /// Disconnect the client if any component is missing on either side.
+    ///
+    /// The client receives [`ProtocolMismatch`] as [`DisconnectReason`] before disconnecting.
+    Disconnect,
+}
+
+/// Differences in replication registrations, sent to clients disconnected with [`MismatchPolicy::Disconnect`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ProtocolMismatch {
+    /// Names of components that are replicated by server, but not registered on the client.
+    pub missing: Vec<String>,
+
+    /// Names of components that are registered on the client, but not replicated by server.
+    pub unknown: Vec<String>,
+}
+
+/// An event that indicates that the client lacks some replicated components.
+///
+/// These components are not replicated to the client.
+#[derive(Clone, Debug, Event, PartialEq, Eq)]
+pub struct ProtocolDowngraded {
+    pub client_id: ClientId,
+
+    /// Names of missing components.
+    pub missing: Vec<String>,
+}
@@ -0,0 +1,202 @@
+use bevy::{ecs::component::Components, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{prediction::PredictionRegistry, ClientPlugin, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_fns::ReplicationFns,
+        replication_rules::ReplicationRules,
+        replication_stages::ComponentStages,
+        replicon_channels::ChannelKind,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        server_event::{SendMode, ServerEventAppExt, ToClients},
+    },
+    server::{
+        connected_clients::ConnectedClients, replicon_server::RepliconServer,
+        warm_standby::WarmStandby, ServerSet,
+    },
+};
+
+/**
+Re-establishes replication registrations at runtime without disconnecting clients.
+
+Useful with hot-reloading of game code, where function pointers from the previous build become invalid.
+Call [`AppProtocolReloadExt::reload_protocol`] after reloading to clear all replication rules and functions
+and register them again.
+
+If the server is running, replication to all clients is paused and they receive a re-handshake instead of
+being disconnected. Each client despawns all replicated entities, emits [`ProtocolReloaded`] and replies
+with its registrations. Server then resumes replication with the new registrations and sends all visible
+entities again, like on a fresh connection. Components are matched by names like in
+[`ProtocolNegotiationPlugin`](crate::protocol_negotiation::ProtocolNegotiationPlugin), so clients that were
+built before the reload keep receiving components they have, and components that they don't have are skipped.
+Only replication rules are reloaded, network events and channels should stay the same.
+
+Intended for development, so it's advised to add it only in development builds.
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins));
+#[cfg(debug_assertions)]
+app.add_plugins(ProtocolReloadPlugin);
+register_replication(&mut app);
+
+// After the game code is reloaded.
+#[cfg(debug_assertions)]
+app.reload_protocol(register_replication);
+
+fn register_replication(app: &mut App) {
+    app.replicate::<Health>();
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
+```
+**/
+pub struct ProtocolReloadPlugin;
+
+impl Plugin for ProtocolReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProtocolReloaded>()
+            .add_server_event::<ReloadNotice>(ChannelKind::Ordered)
+            .add_client_event::<ReloadAck>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::rehandshake
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::resume
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                ),
+            );
+    }
+}
+
+impl ProtocolReloadPlugin {
+    /// Drops the replicated world after a reload on server and replies with the client's registrations.
+    fn rehandshake(world: &mut World) {
+        if world.resource_mut::<Events<ReloadNotice>>().drain().count() == 0 {
+            return;
+        }
+
+        info!("server reloaded replication registrations, receiving the world again");
+        ClientPlugin::despawn_replicated(world);
+
+        let rules = world
+            .resource::<ReplicationFns>()
+            .rule_names(world.components())
+            .map(|(_, name)| name.to_string())
+            .collect();
+        world.send_event(ReloadAck { rules });
+        world.send_event(ProtocolReloaded);
+    }
+
+    /// Resumes replication with the new registrations from scratch.
+    fn resume(
+        mut ack_events: EventReader<FromClient<ReloadAck>>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        replication_fns: Res<ReplicationFns>,
+        components: &Components,
+    ) {
+        for FromClient { client_id, event } in ack_events.read() {
+            let Some(client) = connected_clients.get_client_mut(*client_id) else {
+                continue;
+            };
+
+            let fns_map = replication_fns.map_rules(components, &event.rules);
+            debug!(
+                "resuming replication for {client_id:?} after reload with {} remapped functions",
+                fns_map.len()
+            );
+            client.reset_replication();
+            client.resume_replication(fns_map);
+        }
+    }
+}
+
+/// Protocol reloading for [`App`].
+pub trait AppProtocolReloadExt {
+    /// Clears all replication rules and functions and registers them again with the closure.
+    ///
+    /// Everything registered with [`AppRuleExt`](crate::core::replication_rules::AppRuleExt) and functions set with
+    /// [`AppMarkerExt`](crate::core::command_markers::AppMarkerExt) for replicated components should be registered
+    /// inside the closure. Command markers themselves stay registered.
+    ///
+    /// Helpers that register rules or marker functions, like [`AppDeltaExt::replicate_delta`](crate::core::delta_compression::AppDeltaExt::replicate_delta)
+    /// or [`AppPredictionExt::predict`](crate::client::prediction::AppPredictionExt::predict), can be called
+    /// again inside the closure. Their data is registered again, but their systems are added only once.
+    ///
+    /// Should be called after adding [`ProtocolReloadPlugin`].
+    /// See it for details.
+    fn reload_protocol(&mut self, register: impl FnOnce(&mut App)) -> &mut Self;
+}
+
+impl AppProtocolReloadExt for App {
+    fn reload_protocol(&mut self, register: impl FnOnce(&mut App)) -> &mut Self {
+        info!("reloading replication registrations");
+        self.world.resource_mut::<ReplicationRules>().clear();
+        self.world.resource_mut::<ReplicationFns>().clear();
+        if let Some(mut stages) = self.world.get_resource_mut::<ComponentStages>() {
+            stages.clear();
+        }
+        if let Some(mut registry) = self.world.get_resource_mut::<PredictionRegistry>() {
+            registry.clear();
+        }
+        register(self);
+
+        if let Some(mut standby) = self.world.get_resource_mut::<WarmStandby>() {
+            standby.clear();
+        }
+
+        if self
+            .world
+            .get_resource::<RepliconServer>()
+            .is_some_and(RepliconServer::is_running)
+        {
+            let mut connected_clients = self.world.resource_mut::<ConnectedClients>();
+            for client in connected_clients.iter_mut() {
+                client.pause_replication();
+            }
+            debug!(
+                "pausing replication for {} clients until re-handshake",
+                connected_clients.len()
+            );
+
+            self.world.send_event(ToClients {
+                mode: SendMode::Broadcast,
+                event: ReloadNotice,
+            });
+        }
+
+        self
+    }
+}
+
+/// An event emitted on client when replicated entities were despawned due to a reload on server.
+///
+/// All visible entities will be received again.
+/// See [`ProtocolReloadPlugin`] for details.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct ProtocolReloaded;
+
+/// A server event that asks clients to drop the replicated world and reply with [`ReloadAck`].
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+struct ReloadNotice;
+
+/// A client event with component names of replication functions in registration order.
+#[derive(Deserialize, Event, Serialize)]
+struct ReloadAck {
+    rules: Vec<String>,
+}
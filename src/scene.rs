@@ -1,6 +1,98 @@
-use bevy::{ecs::entity::EntityHashMap, prelude::*, scene::DynamicEntity};
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    scene::{scene_spawner_system, DynamicEntity, SceneInstance, SceneInstanceReady},
+};
+
+use crate::{
+    core::{common_conditions::has_authority, replication_rules::ReplicationRules},
+    Replicated,
+};
+
+/**
+Replicates entities from spawned scene instances and keeps them in sync on scene hot reload.
+
+All entities from instances whose root has [`ReplicatedScene`] will receive [`Replicated`]
+after spawn. On hot reload Bevy re-applies the modified [`DynamicScene`] into the existing
+instance entities, which triggers change detection, so changed components are sent as regular updates
+and newly added scene entities are spawned on clients. Entities removed from the scene asset
+are not despawned by Bevy, so they stay replicated.
+
+Requires [`ScenePlugin`](bevy::scene::ScenePlugin).
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::{asset::AssetPlugin, prelude::*, scene::ScenePlugin};
+use bevy_replicon::{prelude::*, scene::{ReplicatedScene, SceneReplicationPlugin}};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    AssetPlugin::default(),
+    ScenePlugin,
+    RepliconPlugins,
+    SceneReplicationPlugin,
+))
+.add_systems(Startup, spawn_level);
+
+fn spawn_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        DynamicSceneBundle {
+            scene: asset_server.load("level.scn.ron"),
+            ..Default::default()
+        },
+        ReplicatedScene,
+    ));
+}
+```
+*/
+pub struct SceneReplicationPlugin;
+
+impl Plugin for SceneReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            SpawnScene,
+            replicate_instances
+                .after(scene_spawner_system)
+                .run_if(has_authority),
+        );
+    }
+}
+
+/// Inserts [`Replicated`] into entities of spawned or reloaded scene instances.
+fn replicate_instances(
+    mut commands: Commands,
+    mut ready_events: EventReader<SceneInstanceReady>,
+    mut asset_events: EventReader<AssetEvent<DynamicScene>>,
+    scene_spawner: Res<SceneSpawner>,
+    instances: Query<&SceneInstance, With<ReplicatedScene>>,
+    replicated: Query<(), With<Replicated>>,
+) {
+    let spawned = ready_events.read().count() != 0;
+    let modified = asset_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    if !spawned && !modified {
+        return;
+    }
+
+    for instance in &instances {
+        for entity in scene_spawner.iter_instance_entities(**instance) {
+            if !replicated.contains(entity) {
+                commands.entity(entity).insert(Replicated);
+            }
+        }
+    }
+}
 
-use crate::{core::replication_rules::ReplicationRules, Replicated};
+/// Marks scene root whose instance entities should be replicated.
+///
+/// See [`SceneReplicationPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ReplicatedScene;
 
 /**
 Fills scene with all replicated entities and their components.
@@ -1,11 +1,37 @@
+pub mod accumulation;
+pub mod bandwidth_simulation;
 pub mod client_entity_map;
+pub(super) mod clone_buffer;
+pub mod component_history;
+pub mod concealed_components;
 pub mod connected_clients;
+pub mod connection_filter;
 pub(super) mod despawn_buffer;
+pub mod diagnostics;
+pub mod hibernation;
+pub mod ignored;
+pub mod interest_radius;
+pub mod interest_tags;
+pub mod lag_compensation;
+pub mod match_host;
+pub mod pending_from_client;
 pub(super) mod removal_buffer;
 pub(super) mod replicated_archetypes;
+pub mod replication_conditions;
+pub mod replication_heatmap;
 pub(super) mod replication_messages;
 pub mod replicon_server;
+pub mod save_game;
+pub mod send_log;
+pub mod send_rate;
+pub mod server_commands;
 pub mod server_tick;
+pub mod snapshots;
+pub mod stale_priority;
+pub mod tick_source;
+pub mod value_comparison;
+pub mod warm_standby;
+pub mod world_digest;
 
 use std::{io::Cursor, mem, time::Duration};
 
@@ -18,59 +44,121 @@ use bevy::{
     },
     prelude::*,
     ptr::Ptr,
-    time::common_conditions::on_timer,
 };
+use bincode::Options;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use varint_rs::VarintReader;
 
 use crate::core::{
     common_conditions::{server_just_stopped, server_running},
-    replication_fns::{ctx::SerializeCtx, ReplicationFns},
+    component_encryption::{self, EncryptedComponents, ServerEncryptionKeys},
+    replication_fns::{
+        component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns, FnsId,
+        ReplicationFns,
+    },
     replication_rules::ReplicationRules,
+    replication_stages::{ComponentStage, ComponentStages, FilterCtx, ReplicationStages},
     replicon_channels::{ReplicationChannel, RepliconChannels},
     replicon_tick::RepliconTick,
-    ClientId,
+    wire_format, ClientId,
 };
 use client_entity_map::ClientEntityMap;
+use clone_buffer::{CloneBuffer, CloneBufferPlugin};
+use concealed_components::ConcealedComponents;
 use connected_clients::{
     client_visibility::Visibility, ClientBuffers, ConnectedClient, ConnectedClients,
 };
 use despawn_buffer::{DespawnBuffer, DespawnBufferPlugin};
+use diagnostics::ComponentSizeStats;
+use hibernation::Hibernation;
 use removal_buffer::{RemovalBuffer, RemovalBufferPlugin};
 use replicated_archetypes::ReplicatedArchetypes;
+use replication_conditions::ReplicationConditions;
+use replication_heatmap::ReplicationHeatmap;
 use replication_messages::ReplicationMessages;
 use replicon_server::RepliconServer;
+use send_log::{SendLog, SentComponent};
+use send_rate::SendRates;
 use server_tick::ServerTick;
+use warm_standby::WarmStandby;
 
 pub struct ServerPlugin {
     /// Tick configuration.
+    ///
+    /// Can be changed at runtime via [`ServerSettings`].
     pub tick_policy: TickPolicy,
 
+    /// Behavior after a stall when more than one tick period elapsed in a single frame.
+    ///
+    /// Can be changed at runtime via [`ServerSettings`].
+    pub stall_policy: StallPolicy,
+
     /// Visibility configuration.
     pub visibility_policy: VisibilityPolicy,
 
     /// The time after which updates will be considered lost if an acknowledgment is not received for them.
     ///
     /// In practice updates will live at least `update_timeout`, and at most `2*update_timeout`.
+    /// Also used as the interval for compaction of per-client change limits and expiry of delta baselines,
+    /// see [`ConnectedClient::tracked_entities`](connected_clients::ConnectedClient::tracked_entities)
+    /// and [`ComponentStages`].
+    ///
+    /// Can be changed at runtime via [`ServerSettings`].
     pub update_timeout: Duration,
+
+    /// Include the server send time into update messages.
+    ///
+    /// Needed for [`ClientLatency`](crate::client::diagnostics::ClientLatency) on clients.
+    /// Adds 4 bytes to each update message.
+    ///
+    /// By default set to `false`.
+    ///
+    /// Can be changed at runtime via [`ServerSettings`].
+    pub send_time: bool,
+
+    /// Send only changed fields of components registered with
+    /// [`AppDeltaExt::replicate_delta`](crate::core::delta_compression::AppDeltaExt::replicate_delta).
+    ///
+    /// When disabled, such components are sent in full and stored baselines are dropped.
+    ///
+    /// By default set to `true`.
+    ///
+    /// Can be changed at runtime via [`ServerSettings`].
+    pub delta_compression: bool,
 }
 
 impl Default for ServerPlugin {
     fn default() -> Self {
         Self {
             tick_policy: TickPolicy::MaxTickRate(30),
+            stall_policy: Default::default(),
             visibility_policy: Default::default(),
             update_timeout: Duration::from_secs(10),
+            send_time: false,
+            delta_compression: true,
         }
     }
 }
 
 impl Plugin for ServerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin))
+        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin, CloneBufferPlugin))
             .init_resource::<RepliconServer>()
+            .register_type::<RepliconTick>()
+            .register_type::<ServerTick>()
             .init_resource::<ServerTick>()
             .init_resource::<ClientBuffers>()
             .init_resource::<ClientEntityMap>()
+            .init_resource::<TickTimer>()
             .insert_resource(ConnectedClients::new(self.visibility_policy))
+            .insert_resource(ServerSettings {
+                tick_policy: self.tick_policy,
+                stall_policy: self.stall_policy,
+                update_timeout: self.update_timeout,
+                send_time: self.send_time,
+                delta_compression: self.delta_compression,
+            })
             .add_event::<ServerEvent>()
             .configure_sets(
                 PreUpdate,
@@ -96,7 +184,7 @@ impl Plugin for ServerPlugin {
                 (
                     Self::handle_connections,
                     Self::receive_acks,
-                    Self::cleanup_acks(self.update_timeout).run_if(on_timer(self.update_timeout)),
+                    Self::cleanup_acks.run_if(cleanup_timer),
                 )
                     .chain()
                     .in_set(ServerSet::Receive)
@@ -105,36 +193,29 @@ impl Plugin for ServerPlugin {
             .add_systems(
                 PostUpdate,
                 (
-                    Self::send_replication
-                        .map(Result::unwrap)
+                    (
+                        Self::send_replication.map(Result::unwrap),
+                        Self::clear_reenabled,
+                    )
+                        .chain()
                         .in_set(ServerSet::Send)
                         .run_if(server_running)
                         .run_if(resource_changed::<ServerTick>),
-                    Self::reset.run_if(server_just_stopped),
+                    (Self::reset, Self::reset_tick_timer).run_if(server_just_stopped),
                 ),
             );
 
-        match self.tick_policy {
-            TickPolicy::MaxTickRate(max_tick_rate) => {
-                let tick_time = Duration::from_millis(1000 / max_tick_rate as u64);
-                app.add_systems(
-                    PostUpdate,
-                    Self::increment_tick
-                        .before(Self::send_replication)
-                        .run_if(server_running)
-                        .run_if(on_timer(tick_time)),
-                );
-            }
-            TickPolicy::EveryFrame => {
-                app.add_systems(
-                    PostUpdate,
-                    Self::increment_tick
-                        .before(Self::send_replication)
-                        .run_if(server_running),
-                );
-            }
-            TickPolicy::Manual => (),
-        }
+        app.add_systems(
+            PostUpdate,
+            (
+                Self::update_tick_timer,
+                Self::increment_tick.run_if(tick_timer),
+                Self::catch_up_tick.run_if(tick_timer),
+            )
+                .chain()
+                .before(Self::send_replication)
+                .run_if(server_running),
+        );
     }
 }
 
@@ -149,6 +230,75 @@ impl ServerPlugin {
         trace!("incremented {server_tick:?}");
     }
 
+    /// Calculates how many ticks should be produced this frame according to [`ServerSettings`].
+    fn update_tick_timer(
+        mut timer: ResMut<TickTimer>,
+        settings: Res<ServerSettings>,
+        time: Res<Time>,
+    ) {
+        let TickPolicy::MaxTickRate(max_tick_rate) = settings.tick_policy else {
+            timer.backlog = 0;
+            timer.ticks = 0;
+            return;
+        };
+
+        let tick_time = Duration::from_millis(1000 / max_tick_rate as u64);
+        if timer.timer.duration() != tick_time {
+            timer.timer = Timer::new(tick_time, TimerMode::Repeating);
+        }
+        timer.timer.tick(time.delta());
+        let periods = timer.timer.times_finished_this_tick();
+
+        timer.ticks = match settings.stall_policy {
+            StallPolicy::Drop => periods.min(1),
+            StallPolicy::CatchUp { max_ticks } => {
+                timer.backlog = timer.backlog.saturating_add(periods);
+                let ticks = timer.backlog.min(max_ticks.max(1));
+                timer.backlog -= ticks;
+                ticks
+            }
+            StallPolicy::Jump => periods,
+        };
+    }
+
+    /// Increments the tick by the remaining ticks that should be produced this frame.
+    ///
+    /// Runs after [`Self::increment_tick`], which increments the first one.
+    fn catch_up_tick(
+        timer: Res<TickTimer>,
+        settings: Res<ServerSettings>,
+        mut server_tick: ResMut<ServerTick>,
+        connected_clients: Res<ConnectedClients>,
+        mut server: ResMut<RepliconServer>,
+        mut jumped_events: EventWriter<TickJumped>,
+    ) {
+        let extra_ticks = timer.ticks.saturating_sub(1);
+        if extra_ticks == 0 {
+            return;
+        }
+
+        // Already incremented once.
+        let from = **server_tick - 1;
+        server_tick.increment_by(extra_ticks);
+        trace!("caught up to {server_tick:?}");
+        if settings.stall_policy == StallPolicy::Jump {
+            debug!("jumped from {from:?} to {:?}", **server_tick);
+            let event = TickJumped {
+                from,
+                to: **server_tick,
+            };
+            let message = Bytes::from(
+                wire_format::varint()
+                    .serialize(&event)
+                    .expect("tick jump should be serializable"),
+            );
+            for client in connected_clients.iter() {
+                server.send(client.id(), ReplicationChannel::TickJumps, message.clone());
+            }
+            jumped_events.send(event);
+        }
+    }
+
     fn handle_connections(
         mut server_events: EventReader<ServerEvent>,
         mut entity_map: ResMut<ClientEntityMap>,
@@ -170,15 +320,42 @@ impl ServerPlugin {
         }
     }
 
+    /// Forgets components that were enabled back by [`ReplicationRules::set_enabled`]
+    /// after [`Self::send_replication`] sent their current values.
+    fn clear_reenabled(mut rules: ResMut<ReplicationRules>) {
+        rules.bypass_change_detection().clear_reenabled();
+    }
+
     fn cleanup_acks(
-        update_timeout: Duration,
-    ) -> impl FnMut(ResMut<ConnectedClients>, ResMut<ClientBuffers>, Res<Time>) {
-        move |mut connected_clients: ResMut<ConnectedClients>,
-              mut client_buffers: ResMut<ClientBuffers>,
-              time: Res<Time>| {
-            let min_timestamp = time.elapsed().saturating_sub(update_timeout);
-            for client in connected_clients.iter_mut() {
-                client.remove_older_updates(&mut client_buffers, min_timestamp);
+        mut connected_clients: ResMut<ConnectedClients>,
+        mut client_buffers: ResMut<ClientBuffers>,
+        component_stages: Option<ResMut<ComponentStages>>,
+        settings: Res<ServerSettings>,
+        time: Res<Time>,
+    ) {
+        let min_timestamp = time.elapsed().saturating_sub(settings.update_timeout);
+        for client in connected_clients.iter_mut() {
+            client.remove_older_updates(&mut client_buffers, min_timestamp);
+            let removed = client.compact();
+            if removed != 0 {
+                debug!(
+                    "removed {removed} stale change limits for {:?}",
+                    client.id()
+                );
+            }
+        }
+
+        if let Some(mut stages) = component_stages {
+            // Stage state, such as delta baselines, is needed only for entities tracked by at least one client.
+            let removed = stages.retain(|entity| {
+                connected_clients
+                    .iter()
+                    .any(|client| client.get_change_limit(entity).is_some())
+            });
+            if removed != 0 {
+                debug!(
+                    "removed component stage state of {removed} entities outside of client scopes"
+                );
             }
         }
     }
@@ -193,7 +370,7 @@ impl ServerPlugin {
             let mut cursor = Cursor::new(&*message);
             let message_end = message.len() as u64;
             while cursor.position() < message_end {
-                match bincode::deserialize_from(&mut cursor) {
+                match cursor.read_u16_varint() {
                     Ok(update_index) => {
                         let client = connected_clients.client_mut(client_id);
                         client.acknowledge(
@@ -212,6 +389,10 @@ impl ServerPlugin {
     pub(super) fn send_replication(
         mut messages: Local<ReplicationMessages>,
         mut replicated_archetypes: Local<ReplicatedArchetypes>,
+        mut component_sizes: Local<Vec<(ComponentId, usize)>>,
+        mut entity_costs: Local<Vec<(Entity, usize)>>,
+        mut sent_components: Local<Vec<SentComponent>>,
+        mut paused_clients: Local<Vec<ConnectedClient>>,
         change_tick: SystemChangeTick,
         mut set: ParamSet<(
             &World,
@@ -221,39 +402,90 @@ impl ServerPlugin {
             ResMut<RemovalBuffer>,
             ResMut<ClientBuffers>,
             ResMut<RepliconServer>,
+            (
+                Option<ResMut<ComponentSizeStats>>,
+                Option<ResMut<ReplicationHeatmap>>,
+                Option<ResMut<ComponentStages>>,
+                Option<ResMut<SendLog>>,
+            ),
         )>,
         replication_fns: Res<ReplicationFns>,
         rules: Res<ReplicationRules>,
         server_tick: Res<ServerTick>,
+        settings: Res<ServerSettings>,
         time: Res<Time>,
+        real_time: Res<Time<Real>>,
     ) -> bincode::Result<()> {
         replicated_archetypes.update(set.p0(), &rules);
 
-        let connected_clients = mem::take(&mut *set.p1()); // Take ownership to avoid borrowing issues.
+        let mut connected_clients = mem::take(&mut *set.p1()); // Take ownership to avoid borrowing issues.
+        connected_clients.take_paused(&mut paused_clients);
         messages.prepare(connected_clients);
 
         collect_mappings(&mut messages, &mut set.p2())?;
         collect_despawns(&mut messages, &mut set.p3())?;
         collect_removals(&mut messages, &mut set.p4(), change_tick.this_run())?;
+        let disabled_ids = rules.disabled_ids(set.p0());
+        let reenabled_ids = rules.reenabled_ids(set.p0());
+        let (size_stats, heatmap, component_stages, send_log) = set.p7();
+        let record_sizes = size_stats.is_some();
+        let record_sent = send_log.is_some();
+        let record_costs = heatmap.is_some_and(|heatmap| heatmap.is_sampling());
+        let mut component_stages = component_stages
+            .filter(|stages| !stages.is_empty())
+            .and_then(|mut stages| {
+                if settings.delta_compression {
+                    Some(mem::take(&mut *stages))
+                } else {
+                    // Baselines can't be used after re-enabling since values sent in full aren't recorded.
+                    stages.retain(|_| false);
+                    None
+                }
+            });
         collect_changes(
             &mut messages,
-            &replicated_archetypes,
+            &mut replicated_archetypes,
             &replication_fns,
+            &disabled_ids,
+            &reenabled_ids,
+            record_sizes.then_some(&mut *component_sizes),
+            record_costs.then_some(&mut *entity_costs),
+            record_sent.then_some(&mut *sent_components),
+            component_stages.as_mut(),
             set.p0(),
             &change_tick,
             **server_tick,
         )?;
+        let (size_stats, heatmap, stages, send_log) = set.p7();
+        if let (Some(mut stages), Some(component_stages)) = (stages, component_stages) {
+            *stages = component_stages;
+        }
+        if let Some(mut stats) = size_stats {
+            for (component_id, size) in component_sizes.drain(..) {
+                stats.record(component_id, size);
+            }
+        }
+        if let Some(mut heatmap) = heatmap {
+            for (entity, bytes) in entity_costs.drain(..) {
+                heatmap.record(entity, bytes);
+            }
+        }
+        if let Some(mut send_log) = send_log {
+            send_log.record(**server_tick, sent_components.drain(..));
+        }
 
         let mut client_buffers = mem::take(&mut *set.p5());
-        let connected_clients = messages.send(
+        let mut connected_clients = messages.send(
             &mut set.p6(),
             &mut client_buffers,
             **server_tick,
             change_tick.this_run(),
             time.elapsed(),
+            settings.send_time.then(|| real_time.elapsed()),
         )?;
 
         // Return borrowed data back.
+        connected_clients.restore_paused(&mut paused_clients);
         *set.p1() = connected_clients;
         *set.p5() = client_buffers;
 
@@ -270,6 +502,40 @@ impl ServerPlugin {
         entity_map.0.clear();
         connected_clients.clear(&mut client_buffers);
     }
+
+    fn reset_tick_timer(mut timer: ResMut<TickTimer>) {
+        *timer = Default::default();
+    }
+}
+
+/// Returns `true` when the server tick should be incremented according to [`ServerSettings::tick_policy`].
+fn tick_timer(timer: Res<TickTimer>, settings: Res<ServerSettings>) -> bool {
+    match settings.tick_policy {
+        TickPolicy::MaxTickRate(_) => timer.ticks > 0,
+        TickPolicy::EveryFrame => true,
+        TickPolicy::Manual => false,
+    }
+}
+
+/// Timer for [`TickPolicy::MaxTickRate`].
+#[derive(Default, Resource)]
+struct TickTimer {
+    timer: Timer,
+
+    /// Elapsed tick periods that weren't produced yet for [`StallPolicy::CatchUp`].
+    backlog: u32,
+
+    /// Number of ticks that should be produced this frame.
+    ticks: u32,
+}
+
+/// Returns `true` when outdated updates should be cleaned up according to [`ServerSettings::update_timeout`].
+fn cleanup_timer(mut timer: Local<Timer>, settings: Res<ServerSettings>, time: Res<Time>) -> bool {
+    if timer.duration() != settings.update_timeout {
+        *timer = Timer::new(settings.update_timeout, TimerMode::Repeating);
+    }
+    timer.tick(time.delta());
+    timer.just_finished()
 }
 
 /// Collects and writes any new entity mappings that happened in this tick.
@@ -297,8 +563,14 @@ fn collect_mappings(
 /// since the last entity tick.
 fn collect_changes(
     messages: &mut ReplicationMessages,
-    replicated_archetypes: &ReplicatedArchetypes,
+    replicated_archetypes: &mut ReplicatedArchetypes,
     replication_fns: &ReplicationFns,
+    disabled_ids: &[ComponentId],
+    reenabled_ids: &[ComponentId],
+    mut component_sizes: Option<&mut Vec<(ComponentId, usize)>>,
+    mut entity_costs: Option<&mut Vec<(Entity, usize)>>,
+    mut sent_components: Option<&mut Vec<SentComponent>>,
+    mut component_stages: Option<&mut ComponentStages>,
     world: &World,
     change_tick: &SystemChangeTick,
     server_tick: RepliconTick,
@@ -307,7 +579,54 @@ fn collect_changes(
         init_message.start_array();
     }
 
-    for replicated_archetype in replicated_archetypes.iter() {
+    let woken = world
+        .get_resource::<Hibernation>()
+        .map(|hibernation| hibernation.woken())
+        .filter(|woken| !woken.is_empty());
+    let warm_standby = world
+        .get_resource::<WarmStandby>()
+        .filter(|standby| !standby.is_empty());
+    let clone_buffer = world
+        .get_resource::<CloneBuffer>()
+        .filter(|buffer| !buffer.is_empty());
+    let encrypted_components = world
+        .get_resource::<EncryptedComponents>()
+        .filter(|components| !components.is_empty());
+    let encryption_keys = world.get_resource::<ServerEncryptionKeys>();
+    let concealed_components = world.get_resource::<ConcealedComponents>();
+    let conditions = world
+        .get_resource::<ReplicationConditions>()
+        .filter(|conditions| !conditions.is_empty());
+    let stages = world
+        .get_resource::<ReplicationStages>()
+        .filter(|stages| stages.has_entity_filters());
+    let send_rates = world.get_resource::<SendRates>();
+    // Clients for which updates of the current entity are skipped by filters.
+    let mut filtered_clients = Vec::new();
+    // Clients for which changes of the current entity were delayed by send rates.
+    let mut delayed_clients = Vec::new();
+    let mut stage_cursor = Cursor::new(Vec::new());
+
+    // Archetypes that had nothing to send can be skipped until their components change,
+    // unless entities could be sent without changes.
+    let visibility_changed = messages
+        .iter_mut_with_clients()
+        .any(|(_, _, client)| client.visibility().has_changes());
+    let can_skip = replicated_archetypes.update_sync_context(
+        messages
+            .iter_mut_with_clients()
+            .map(|(_, _, client)| client.id()),
+        disabled_ids,
+        woken.is_none()
+            && reenabled_ids.is_empty()
+            && encrypted_components.is_none()
+            && stages.is_none()
+            && conditions.is_none()
+            && !visibility_changed,
+    );
+
+    let marker_id = replicated_archetypes.marker_id();
+    for replicated_archetype in replicated_archetypes.iter_mut() {
         // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
         let archetype = unsafe {
             world
@@ -324,6 +643,13 @@ fn collect_changes(
                 .unwrap_unchecked()
         };
 
+        if can_skip
+            && replicated_archetype.is_synced(archetype, table, marker_id, change_tick.this_run())
+        {
+            continue;
+        }
+
+        let mut written = false;
         for entity in archetype.entities() {
             for (init_message, update_message, client) in messages.iter_mut_with_clients() {
                 init_message.start_entity_data(entity.id());
@@ -331,6 +657,20 @@ fn collect_changes(
                 client.visibility_mut().cache_visibility(entity.id());
             }
 
+            delayed_clients.clear();
+            delayed_clients.resize(messages.iter_mut().count(), false);
+            filtered_clients.clear();
+            if let Some(stages) = stages {
+                for (_, _, client) in messages.iter_mut_with_clients() {
+                    let ctx = FilterCtx {
+                        world,
+                        client,
+                        server_tick,
+                    };
+                    filtered_clients.push(!stages.filter_entity(&ctx, entity.id()));
+                }
+            }
+
             // SAFETY: all replicated archetypes have marker component with table storage.
             let (_, marker_ticks) = unsafe {
                 get_component_unchecked(
@@ -338,16 +678,23 @@ fn collect_changes(
                     &world.storages().sparse_sets,
                     entity,
                     StorageType::Table,
-                    replicated_archetypes.marker_id(),
+                    marker_id,
                 )
             };
             // If the marker was added in this tick, the entity just started replicating.
             // It could be a newly spawned entity or an old entity with just-enabled replication,
             // so we need to include even old components that were registered for replication.
-            let marker_added =
-                marker_ticks.is_added(change_tick.last_run(), change_tick.this_run());
+            // Entities that left hibernation are also sent as new,
+            // since clients that connected during hibernation don't have them.
+            let marker_added = marker_ticks
+                .is_added(change_tick.last_run(), change_tick.this_run())
+                || woken.is_some_and(|woken| woken.contains(&entity.id()));
 
             for replicated_component in &replicated_archetype.components {
+                if disabled_ids.contains(&replicated_component.component_id) {
+                    continue;
+                }
+
                 // SAFETY: component and storage were obtained from this archetype.
                 let (component, ticks) = unsafe {
                     get_component_unchecked(
@@ -359,62 +706,324 @@ fn collect_changes(
                     )
                 };
 
+                // SAFETY: the component was obtained with this ID.
+                if conditions.is_some_and(|conditions| unsafe {
+                    !conditions.check(replicated_component.component_id, component, world)
+                }) {
+                    continue;
+                }
+
                 let (component_fns, rule_fns) = replication_fns.get(replicated_component.fns_id);
-                let ctx = SerializeCtx { server_tick };
-                let mut shared_bytes = None;
-                for (init_message, update_message, client) in messages.iter_mut_with_clients() {
+                let ctx = SerializeCtx::new(world, server_tick);
+                // Components that were enabled back are sent as new to deliver changes made while disabled.
+                let reenabled = reenabled_ids.contains(&replicated_component.component_id);
+                let per_client = rule_fns.is_per_client();
+                // Bytes from warm standby can be reused only if the component didn't change since caching.
+                let mut shared_bytes = warm_standby
+                    .and_then(|standby| {
+                        standby.get(
+                            entity.id(),
+                            replicated_component.component_id,
+                            ticks,
+                            change_tick.this_run(),
+                        )
+                    })
+                    // Unchanged clones reuse bytes of their template.
+                    .or_else(|| {
+                        clone_buffer.and_then(|buffer| {
+                            buffer.get(
+                                entity.id(),
+                                replicated_component.component_id,
+                                replicated_component.fns_id,
+                                ticks,
+                                change_tick.this_run(),
+                            )
+                        })
+                    });
+                let required_privilege = concealed_components.and_then(|components| {
+                    components.required_privilege(replicated_component.component_id)
+                });
+                let encrypted = encrypted_components.is_some_and(|components| {
+                    components.contains(replicated_component.component_id)
+                });
+                let due = replicated_component.atomic_group.is_some()
+                    || send_rates.is_none_or(|rates| {
+                        rates.is_due(replicated_component.component_id, server_tick)
+                    });
+                // Changes can omit data that is needed only on insertion, so they are serialized separately.
+                let mut shared_update_bytes = None;
+                // Serialized once and encrypted separately for each client.
+                let mut plaintext = None;
+                let mut update_plaintext = None;
+                let component_stage = component_stages
+                    .as_deref_mut()
+                    .and_then(|stages| stages.get_mut(replicated_component.component_id));
+                // Passed to the stage to discard state that no client needs anymore.
+                let mut oldest_limit = None;
+                let mut sent = false;
+                let mut sent_clients = 0;
+                let mut sent_bytes = 0;
+                for (index, (init_message, update_message, client)) in
+                    messages.iter_mut_with_clients().enumerate()
+                {
                     let visibility = client.visibility().cached_visibility();
                     if visibility == Visibility::Hidden {
                         continue;
                     }
 
+                    if component_stage.is_some() {
+                        if let Some(limit) = client.get_change_limit(entity.id()) {
+                            if oldest_limit.is_none_or(|oldest: Tick| {
+                                oldest.is_newer_than(limit, change_tick.this_run())
+                            }) {
+                                oldest_limit = Some(limit);
+                            }
+                        }
+                    }
+
+                    if required_privilege.is_some_and(|privilege| client.privilege() < privilege) {
+                        continue;
+                    }
+
+                    let Some(fns_id) = client.client_fns_id(replicated_component.fns_id) else {
+                        continue;
+                    };
+                    let key = if encrypted {
+                        // Clients without a key can't decrypt the component, so it's not sent to them.
+                        let Some(key) = encryption_keys.and_then(|keys| keys.get(&client.id()))
+                        else {
+                            continue;
+                        };
+                        Some(key)
+                    } else {
+                        None
+                    };
+                    // Shared bytes include the ID, so they can't be reused with a different one.
+                    // Bytes of per-client components are never shared.
+                    let shared = fns_id == replicated_component.fns_id && !per_client;
+                    let mut client_bytes = None;
+                    let ctx = if per_client {
+                        plaintext = None;
+                        update_plaintext = None;
+                        ctx.for_client(client.id())
+                    } else {
+                        ctx
+                    };
+
                     let new_entity = marker_added || visibility == Visibility::Gained;
-                    if new_entity || ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                    if new_entity
+                        || reenabled
+                        || ticks.is_added(change_tick.last_run(), change_tick.this_run())
                     {
-                        init_message.write_component(
-                            &mut shared_bytes,
-                            rule_fns,
-                            component_fns,
-                            &ctx,
-                            replicated_component.fns_id,
-                            component,
-                        )?;
-                    } else {
-                        let tick = client
-                            .get_change_limit(entity.id())
-                            .expect("entity should be present after adding component");
-                        if ticks.is_changed(tick, change_tick.this_run()) {
-                            update_message.write_component(
-                                &mut shared_bytes,
+                        sent = true;
+                        sent_clients += 1;
+                        let shared_bytes = if shared {
+                            &mut shared_bytes
+                        } else {
+                            &mut client_bytes
+                        };
+                        if let Some(key) = key {
+                            // SAFETY: `component_fns`, `component` and `rule_fns` were created for the same component type.
+                            let bytes = unsafe {
+                                encrypted_bytes(
+                                    &mut plaintext,
+                                    key,
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    fns_id,
+                                    component,
+                                )?
+                            };
+                            init_message.write_component(
+                                &mut Some(&bytes),
                                 rule_fns,
                                 component_fns,
                                 &ctx,
-                                replicated_component.fns_id,
+                                fns_id,
                                 component,
                             )?;
+                            sent_bytes += bytes.len();
+                        } else {
+                            init_message.write_component(
+                                shared_bytes,
+                                rule_fns,
+                                component_fns,
+                                &ctx,
+                                fns_id,
+                                component,
+                            )?;
+                            sent_bytes += shared_bytes.map_or(0, <[u8]>::len);
+                        }
+                    } else if !replicated_component.once
+                        && !filtered_clients.get(index).copied().unwrap_or_default()
+                    {
+                        let tick = client
+                            .get_change_limit(entity.id())
+                            .expect("entity should be present after adding component");
+                        // SAFETY: atomic groups were obtained from this archetype.
+                        if ticks.is_changed(tick, change_tick.this_run())
+                            || replicated_component
+                                .atomic_group
+                                .is_some_and(|index| unsafe {
+                                    atomic_group_changed(
+                                        table,
+                                        &world.storages().sparse_sets,
+                                        entity,
+                                        &replicated_archetype.atomic_groups[index],
+                                        tick,
+                                        change_tick.this_run(),
+                                    )
+                                })
+                        {
+                            if !due {
+                                delayed_clients[index] = true;
+                                continue;
+                            }
+
+                            sent = true;
+                            sent_clients += 1;
+                            let shared_bytes = if shared {
+                                &mut shared_update_bytes
+                            } else {
+                                &mut client_bytes
+                            };
+                            let ctx = ctx.for_update();
+                            if let Some(key) = key {
+                                // SAFETY: `component_fns`, `component` and `rule_fns` were created for the same component type.
+                                let bytes = unsafe {
+                                    encrypted_bytes(
+                                        &mut update_plaintext,
+                                        key,
+                                        rule_fns,
+                                        component_fns,
+                                        &ctx,
+                                        fns_id,
+                                        component,
+                                    )?
+                                };
+                                update_message.write_component(
+                                    &mut Some(&bytes),
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    fns_id,
+                                    component,
+                                )?;
+                                sent_bytes += bytes.len();
+                            } else if unsafe {
+                                // SAFETY: the component was obtained with the ID of the stage.
+                                stage_bytes(
+                                    &mut stage_cursor,
+                                    component_stage.as_deref(),
+                                    entity.id(),
+                                    component,
+                                    fns_id,
+                                    tick,
+                                    change_tick.this_run(),
+                                )?
+                            } {
+                                update_message.write_component(
+                                    &mut Some(stage_cursor.get_ref()),
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    fns_id,
+                                    component,
+                                )?;
+                                sent_bytes += stage_cursor.get_ref().len();
+                            } else {
+                                update_message.write_component(
+                                    shared_bytes,
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    fns_id,
+                                    component,
+                                )?;
+                                sent_bytes += shared_bytes.map_or(0, <[u8]>::len);
+                            }
                         }
                     }
                 }
+
+                if let (true, Some(stage)) = (sent, component_stage) {
+                    // SAFETY: the component was obtained with the ID of the stage.
+                    unsafe {
+                        stage.sent(entity.id(), component, oldest_limit, change_tick.this_run())
+                    };
+                }
+
+                if let (Some(component_sizes), Some(bytes)) = (
+                    component_sizes.as_deref_mut(),
+                    shared_bytes.or(shared_update_bytes),
+                ) {
+                    component_sizes.push((replicated_component.component_id, bytes.len()));
+                }
+
+                if let Some(sent_components) = sent_components.as_deref_mut() {
+                    if sent_clients != 0 {
+                        sent_components.push(SentComponent {
+                            tick: server_tick,
+                            entity: entity.id(),
+                            component_id: replicated_component.component_id,
+                            clients: sent_clients,
+                            bytes: sent_bytes,
+                        });
+                    }
+                }
             }
 
-            for (init_message, update_message, client) in messages.iter_mut_with_clients() {
+            let mut entity_cost = 0;
+            for ((init_message, update_message, client), delayed) in messages
+                .iter_mut_with_clients()
+                .zip(delayed_clients.iter().copied())
+            {
                 let visibility = client.visibility().cached_visibility();
                 if visibility == Visibility::Hidden {
                     continue;
                 }
 
+                entity_cost += init_message.entity_data_size() as usize
+                    + update_message.entity_data_size() as usize;
+
                 let new_entity = marker_added || visibility == Visibility::Gained;
+                // Delayed changes should be checked again on the next tick.
+                if new_entity
+                    || delayed
+                    || init_message.entity_data_size() != 0
+                    || update_message.entity_data_size() != 0
+                {
+                    written = true;
+                }
+
                 if new_entity || init_message.entity_data_size() != 0 {
                     // If there is any insertion or we must initialize, include all updates into init message
                     // and bump the last acknowledged tick to keep entity updates atomic.
+                    // With delayed changes the tick is kept to send them later.
                     init_message.take_entity_data(update_message)?;
-                    client.set_change_limit(entity.id(), change_tick.this_run());
+                    if !delayed {
+                        client.set_change_limit(entity.id(), change_tick.this_run());
+                    }
                 } else {
-                    update_message.end_entity_data()?;
+                    // Acknowledging would advance the tick past the delayed changes.
+                    update_message.end_entity_data(!delayed)?;
                 }
 
                 init_message.end_entity_data(new_entity)?;
             }
+
+            if let Some(entity_costs) = entity_costs.as_deref_mut() {
+                if entity_cost != 0 {
+                    entity_costs.push((entity.id(), entity_cost));
+                }
+            }
+        }
+
+        if written {
+            replicated_archetype.reset_synced();
+        } else {
+            replicated_archetype.set_synced(archetype, change_tick.this_run());
         }
     }
 
@@ -425,6 +1034,66 @@ fn collect_changes(
     Ok(())
 }
 
+/// Returns replication functions ID and the component encrypted with the key of a client.
+///
+/// The component is serialized into `plaintext` only once and reused for other clients.
+///
+/// # Safety
+///
+/// `component_fns`, `ptr` and `rule_fns` should be created for the same component type.
+#[allow(clippy::too_many_arguments)]
+unsafe fn encrypted_bytes(
+    plaintext: &mut Option<Vec<u8>>,
+    key: &[u8; 32],
+    rule_fns: &UntypedRuleFns,
+    component_fns: &ComponentFns,
+    ctx: &SerializeCtx,
+    fns_id: FnsId,
+    ptr: Ptr,
+) -> bincode::Result<Vec<u8>> {
+    let plaintext = match plaintext {
+        Some(plaintext) => plaintext,
+        None => {
+            let mut cursor = Cursor::new(Vec::new());
+            component_fns.serialize(ctx, rule_fns, ptr, &mut cursor)?;
+            plaintext.insert(cursor.into_inner())
+        }
+    };
+
+    let encrypted = component_encryption::encrypt(key, fns_id, plaintext);
+
+    let mut cursor = Cursor::new(Vec::new());
+    wire_format::varint().serialize_into(&mut cursor, &fns_id)?;
+    wire_format::varint().serialize_into(&mut cursor, &encrypted)?;
+
+    Ok(cursor.into_inner())
+}
+
+/// Writes the component change with its stage into the cursor if it has one.
+///
+/// Returns `false` if the full component should be written instead.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` points to the component of the stage.
+unsafe fn stage_bytes(
+    cursor: &mut Cursor<Vec<u8>>,
+    stage: Option<&dyn ComponentStage>,
+    entity: Entity,
+    ptr: Ptr,
+    fns_id: FnsId,
+    change_limit: Tick,
+    this_run: Tick,
+) -> bincode::Result<bool> {
+    let Some(stage) = stage else {
+        return Ok(false);
+    };
+
+    cursor.get_mut().clear();
+    cursor.set_position(0);
+    stage.write_update(entity, ptr, fns_id, change_limit, this_run, cursor)
+}
+
 /// Extracts component in form of [`Ptr`] and its ticks from table or sparse set based on its storage type.
 ///
 /// # Safety
@@ -455,6 +1124,26 @@ unsafe fn get_component_unchecked<'w>(
     }
 }
 
+/// Returns `true` if any component from an atomic group changed since the `last_run` tick.
+///
+/// # Safety
+///
+/// All group components should be present in this archetype and have the specified storage types.
+unsafe fn atomic_group_changed(
+    table: &Table,
+    sparse_sets: &SparseSets,
+    entity: &ArchetypeEntity,
+    group: &[(ComponentId, StorageType)],
+    last_run: Tick,
+    this_run: Tick,
+) -> bool {
+    group.iter().any(|&(component_id, storage_type)| {
+        let (_, ticks) =
+            get_component_unchecked(table, sparse_sets, entity, storage_type, component_id);
+        ticks.is_changed(last_run, this_run)
+    })
+}
+
 /// Collect entity despawns from this tick into init messages.
 fn collect_despawns(
     messages: &mut ReplicationMessages,
@@ -498,7 +1187,9 @@ fn collect_removals(
             message.start_entity_data(entity);
             for fns_info in remove_ids {
                 client.set_change_limit(entity, tick);
-                message.write_fns_id(fns_info.fns_id())?;
+                if let Some(fns_id) = client.client_fns_id(fns_info.fns_id()) {
+                    message.write_fns_id(fns_id)?;
+                }
             }
             message.end_entity_data(false)?;
         }
@@ -552,6 +1243,33 @@ pub enum ServerSet {
     SendPackets,
 }
 
+/// Server configuration that can be changed at runtime.
+///
+/// Initialized from [`ServerPlugin`] fields. Changes are applied starting from the next frame,
+/// which allows a dev console or an admin RPC to tune a live server.
+///
+/// Visibility radius is configured by [`InterestRadius`](interest_radius::InterestRadius),
+/// which can be changed at runtime as well.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct ServerSettings {
+    /// Tick configuration, see [`ServerPlugin::tick_policy`].
+    ///
+    /// Switching to [`TickPolicy::Manual`] stops automatic tick increments.
+    pub tick_policy: TickPolicy,
+
+    /// See [`ServerPlugin::stall_policy`].
+    pub stall_policy: StallPolicy,
+
+    /// See [`ServerPlugin::update_timeout`].
+    pub update_timeout: Duration,
+
+    /// See [`ServerPlugin::send_time`].
+    pub send_time: bool,
+
+    /// See [`ServerPlugin::delta_compression`].
+    pub delta_compression: bool,
+}
+
 /// Controls how often [`RepliconTick`] is incremented on the server.
 ///
 /// When [`RepliconTick`] is mutated, the server's replication
@@ -559,6 +1277,7 @@ pub enum ServerSet {
 ///
 /// Note that component updates are replicated over the unreliable channel, so if a component update packet is lost
 /// then component updates won't be resent until the server's replication system runs again.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TickPolicy {
     /// The replicon tick is incremented at most max ticks per second. In practice the tick rate may be lower if the
     /// app's update cycle duration is too long.
@@ -572,6 +1291,40 @@ pub enum TickPolicy {
     Manual,
 }
 
+/// Controls how [`TickPolicy::MaxTickRate`] recovers after a stall, like a hitch or a debugger pause.
+///
+/// Regardless of the policy, replication is sent at most once per frame, so the server never bursts
+/// a backlog of messages. Policies only control how the tick value advances.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallPolicy {
+    /// Missed ticks are dropped and the tick is incremented by 1.
+    ///
+    /// The tick falls behind the wall clock after a stall.
+    #[default]
+    Drop,
+    /// Missed ticks are accumulated and the tick is incremented by up to `max_ticks` per frame
+    /// until the backlog is exhausted.
+    ///
+    /// Replication is still sent once per frame, so ticks skipped within a frame are never sent to clients.
+    CatchUp { max_ticks: u32 },
+    /// The tick is incremented by all missed ticks at once and [`TickJumped`] is emitted.
+    Jump,
+}
+
+/// An event sent to all clients when the tick advances by more than 1 because of [`StallPolicy::Jump`].
+///
+/// Sent over the reserved [`ReplicationChannel::TickJumps`] channel, so it doesn't affect IDs
+/// of channels for user events regardless of the policy. Registered automatically.
+/// Also emitted locally on server.
+#[derive(Clone, Copy, Debug, Deserialize, Event, PartialEq, Eq, Serialize)]
+pub struct TickJumped {
+    /// Tick before the jump.
+    pub from: RepliconTick,
+
+    /// Tick after the jump.
+    pub to: RepliconTick,
+}
+
 /// Controls how visibility will be managed via [`ClientVisibility`](connected_clients::client_visibility::ClientVisibility).
 #[derive(Default, Debug, Clone, Copy)]
 pub enum VisibilityPolicy {
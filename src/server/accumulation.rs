@@ -0,0 +1,110 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use super::{server_tick::ServerTick, ServerSet};
+use crate::core::{common_conditions::server_running, Replicated};
+
+/// Accumulation of changes between sends for [`App`].
+pub trait AppAccumulationExt {
+    /**
+    Combines all changes of the component between sends instead of sending only the latest value.
+
+    Components are serialized when replication is sent on tick, so if a component changes
+    multiple times between ticks, only its latest value is sent. This is what most components need,
+    but some values should be aggregated, like damage taken during the frames between ticks.
+
+    With this option each change on server is combined with the value accumulated since the last
+    sent tick using `accumulate` and the result is written back into the component, so it's what
+    gets sent. The accumulated value is reset after each sent tick and the next change starts
+    a new accumulation.
+
+    The accumulation runs in [`PostUpdate`] before [`ServerSet::Send`], so systems that run
+    after it will see the accumulated value.
+
+    Should be called after the component is registered for replication.
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<DamageTaken>()
+        .accumulate_between_sends::<DamageTaken>(|accumulated, damage| accumulated.0 += damage.0);
+
+    /// Damage taken by the entity during the frame.
+    #[derive(Clone, Component, Deserialize, Serialize)]
+    struct DamageTaken(u32);
+    ```
+    **/
+    fn accumulate_between_sends<C: Component + Clone>(
+        &mut self,
+        accumulate: fn(&mut C, &C),
+    ) -> &mut Self;
+}
+
+impl AppAccumulationExt for App {
+    fn accumulate_between_sends<C: Component + Clone>(
+        &mut self,
+        accumulate: fn(&mut C, &C),
+    ) -> &mut Self {
+        self.insert_resource(AccumulatedValues::<C> {
+            accumulate,
+            values: Default::default(),
+        })
+        .add_systems(
+            PostUpdate,
+            (
+                (cleanup_removed::<C>, accumulate_changed::<C>)
+                    .chain()
+                    .before(ServerSet::Send),
+                reset_accumulated::<C>
+                    .after(ServerSet::Send)
+                    .run_if(resource_changed::<ServerTick>),
+            )
+                .run_if(server_running),
+        )
+    }
+}
+
+fn cleanup_removed<C: Component + Clone>(
+    mut removed: RemovedComponents<C>,
+    mut accumulated_values: ResMut<AccumulatedValues<C>>,
+) {
+    for entity in removed.read() {
+        accumulated_values.values.remove(&entity);
+    }
+}
+
+/// Combines changed components with their accumulated values and writes the result back.
+fn accumulate_changed<C: Component + Clone>(
+    mut accumulated_values: ResMut<AccumulatedValues<C>>,
+    mut components: Query<(Entity, &mut C), (Changed<C>, With<Replicated>)>,
+) {
+    let accumulated_values = &mut *accumulated_values;
+    for (entity, mut component) in &mut components {
+        if let Some(value) = accumulated_values.values.get_mut(&entity) {
+            (accumulated_values.accumulate)(value, &component);
+            // Already marked as changed.
+            *component.bypass_change_detection() = value.clone();
+        } else {
+            accumulated_values.values.insert(entity, component.clone());
+        }
+    }
+}
+
+/// Starts a new accumulation after values were sent.
+fn reset_accumulated<C: Component + Clone>(mut accumulated_values: ResMut<AccumulatedValues<C>>) {
+    accumulated_values.values.clear();
+}
+
+/// Values accumulated since the last sent tick for components marked with
+/// [`AppAccumulationExt::accumulate_between_sends`].
+#[derive(Resource)]
+struct AccumulatedValues<C> {
+    accumulate: fn(&mut C, &C),
+    values: EntityHashMap<C>,
+}
@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::{replicon_server::RepliconServer, ServerEvent, ServerSet};
+use crate::core::{
+    common_conditions::{server_just_stopped, server_running},
+    replicon_channels::{ChannelKind, RepliconChannels},
+    ClientId,
+};
+
+/**
+Limits outgoing bandwidth for each client inside [`RepliconServer`] without network shaping tools.
+
+Intended for tests and local development to exercise logic that depends on limited bandwidth.
+Limits are configured in [`SimulatedBandwidth`]. Each limited client has a budget that refills
+with the specified rate and holds at most one second of traffic. Messages that don't fit the budget
+are held and sent on the next frames if their channel is reliable or dropped if it's
+[`ChannelKind::Unreliable`], like on a congested link. Messages for each client keep their order.
+
+Applied after [`ServerSet::Send`], so all messages sent in it, including replication, are limited.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::ClientId, prelude::*};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, BandwidthSimulationPlugin));
+
+let mut bandwidth = app.world.resource_mut::<SimulatedBandwidth>();
+bandwidth.set_limit(ClientId::new(1), Some(16 * 1024)); // 16 KiB/s for a specific client.
+bandwidth.default_limit = Some(64 * 1024); // 64 KiB/s for all other clients.
+```
+**/
+pub struct BandwidthSimulationPlugin;
+
+impl Plugin for BandwidthSimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulatedBandwidth>().add_systems(
+            PostUpdate,
+            (
+                Self::limit
+                    .after(ServerSet::Send)
+                    .before(ServerSet::SendPackets)
+                    .run_if(server_running),
+                Self::reset.run_if(server_just_stopped),
+            ),
+        );
+    }
+}
+
+impl BandwidthSimulationPlugin {
+    /// Passes sent messages that fit into budgets of their clients and holds or drops the rest.
+    fn limit(
+        mut messages: Local<Vec<(ClientId, u8, Bytes)>>,
+        mut server_events: EventReader<ServerEvent>,
+        mut server: ResMut<RepliconServer>,
+        mut bandwidth: ResMut<SimulatedBandwidth>,
+        channels: Res<RepliconChannels>,
+        time: Res<Time>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+                bandwidth.clients.remove(client_id);
+            }
+        }
+
+        let SimulatedBandwidth {
+            clients,
+            default_limit,
+            limits,
+        } = &mut *bandwidth;
+
+        for (client_id, state) in clients.iter_mut() {
+            let rate = limits.get(client_id).copied().unwrap_or(*default_limit);
+            state.refill(rate, time.delta_seconds());
+        }
+
+        messages.extend(server.drain_sent());
+
+        // Held messages were sent earlier, so try them first.
+        for (&client_id, state) in clients.iter_mut() {
+            while let Some(len) = state.held.front().map(|(_, message)| message.len()) {
+                if !state.try_spend(len) {
+                    break;
+                }
+                let (channel_id, message) = state.held.pop_front().unwrap();
+                server.send(client_id, channel_id, message);
+            }
+        }
+
+        for (client_id, channel_id, message) in messages.drain(..) {
+            let Some(rate) = limits.get(&client_id).copied().unwrap_or(*default_limit) else {
+                server.send(client_id, channel_id, message);
+                continue;
+            };
+
+            let state = clients
+                .entry(client_id)
+                .or_insert_with(|| ClientBandwidth::new(rate));
+            if state.held.is_empty() && state.try_spend(message.len()) {
+                server.send(client_id, channel_id, message);
+            } else if channels.server_channels()[channel_id as usize].kind
+                == ChannelKind::Unreliable
+            {
+                trace!(
+                    "dropping message with {} bytes for {client_id:?} over channel {channel_id}",
+                    message.len()
+                );
+                state.dropped_bytes += message.len();
+            } else {
+                state.held.push_back((channel_id, message));
+            }
+        }
+    }
+
+    fn reset(mut bandwidth: ResMut<SimulatedBandwidth>) {
+        bandwidth.clients.clear();
+    }
+}
+
+/// Simulated bandwidth limits in bytes per second.
+///
+/// See [`BandwidthSimulationPlugin`] for details.
+#[derive(Default, Resource)]
+pub struct SimulatedBandwidth {
+    /// Limit for clients without a specific limit.
+    ///
+    /// [`None`] means unlimited.
+    pub default_limit: Option<usize>,
+
+    limits: HashMap<ClientId, Option<usize>>,
+    clients: HashMap<ClientId, ClientBandwidth>,
+}
+
+impl SimulatedBandwidth {
+    /// Sets a limit for a specific client, overriding [`Self::default_limit`].
+    ///
+    /// [`None`] means unlimited.
+    pub fn set_limit(&mut self, client_id: ClientId, limit: Option<usize>) {
+        self.limits.insert(client_id, limit);
+    }
+
+    /// Removes the specific limit for a client, so [`Self::default_limit`] will be used.
+    pub fn remove_limit(&mut self, client_id: ClientId) {
+        self.limits.remove(&client_id);
+    }
+
+    /// Returns the limit that applies to the client.
+    pub fn limit(&self, client_id: ClientId) -> Option<usize> {
+        self.limits
+            .get(&client_id)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// Returns the number of bytes held for the client because they didn't fit the budget.
+    pub fn held_bytes(&self, client_id: ClientId) -> usize {
+        self.clients.get(&client_id).map_or(0, |state| {
+            state.held.iter().map(|(_, message)| message.len()).sum()
+        })
+    }
+
+    /// Returns the total number of bytes that were dropped for the client.
+    pub fn dropped_bytes(&self, client_id: ClientId) -> usize {
+        self.clients
+            .get(&client_id)
+            .map_or(0, |state| state.dropped_bytes)
+    }
+}
+
+/// Budget state of a limited client.
+struct ClientBandwidth {
+    /// Bytes that can be sent right now.
+    budget: f32,
+
+    /// Maximum budget, equal to one second of traffic.
+    capacity: f32,
+
+    /// Messages from reliable channels that didn't fit the budget.
+    held: VecDeque<(u8, Bytes)>,
+
+    dropped_bytes: usize,
+}
+
+impl ClientBandwidth {
+    fn new(rate: usize) -> Self {
+        Self {
+            budget: rate as f32,
+            capacity: rate as f32,
+            held: Default::default(),
+            dropped_bytes: 0,
+        }
+    }
+
+    /// Adds bytes for the elapsed time, keeping at most one second of traffic.
+    ///
+    /// If the client is no longer limited, the budget becomes unlimited to flush held messages.
+    fn refill(&mut self, rate: Option<usize>, delta_seconds: f32) {
+        match rate {
+            Some(rate) => {
+                self.capacity = rate as f32;
+                self.budget = (self.budget + self.capacity * delta_seconds).min(self.capacity);
+            }
+            None => {
+                self.capacity = f32::INFINITY;
+                self.budget = f32::INFINITY;
+            }
+        }
+    }
+
+    /// Spends the budget if the message fits.
+    ///
+    /// Messages larger than the capacity are allowed with a full budget, otherwise they would never be sent.
+    fn try_spend(&mut self, bytes: usize) -> bool {
+        let bytes = bytes as f32;
+        if self.budget < bytes && self.budget < self.capacity {
+            return false;
+        }
+
+        self.budget -= bytes;
+        true
+    }
+}
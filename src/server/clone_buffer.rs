@@ -0,0 +1,197 @@
+use std::io::Cursor;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, ComponentTicks, Tick},
+        entity::EntityHashMap,
+        system::CommandQueue,
+        world::FromWorld,
+    },
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bincode::Options;
+
+use super::{server_tick::ServerTick, ServerPlugin, ServerSet};
+use crate::{
+    client::server_entity_map::ServerEntityMap,
+    core::{
+        command_markers::EntityMarkers,
+        common_conditions::server_running,
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            FnsId, ReplicationFns,
+        },
+        replication_rules::ReplicationRules,
+        wire_format,
+    },
+    Replicated,
+};
+
+/// Clears [`CloneBuffer`] after sending replication.
+pub(super) struct CloneBufferPlugin;
+
+impl Plugin for CloneBufferPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CloneBuffer>().add_systems(
+            PostUpdate,
+            Self::clear
+                .after(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send)
+                .run_if(server_running)
+                .run_if(resource_changed::<ServerTick>),
+        );
+    }
+}
+
+impl CloneBufferPlugin {
+    fn clear(mut clone_buffer: ResMut<CloneBuffer>) {
+        if !clone_buffer.is_empty() {
+            clone_buffer.templates.clear();
+            clone_buffer.clones.clear();
+        }
+    }
+}
+
+/// Serialized components of cloned entities.
+///
+/// Used to write bytes of a template into replication messages for all its clones
+/// instead of serializing each clone separately.
+#[derive(Default, Resource)]
+pub(crate) struct CloneBuffer {
+    /// Serialized components of each template with the [`FnsId`] they were serialized with.
+    ///
+    /// Bytes include the serialized [`FnsId`], like bytes in replication messages.
+    templates: Vec<HashMap<ComponentId, (FnsId, Vec<u8>)>>,
+
+    /// Maps clones to their template index and the tick at which they were cloned.
+    clones: EntityHashMap<(usize, Tick)>,
+}
+
+impl CloneBuffer {
+    pub(super) fn is_empty(&self) -> bool {
+        self.clones.is_empty()
+    }
+
+    /// Returns template bytes for a clone's component if it didn't change since cloning.
+    pub(super) fn get(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+        fns_id: FnsId,
+        ticks: ComponentTicks,
+        this_run: Tick,
+    ) -> Option<&[u8]> {
+        let &(index, tick) = self.clones.get(&entity)?;
+        if ticks.is_changed(tick, this_run) {
+            return None;
+        }
+
+        let (cached_id, bytes) = self.templates[index].get(&component_id)?;
+        (*cached_id == fns_id).then_some(bytes)
+    }
+}
+
+/// Inserts replicated components of the template into each clone.
+///
+/// Components are serialized once and deserialized for each clone without mapping entities,
+/// so entities inside components keep pointing to the same entities.
+pub(super) fn clone_replicated(
+    world: &mut World,
+    template: Entity,
+    clones: &[Entity],
+) -> bincode::Result<()> {
+    let Some(location) = world
+        .get_entity(template)
+        .filter(|entity| entity.contains::<Replicated>())
+        .map(|entity| entity.location())
+    else {
+        warn!("unable to clone {template:?} since it isn't replicated");
+        for &entity in clones {
+            world.despawn(entity);
+        }
+        return Ok(());
+    };
+
+    let server_tick = world
+        .get_resource::<ServerTick>()
+        .copied()
+        .unwrap_or_default();
+    let ctx = SerializeCtx::new(world, *server_tick);
+    let replication_fns = world.resource::<ReplicationFns>();
+    let rules = world.resource::<ReplicationRules>();
+    let archetype = &world.archetypes()[location.archetype_id];
+
+    // Rules are sorted by priority, so functions of the first matching rule are used for each component.
+    let mut components = HashMap::new();
+    let mut cloned_ids = HashSet::new();
+    for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
+        for fns_info in &rule.components {
+            if !cloned_ids.insert(fns_info.component_id()) {
+                continue;
+            }
+
+            let (component_fns, rule_fns) = replication_fns.get(fns_info.fns_id());
+            let component = world
+                .get_by_id(template, fns_info.component_id())
+                .expect("archetype should contain the component");
+            let mut cursor = Cursor::new(Vec::new());
+            wire_format::varint().serialize_into(&mut cursor, &fns_info.fns_id())?;
+            // SAFETY: `component_fns`, `component` and `rule_fns` were created for the same component type.
+            unsafe { component_fns.serialize(&ctx, rule_fns, component, &mut cursor)? };
+            components.insert(
+                fns_info.component_id(),
+                (fns_info.fns_id(), cursor.into_inner()),
+            );
+        }
+    }
+
+    world.resource_scope(
+        |world, replication_fns: Mut<ReplicationFns>| -> bincode::Result<()> {
+            let mut queue = CommandQueue::default();
+            let mut entity_map = ServerEntityMap::default();
+            let entity_markers = EntityMarkers::from_world(world);
+            for &entity in clones {
+                world.entity_mut(entity).insert(Replicated);
+                for (fns_id, bytes) in components.values() {
+                    let mut cursor = Cursor::new(&bytes[..]);
+                    wire_format::varint().deserialize_from::<_, FnsId>(&mut cursor)?;
+
+                    let (component_fns, rule_fns) = replication_fns.get(*fns_id);
+                    let world_cell = world.as_unsafe_world_cell();
+                    // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
+                    let mut entity_mut: EntityMut =
+                        unsafe { world_cell.world_mut().entity_mut(entity).into() };
+                    let mut commands =
+                        Commands::new_from_entities(&mut queue, world_cell.entities());
+                    let mut ctx = WriteCtx::new(&mut commands, &mut entity_map, *server_tick)
+                        .ignoring_mapping();
+                    // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+                    unsafe {
+                        component_fns.write(
+                            &mut ctx,
+                            rule_fns,
+                            &entity_markers,
+                            &mut entity_mut,
+                            &mut cursor,
+                        )?;
+                    }
+                    queue.apply(world);
+                }
+            }
+
+            Ok(())
+        },
+    )?;
+
+    // Advance the tick to detect changes from systems that run after this command.
+    let tick = world.increment_change_tick();
+    let mut clone_buffer = world.resource_mut::<CloneBuffer>();
+    let index = clone_buffer.templates.len();
+    clone_buffer.templates.push(components);
+    clone_buffer
+        .clones
+        .extend(clones.iter().map(|&entity| (entity, (index, tick))));
+
+    Ok(())
+}
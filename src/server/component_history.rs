@@ -0,0 +1,179 @@
+use std::{collections::VecDeque, marker::PhantomData};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{server_tick::ServerTick, ServerPlugin, ServerSet};
+use crate::core::{
+    common_conditions::server_running,
+    replication_rules::{AppHelperExt, AppRuleExt},
+    replicon_tick::RepliconTick,
+    Replicated,
+};
+
+/// Component history for [`App`].
+pub trait AppHistoryExt {
+    /**
+    Same as [`AppRuleExt::replicate`], but also records past values of the component on server.
+
+    Each tick the value of the component on replicated entities is stored in [`ComponentHistory`]
+    together with the server tick. Only the last `len` values are kept. This allows gameplay code
+    to query values from the past, for example to check what a client saw for lag compensation.
+
+    Values are recorded in [`PostUpdate`] after the tick increment and before sending replication,
+    so the latest record matches the sent value. [`ComponentHistory`] is inserted automatically and
+    removed together with the component.
+
+    For positions of projectile targets see [`LagCompensationPlugin`](super::lag_compensation::LagCompensationPlugin).
+
+    Should be called on both client and server, since it registers the replication rule.
+    Values are recorded only on server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_with_history::<Transform>(64)
+        .add_systems(Update, check_hits.run_if(server_running));
+
+    fn check_hits(server_tick: Res<ServerTick>, players: Query<&ComponentHistory<Transform>>) {
+        for history in &players {
+            if let Some(transform) = history.get(**server_tick - 10) {
+                info!("10 ticks ago the player was at {}", transform.translation);
+            }
+        }
+    }
+    ```
+    **/
+    fn replicate_with_history<C>(&mut self, len: usize) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned;
+}
+
+impl AppHistoryExt for App {
+    fn replicate_with_history<C>(&mut self, len: usize) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned,
+    {
+        self.insert_resource(HistoryLen::<C> {
+            len,
+            marker: PhantomData,
+        })
+        .replicate::<C>();
+
+        if self.register_helper::<ComponentHistory<C>>() {
+            self.add_systems(
+                PostUpdate,
+                (
+                    remove_history::<C>,
+                    record_history::<C>.run_if(resource_changed::<ServerTick>),
+                )
+                    .chain()
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+        }
+
+        self
+    }
+}
+
+/// Removes history of entities that lost the component.
+fn remove_history<C: Component>(mut commands: Commands, mut removed: RemovedComponents<C>) {
+    for entity in removed.read() {
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<ComponentHistory<C>>();
+        }
+    }
+}
+
+/// Stores current values of components for the current tick.
+pub(super) fn record_history<C: Component + Clone>(
+    mut commands: Commands,
+    history_len: Res<HistoryLen<C>>,
+    server_tick: Res<ServerTick>,
+    mut components: Query<(Entity, &C, Option<&mut ComponentHistory<C>>), With<Replicated>>,
+) {
+    let tick = **server_tick;
+    for (entity, component, history) in &mut components {
+        if let Some(mut history) = history {
+            history.push(tick, component.clone(), history_len.len);
+        } else {
+            let mut history = ComponentHistory::default();
+            history.push(tick, component.clone(), history_len.len);
+            commands.entity(entity).insert(history);
+        }
+    }
+}
+
+/// Number of recorded values for component `C`.
+#[derive(Resource)]
+pub(super) struct HistoryLen<C> {
+    len: usize,
+    marker: PhantomData<C>,
+}
+
+/// Recorded values of component `C` by server tick, sorted from oldest to newest.
+///
+/// Inserted automatically on server for components registered with [`AppHistoryExt::replicate_with_history`].
+#[derive(Component)]
+pub struct ComponentHistory<C>(VecDeque<(RepliconTick, C)>);
+
+impl<C> ComponentHistory<C> {
+    /// Returns the value at the tick.
+    ///
+    /// If there is no record for the tick, returns the last value before it.
+    pub fn get(&self, tick: RepliconTick) -> Option<&C> {
+        self.0
+            .iter()
+            .rev()
+            .find(|&&(record_tick, _)| record_tick <= tick)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the value the specified number of ticks before the latest record.
+    pub fn ago(&self, ticks: u32) -> Option<&C> {
+        let &(last_tick, _) = self.0.back()?;
+        self.get(last_tick - ticks)
+    }
+
+    /// Returns the latest recorded value with its tick.
+    pub fn last(&self) -> Option<&(RepliconTick, C)> {
+        self.0.back()
+    }
+
+    /// Returns recorded values with their ticks from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &(RepliconTick, C)> {
+        self.0.iter()
+    }
+
+    /// Returns the number of recorded values.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if nothing is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Records the value and removes the oldest records to keep at most `max_len`.
+    fn push(&mut self, tick: RepliconTick, value: C, max_len: usize) {
+        self.0.push_back((tick, value));
+        while self.0.len() > max_len {
+            self.0.pop_front();
+        }
+    }
+}
+
+impl<C> Default for ComponentHistory<C> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use super::connected_clients::ClientPrivilege;
+
+/// Component concealment for [`App`].
+pub trait AppConcealExt {
+    /**
+    Replicates the component only to clients with at least the specified privilege.
+
+    Useful for data like hidden stats or spawn timers that only game masters should see.
+    Clients below the privilege still receive the entity with other components.
+    Removals of concealed components are still sent to all clients that see the entity.
+
+    Needed only on server, calling it on client does nothing.
+    See also [`ClientPrivilege`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Health>()
+        .replicate::<LootTable>()
+        .conceal_component::<LootTable>(ClientPrivilege::Moderator);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct LootTable(Vec<u32>);
+    ```
+    **/
+    fn conceal_component<C: Component>(&mut self, privilege: ClientPrivilege) -> &mut Self;
+}
+
+impl AppConcealExt for App {
+    fn conceal_component<C: Component>(&mut self, privilege: ClientPrivilege) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let mut concealed_components = self
+            .world
+            .get_resource_or_insert_with(ConcealedComponents::default);
+        concealed_components.0.retain(|&(id, _)| id != component_id);
+        concealed_components.0.push((component_id, privilege));
+
+        self
+    }
+}
+
+/// Components marked with [`AppConcealExt::conceal_component`] and their required privilege.
+#[derive(Default, Resource)]
+pub struct ConcealedComponents(Vec<(ComponentId, ClientPrivilege)>);
+
+impl ConcealedComponents {
+    /// Returns the privilege required to receive the component or [`None`] if it's not concealed.
+    pub fn required_privilege(&self, component_id: ComponentId) -> Option<ClientPrivilege> {
+        self.0
+            .iter()
+            .find_map(|&(id, privilege)| (id == component_id).then_some(privilege))
+    }
+}
@@ -9,7 +9,7 @@ use bevy::{
 };
 
 use crate::{
-    core::{replicon_tick::RepliconTick, ClientId},
+    core::{replication_fns::FnsId, replicon_tick::RepliconTick, ClientId},
     server::VisibilityPolicy,
 };
 use client_visibility::ClientVisibility;
@@ -103,6 +103,27 @@ impl ConnectedClients {
         self.clients.is_empty()
     }
 
+    /// Moves clients with paused replication and digest-only clients into the buffer.
+    ///
+    /// See also [`Self::restore_paused`].
+    pub(super) fn take_paused(&mut self, paused_clients: &mut Vec<ConnectedClient>) {
+        let mut index = 0;
+        while index < self.clients.len() {
+            if self.clients[index].replication_paused || self.clients[index].digest_only {
+                paused_clients.push(self.clients.swap_remove(index));
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    /// Moves clients from the buffer back.
+    ///
+    /// See also [`Self::take_paused`].
+    pub(super) fn restore_paused(&mut self, paused_clients: &mut Vec<ConnectedClient>) {
+        self.clients.append(paused_clients);
+    }
+
     /// Initializes a new [`ConnectedClient`] for this client.
     ///
     /// Reuses the memory from the buffers if available.
@@ -156,6 +177,9 @@ pub struct ConnectedClient {
     /// Entity visibility settings.
     visibility: ClientVisibility,
 
+    /// Privilege level for visibility and concealed components.
+    privilege: ClientPrivilege,
+
     /// The last tick in which a replicated entity was spawned, despawned, or gained/lost a component from the
     /// perspective of the client.
     ///
@@ -170,6 +194,21 @@ pub struct ConnectedClient {
     ///
     /// See also [`Self::register_update`].
     next_update_index: u16,
+
+    /// Indicates that nothing is replicated to this client until registrations are negotiated.
+    ///
+    /// See also [`ProtocolNegotiationPlugin`](crate::protocol_negotiation::ProtocolNegotiationPlugin).
+    replication_paused: bool,
+
+    /// Indicates that entities are not replicated to this client, only the world digest is sent.
+    ///
+    /// See also [`WorldDigestPlugin`](crate::server::world_digest::WorldDigestPlugin).
+    digest_only: bool,
+
+    /// Server replication function IDs mapped to the client's ones.
+    ///
+    /// Contains only IDs that differ, [`None`] values are for functions missing on the client.
+    fns_map: HashMap<FnsId, Option<FnsId>>,
 }
 
 impl ConnectedClient {
@@ -178,9 +217,13 @@ impl ConnectedClient {
             id,
             ticks: Default::default(),
             visibility: ClientVisibility::new(policy),
+            privilege: Default::default(),
             change_tick: Default::default(),
             updates: Default::default(),
             next_update_index: Default::default(),
+            replication_paused: false,
+            digest_only: false,
+            fns_map: Default::default(),
         }
     }
 
@@ -199,6 +242,78 @@ impl ConnectedClient {
         &mut self.visibility
     }
 
+    /// Returns the client's privilege level.
+    pub fn privilege(&self) -> ClientPrivilege {
+        self.privilege
+    }
+
+    /// Sets the client's privilege level.
+    ///
+    /// All entities visible to the client will be sent again on the next tick to include
+    /// components that depend on the privilege.
+    /// See [`ClientPrivilege`] for details.
+    pub fn set_privilege(&mut self, privilege: ClientPrivilege) {
+        if self.privilege == privilege {
+            return;
+        }
+
+        debug!("changing privilege of {:?} to {privilege:?}", self.id);
+        self.privilege = privilege;
+        self.visibility
+            .change_privilege(privilege, self.ticks.keys().copied());
+    }
+
+    /// Returns `true` if replication to this client is paused until negotiation.
+    pub fn is_replication_paused(&self) -> bool {
+        self.replication_paused
+    }
+
+    /// Pauses replication to this client.
+    ///
+    /// Should be called before the first replication to the client,
+    /// otherwise the client will have stale data after resuming.
+    pub(crate) fn pause_replication(&mut self) {
+        self.replication_paused = true;
+    }
+
+    /// Returns `true` if only the world digest is sent to this client.
+    pub fn is_digest_only(&self) -> bool {
+        self.digest_only
+    }
+
+    /// Sets whether entities should be replicated to this client or only the world digest.
+    ///
+    /// Should be set before the first replication to the client, for example when handling
+    /// [`ServerEvent::ClientConnected`](crate::server::ServerEvent::ClientConnected),
+    /// otherwise the client will keep stale entities.
+    /// See [`WorldDigestPlugin`](crate::server::world_digest::WorldDigestPlugin) for details.
+    pub fn set_digest_only(&mut self, digest_only: bool) {
+        self.digest_only = digest_only;
+    }
+
+    /// Resumes replication with server replication function IDs mapped to the client's ones.
+    ///
+    /// See also [`ReplicationFns::map_rules`](crate::core::replication_fns::ReplicationFns::map_rules).
+    pub(crate) fn resume_replication(&mut self, fns_map: HashMap<FnsId, Option<FnsId>>) {
+        self.replication_paused = false;
+        self.fns_map = fns_map;
+    }
+
+    /// Forgets all entities replicated to this client.
+    ///
+    /// All visible entities will be sent again as new on the next replication to the client.
+    /// See also [`ProtocolReloadPlugin`](crate::protocol_reload::ProtocolReloadPlugin).
+    pub(crate) fn reset_replication(&mut self) {
+        self.ticks.clear();
+        self.updates.clear();
+        self.visibility.resend_all();
+    }
+
+    /// Returns the ID of replication functions on the client or [`None`] if the client doesn't have them.
+    pub(super) fn client_fns_id(&self, fns_id: FnsId) -> Option<FnsId> {
+        self.fns_map.get(&fns_id).copied().unwrap_or(Some(fns_id))
+    }
+
     /// Sets the client's change tick.
     pub(super) fn set_change_tick(&mut self, tick: RepliconTick) {
         self.change_tick = tick;
@@ -225,9 +340,13 @@ impl ConnectedClient {
     fn reset(&mut self, id: ClientId) {
         self.id = id;
         self.visibility.clear();
+        self.privilege = Default::default();
         self.ticks.clear();
         self.updates.clear();
         self.next_update_index = 0;
+        self.replication_paused = false;
+        self.digest_only = false;
+        self.fns_map.clear();
     }
 
     /// Registers update at specified `tick` and `timestamp` and returns its index with entities to fill.
@@ -268,10 +387,39 @@ impl ConnectedClient {
     }
 
     /// Gets the change limit for an entity that is replicated to this client.
-    pub fn get_change_limit(&mut self, entity: Entity) -> Option<Tick> {
+    pub fn get_change_limit(&self, entity: Entity) -> Option<Tick> {
         self.ticks.get(&entity).copied()
     }
 
+    /// Returns the number of entities with change limits for this client.
+    ///
+    /// Should stay close to the number of replicated entities visible to the client.
+    pub fn tracked_entities(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Removes change limits of entities that are no longer visible and frees unused memory.
+    ///
+    /// Change limits are already removed on despawns and visibility loss, so this is a safety net
+    /// that also returns memory after spikes in the number of visible entities.
+    ///
+    /// Returns the number of removed change limits.
+    pub(super) fn compact(&mut self) -> usize {
+        let len = self.ticks.len();
+        let visibility = &self.visibility;
+        self.ticks
+            .retain(|&entity, _| visibility.is_visible(entity));
+
+        if self.ticks.capacity() > 2 * self.ticks.len() {
+            self.ticks.shrink_to(self.ticks.len());
+        }
+        if self.updates.capacity() > 2 * self.updates.len() {
+            self.updates.shrink_to(self.updates.len());
+        }
+
+        len - self.ticks.len()
+    }
+
     /// Marks update with the specified index as acknowledged.
     ///
     /// Change limits for all entities from this update will be set to the update's tick if it's higher.
@@ -350,6 +498,27 @@ impl ConnectedClient {
     }
 }
 
+/// Privilege level of a connected client.
+///
+/// Levels are ordered, higher levels include everything available to lower ones.
+/// Clients with [`Self::Admin`] see entities hidden by [`ClientVisibility`], which is
+/// useful for game masters and moderators without per-entity overrides.
+/// Components can also be concealed from clients below a certain level with
+/// [`AppConcealExt::conceal_component`](crate::server::concealed_components::AppConcealExt::conceal_component).
+///
+/// Lowering the privilege despawns entities that are no longer visible on the client,
+/// but keeps already replicated concealed components on visible entities.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ClientPrivilege {
+    /// Regular player.
+    #[default]
+    Regular,
+    /// Receives components concealed up to this level, but not hidden entities.
+    Moderator,
+    /// Receives all hidden entities and concealed components.
+    Admin,
+}
+
 /// Reusable buffers for [`ConnectedClients`] and [`ConnectedClient`].
 #[derive(Default, Resource)]
 pub(crate) struct ClientBuffers {
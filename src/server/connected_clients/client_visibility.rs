@@ -4,12 +4,25 @@ use bevy::{
     utils::hashbrown::hash_map::Entry,
 };
 
-use super::VisibilityPolicy;
+use super::{ClientPrivilege, VisibilityPolicy};
 
 /// Entity visibility settings for a client.
 pub struct ClientVisibility {
     filter: VisibilityFilter,
 
+    /// Makes entities hidden by the filter visible.
+    ///
+    /// See also [`ClientPrivilege::Admin`].
+    privileged: bool,
+
+    /// Indicates that the privilege changed in this tick or all entities should be sent again.
+    ///
+    /// All visible entities are sent as gained to include components that depend on the privilege.
+    privilege_changed: bool,
+
+    /// Entities hidden by the filter that were visible before the privilege was revoked in this tick.
+    revoked: EntityHashSet,
+
     /// Visibility for a specific entity that has been cached for re-referencing.
     ///
     /// Used as an optimization by server replication.
@@ -40,6 +53,9 @@ impl ClientVisibility {
     fn with_filter(filter: VisibilityFilter) -> Self {
         Self {
             filter,
+            privileged: false,
+            privilege_changed: false,
+            revoked: Default::default(),
             cached_visibility: Default::default(),
         }
     }
@@ -48,6 +64,9 @@ impl ClientVisibility {
     ///
     /// `cached_visibility` remains untouched.
     pub(super) fn clear(&mut self) {
+        self.privileged = false;
+        self.privilege_changed = false;
+        self.revoked.clear();
         match &mut self.filter {
             VisibilityFilter::All { just_connected } => *just_connected = true,
             VisibilityFilter::Blacklist {
@@ -75,6 +94,7 @@ impl ClientVisibility {
     ///
     /// Should be called after each tick.
     pub(crate) fn update(&mut self) {
+        self.privilege_changed = false;
         match &mut self.filter {
             VisibilityFilter::All { just_connected } => *just_connected = false,
             VisibilityFilter::Blacklist {
@@ -103,6 +123,21 @@ impl ClientVisibility {
         }
     }
 
+    /// Returns `true` if visibility of any entity changed in this tick.
+    pub(crate) fn has_changes(&self) -> bool {
+        if self.privilege_changed || !self.revoked.is_empty() {
+            return true;
+        }
+
+        match &self.filter {
+            VisibilityFilter::All { just_connected } => *just_connected,
+            VisibilityFilter::Blacklist { added, removed, .. }
+            | VisibilityFilter::Whitelist { added, removed, .. } => {
+                !added.is_empty() || !removed.is_empty()
+            }
+        }
+    }
+
     /// Removes a despawned entity tracked by this client.
     pub(super) fn remove_despawned(&mut self, entity: Entity) {
         match &mut self.filter {
@@ -132,19 +167,63 @@ impl ClientVisibility {
 
     /// Drains all entities for which visibility was lost during this tick.
     pub(super) fn drain_lost_visibility(&mut self) -> impl Iterator<Item = Entity> + '_ {
-        match &mut self.filter {
+        let lost = match &mut self.filter {
             VisibilityFilter::All { .. } => VisibilityLostIter::AllVisible,
             VisibilityFilter::Blacklist { added, .. } => VisibilityLostIter::Lost(added.drain()),
             VisibilityFilter::Whitelist { removed, .. } => {
                 VisibilityLostIter::Lost(removed.drain())
             }
+        };
+
+        // Privileged clients keep entities hidden by the filter.
+        let privileged = self.privileged;
+        lost.filter(move |_| !privileged)
+            .chain(self.revoked.drain())
+    }
+
+    /// Updates visibility after a change of the client's privilege.
+    ///
+    /// `tracked` should contain entities that were replicated to the client.
+    pub(super) fn change_privilege(
+        &mut self,
+        privilege: ClientPrivilege,
+        tracked: impl Iterator<Item = Entity>,
+    ) {
+        let privileged = privilege >= ClientPrivilege::Admin;
+        if self.privileged && !privileged {
+            self.privileged = false;
+            let lost = match &self.filter {
+                VisibilityFilter::All { .. } => None,
+                VisibilityFilter::Blacklist { added, .. } => Some(added),
+                VisibilityFilter::Whitelist { removed, .. } => Some(removed),
+            };
+            // Entities lost by the filter in this tick are already drained with the revoked ones.
+            let revoked: Vec<_> = tracked
+                .filter(|&entity| {
+                    self.get_filter_state(entity) == Visibility::Hidden
+                        && lost.is_none_or(|lost| !lost.contains(&entity))
+                })
+                .collect();
+            self.revoked.extend(revoked);
+        } else if privileged {
+            self.privileged = true;
+            self.revoked.clear();
         }
+        self.privilege_changed = true;
+    }
+
+    /// Marks all visible entities as gained until the next tick.
+    pub(super) fn resend_all(&mut self) {
+        self.privilege_changed = true;
     }
 
     /// Sets visibility for a specific entity.
     ///
     /// Does nothing if the visibility policy for the server plugin is set to [`VisibilityPolicy::All`].
     pub fn set_visibility(&mut self, entity: Entity, visibile: bool) {
+        if visibile {
+            self.revoked.remove(&entity);
+        }
         match &mut self.filter {
             VisibilityFilter::All { .. } => {
                 if visibile {
@@ -249,6 +328,20 @@ impl ClientVisibility {
 
     /// Returns visibility of a specific entity.
     fn get_visibility_state(&self, entity: Entity) -> Visibility {
+        let visibility = self.get_filter_state(entity);
+        if visibility == Visibility::Hidden && !self.privileged {
+            Visibility::Hidden
+        } else if self.privilege_changed {
+            Visibility::Gained
+        } else if visibility == Visibility::Hidden {
+            Visibility::Visible
+        } else {
+            visibility
+        }
+    }
+
+    /// Returns visibility of a specific entity based only on the filter.
+    fn get_filter_state(&self, entity: Entity) -> Visibility {
         match &self.filter {
             VisibilityFilter::All { just_connected } => {
                 if *just_connected {
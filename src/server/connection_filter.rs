@@ -0,0 +1,198 @@
+use std::time::{Duration, SystemTime};
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use super::{replicon_server::RepliconServer, ServerEvent, ServerSet};
+use crate::core::{common_conditions::server_running, ClientId};
+
+/**
+Disconnects clients that are denied by [`ConnectionFilter`].
+
+Connections are checked in [`ServerSet::Receive`]. Denied clients are disconnected with
+[`RepliconServer::disconnect`] and reported with [`ConnectionDenied`].
+Other systems still receive [`ServerEvent::ClientConnected`] for them, so systems that
+react to new connections can check [`ConnectionFilter::is_allowed`] to skip them.
+
+Clients are identified by IDs from the messaging backend. To filter by addresses,
+the backend should derive client IDs from them.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ConnectionFilterPlugin))
+    .add_systems(Update, ban_on_start);
+
+fn ban_on_start(mut filter: ResMut<ConnectionFilter>) {
+    filter.deny(ClientId::new(42), Some(Duration::from_secs(3600)));
+}
+```
+**/
+pub struct ConnectionFilterPlugin;
+
+impl Plugin for ConnectionFilterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectionFilter>()
+            .add_event::<ConnectionDenied>()
+            .add_systems(
+                PreUpdate,
+                Self::filter_connections
+                    .in_set(ServerSet::Receive)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl ConnectionFilterPlugin {
+    fn filter_connections(
+        mut server_events: EventReader<ServerEvent>,
+        mut denied_events: EventWriter<ConnectionDenied>,
+        mut filter: ResMut<ConnectionFilter>,
+        mut server: ResMut<RepliconServer>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                filter.remove_expired();
+                if !filter.is_allowed(client_id) {
+                    info!("denying connection from `{client_id:?}`");
+                    server.disconnect(client_id);
+                    denied_events.send(ConnectionDenied { client_id });
+                }
+            }
+        }
+    }
+}
+
+/// Allowlist and denylist for [`ConnectionFilterPlugin`].
+///
+/// Can be serialized to persist lists between server runs.
+/// Use [`Self::persist`] to save lists on each change.
+#[derive(Default, Deserialize, Resource, Serialize)]
+pub struct ConnectionFilter {
+    /// If `true`, only clients from the allowlist can connect.
+    ///
+    /// By default set to `false`.
+    pub allowlist_only: bool,
+
+    /// Allowed clients with optional expiration time.
+    allowed: HashMap<ClientId, Option<SystemTime>>,
+
+    /// Denied clients with optional expiration time.
+    denied: HashMap<ClientId, Option<SystemTime>>,
+
+    /// Function called after each change of the lists.
+    #[serde(skip)]
+    persist: Option<fn(&ConnectionFilter)>,
+}
+
+impl ConnectionFilter {
+    /// Sets a function that will be called after each change of the lists, for example to save them to a file.
+    pub fn persist(mut self, persist: fn(&ConnectionFilter)) -> Self {
+        self.persist = Some(persist);
+        self
+    }
+
+    /// Adds a client to the allowlist for the duration or permanently.
+    ///
+    /// Allowed clients still can't connect while they are denied.
+    pub fn allow(&mut self, client_id: ClientId, duration: Option<Duration>) {
+        self.allowed.insert(client_id, expiration(duration));
+        self.notify();
+    }
+
+    /// Adds a client to the denylist for the duration or permanently.
+    ///
+    /// Already connected clients are not affected.
+    pub fn deny(&mut self, client_id: ClientId, duration: Option<Duration>) {
+        self.denied.insert(client_id, expiration(duration));
+        self.notify();
+    }
+
+    /// Removes a client from the allowlist.
+    pub fn remove_allowed(&mut self, client_id: ClientId) {
+        if self.allowed.remove(&client_id).is_some() {
+            self.notify();
+        }
+    }
+
+    /// Removes a client from the denylist.
+    pub fn remove_denied(&mut self, client_id: ClientId) {
+        if self.denied.remove(&client_id).is_some() {
+            self.notify();
+        }
+    }
+
+    /// Returns `true` if the client can connect.
+    pub fn is_allowed(&self, client_id: ClientId) -> bool {
+        let now = SystemTime::now();
+        if self
+            .denied
+            .get(&client_id)
+            .is_some_and(|&expiration| is_active(expiration, now))
+        {
+            return false;
+        }
+
+        !self.allowlist_only
+            || self
+                .allowed
+                .get(&client_id)
+                .is_some_and(|&expiration| is_active(expiration, now))
+    }
+
+    /// Returns an iterator over allowed clients with their expiration time.
+    pub fn iter_allowed(&self) -> impl Iterator<Item = (ClientId, Option<SystemTime>)> + '_ {
+        self.allowed
+            .iter()
+            .map(|(&client_id, &expiration)| (client_id, expiration))
+    }
+
+    /// Returns an iterator over denied clients with their expiration time.
+    pub fn iter_denied(&self) -> impl Iterator<Item = (ClientId, Option<SystemTime>)> + '_ {
+        self.denied
+            .iter()
+            .map(|(&client_id, &expiration)| (client_id, expiration))
+    }
+
+    /// Removes entries with passed expiration time.
+    fn remove_expired(&mut self) {
+        let now = SystemTime::now();
+        let len = self.allowed.len() + self.denied.len();
+        self.allowed
+            .retain(|_, &mut expiration| is_active(expiration, now));
+        self.denied
+            .retain(|_, &mut expiration| is_active(expiration, now));
+
+        if self.allowed.len() + self.denied.len() != len {
+            self.notify();
+        }
+    }
+
+    fn notify(&self) {
+        if let Some(persist) = self.persist {
+            (persist)(self);
+        }
+    }
+}
+
+fn expiration(duration: Option<Duration>) -> Option<SystemTime> {
+    duration.map(|duration| SystemTime::now() + duration)
+}
+
+fn is_active(expiration: Option<SystemTime>, now: SystemTime) -> bool {
+    expiration.is_none_or(|expiration| expiration > now)
+}
+
+/// An event that indicates a client was disconnected by [`ConnectionFilterPlugin`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct ConnectionDenied {
+    pub client_id: ClientId,
+}
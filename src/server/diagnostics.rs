@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic,
+    },
+    ecs::component::{ComponentId, Components},
+    prelude::*,
+    time::common_conditions::on_timer,
+    utils::{HashMap, Instant},
+};
+
+use super::connected_clients::ConnectedClients;
+use crate::core::replication_stages::ComponentStages;
+
+/// Serialized size statistics for each replicated component.
+///
+/// Sizes are recorded once per tick for each serialized component and include
+/// the replication functions ID. Only collected if [`ServerDiagnosticsPlugin`] is added.
+///
+/// Flushed to Diagnostics system periodically.
+#[derive(Default, Resource, Debug, Deref)]
+pub struct ComponentSizeStats(HashMap<ComponentId, SizeHistogram>);
+
+impl ComponentSizeStats {
+    /// Records a serialized size for a component.
+    pub(super) fn record(&mut self, component_id: ComponentId, size: usize) {
+        self.0.entry(component_id).or_default().record(size);
+    }
+}
+
+/// Histogram of serialized sizes in bytes.
+///
+/// Values are distributed into power-of-two buckets,
+/// so percentiles are approximate.
+#[derive(Clone, Copy, Debug)]
+pub struct SizeHistogram {
+    count: u32,
+    sum: u64,
+    min: usize,
+    max: usize,
+    buckets: [u32; Self::BUCKETS_COUNT],
+}
+
+impl SizeHistogram {
+    /// Number of buckets, enough to cover the maximum entity data size.
+    const BUCKETS_COUNT: usize = u16::BITS as usize + 1;
+
+    fn record(&mut self, size: usize) {
+        let bucket = (usize::BITS - size.leading_zeros()) as usize;
+        self.buckets[bucket.min(Self::BUCKETS_COUNT - 1)] += 1;
+        self.count += 1;
+        self.sum += size as u64;
+        self.min = self.min.min(size);
+        self.max = self.max.max(size);
+    }
+
+    /// Returns the number of recorded sizes.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the smallest recorded size or `0` if nothing was recorded.
+    pub fn min(&self) -> usize {
+        if self.count == 0 {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    /// Returns the largest recorded size.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the average recorded size.
+    pub fn avg(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum as f64 / self.count as f64
+        }
+    }
+
+    /// Returns an estimate of the size below which the specified fraction of recorded sizes falls.
+    ///
+    /// The estimate is the upper bound of the matching bucket, but never exceeds [`Self::max`].
+    pub fn percentile(&self, fraction: f64) -> usize {
+        let target = (self.count as f64 * fraction).ceil() as u32;
+        let mut accumulated = 0;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            accumulated += count;
+            if accumulated >= target.max(1) {
+                let upper_bound = (1 << bucket) - 1;
+                return upper_bound.min(self.max);
+            }
+        }
+
+        self.max
+    }
+
+    /// Same as [`Self::percentile`] with `0.99`.
+    pub fn p99(&self) -> usize {
+        self.percentile(0.99)
+    }
+}
+
+impl Default for SizeHistogram {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0,
+            min: usize::MAX,
+            max: 0,
+            buckets: Default::default(),
+        }
+    }
+}
+
+/// Plugin to write serialized component sizes to Diagnostics every second.
+///
+/// For each replicated component registers `min`, `avg` and `p99` diagnostics
+/// under [`Self::COMPONENT_SIZE`] followed by the component name.
+/// Useful to catch oversized components during development.
+///
+/// Also registers [`Self::TRACKED_ENTITIES`] and [`Self::DELTA_BASELINES`] to monitor memory used
+/// for per-client change tracking.
+///
+/// Not added by default.
+pub struct ServerDiagnosticsPlugin;
+
+impl Plugin for ServerDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComponentSizeStats>()
+            .register_diagnostic(
+                Diagnostic::new(Self::TRACKED_ENTITIES)
+                    .with_suffix(" entities")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::DELTA_BASELINES)
+                    .with_suffix(" baselines")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .add_systems(
+                Update,
+                Self::add_measurements.run_if(on_timer(Duration::from_secs(1))),
+            );
+    }
+}
+
+impl ServerDiagnosticsPlugin {
+    /// Prefix for serialized component sizes in bytes.
+    pub const COMPONENT_SIZE: &'static str = "replication.server.component_size";
+
+    /// Total number of entities with change limits across all clients.
+    ///
+    /// See [`ConnectedClient::tracked_entities`](super::connected_clients::ConnectedClient::tracked_entities).
+    pub const TRACKED_ENTITIES: DiagnosticPath =
+        DiagnosticPath::const_new("replication.server.tracked_entities");
+
+    /// Number of stored values for delta compression.
+    ///
+    /// See [`ComponentStages::stored_values`].
+    pub const DELTA_BASELINES: DiagnosticPath =
+        DiagnosticPath::const_new("replication.server.delta_baselines");
+
+    /// Max diagnostic history length.
+    pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
+
+    /// Returns diagnostic paths for min, avg and p99 sizes of a component with the specified name.
+    pub fn component_paths(component_name: &str) -> [DiagnosticPath; 3] {
+        ["min", "avg", "p99"].map(|statistic| {
+            DiagnosticPath::new(format!(
+                "{}/{component_name}/{statistic}",
+                Self::COMPONENT_SIZE
+            ))
+        })
+    }
+
+    fn add_measurements(
+        mut stats: ResMut<ComponentSizeStats>,
+        mut store: ResMut<DiagnosticsStore>,
+        components: &Components,
+        connected_clients: Option<Res<ConnectedClients>>,
+        component_stages: Option<Res<ComponentStages>>,
+    ) {
+        let time = Instant::now();
+        if let Some(connected_clients) = connected_clients {
+            let tracked_entities: usize = connected_clients
+                .iter()
+                .map(|client| client.tracked_entities())
+                .sum();
+            if let Some(diagnostic) = store.get_mut(&Self::TRACKED_ENTITIES) {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: tracked_entities as f64,
+                });
+            }
+        }
+        if let Some(component_stages) = component_stages {
+            if let Some(diagnostic) = store.get_mut(&Self::DELTA_BASELINES) {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: component_stages.stored_values() as f64,
+                });
+            }
+        }
+
+        for (&component_id, histogram) in stats.iter() {
+            let component_name = components
+                .get_name(component_id)
+                .expect("stats should be recorded only for registered components");
+
+            let values = [
+                histogram.min() as f64,
+                histogram.avg(),
+                histogram.p99() as f64,
+            ];
+            for (path, value) in Self::component_paths(component_name)
+                .into_iter()
+                .zip(values)
+            {
+                if store.get(&path).is_none() {
+                    store.add(
+                        Diagnostic::new(path.clone())
+                            .with_suffix(" bytes")
+                            .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+                    );
+                }
+
+                let diagnostic = store
+                    .get_mut(&path)
+                    .expect("diagnostic should be registered");
+                diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+            }
+        }
+        stats.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram() {
+        let mut histogram = SizeHistogram::default();
+        assert_eq!(histogram.min(), 0);
+        assert_eq!(histogram.avg(), 0.0);
+        assert_eq!(histogram.p99(), 0);
+
+        for _ in 0..99 {
+            histogram.record(10);
+        }
+        histogram.record(5000);
+
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.min(), 10);
+        assert_eq!(histogram.max(), 5000);
+        assert_eq!(histogram.avg(), 59.9);
+        assert_eq!(histogram.p99(), 15, "bucket upper bound for 10");
+        assert_eq!(histogram.percentile(1.0), 5000);
+    }
+}
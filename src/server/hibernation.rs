@@ -0,0 +1,361 @@
+use bevy::{ecs::entity::EntityHashSet, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    interest_radius::InterestAnchor, server_tick::ServerTick, ServerEvent, ServerPlugin, ServerSet,
+};
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_running},
+        replicon_channels::ChannelKind,
+        replicon_tick::RepliconTick,
+        Replicated,
+    },
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+};
+
+/**
+Stops scanning whole regions of the world for changes.
+
+Assign [`HibernationRegion`] to replicated entities and call [`Hibernation::hibernate`] for regions
+without nearby players. Entities of hibernated regions receive [`Hibernated`] marker and their archetypes
+are excluded from change collection entirely. Clients receive only a [`RegionDigest`] for each
+hibernated region in [`RegionDigests`]. Use [`Hibernation::hibernate_with_summary`] to include
+game-specific data into the digest, like the number of monsters or owned buildings.
+
+Regions that contain an entity with [`InterestAnchor`], like a player character, are woken automatically.
+So a region wakes as soon as a player enters it, even if it was hibernated in the same frame.
+
+After [`Hibernation::wake`] all components of the region entities will be sent to clients,
+including changes that happened during hibernation. Clients that connected during hibernation
+will receive these entities only after the wake. Despawns and removals are always sent.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::hibernation::{Hibernation, HibernationPlugin, HibernationRegion}};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, HibernationPlugin))
+    .add_systems(Update, update_regions.run_if(server_running));
+
+fn update_regions(mut hibernation: ResMut<Hibernation>, players: Query<&Transform, With<Player>>) {
+    // Region 0 is the only region in this example.
+    if players.is_empty() {
+        hibernation.hibernate(0);
+    } else {
+        hibernation.wake(0);
+    }
+}
+
+#[derive(Component)]
+struct Player;
+```
+*/
+pub struct HibernationPlugin;
+
+impl Plugin for HibernationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Hibernation>()
+            .init_resource::<RegionDigests>()
+            .add_server_event::<RegionEvent>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    Self::receive
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        Self::wake_anchored,
+                        Self::update_regions,
+                        Self::update_entities,
+                        Self::send_digests,
+                    )
+                        .chain()
+                        .after(ServerPlugin::increment_tick)
+                        .before(ServerPlugin::send_replication),
+                    Self::clear_woken
+                        .after(ServerPlugin::send_replication)
+                        .run_if(resource_changed::<ServerTick>),
+                )
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl HibernationPlugin {
+    /// Queues wake for hibernated regions with interest anchors.
+    fn wake_anchored(
+        mut hibernation: ResMut<Hibernation>,
+        anchors: Query<&HibernationRegion, With<InterestAnchor>>,
+    ) {
+        for &region in &anchors {
+            if hibernation.will_hibernate(*region) {
+                hibernation.wake(*region);
+            }
+        }
+    }
+
+    /// Applies queued region changes.
+    fn update_regions(
+        mut commands: Commands,
+        mut hibernation: ResMut<Hibernation>,
+        mut region_events: EventWriter<ToClients<RegionEvent>>,
+        server_tick: Res<ServerTick>,
+        entities: Query<
+            (
+                Entity,
+                &HibernationRegion,
+                Has<Hibernated>,
+                Option<&Transform>,
+            ),
+            With<Replicated>,
+        >,
+    ) {
+        if hibernation.queued.is_empty() {
+            return;
+        }
+
+        let hibernation = &mut *hibernation;
+        for (region, summary) in hibernation.queued.drain(..) {
+            let hibernate = summary.is_some();
+            if hibernate == hibernation.digests.contains_key(&region) {
+                continue;
+            }
+
+            let mut entities_count = 0;
+            let mut bounds: Option<(Vec3, Vec3)> = None;
+            for (entity, _, hibernated, transform) in entities
+                .iter()
+                .filter(|(_, entity_region, ..)| ***entity_region == region)
+            {
+                entities_count += 1;
+                if let Some(transform) = transform {
+                    let translation = transform.translation;
+                    bounds = Some(bounds.map_or((translation, translation), |(min, max)| {
+                        (min.min(translation), max.max(translation))
+                    }));
+                }
+
+                if hibernate && !hibernated {
+                    commands.entity(entity).insert(Hibernated);
+                } else if !hibernate && hibernated {
+                    commands.entity(entity).remove::<Hibernated>();
+                    hibernation.woken.insert(entity);
+                }
+            }
+
+            let event = if let Some(summary) = summary {
+                let digest = RegionDigest {
+                    entities_count,
+                    tick: **server_tick,
+                    bounds,
+                    summary,
+                };
+                debug!("hibernating region {region} with {entities_count} entities");
+                hibernation.digests.insert(region, digest.clone());
+                RegionEvent::Hibernated { region, digest }
+            } else {
+                debug!("waking region {region} with {entities_count} entities");
+                hibernation.digests.remove(&region);
+                RegionEvent::Woken { region }
+            };
+
+            region_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event,
+            });
+        }
+    }
+
+    /// Updates hibernation state for entities that were assigned to a region.
+    fn update_entities(
+        mut commands: Commands,
+        mut hibernation: ResMut<Hibernation>,
+        entities: Query<
+            (Entity, &HibernationRegion, Has<Hibernated>),
+            (With<Replicated>, Changed<HibernationRegion>),
+        >,
+    ) {
+        for (entity, region, hibernated) in &entities {
+            let hibernate = hibernation.digests.contains_key(&**region);
+            if hibernate && !hibernated {
+                commands.entity(entity).insert(Hibernated);
+            } else if !hibernate && hibernated {
+                commands.entity(entity).remove::<Hibernated>();
+                hibernation.woken.insert(entity);
+            }
+        }
+    }
+
+    /// Sends digests of all hibernated regions to newly connected clients.
+    fn send_digests(
+        hibernation: Res<Hibernation>,
+        mut server_events: EventReader<ServerEvent>,
+        mut region_events: EventWriter<ToClients<RegionEvent>>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                for (&region, digest) in &hibernation.digests {
+                    region_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: RegionEvent::Hibernated {
+                            region,
+                            digest: digest.clone(),
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    fn clear_woken(mut hibernation: ResMut<Hibernation>) {
+        hibernation.woken.clear();
+    }
+
+    fn receive(mut digests: ResMut<RegionDigests>, mut region_events: EventReader<RegionEvent>) {
+        for event in region_events.read() {
+            match event {
+                RegionEvent::Hibernated { region, digest } => {
+                    trace!("received digest for hibernated region {region}");
+                    digests.insert(*region, digest.clone());
+                }
+                RegionEvent::Woken { region } => {
+                    trace!("received wake for region {region}");
+                    digests.remove(region);
+                }
+            }
+        }
+    }
+
+    fn reset(mut digests: ResMut<RegionDigests>) {
+        digests.clear();
+    }
+}
+
+/// Hibernation state of regions on server.
+///
+/// See [`HibernationPlugin`] for details.
+#[derive(Default, Resource)]
+pub struct Hibernation {
+    /// Digests of hibernated regions.
+    digests: HashMap<u32, RegionDigest>,
+
+    /// Region state changes that will be applied before the next replication.
+    ///
+    /// Contains summary for hibernation and `None` for wake.
+    queued: Vec<(u32, Option<Vec<u8>>)>,
+
+    /// Entities that left hibernation since the last replication.
+    ///
+    /// Will be sent to clients as new.
+    woken: EntityHashSet,
+}
+
+impl Hibernation {
+    /// Stops collecting changes for entities of the region.
+    ///
+    /// Applied before the replication in this frame.
+    pub fn hibernate(&mut self, region: u32) {
+        self.hibernate_with_summary(region, Vec::new());
+    }
+
+    /// Like [`Self::hibernate`], but also sends the application-defined summary inside [`RegionDigest`].
+    ///
+    /// Ignored if the region is already hibernated.
+    pub fn hibernate_with_summary(&mut self, region: u32, summary: Vec<u8>) {
+        self.queued.push((region, Some(summary)));
+    }
+
+    /// Resumes collecting changes for entities of the region.
+    ///
+    /// Applied before the replication in this frame.
+    pub fn wake(&mut self, region: u32) {
+        self.queued.push((region, None));
+    }
+
+    /// Returns `true` if the region will be hibernated after applying queued changes.
+    fn will_hibernate(&self, region: u32) -> bool {
+        self.queued
+            .iter()
+            .rev()
+            .find(|&&(queued_region, _)| queued_region == region)
+            .map_or_else(
+                || self.is_hibernated(region),
+                |(_, summary)| summary.is_some(),
+            )
+    }
+
+    /// Returns `true` if the region is hibernated.
+    ///
+    /// Doesn't include changes queued in this frame.
+    pub fn is_hibernated(&self, region: u32) -> bool {
+        self.digests.contains_key(&region)
+    }
+
+    /// Returns iterator over all hibernated regions.
+    pub fn iter_regions(&self) -> impl Iterator<Item = u32> + '_ {
+        self.digests.keys().copied()
+    }
+
+    /// Returns entities that should be sent to clients as new.
+    pub(super) fn woken(&self) -> &EntityHashSet {
+        &self.woken
+    }
+}
+
+/// Assigns an entity to a region that can be hibernated.
+///
+/// See [`HibernationPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, PartialEq, Eq, Hash)]
+pub struct HibernationRegion(pub u32);
+
+/// Marks an entity whose region is hibernated.
+///
+/// Inserted and removed automatically on server. Entities with this component are not scanned
+/// for changes. Could be used to skip game logic for hibernated regions too.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Hibernated;
+
+/// Lightweight summary of a hibernated region.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RegionDigest {
+    /// Number of replicated entities in the region at the moment of hibernation.
+    pub entities_count: u32,
+
+    /// Server tick at which the region was hibernated.
+    pub tick: RepliconTick,
+
+    /// Minimum and maximum translations of region entities with [`Transform`].
+    ///
+    /// Could be used to draw a placeholder for the region. `None` if no entity has [`Transform`].
+    pub bounds: Option<(Vec3, Vec3)>,
+
+    /// Application-defined data passed to [`Hibernation::hibernate_with_summary`].
+    ///
+    /// Empty for regions hibernated with [`Hibernation::hibernate`].
+    pub summary: Vec<u8>,
+}
+
+/// Digests of hibernated regions received from the server.
+///
+/// Updated only on clients. Cleared on disconnect.
+#[derive(Default, Deref, DerefMut, Resource)]
+pub struct RegionDigests(HashMap<u32, RegionDigest>);
+
+#[derive(Deserialize, Event, Serialize)]
+enum RegionEvent {
+    Hibernated { region: u32, digest: RegionDigest },
+    Woken { region: u32 },
+}
@@ -0,0 +1,105 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{component::ComponentId, system::EntityCommands},
+    prelude::*,
+};
+
+/// Runtime control over replication of individual components for [`EntityCommands`].
+pub trait IgnoredCommandsExt {
+    /**
+    Stops replication of component `C` for this entity by inserting [`Ignored<C>`].
+
+    Insertions and changes of the component won't be sent to clients, clients keep the last received value.
+    All other components of the entity continue replicating. Removals are still sent.
+
+    Ignored components are excluded when the entity's archetype is cached, so the
+    send system doesn't check them per entity.
+
+    Should be called on server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Health>()
+        .add_systems(Update, hide_health.run_if(server_running));
+
+    fn hide_health(mut commands: Commands, players: Query<Entity, Added<Invisible>>) {
+        for entity in &players {
+            commands.entity(entity).dont_replicate::<Health>();
+        }
+    }
+
+    #[derive(Component)]
+    struct Invisible;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+    ```
+    **/
+    fn dont_replicate<C: Component>(&mut self) -> &mut Self;
+
+    /// Resumes replication of component `C` stopped with [`Self::dont_replicate`].
+    ///
+    /// The current value of the component is sent even if it didn't change.
+    fn resume_replication<C: Component>(&mut self) -> &mut Self;
+}
+
+impl IgnoredCommandsExt for EntityCommands<'_> {
+    fn dont_replicate<C: Component>(&mut self) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            let component_id = world.init_component::<C>();
+            let ignored_id = world.init_component::<Ignored<C>>();
+            let mut ignored_components =
+                world.get_resource_or_insert_with(IgnoredComponents::default);
+            if !ignored_components.0.contains(&(component_id, ignored_id)) {
+                ignored_components.0.push((component_id, ignored_id));
+            }
+
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.insert(Ignored::<C>(PhantomData));
+            }
+        })
+    }
+
+    fn resume_replication<C: Component>(&mut self) -> &mut Self {
+        self.add(|entity: Entity, world: &mut World| {
+            let Some(mut entity) = world.get_entity_mut(entity) else {
+                return;
+            };
+            if entity.take::<Ignored<C>>().is_some() {
+                if let Some(mut component) = entity.get_mut::<C>() {
+                    component.set_changed();
+                }
+            }
+        })
+    }
+}
+
+/// Excludes component `C` of an entity from replication.
+///
+/// Can be inserted only with [`IgnoredCommandsExt::dont_replicate`].
+#[derive(Component)]
+pub struct Ignored<C>(PhantomData<C>);
+
+/// Pairs of component IDs and IDs of their [`Ignored`] markers.
+///
+/// Filled by [`IgnoredCommandsExt::dont_replicate`].
+#[derive(Default, Resource)]
+pub(crate) struct IgnoredComponents(Vec<(ComponentId, ComponentId)>);
+
+impl IgnoredComponents {
+    /// Returns ID of the [`Ignored`] marker for a component if it was ever ignored.
+    pub(crate) fn marker_id(&self, component_id: ComponentId) -> Option<ComponentId> {
+        self.0
+            .iter()
+            .find(|&&(id, _)| id == component_id)
+            .map(|&(_, ignored_id)| ignored_id)
+    }
+}
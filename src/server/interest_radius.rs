@@ -0,0 +1,176 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{connected_clients::ConnectedClients, ServerEvent, ServerSet};
+use crate::core::{common_conditions::server_running, ClientId, Replicated};
+
+/**
+Controls visibility of entities by distance to client anchors and expands it progressively after join.
+
+Each client sees replicated entities with [`Transform`] that are within its current radius from any
+entity with [`InterestAnchor`] for this client, like the client's character or camera.
+On connect the radius of a client starts at [`InterestRadius::initial_radius`] and grows
+by [`InterestRadius::expansion_speed`] per second up to [`InterestRadius::radius`].
+This way the client receives nearby entities first and the rest of its scope is streamed over
+the first seconds of play instead of in a single burst, which smooths bandwidth and spawn hitches.
+
+Entities without [`Transform`] aren't affected, so the plugin can be combined with manual control over
+[`ClientVisibility`](super::connected_clients::client_visibility::ClientVisibility). Clients without anchors
+don't see any entities with [`Transform`].
+
+Requires [`VisibilityPolicy::Whitelist`](super::VisibilityPolicy::Whitelist)
+or [`VisibilityPolicy::Blacklist`](super::VisibilityPolicy::Blacklist).
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins.set(ServerPlugin {
+        visibility_policy: VisibilityPolicy::Whitelist,
+        ..Default::default()
+    }),
+    InterestRadiusPlugin,
+))
+.add_systems(Update, spawn_characters.run_if(server_running));
+
+let mut interest = app.world.resource_mut::<InterestRadius>();
+interest.radius = 200.0;
+interest.initial_radius = 20.0;
+interest.expansion_speed = 60.0;
+
+fn spawn_characters(mut commands: Commands, mut server_events: EventReader<ServerEvent>) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            commands.spawn((
+                Replicated,
+                Transform::default(),
+                InterestAnchor { client_id },
+            ));
+        }
+    }
+}
+```
+**/
+pub struct InterestRadiusPlugin;
+
+impl Plugin for InterestRadiusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InterestRadius>().add_systems(
+            PostUpdate,
+            Self::update_visibility
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl InterestRadiusPlugin {
+    /// Expands radii of clients and updates visibility of entities with [`Transform`].
+    fn update_visibility(
+        mut anchor_positions: Local<Vec<Vec3>>,
+        mut server_events: EventReader<ServerEvent>,
+        mut interest: ResMut<InterestRadius>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        time: Res<Time>,
+        anchors: Query<(&InterestAnchor, &Transform)>,
+        entities: Query<(Entity, &Transform), With<Replicated>>,
+    ) {
+        for event in server_events.read() {
+            match event {
+                ServerEvent::ClientConnected { client_id } => {
+                    let initial_radius = interest.initial_radius.min(interest.radius);
+                    interest.client_radii.insert(*client_id, initial_radius);
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    interest.client_radii.remove(client_id);
+                }
+            }
+        }
+
+        let InterestRadius {
+            radius,
+            expansion_speed,
+            client_radii,
+            ..
+        } = &mut *interest;
+        for client in connected_clients.iter_mut() {
+            let client_radius = client_radii.entry(client.id()).or_insert(*radius);
+            *client_radius =
+                (*client_radius + *expansion_speed * time.delta_seconds()).min(*radius);
+
+            anchor_positions.clear();
+            anchor_positions.extend(
+                anchors
+                    .iter()
+                    .filter(|(anchor, _)| anchor.client_id == client.id())
+                    .map(|(_, transform)| transform.translation),
+            );
+
+            let radius_squared = *client_radius * *client_radius;
+            for (entity, transform) in &entities {
+                let visible = anchor_positions.iter().any(|position| {
+                    position.distance_squared(transform.translation) <= radius_squared
+                });
+                client.visibility_mut().set_visibility(entity, visible);
+            }
+        }
+    }
+}
+
+/// Radius settings for [`InterestRadiusPlugin`].
+///
+/// Radii of connected clients are not affected by changes of [`Self::initial_radius`],
+/// but changes of [`Self::radius`] and [`Self::expansion_speed`] apply immediately.
+#[derive(Resource)]
+pub struct InterestRadius {
+    /// Maximum distance from an anchor at which entities are visible.
+    pub radius: f32,
+
+    /// Radius of a client right after connection.
+    ///
+    /// Set it equal to [`Self::radius`] to disable progressive expansion.
+    pub initial_radius: f32,
+
+    /// Growth of the radius in units per second.
+    pub expansion_speed: f32,
+
+    /// Current radii of connected clients.
+    client_radii: HashMap<ClientId, f32>,
+}
+
+impl InterestRadius {
+    /// Returns the current radius of a client.
+    ///
+    /// Clients that weren't seen connecting by the plugin use [`Self::radius`].
+    pub fn client_radius(&self, client_id: ClientId) -> f32 {
+        self.client_radii
+            .get(&client_id)
+            .copied()
+            .unwrap_or(self.radius)
+    }
+}
+
+impl Default for InterestRadius {
+    fn default() -> Self {
+        Self {
+            radius: 100.0,
+            initial_radius: 25.0,
+            expansion_speed: 25.0,
+            client_radii: Default::default(),
+        }
+    }
+}
+
+/// Marks an entity as a point around which the client sees entities.
+///
+/// See [`InterestRadiusPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InterestAnchor {
+    pub client_id: ClientId,
+}
@@ -0,0 +1,204 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{connected_clients::ConnectedClients, ServerEvent, ServerSet};
+use crate::core::{common_conditions::server_running, ClientId};
+
+/// Maximum number of distinct tags in [`InterestTags`].
+pub const MAX_TAGS: u8 = u64::BITS as u8;
+
+/**
+Controls visibility of entities by gameplay tags.
+
+Entities carry a set of tags in [`InterestTags`] and each client holds a subscription mask
+in [`InterestSubscriptions`]. An entity is visible to a client if they share at least one tag.
+Useful for abstract scopes like "auction house" or "guild events" that don't depend on positions.
+
+Visibility is recalculated before sending only for entities whose tags changed and for clients
+whose subscriptions changed or who just connected. Entities without [`InterestTags`] aren't affected,
+so the plugin can be combined with manual control over [`ClientVisibility`](super::connected_clients::client_visibility::ClientVisibility).
+Removing [`InterestTags`] keeps the last visibility of the entity.
+
+Requires [`VisibilityPolicy::Whitelist`](super::VisibilityPolicy::Whitelist)
+or [`VisibilityPolicy::Blacklist`](super::VisibilityPolicy::Blacklist).
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::ClientId, prelude::*};
+
+const AUCTION_HOUSE: InterestTags = InterestTags::tag(0);
+const GUILD_EVENTS: InterestTags = InterestTags::tag(1);
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins.set(ServerPlugin {
+        visibility_policy: VisibilityPolicy::Whitelist,
+        ..Default::default()
+    }),
+    InterestTagsPlugin,
+));
+
+app.world.spawn((Replicated, AUCTION_HOUSE));
+app.world.spawn((Replicated, AUCTION_HOUSE | GUILD_EVENTS));
+
+let mut subscriptions = app.world.resource_mut::<InterestSubscriptions>();
+subscriptions.subscribe(ClientId::new(1), GUILD_EVENTS);
+```
+**/
+pub struct InterestTagsPlugin;
+
+impl Plugin for InterestTagsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InterestSubscriptions>().add_systems(
+            PostUpdate,
+            Self::update_visibility
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl InterestTagsPlugin {
+    /// Updates visibility of tagged entities for clients with changed subscriptions and of entities with changed tags.
+    fn update_visibility(
+        mut server_events: EventReader<ServerEvent>,
+        mut subscriptions: ResMut<InterestSubscriptions>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        entities: Query<(Entity, Ref<InterestTags>)>,
+    ) {
+        for event in server_events.read() {
+            match event {
+                ServerEvent::ClientConnected { client_id } => {
+                    subscriptions.changed.insert(*client_id);
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    subscriptions.masks.remove(client_id);
+                    subscriptions.changed.remove(client_id);
+                }
+            }
+        }
+
+        for client in connected_clients.iter_mut() {
+            let mask = subscriptions.get(client.id());
+            let client_changed = subscriptions.changed.contains(&client.id());
+            for (entity, tags) in &entities {
+                if client_changed || tags.is_changed() {
+                    client
+                        .visibility_mut()
+                        .set_visibility(entity, tags.intersects(mask));
+                }
+            }
+        }
+
+        subscriptions.changed.clear();
+    }
+}
+
+/// Subscription masks of clients for [`InterestTagsPlugin`].
+///
+/// Clients without a subscription don't see any tagged entities.
+/// Subscriptions are removed on disconnect.
+#[derive(Default, Resource)]
+pub struct InterestSubscriptions {
+    masks: HashMap<ClientId, InterestTags>,
+
+    /// Clients whose visibility needs to be recalculated.
+    changed: HashSet<ClientId>,
+}
+
+impl InterestSubscriptions {
+    /// Returns the subscription mask of a client.
+    pub fn get(&self, client_id: ClientId) -> InterestTags {
+        self.masks.get(&client_id).copied().unwrap_or_default()
+    }
+
+    /// Replaces the subscription mask of a client.
+    pub fn set(&mut self, client_id: ClientId, mask: InterestTags) {
+        let current = self.masks.entry(client_id).or_default();
+        if *current != mask {
+            *current = mask;
+            self.changed.insert(client_id);
+        }
+    }
+
+    /// Adds tags to the subscription mask of a client.
+    pub fn subscribe(&mut self, client_id: ClientId, tags: InterestTags) {
+        let mask = self.get(client_id) | tags;
+        self.set(client_id, mask);
+    }
+
+    /// Removes tags from the subscription mask of a client.
+    pub fn unsubscribe(&mut self, client_id: ClientId, tags: InterestTags) {
+        let mut mask = self.get(client_id);
+        mask.remove(tags);
+        self.set(client_id, mask);
+    }
+}
+
+/// Set of up to [`MAX_TAGS`] gameplay tags.
+///
+/// Used as a component for entities and as a subscription mask in [`InterestSubscriptions`].
+/// Tags can be combined with `|`.
+#[derive(Component, Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct InterestTags(u64);
+
+impl InterestTags {
+    /// Creates a set with a single tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index isn't less than [`MAX_TAGS`].
+    pub const fn tag(index: u8) -> Self {
+        assert!(index < MAX_TAGS, "tag index should be less than 64");
+        Self(1 << index)
+    }
+
+    /// Creates a set from raw bits, where each bit is a tag.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns tags as bits.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the sets have at least one common tag.
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Adds tags from another set.
+    pub fn insert(&mut self, tags: Self) {
+        self.0 |= tags.0;
+    }
+
+    /// Removes tags of another set.
+    pub fn remove(&mut self, tags: Self) {
+        self.0 &= !tags.0;
+    }
+}
+
+impl BitOr for InterestTags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for InterestTags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
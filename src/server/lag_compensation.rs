@@ -0,0 +1,342 @@
+use bevy::prelude::*;
+
+use super::{
+    component_history::{self, ComponentHistory},
+    server_tick::ServerTick,
+    ServerPlugin, ServerSet,
+};
+use crate::core::{common_conditions::server_running, replicon_tick::RepliconTick};
+
+/**
+Spawns server projectiles that account for what the shooting client saw.
+
+Positions of entities with [`HitCollider`] are taken from [`ComponentHistory<Transform>`], so [`Transform`]
+should be registered with [`AppHistoryExt::replicate_with_history`](super::component_history::AppHistoryExt::replicate_with_history)
+for at least [`LagCompensation::max_rewind`] + 1 ticks and colliders should be replicated.
+When the server receives [`LaunchProjectile`] with the tick that the client saw at the moment of the shot,
+the projectile is rewound to this tick and fast-forwarded to the present against the recorded positions.
+After that the projectile is spawned with [`Projectile`] and moves each tick against the current positions.
+Hits in both phases emit [`ProjectileHit`].
+
+Insert [`Replicated`](crate::core::Replicated) and visual components for added [`Projectile`]
+entities if clients need to see them. The rewind is limited by [`LagCompensation::max_rewind`]
+to limit the advantage of clients with high latency or fake ticks.
+
+Positions are taken from [`Transform::translation`], velocity is measured in units per tick.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, core::replicon_tick::RepliconTick};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, LagCompensationPlugin))
+    .replicate_with_history::<Transform>(31)
+    .add_client_event::<Shoot>(ChannelKind::Ordered)
+    .add_systems(Update, (shoot, apply_hits).run_if(server_running));
+
+fn shoot(
+    mut shoot_events: EventReader<FromClient<Shoot>>,
+    mut launch_events: EventWriter<LaunchProjectile>,
+    players: Query<(Entity, &Player, &Transform)>,
+) {
+    for FromClient { client_id, event } in shoot_events.read() {
+        if let Some((entity, _, transform)) = players.iter().find(|(_, player, _)| player.0 == *client_id) {
+            launch_events.send(LaunchProjectile {
+                shooter: Some(entity),
+                origin: transform.translation,
+                velocity: event.direction * 2.0,
+                radius: 0.1,
+                lifetime: 60,
+                tick: event.tick,
+            });
+        }
+    }
+}
+
+fn apply_hits(mut hit_events: EventReader<ProjectileHit>) {
+    for hit in hit_events.read() {
+        info!("{:?} hit {:?}", hit.shooter, hit.target);
+    }
+}
+
+#[derive(Component)]
+struct Player(ClientId);
+
+/// Sent by client with the last received tick.
+#[derive(Deserialize, Event, Serialize)]
+struct Shoot {
+    direction: Vec3,
+    tick: RepliconTick,
+}
+```
+**/
+pub struct LagCompensationPlugin;
+
+impl Plugin for LagCompensationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LagCompensation>()
+            .add_event::<LaunchProjectile>()
+            .add_event::<ProjectileHit>()
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::advance_projectiles.run_if(resource_changed::<ServerTick>),
+                    Self::launch_projectiles,
+                )
+                    .chain()
+                    .after(component_history::record_history::<Transform>)
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl LagCompensationPlugin {
+    fn advance_projectiles(
+        mut commands: Commands,
+        mut hit_events: EventWriter<ProjectileHit>,
+        server_tick: Res<ServerTick>,
+        mut projectiles: Query<(Entity, &mut Transform, &mut Projectile), Without<HitCollider>>,
+        colliders: Query<(Entity, &Transform, &HitCollider)>,
+    ) {
+        let tick = **server_tick;
+        for (entity, mut transform, mut projectile) in &mut projectiles {
+            let start = transform.translation;
+            let end = start + projectile.velocity;
+            let hit = colliders
+                .iter()
+                .filter(|&(target, ..)| Some(target) != projectile.shooter)
+                .find(|(_, target_transform, collider)| {
+                    segment_hits(
+                        start,
+                        end,
+                        target_transform.translation,
+                        collider.radius + projectile.radius,
+                    )
+                });
+
+            if let Some((target, target_transform, _)) = hit {
+                hit_events.send(ProjectileHit {
+                    shooter: projectile.shooter,
+                    target,
+                    position: target_transform.translation,
+                    tick,
+                });
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+
+            transform.translation = end;
+            projectile.lifetime = projectile.lifetime.saturating_sub(1);
+            if projectile.lifetime == 0 {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+
+    fn launch_projectiles(
+        mut commands: Commands,
+        mut launch_events: EventReader<LaunchProjectile>,
+        mut hit_events: EventWriter<ProjectileHit>,
+        lag_compensation: Res<LagCompensation>,
+        server_tick: Res<ServerTick>,
+        colliders: Query<(Entity, &ComponentHistory<Transform>, &HitCollider)>,
+    ) {
+        let current_tick = **server_tick;
+        for event in launch_events.read() {
+            // Ticks from the future or too far in the past are clamped.
+            let rewind = if event.tick > current_tick {
+                0
+            } else {
+                (current_tick - event.tick).min(lag_compensation.max_rewind)
+            };
+            let start_tick = current_tick - rewind;
+            trace!(
+                "rewinding projectile from {:?} by {rewind} ticks",
+                event.shooter
+            );
+
+            let mut position = event.origin;
+            let mut hit = None;
+            for step in 0..=rewind.min(event.lifetime) {
+                let tick = start_tick + step;
+                // Check only the initial overlap on the first step.
+                let end = if step == 0 {
+                    position
+                } else {
+                    position + event.velocity
+                };
+                hit = colliders
+                    .iter()
+                    .filter(|&(target, ..)| Some(target) != event.shooter)
+                    .filter_map(|(target, history, collider)| {
+                        history
+                            .get(tick)
+                            .map(|transform| (target, transform.translation, collider))
+                    })
+                    .find(|(_, target_position, collider)| {
+                        segment_hits(
+                            position,
+                            end,
+                            *target_position,
+                            collider.radius + event.radius,
+                        )
+                    })
+                    .map(|(target, target_position, _)| (target, target_position, tick));
+                position = end;
+
+                if hit.is_some() {
+                    break;
+                }
+            }
+
+            if let Some((target, target_position, tick)) = hit {
+                hit_events.send(ProjectileHit {
+                    shooter: event.shooter,
+                    target,
+                    position: target_position,
+                    tick,
+                });
+            } else if event.lifetime > rewind {
+                commands.spawn((
+                    Projectile {
+                        shooter: event.shooter,
+                        velocity: event.velocity,
+                        radius: event.radius,
+                        lifetime: event.lifetime - rewind,
+                    },
+                    Transform::from_translation(position),
+                ));
+            }
+        }
+    }
+}
+
+/// Returns `true` if the segment passes within the distance from the point.
+fn segment_hits(start: Vec3, end: Vec3, point: Vec3, distance: f32) -> bool {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    let closest = if length_squared == 0.0 {
+        start
+    } else {
+        let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+        start + segment * t
+    };
+
+    closest.distance_squared(point) <= distance * distance
+}
+
+/// Settings for [`LagCompensationPlugin`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct LagCompensation {
+    /// Maximum number of ticks for which projectiles can be rewound.
+    ///
+    /// Rewinding is also limited by the length of [`ComponentHistory<Transform>`].
+    /// By default set to 30.
+    pub max_rewind: u32,
+}
+
+impl Default for LagCompensation {
+    fn default() -> Self {
+        Self { max_rewind: 30 }
+    }
+}
+
+/// Sphere that can be hit by projectiles.
+///
+/// See [`LagCompensationPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HitCollider {
+    pub radius: f32,
+}
+
+/// A moving projectile spawned by [`LaunchProjectile`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Projectile {
+    /// Entity that launched the projectile, will be ignored for hits.
+    pub shooter: Option<Entity>,
+
+    /// Movement per tick.
+    pub velocity: Vec3,
+
+    /// Radius of the projectile sphere.
+    pub radius: f32,
+
+    /// Remaining ticks until despawn.
+    pub lifetime: u32,
+}
+
+/// An event to launch a lag-compensated projectile on server.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct LaunchProjectile {
+    /// Entity that launched the projectile, will be ignored for hits.
+    pub shooter: Option<Entity>,
+
+    /// Position of the projectile at [`Self::tick`].
+    pub origin: Vec3,
+
+    /// Movement per tick.
+    pub velocity: Vec3,
+
+    /// Radius of the projectile sphere.
+    pub radius: f32,
+
+    /// Number of ticks until despawn, counting from [`Self::tick`].
+    pub lifetime: u32,
+
+    /// Server tick that the client saw at the moment of the shot.
+    pub tick: RepliconTick,
+}
+
+/// An event that indicates a projectile hit.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct ProjectileHit {
+    /// Entity that launched the projectile.
+    pub shooter: Option<Entity>,
+
+    /// Hit entity.
+    pub target: Entity,
+
+    /// Position of the target at the moment of the hit.
+    pub position: Vec3,
+
+    /// Tick at which the hit happened.
+    ///
+    /// Could be in the past for rewound projectiles.
+    pub tick: RepliconTick,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment() {
+        assert!(segment_hits(
+            Vec3::ZERO,
+            Vec3::X * 10.0,
+            Vec3::new(5.0, 0.5, 0.0),
+            1.0
+        ));
+        assert!(!segment_hits(
+            Vec3::ZERO,
+            Vec3::X * 10.0,
+            Vec3::new(5.0, 2.0, 0.0),
+            1.0
+        ));
+        assert!(!segment_hits(
+            Vec3::ZERO,
+            Vec3::X * 10.0,
+            Vec3::X * 12.0,
+            1.0
+        ));
+        assert!(segment_hits(Vec3::ZERO, Vec3::ZERO, Vec3::X * 0.5, 1.0));
+    }
+}
@@ -0,0 +1,334 @@
+use std::mem;
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use super::{replicon_server::RepliconServer, ServerEvent, ServerSet};
+use crate::{
+    client::{ClientPlugin, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_running},
+        replicon_channels::{ChannelKind, RepliconChannels},
+        ClientId,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        server_event::{SendMode, ServerEventAppExt, ToClients},
+    },
+};
+
+/**
+Hosts multiple isolated matches in one process, sharing a single messaging backend.
+
+Each match is a separate [`App`] with its own world, [`RepliconPlugins`](crate::RepliconPlugins),
+replication state and set of clients. The host app contains the messaging backend and this plugin.
+Add matches with [`MatchHost::add_match`] and assign connected clients with [`MatchHost::move_client`].
+Messages from assigned clients are forwarded to their match and messages from matches are sent through
+the host backend. Clients that aren't assigned to any match stay in the host app, which can be used as a lobby.
+
+Matches are updated in [`PreUpdate`] of the host app after [`ServerSet::SendEvents`], so connection
+events from the backend are already available.
+
+For the client, a move is a new session of another server. So on each move the client is removed
+from its previous match, which receives [`ServerEvent::ClientDisconnected`], and asked to reset.
+The client despawns all replicated entities, resets received ticks and replies to the host. Only then
+the client is assigned to the new match, which receives [`ServerEvent::ClientConnected`], and the host
+emits [`MatchMoved`]. Until then the client stays unassigned. Matches also receive
+[`ServerEvent::ClientDisconnected`] when an assigned client disconnects.
+
+All matches and clients should register the same network events and replication rules in the same order.
+The host app shouldn't replicate entities to assigned clients.
+
+Not added by default. Should be added to the host app, match apps and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::ClientId, prelude::*};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, MatchHostPlugin));
+
+let mut match_app = App::new();
+match_app.add_plugins((MinimalPlugins, RepliconPlugins, MatchHostPlugin));
+
+let mut host = app.world.resource_mut::<MatchHost>();
+let match_id = host.add_match(match_app);
+host.move_client(ClientId::new(1), Some(match_id));
+```
+**/
+pub struct MatchHostPlugin;
+
+impl Plugin for MatchHostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MatchHost>()
+            .add_event::<MatchMoved>()
+            .add_server_event::<MatchReset>(ChannelKind::Ordered)
+            .add_client_event::<MatchResetAck>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::update_matches
+                        .after(ServerSet::SendEvents)
+                        .before(ServerSet::Receive)
+                        .run_if(server_running),
+                ),
+            );
+    }
+}
+
+impl MatchHostPlugin {
+    /// Drops the replicated world of the previous match and replies to the host.
+    fn reset(world: &mut World) {
+        if world.resource_mut::<Events<MatchReset>>().drain().count() == 0 {
+            return;
+        }
+
+        info!("moved to another match, receiving the world again");
+        ClientPlugin::reset_replicated(world);
+        world.send_event(MatchResetAck);
+    }
+
+    /// Applies moves, forwards received messages into matches, updates them and sends their messages.
+    #[allow(clippy::too_many_arguments)]
+    fn update_matches(
+        mut received: Local<Vec<(ClientId, u8, Bytes)>>,
+        mut server_events: EventReader<ServerEvent>,
+        mut ack_events: EventReader<FromClient<MatchResetAck>>,
+        mut reset_events: EventWriter<ToClients<MatchReset>>,
+        mut moved_events: EventWriter<MatchMoved>,
+        mut host: ResMut<MatchHost>,
+        mut server: ResMut<RepliconServer>,
+        channels: Res<RepliconChannels>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, reason } = event {
+                host.moves.retain(|&(id, _)| id != *client_id);
+                host.pending.remove(client_id);
+                if let Some(match_id) = host.clients.remove(client_id) {
+                    debug!("removing disconnected {client_id:?} from {match_id:?}");
+                    host.match_mut(match_id)
+                        .send_event(ServerEvent::ClientDisconnected {
+                            client_id: *client_id,
+                            reason: reason.clone(),
+                        });
+                }
+            }
+        }
+
+        for FromClient { client_id, .. } in ack_events.read() {
+            let Some((from, to)) = host.pending.remove(client_id) else {
+                continue;
+            };
+
+            if let Some(to) = to {
+                host.clients.insert(*client_id, to);
+                host.match_mut(to).send_event(ServerEvent::ClientConnected {
+                    client_id: *client_id,
+                });
+            }
+
+            debug!("moved {client_id:?} from {from:?} to {to:?}");
+            moved_events.send(MatchMoved {
+                client_id: *client_id,
+                from,
+                to,
+            });
+        }
+
+        for (client_id, to) in mem::take(&mut host.moves) {
+            let current = host.clients.get(&client_id).copied();
+            if current.is_some() && current == to {
+                continue;
+            }
+
+            if let Some(current) = current {
+                host.clients.remove(&client_id);
+                host.match_mut(current)
+                    .send_event(ServerEvent::ClientDisconnected {
+                        client_id,
+                        reason: "Moved to another match".to_string(),
+                    });
+            }
+
+            // Keep the original match if the client is already waiting for a reset.
+            let from = host
+                .pending
+                .get(&client_id)
+                .map_or(current, |&(from, _)| from);
+            debug!("resetting {client_id:?} to move it from {from:?} to {to:?}");
+            host.pending.insert(client_id, (from, to));
+            reset_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: MatchReset,
+            });
+        }
+
+        for channel_id in 0..channels.client_channels().len() as u8 {
+            received.extend(
+                server
+                    .receive(channel_id)
+                    .map(|(client_id, message)| (client_id, channel_id, message)),
+            );
+        }
+
+        let MatchHost {
+            matches, clients, ..
+        } = &mut *host;
+        for (client_id, channel_id, message) in received.drain(..) {
+            match clients
+                .get(&client_id)
+                .and_then(|match_id| matches.get_mut(match_id))
+            {
+                Some(world) => world
+                    .resource_mut::<RepliconServer>()
+                    .insert_received(client_id, channel_id, message),
+                // Keep messages of unassigned clients for the host app.
+                None => server.insert_received(client_id, channel_id, message),
+            }
+        }
+
+        for (&match_id, world) in matches.iter_mut() {
+            world.run_schedule(Main);
+            world.clear_trackers();
+
+            for (client_id, channel_id, message) in
+                world.resource_mut::<RepliconServer>().drain_sent()
+            {
+                // Drop messages that were sent before the client was moved out.
+                if clients.get(&client_id) == Some(&match_id) {
+                    server.send(client_id, channel_id, message);
+                }
+            }
+        }
+    }
+}
+
+/// Matches hosted in this process and their clients.
+///
+/// See [`MatchHostPlugin`] for details.
+#[derive(Default, Resource)]
+pub struct MatchHost {
+    matches: HashMap<MatchId, World>,
+    clients: HashMap<ClientId, MatchId>,
+
+    /// Requested moves that will be applied on the next update.
+    moves: Vec<(ClientId, Option<MatchId>)>,
+
+    /// Unassigned clients that were asked to reset, mapped to their previous and new matches.
+    pending: HashMap<ClientId, (Option<MatchId>, Option<MatchId>)>,
+
+    next_id: u32,
+}
+
+impl MatchHost {
+    /// Adds a match from an app and returns its ID.
+    ///
+    /// The app should contain [`RepliconPlugins`](crate::RepliconPlugins) with the same protocol as the host app,
+    /// but without a messaging backend. Plugins will be finished and the app server will be marked as running.
+    pub fn add_match(&mut self, mut app: App) -> MatchId {
+        app.finish();
+        app.cleanup();
+        app.world.resource_mut::<RepliconServer>().set_running(true);
+
+        let match_id = MatchId(self.next_id);
+        self.next_id += 1;
+        self.matches.insert(match_id, mem::take(&mut app.world));
+
+        match_id
+    }
+
+    /// Removes a match and returns its world.
+    ///
+    /// Clients of the match become unassigned without any events.
+    /// Clients that are moving into the match will stay unassigned.
+    pub fn remove_match(&mut self, match_id: MatchId) -> Option<World> {
+        self.clients.retain(|_, id| *id != match_id);
+        self.moves.retain(|&(_, id)| id != Some(match_id));
+        for (_, to) in self.pending.values_mut() {
+            if *to == Some(match_id) {
+                *to = None;
+            }
+        }
+        self.matches.remove(&match_id)
+    }
+
+    /// Returns the world of a match.
+    pub fn world(&self, match_id: MatchId) -> Option<&World> {
+        self.matches.get(&match_id)
+    }
+
+    /// Returns the mutable world of a match.
+    pub fn world_mut(&mut self, match_id: MatchId) -> Option<&mut World> {
+        self.matches.get_mut(&match_id)
+    }
+
+    /// Returns an iterator over match IDs.
+    pub fn iter_match_ids(&self) -> impl Iterator<Item = MatchId> + '_ {
+        self.matches.keys().copied()
+    }
+
+    /// Returns the match of a client or [`None`] if it's not assigned.
+    ///
+    /// Moves requested with [`Self::move_client`] are reflected only after the client resets,
+    /// see [`MatchMoved`].
+    pub fn client_match(&self, client_id: ClientId) -> Option<MatchId> {
+        self.clients.get(&client_id).copied()
+    }
+
+    /// Moves a connected client into a match or back to the host app if [`None`].
+    ///
+    /// The client will be removed from its current match on the next update
+    /// and assigned to the new one after it resets. See [`MatchHostPlugin`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the match doesn't exist.
+    pub fn move_client(&mut self, client_id: ClientId, match_id: Option<MatchId>) {
+        if let Some(match_id) = match_id {
+            assert!(
+                self.matches.contains_key(&match_id),
+                "{match_id:?} should be added before moving clients into it"
+            );
+        }
+        self.moves.push((client_id, match_id));
+    }
+
+    fn match_mut(&mut self, match_id: MatchId) -> &mut World {
+        self.matches
+            .get_mut(&match_id)
+            .expect("clients should be assigned only to existing matches")
+    }
+}
+
+/// Unique identifier of a match in [`MatchHost`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MatchId(u32);
+
+/// An event emitted on the host app when a client completed a move between matches.
+///
+/// See [`MatchHost::move_client`].
+#[derive(Clone, Copy, Debug, Event, PartialEq, Eq)]
+pub struct MatchMoved {
+    /// Moved client.
+    pub client_id: ClientId,
+
+    /// Previous match or [`None`] if the client was unassigned.
+    pub from: Option<MatchId>,
+
+    /// New match or [`None`] if the client is unassigned now.
+    pub to: Option<MatchId>,
+}
+
+/// A server event that asks a client to drop the replicated world and reply with [`MatchResetAck`].
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+struct MatchReset;
+
+/// A client event that confirms [`MatchReset`].
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+struct MatchResetAck;
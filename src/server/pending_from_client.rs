@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use super::ServerSet;
+use crate::core::{common_conditions::server_running, ClientId};
+
+/// Tracking of client-provided values for [`App`].
+pub trait AppPendingExt {
+    /**
+    Enables [`PendingFromClient<C>`] markers for the component.
+
+    Values applied with [`ServerCommands::apply_from_client`](super::server_commands::ServerCommands::apply_from_client)
+    are marked with [`PendingFromClient<C>`] until validation systems remove the marker or until the end of the tick. Markers are removed
+    automatically in [`PostUpdate`] before [`ServerSet::Send`], so server systems that run in [`Update`]
+    can distinguish freshly applied untrusted values from the validated state.
+
+    Needed only on server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_event::<MoveTo>(ChannelKind::Ordered)
+        .track_pending_from_client::<Position>()
+        .add_systems(
+            PreUpdate,
+            apply_moves
+                .after(ServerSet::Receive)
+                .run_if(server_running),
+        )
+        .add_systems(Update, validate_moves.run_if(server_running));
+
+    fn apply_moves(mut move_events: EventReader<FromClient<MoveTo>>, mut commands: ServerCommands) {
+        for FromClient { client_id, event } in move_events.read() {
+            commands.apply_from_client(event.entity, *client_id, Position(event.position));
+        }
+    }
+
+    fn validate_moves(
+        mut commands: Commands,
+        mut positions: Query<(Entity, &mut Position), With<PendingFromClient<Position>>>,
+    ) {
+        for (entity, mut position) in &mut positions {
+            position.0 = position.0.clamp(Vec2::splat(-100.0), Vec2::splat(100.0));
+            commands
+                .entity(entity)
+                .remove::<PendingFromClient<Position>>();
+        }
+    }
+
+    #[derive(Component)]
+    struct Position(Vec2);
+
+    #[derive(Deserialize, Event, Serialize)]
+    struct MoveTo {
+        entity: Entity,
+        position: Vec2,
+    }
+    ```
+    **/
+    fn track_pending_from_client<C: Component>(&mut self) -> &mut Self;
+}
+
+impl AppPendingExt for App {
+    fn track_pending_from_client<C: Component>(&mut self) -> &mut Self {
+        self.add_systems(
+            PostUpdate,
+            clear_pending::<C>
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        )
+    }
+}
+
+fn clear_pending<C: Component>(
+    mut commands: Commands,
+    entities: Query<Entity, With<PendingFromClient<C>>>,
+) {
+    for entity in &entities {
+        commands.entity(entity).remove::<PendingFromClient<C>>();
+    }
+}
+
+/// Marks a component value applied from a client that hasn't been validated yet.
+///
+/// See [`AppPendingExt::track_pending_from_client`] for details.
+#[derive(Component)]
+pub struct PendingFromClient<C> {
+    client_id: ClientId,
+    marker: PhantomData<C>,
+}
+
+impl<C> PendingFromClient<C> {
+    pub(super) fn new(client_id: ClientId) -> Self {
+        Self {
+            client_id,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the client that sent the value.
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+}
@@ -130,8 +130,12 @@ impl FromWorld for ReplicatedComponents {
         let rules = world.resource::<ReplicationRules>();
         let component_ids = rules
             .iter()
-            .flat_map(|rule| &rule.components)
-            .map(|fns_info| fns_info.component_id())
+            .flat_map(|rule| {
+                rule.components
+                    .iter()
+                    .map(|fns_info| fns_info.component_id())
+                    .chain(rule.markers.iter().copied())
+            })
             .collect();
 
         Self(component_ids)
@@ -184,7 +188,12 @@ impl RemovalBuffer {
                 }
             }
         }
-        self.removals.push((entity, removed_ids));
+
+        if removed_ids.is_empty() {
+            self.ids_buffer.push(removed_ids);
+        } else {
+            self.removals.push((entity, removed_ids));
+        }
     }
 
     /// Clears all removals.
@@ -205,7 +214,11 @@ mod tests {
 
     use super::*;
     use crate::{
-        core::{replication_fns::ReplicationFns, replication_rules::AppRuleExt, Replicated},
+        core::{
+            replication_fns::{rule_fns::RuleFns, ReplicationFns},
+            replication_rules::AppRuleExt,
+            Replicated,
+        },
         server::replicon_server::RepliconServer,
     };
 
@@ -363,6 +376,39 @@ mod tests {
         assert_eq!(removals_id.len(), 1);
     }
 
+    #[test]
+    fn marker() {
+        let mut app = App::new();
+        app.add_plugins(RemovalBufferPlugin)
+            .init_resource::<RepliconServer>()
+            .init_resource::<ReplicationFns>()
+            .init_resource::<ReplicationRules>()
+            .replicate_with_marker::<ComponentB, ComponentA>(RuleFns::default());
+
+        app.world.resource_mut::<RepliconServer>().set_running(true);
+
+        app.update();
+
+        app.world
+            .spawn((Replicated, ComponentA, ComponentB))
+            .remove::<ComponentB>();
+        app.world
+            .spawn((Replicated, ComponentA, ComponentB))
+            .remove::<(ComponentA, ComponentB)>();
+
+        app.update();
+
+        let removal_buffer = app.world.resource::<RemovalBuffer>();
+        assert_eq!(
+            removal_buffer.removals.len(),
+            1,
+            "marker removal shouldn't be counted"
+        );
+
+        let (_, removals_id) = removal_buffer.removals.first().unwrap();
+        assert_eq!(removals_id.len(), 1);
+    }
+
     #[test]
     fn despawn() {
         let mut app = App::new();
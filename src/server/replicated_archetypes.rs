@@ -2,15 +2,19 @@ use std::mem;
 
 use bevy::{
     ecs::{
-        archetype::{ArchetypeGeneration, ArchetypeId},
-        component::{ComponentId, StorageType},
+        archetype::{Archetype, ArchetypeGeneration, ArchetypeId},
+        component::{ComponentId, StorageType, Tick},
+        storage::Table,
     },
     log::Level,
     prelude::*,
     utils::tracing::enabled,
 };
 
-use crate::core::{replication_fns::FnsId, replication_rules::ReplicationRules, Replicated};
+use super::{hibernation::Hibernated, ignored::IgnoredComponents};
+use crate::core::{
+    replication_fns::FnsId, replication_rules::ReplicationRules, ClientId, Replicated,
+};
 
 /// Cached information about all replicated archetypes.
 #[derive(Deref)]
@@ -18,12 +22,24 @@ pub(crate) struct ReplicatedArchetypes {
     /// ID of [`Replicated`] component.
     marker_id: ComponentId,
 
+    /// ID of [`Hibernated`] component.
+    hibernated_id: ComponentId,
+
     /// Highest processed archetype ID.
     generation: ArchetypeGeneration,
 
+    /// Generation of [`ReplicationRules`] from which archetypes were matched.
+    rules_generation: u32,
+
     /// Archetypes marked as replicated.
     #[deref]
     archetypes: Vec<ReplicatedArchetype>,
+
+    /// Clients from the last call of [`Self::update_sync_context`].
+    synced_clients: Vec<ClientId>,
+
+    /// Disabled components from the last call of [`Self::update_sync_context`].
+    synced_disabled_ids: Vec<ComponentId>,
 }
 
 impl ReplicatedArchetypes {
@@ -32,27 +48,115 @@ impl ReplicatedArchetypes {
         self.marker_id
     }
 
+    /// Returns mutable iterator over replicated archetypes.
+    pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut ReplicatedArchetype> {
+        self.archetypes.iter_mut()
+    }
+
+    /// Stores clients and disabled components that replication is collected for.
+    ///
+    /// If they differ from the previous call or `valid` is `false`, the sync state of
+    /// all archetypes is reset, since entities may need to be sent even without changes.
+    /// Returns `true` if archetypes can be skipped with [`ReplicatedArchetype::is_synced`].
+    pub(super) fn update_sync_context(
+        &mut self,
+        clients: impl Iterator<Item = ClientId>,
+        disabled_ids: &[ComponentId],
+        valid: bool,
+    ) -> bool {
+        let mut clients_changed = false;
+        let mut len = 0;
+        for client_id in clients {
+            if self.synced_clients.get(len) != Some(&client_id) {
+                self.synced_clients.truncate(len);
+                self.synced_clients.push(client_id);
+                clients_changed = true;
+            }
+            len += 1;
+        }
+        if self.synced_clients.len() != len {
+            self.synced_clients.truncate(len);
+            clients_changed = true;
+        }
+
+        let disabled_changed = self.synced_disabled_ids != disabled_ids;
+        if disabled_changed {
+            self.synced_disabled_ids.clear();
+            self.synced_disabled_ids.extend_from_slice(disabled_ids);
+        }
+
+        let valid = valid && !clients_changed && !disabled_changed;
+        if !valid {
+            for archetype in &mut self.archetypes {
+                archetype.synced = None;
+            }
+        }
+
+        valid
+    }
+
     /// Updates the internal view of the [`World`]'s replicated archetypes.
     ///
     /// If this is not called before querying data, the results may not accurately reflect what is in the world.
     pub(super) fn update(&mut self, world: &World, rules: &ReplicationRules) {
+        if self.rules_generation != rules.generation() {
+            debug!("re-matching replicated archetypes for the new rules");
+            self.rules_generation = rules.generation();
+            self.generation = ArchetypeGeneration::initial();
+            self.archetypes.clear();
+        }
+
         let old_generation = mem::replace(&mut self.generation, world.archetypes().generation());
+        let ignored_components = world.get_resource::<IgnoredComponents>();
 
         // Archetypes are never removed, iterate over newly added since the last update.
         for archetype in world.archetypes()[old_generation..]
             .iter()
-            .filter(|archetype| archetype.contains(self.marker_id))
+            .filter(|archetype| {
+                archetype.contains(self.marker_id) && !archetype.contains(self.hibernated_id)
+            })
         {
             let mut replicated_archetype = ReplicatedArchetype::new(archetype.id());
             for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
+                let mut atomic_group = rule.atomic.then(|| {
+                    let group = rule
+                        .components
+                        .iter()
+                        .map(|fns_info| {
+                            // SAFETY: component ID obtained from this archetype.
+                            let storage_type = unsafe {
+                                archetype
+                                    .get_storage_type(fns_info.component_id())
+                                    .unwrap_unchecked()
+                            };
+                            (fns_info.component_id(), storage_type)
+                        })
+                        .collect();
+                    replicated_archetype.atomic_groups.push(group);
+                    replicated_archetype.atomic_groups.len() - 1
+                });
+
                 for fns_info in &rule.components {
+                    if ignored_components
+                        .and_then(|components| components.marker_id(fns_info.component_id()))
+                        .is_some_and(|ignored_id| archetype.contains(ignored_id))
+                    {
+                        continue;
+                    }
+
                     // Since rules are sorted by priority,
                     // we are inserting only new components that aren't present.
-                    if replicated_archetype
+                    if let Some(index) = replicated_archetype
                         .components
                         .iter()
-                        .any(|component| component.component_id == fns_info.component_id())
+                        .position(|component| component.component_id == fns_info.component_id())
                     {
+                        // The component is still sent with the group even if another rule claimed it.
+                        if let Some(group) = atomic_group {
+                            atomic_group =
+                                Some(replicated_archetype.join_atomic_group(index, group));
+                        }
+
                         if enabled!(Level::DEBUG) {
                             let component_name = world
                                 .components()
@@ -84,9 +188,14 @@ impl ReplicatedArchetypes {
                         component_id: fns_info.component_id(),
                         storage_type,
                         fns_id: fns_info.fns_id(),
+                        atomic_group,
+                        once: rule.once,
                     });
                 }
             }
+            if rules.has_dependencies() {
+                sort_by_dependencies(&mut replicated_archetype.components, rules);
+            }
             self.archetypes.push(replicated_archetype);
         }
     }
@@ -96,8 +205,12 @@ impl FromWorld for ReplicatedArchetypes {
     fn from_world(world: &mut World) -> Self {
         Self {
             marker_id: world.init_component::<Replicated>(),
+            hibernated_id: world.init_component::<Hibernated>(),
             generation: ArchetypeGeneration::initial(),
+            rules_generation: 0,
             archetypes: Default::default(),
+            synced_clients: Default::default(),
+            synced_disabled_ids: Default::default(),
         }
     }
 }
@@ -109,14 +222,132 @@ pub(crate) struct ReplicatedArchetype {
 
     /// Components marked as replicated.
     pub(super) components: Vec<ReplicatedComponent>,
+
+    /// Components of atomic rules that match this archetype.
+    ///
+    /// Referenced by [`ReplicatedComponent::atomic_group`].
+    pub(super) atomic_groups: Vec<Vec<(ComponentId, StorageType)>>,
+
+    /// Tick of the last send that had nothing to write for this archetype and entities at that moment.
+    ///
+    /// Allows skipping the archetype until something changes.
+    synced: Option<(Tick, Vec<Entity>)>,
 }
 
 impl ReplicatedArchetype {
+    /// Assigns the atomic group to the component at the index and returns the resulting group index.
+    ///
+    /// If the component already belongs to another group, both groups are merged into it.
+    fn join_atomic_group(&mut self, index: usize, group: usize) -> usize {
+        let Some(other_group) = self.components[index].atomic_group else {
+            self.components[index].atomic_group = Some(group);
+            return group;
+        };
+
+        if other_group != group {
+            for (component_id, storage_type) in mem::take(&mut self.atomic_groups[group]) {
+                if !self.atomic_groups[other_group]
+                    .iter()
+                    .any(|&(other_id, _)| other_id == component_id)
+                {
+                    self.atomic_groups[other_group].push((component_id, storage_type));
+                }
+            }
+            for component in &mut self.components {
+                if component.atomic_group == Some(group) {
+                    component.atomic_group = Some(other_group);
+                }
+            }
+        }
+
+        other_group
+    }
+
     fn new(id: ArchetypeId) -> Self {
         Self {
             id,
             components: Default::default(),
+            atomic_groups: Default::default(),
+            synced: None,
+        }
+    }
+
+    /// Returns `true` if nothing was written for the archetype since the last [`Self::set_synced`] call
+    /// and its entities and components didn't change after it.
+    ///
+    /// Only archetypes with table components can be synced, since the check iterates over
+    /// change ticks of the whole table columns instead of each entity.
+    pub(super) fn is_synced(
+        &self,
+        archetype: &Archetype,
+        table: &Table,
+        marker_id: ComponentId,
+        this_run: Tick,
+    ) -> bool {
+        let Some((tick, entities)) = &self.synced else {
+            return false;
+        };
+
+        if entities.len() != archetype.len()
+            || entities
+                .iter()
+                .zip(archetype.entities())
+                .any(|(&entity, archetype_entity)| entity != archetype_entity.id())
+        {
+            return false;
         }
+
+        // Changes of components that are sent only on insertion don't need to be checked.
+        let components = self
+            .components
+            .iter()
+            .map(|component| (component.component_id, component.once))
+            .chain(
+                self.atomic_groups
+                    .iter()
+                    .flatten()
+                    .map(|&(component_id, _)| (component_id, false)),
+            );
+
+        for (component_id, once) in components.chain([(marker_id, false)]) {
+            let Some(column) = table.get_column(component_id) else {
+                // Sparse set component.
+                return false;
+            };
+
+            let changed_ticks = if once {
+                &[]
+            } else {
+                column.get_changed_ticks_slice()
+            };
+            if column
+                .get_added_ticks_slice()
+                .iter()
+                .chain(changed_ticks)
+                .any(|changed_tick| {
+                    // SAFETY: ticks are not mutated while the world is borrowed immutably.
+                    let changed_tick = unsafe { *changed_tick.get() };
+                    changed_tick.is_newer_than(*tick, this_run)
+                })
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Marks the archetype as synced at the tick, see [`Self::is_synced`].
+    pub(super) fn set_synced(&mut self, archetype: &Archetype, tick: Tick) {
+        let (synced_tick, entities) = self.synced.get_or_insert_with(|| (tick, Vec::new()));
+        *synced_tick = tick;
+        entities.clear();
+        entities.extend(archetype.entities().iter().map(|entity| entity.id()));
+    }
+
+    /// Resets the sync state, see [`Self::is_synced`].
+    pub(super) fn reset_synced(&mut self) {
+        self.synced = None;
     }
 }
 
@@ -125,6 +356,34 @@ pub(super) struct ReplicatedComponent {
     pub(super) component_id: ComponentId,
     pub(super) storage_type: StorageType,
     pub(super) fns_id: FnsId,
+
+    /// Index of the atomic group from [`ReplicatedArchetype::atomic_groups`] this component belongs to.
+    pub(super) atomic_group: Option<usize>,
+
+    /// Whether the component should be sent only on insertion.
+    ///
+    /// See [`AppRuleExt::replicate_once`](crate::core::replication_rules::AppRuleExt::replicate_once).
+    pub(super) once: bool,
+}
+
+/// Sorts components so that each component is placed after components it depends on.
+///
+/// Keeps the original order of independent components. Components in dependency cycles keep their original order.
+fn sort_by_dependencies(components: &mut Vec<ReplicatedComponent>, rules: &ReplicationRules) {
+    let mut remaining = mem::take(components);
+    while !remaining.is_empty() {
+        let index = remaining
+            .iter()
+            .position(|component| {
+                !remaining.iter().any(|other| {
+                    other.component_id != component.component_id
+                        && rules.depends_on(component.component_id, other.component_id)
+                        && !rules.depends_on(other.component_id, component.component_id)
+                })
+            })
+            .unwrap_or_default();
+        components.push(remaining.remove(index));
+    }
 }
 
 #[cfg(test)]
@@ -132,7 +391,10 @@ mod tests {
     use serde::{Deserialize, Serialize};
 
     use super::*;
-    use crate::{core::replication_fns::ReplicationFns, AppRuleExt};
+    use crate::{
+        core::replication_fns::{rule_fns::RuleFns, ReplicationFns},
+        AppRuleExt,
+    };
 
     #[test]
     fn empty() {
@@ -262,6 +524,161 @@ mod tests {
         assert_eq!(archetype.components.len(), 3);
     }
 
+    #[test]
+    fn atomic_group() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate::<ComponentA>()
+            .replicate_atomic_group::<(ComponentA, ComponentB)>();
+
+        app.world.spawn((Replicated, ComponentA, ComponentB));
+
+        let archetypes = match_archetypes(&mut app.world);
+        let archetype = archetypes.first().unwrap();
+        assert_eq!(archetype.atomic_groups.len(), 1);
+        assert!(archetype
+            .components
+            .iter()
+            .all(|component| component.atomic_group == Some(0)));
+    }
+
+    #[test]
+    fn atomic_group_with_overlap() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate_group::<(ComponentA, ComponentB, ComponentC)>()
+            .replicate_atomic_group::<(ComponentA, ComponentB)>();
+
+        app.world
+            .spawn((Replicated, ComponentA, ComponentB, ComponentC));
+
+        let archetypes = match_archetypes(&mut app.world);
+        let archetype = archetypes.first().unwrap();
+        assert_eq!(archetype.atomic_groups.len(), 1);
+        let component_c = app.world.components().component_id::<ComponentC>();
+        for component in &archetype.components {
+            let expected_group = (Some(component.component_id) != component_c).then_some(0);
+            assert_eq!(
+                component.atomic_group, expected_group,
+                "components claimed by a non-atomic rule should keep their atomic group"
+            );
+        }
+    }
+
+    #[test]
+    fn overlapping_atomic_groups() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate_atomic_group::<(ComponentA, ComponentB)>()
+            .replicate_atomic_group::<(ComponentB, ComponentC)>();
+
+        app.world
+            .spawn((Replicated, ComponentA, ComponentB, ComponentC));
+
+        let archetypes = match_archetypes(&mut app.world);
+        let archetype = archetypes.first().unwrap();
+        let group = archetype.components[0]
+            .atomic_group
+            .expect("component should belong to a group");
+        assert!(
+            archetype
+                .components
+                .iter()
+                .all(|component| component.atomic_group == Some(group)),
+            "overlapping groups should be merged"
+        );
+        assert_eq!(archetype.atomic_groups[group].len(), 3);
+    }
+
+    #[test]
+    fn once() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate_once::<ComponentA>()
+            .replicate::<ComponentB>();
+
+        app.world.spawn((Replicated, ComponentA, ComponentB));
+
+        let archetypes = match_archetypes(&mut app.world);
+        let archetype = archetypes.first().unwrap();
+        let component_a = app.world.components().component_id::<ComponentA>();
+        for component in &archetype.components {
+            assert_eq!(
+                component.once,
+                Some(component.component_id) == component_a,
+                "only components registered with `replicate_once` should be sent once"
+            );
+        }
+    }
+
+    #[test]
+    fn marker() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate::<ComponentA>()
+            .replicate_with_marker::<ComponentB, ComponentA>(RuleFns::default());
+
+        let plain = app.world.spawn((Replicated, ComponentA)).id();
+        let marked = app.world.spawn((Replicated, ComponentA, ComponentB)).id();
+
+        let archetypes = match_archetypes(&mut app.world);
+        let plain_archetype = archetypes
+            .iter()
+            .find(|archetype| archetype.id == app.world.entity(plain).archetype().id())
+            .unwrap();
+        let marked_archetype = archetypes
+            .iter()
+            .find(|archetype| archetype.id == app.world.entity(marked).archetype().id())
+            .unwrap();
+
+        assert_eq!(plain_archetype.components.len(), 1);
+        assert_eq!(
+            marked_archetype.components.len(),
+            1,
+            "marker shouldn't be replicated"
+        );
+        let plain_component = plain_archetype.components.first().unwrap();
+        let marked_component = marked_archetype.components.first().unwrap();
+        assert!(
+            plain_component.fns_id != marked_component.fns_id,
+            "marker rule should take precedence"
+        );
+    }
+
+    #[test]
+    fn dependencies() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate::<ComponentA>()
+            .replicate::<ComponentB>()
+            .replicate::<ComponentC>()
+            .apply_before::<ComponentC, ComponentB>()
+            .apply_before::<ComponentB, ComponentA>();
+
+        app.world
+            .spawn((Replicated, ComponentA, ComponentB, ComponentC));
+
+        let archetypes = match_archetypes(&mut app.world);
+        let archetype = archetypes.first().unwrap();
+        let components: Vec<_> = archetype
+            .components
+            .iter()
+            .map(|component| component.component_id)
+            .collect();
+        let expected = [
+            app.world.component_id::<ComponentC>().unwrap(),
+            app.world.component_id::<ComponentB>().unwrap(),
+            app.world.component_id::<ComponentA>().unwrap(),
+        ];
+        assert_eq!(components, expected);
+    }
+
     fn match_archetypes(world: &mut World) -> ReplicatedArchetypes {
         let mut archetypes = ReplicatedArchetypes::from_world(world);
         archetypes.update(world, world.resource::<ReplicationRules>());
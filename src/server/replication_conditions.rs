@@ -0,0 +1,90 @@
+use bevy::{ecs::component::ComponentId, prelude::*, ptr::Ptr};
+
+/// Replication conditions for [`App`].
+pub trait AppConditionExt {
+    /**
+    Replicates the component only while the condition returns `true`.
+
+    The condition is evaluated on server for each entity with the component every time replication is collected.
+    While it returns `false`, insertions and changes of the component on this entity are not sent,
+    all other components continue replicating. Removals are still sent.
+    Useful to skip components that don't need updates in some states without removing them.
+
+    Changes made while the condition returned `false` won't be sent after it returns `true`
+    until the component changes again. Conditions can read other data from the world,
+    but changes of that data alone don't trigger sending.
+
+    Calling it again for the same component replaces the previous condition.
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    Skip sending health while it's full:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Health>()
+        .replicate_if::<Health>(|health, _world| health.current < health.max);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+    ```
+    **/
+    fn replicate_if<C: Component>(&mut self, condition: fn(&C, &World) -> bool) -> &mut Self;
+}
+
+impl AppConditionExt for App {
+    fn replicate_if<C: Component>(&mut self, condition: fn(&C, &World) -> bool) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let mut conditions = self
+            .world
+            .get_resource_or_insert_with(ReplicationConditions::default);
+        conditions.0.retain(|(id, _)| *id != component_id);
+        conditions.0.push((
+            component_id,
+            Box::new(move |ptr, world| {
+                // SAFETY: called only with pointers to the component of this ID.
+                let component = unsafe { ptr.deref::<C>() };
+                (condition)(component, world)
+            }),
+        ));
+
+        self
+    }
+}
+
+/// Components with conditions registered by [`AppConditionExt::replicate_if`].
+#[derive(Default, Resource)]
+pub struct ReplicationConditions(Vec<(ComponentId, UntypedConditionFn)>);
+
+impl ReplicationConditions {
+    /// Returns `true` if the component should be replicated.
+    ///
+    /// Components without conditions are always replicated.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` should point to the component with this ID.
+    pub(crate) unsafe fn check(&self, component_id: ComponentId, ptr: Ptr, world: &World) -> bool {
+        self.0
+            .iter()
+            .find(|(id, _)| *id == component_id)
+            .is_none_or(|(_, condition)| (condition)(ptr, world))
+    }
+
+    /// Returns `true` if no conditions are registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Type-erased condition from [`AppConditionExt::replicate_if`].
+type UntypedConditionFn = Box<dyn Fn(Ptr, &World) -> bool + Send + Sync>;
@@ -0,0 +1,181 @@
+use std::{cmp::Reverse, io::Write, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::ServerPlugin;
+use crate::core::common_conditions::server_running;
+
+/**
+Samples replication cost of each entity over a time window to find areas that generate excessive traffic.
+
+Call [`ReplicationHeatmap::start`] on server to begin a window. For each replicated entity
+the plugin sums the bytes of its component data sent to all clients and tracks its last position
+from [`GlobalTransform`]. When the window ends, results are available via [`ReplicationHeatmap::samples`]
+and can be exported with [`ReplicationHeatmap::write_csv`] to visualize them on top of the level.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use std::{fs::File, time::Duration};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ReplicationHeatmapPlugin))
+    .add_systems(Update, export.run_if(server_running));
+
+app.world
+    .resource_mut::<ReplicationHeatmap>()
+    .start(Duration::from_secs(30));
+
+fn export(heatmap: Res<ReplicationHeatmap>) {
+    if heatmap.is_changed() && heatmap.is_finished() {
+        for sample in heatmap.samples().iter().take(10) {
+            info!("{:?} at {:?} sent {} bytes", sample.entity, sample.position, sample.bytes);
+        }
+        let file = File::create("heatmap.csv").expect("file should be writable");
+        heatmap.write_csv(file).expect("heatmap should be exported");
+    }
+}
+```
+**/
+pub struct ReplicationHeatmapPlugin;
+
+impl Plugin for ReplicationHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplicationHeatmap>().add_systems(
+            PostUpdate,
+            Self::update
+                .after(ServerPlugin::send_replication)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl ReplicationHeatmapPlugin {
+    /// Updates positions of sampled entities and finishes the window when it elapses.
+    fn update(
+        mut heatmap: ResMut<ReplicationHeatmap>,
+        transforms: Query<&GlobalTransform>,
+        time: Res<Time>,
+    ) {
+        let Some(timer) = &mut heatmap.bypass_change_detection().timer else {
+            return;
+        };
+
+        timer.tick(time.delta());
+        let finished = timer.finished();
+
+        for (&entity, sample) in &mut heatmap.bypass_change_detection().costs {
+            if let Ok(transform) = transforms.get(entity) {
+                sample.position = Some(transform.translation());
+            }
+        }
+
+        if finished {
+            let heatmap = &mut *heatmap;
+            heatmap.timer = None;
+            heatmap.finished = true;
+            heatmap.samples.clear();
+            heatmap
+                .samples
+                .extend(heatmap.costs.drain().map(|(_, sample)| sample));
+            heatmap
+                .samples
+                .sort_unstable_by_key(|sample| Reverse(sample.bytes));
+            debug!(
+                "finished replication heatmap with {} entities",
+                heatmap.samples.len()
+            );
+        }
+    }
+}
+
+/// Per-entity replication costs.
+///
+/// See [`ReplicationHeatmapPlugin`] for details.
+#[derive(Default, Resource)]
+pub struct ReplicationHeatmap {
+    /// Remaining time of the current window.
+    timer: Option<Timer>,
+
+    /// Costs collected in the current window.
+    costs: HashMap<Entity, HeatmapSample>,
+
+    /// Results of the last finished window.
+    samples: Vec<HeatmapSample>,
+
+    finished: bool,
+}
+
+impl ReplicationHeatmap {
+    /// Starts a new sampling window, discarding the current one if it's in progress.
+    ///
+    /// Results of the previous finished window stay available until this one ends.
+    pub fn start(&mut self, window: Duration) {
+        self.timer = Some(Timer::new(window, TimerMode::Once));
+        self.costs.clear();
+        self.finished = false;
+    }
+
+    /// Returns `true` if a sampling window is in progress.
+    pub fn is_sampling(&self) -> bool {
+        self.timer.is_some()
+    }
+
+    /// Returns `true` if the last started window has finished.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns samples of the last finished window sorted by bytes in descending order.
+    pub fn samples(&self) -> &[HeatmapSample] {
+        &self.samples
+    }
+
+    /// Writes samples of the last finished window as CSV with `entity,x,y,z,bytes` columns.
+    ///
+    /// Coordinates are empty for entities without [`GlobalTransform`].
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "entity,x,y,z,bytes")?;
+        for sample in &self.samples {
+            write!(writer, "{},", sample.entity.to_bits())?;
+            match sample.position {
+                Some(position) => write!(writer, "{},{},{},", position.x, position.y, position.z)?,
+                None => write!(writer, ",,,")?,
+            }
+            writeln!(writer, "{}", sample.bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds bytes sent for the entity in the current window.
+    pub(super) fn record(&mut self, entity: Entity, bytes: usize) {
+        self.costs
+            .entry(entity)
+            .or_insert(HeatmapSample {
+                entity,
+                position: None,
+                bytes: 0,
+            })
+            .bytes += bytes;
+    }
+}
+
+/// Replication cost of a single entity over a window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeatmapSample {
+    pub entity: Entity,
+
+    /// Last known position of the entity.
+    ///
+    /// [`None`] if the entity doesn't have [`GlobalTransform`].
+    pub position: Option<Vec3>,
+
+    /// Component data bytes sent to all clients.
+    pub bytes: usize,
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 use bevy::{ecs::component::Tick, prelude::*, ptr::Ptr};
-use bincode::{DefaultOptions, Options};
+use bincode::Options;
 use bytes::Bytes;
 use varint_rs::VarintWriter;
 
@@ -21,6 +21,7 @@ use crate::core::{
     },
     replicon_channels::ReplicationChannel,
     replicon_tick::RepliconTick,
+    wire_format, ClientId,
 };
 
 /// Accumulates replication messages and sends them to clients.
@@ -85,6 +86,7 @@ impl ReplicationMessages {
         replicon_tick: RepliconTick,
         tick: Tick,
         timestamp: Duration,
+        send_time: Option<Duration>,
     ) -> bincode::Result<ConnectedClients> {
         for ((init_message, update_message), client) in
             self.data.iter_mut().zip(self.connected_clients.iter_mut())
@@ -97,6 +99,7 @@ impl ReplicationMessages {
                 replicon_tick,
                 tick,
                 timestamp,
+                send_time,
             )?;
             client.visibility_mut().update();
         }
@@ -156,7 +159,7 @@ impl InitMessage {
         self.entity_data_size
     }
 
-    /// Starts writing array by remembering its position to write length after.
+    /// Starts writing array by remembering its position to insert length after.
     ///
     /// Arrays can contain entity data or despawns inside.
     /// See also [`Self::end_array`], [`Self::write_client_mapping`], [`Self::write_entity`] and [`Self::start_entity_data`].
@@ -164,27 +167,18 @@ impl InitMessage {
         debug_assert_eq!(self.array_len, 0);
 
         self.array_pos = self.cursor.position();
-        self.cursor
-            .set_position(self.array_pos + mem::size_of_val(&self.array_len) as u64);
     }
 
-    /// Ends writing array by writing its length into the last remembered position.
+    /// Ends writing array by inserting its length as varint into the last remembered position.
     ///
     /// See also [`Self::start_array`].
     pub(super) fn end_array(&mut self) -> bincode::Result<()> {
+        insert_varint(&mut self.cursor, self.array_pos, self.array_len)?;
         if self.array_len != 0 {
-            let previous_pos = self.cursor.position();
-            self.cursor.set_position(self.array_pos);
-
-            bincode::serialize_into(&mut self.cursor, &self.array_len)?;
-
-            self.cursor.set_position(previous_pos);
             self.array_len = 0;
             self.trailing_empty_arrays = 0;
         } else {
             self.trailing_empty_arrays += 1;
-            self.cursor.set_position(self.array_pos);
-            bincode::serialize_into(&mut self.cursor, &self.array_len)?;
         }
 
         Ok(())
@@ -240,20 +234,17 @@ impl InitMessage {
         self.entity_data_pos = self.cursor.position();
     }
 
-    /// Writes entity for the current data and remembers the position after it to write length later.
+    /// Writes entity for the current data and remembers the position after it to insert length later.
     ///
     /// Should be called only after first data write.
     fn write_data_entity(&mut self) -> bincode::Result<()> {
         serialize_entity(&mut self.cursor, self.data_entity)?;
         self.entity_data_size_pos = self.cursor.position();
-        self.cursor.set_position(
-            self.entity_data_size_pos + mem::size_of_val(&self.entity_data_size) as u64,
-        );
 
         Ok(())
     }
 
-    /// Ends writing entity data by writing its length into the last remembered position.
+    /// Ends writing entity data by inserting its length as varint into the last remembered position.
     ///
     /// If the entity data is empty, nothing will be written unless `save_empty` is set to true.
     /// Should be called only inside an array and increases its length by 1.
@@ -269,12 +260,11 @@ impl InitMessage {
             self.write_data_entity()?;
         }
 
-        let previous_pos = self.cursor.position();
-        self.cursor.set_position(self.entity_data_size_pos);
-
-        bincode::serialize_into(&mut self.cursor, &self.entity_data_size)?;
-
-        self.cursor.set_position(previous_pos);
+        insert_varint(
+            &mut self.cursor,
+            self.entity_data_size_pos,
+            self.entity_data_size,
+        )?;
         self.entity_data_size = 0;
         self.array_len = self
             .array_len
@@ -303,7 +293,7 @@ impl InitMessage {
         }
 
         let size = write_with(shared_bytes, &mut self.cursor, |cursor| {
-            DefaultOptions::new().serialize_into(&mut *cursor, &fns_id)?;
+            wire_format::varint().serialize_into(&mut *cursor, &fns_id)?;
             // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
             unsafe { component_fns.serialize(ctx, rule_fns, ptr, cursor) }
         })?;
@@ -326,7 +316,7 @@ impl InitMessage {
         }
 
         let previous_pos = self.cursor.position();
-        DefaultOptions::new().serialize_into(&mut self.cursor, &fns_id)?;
+        wire_format::varint().serialize_into(&mut self.cursor, &fns_id)?;
 
         let id_size = self.cursor.position() - previous_pos;
         self.entity_data_size = self
@@ -347,8 +337,7 @@ impl InitMessage {
     ) -> bincode::Result<()> {
         if update_message.entity_data_size != 0 {
             let slice = update_message.as_slice();
-            let offset = update_message.entity_data_size_pos as usize
-                + mem::size_of_val(&update_message.entity_data_size);
+            let offset = update_message.entity_data_size_pos as usize;
             self.cursor.write_all(&slice[offset..]).unwrap();
 
             self.entity_data_size = self
@@ -369,8 +358,8 @@ impl InitMessage {
     fn as_slice(&self) -> &[u8] {
         let slice = self.cursor.get_ref();
         let position = self.cursor.position() as usize;
-        let extra_len = self.trailing_empty_arrays * mem::size_of_val(&self.array_len);
-        &slice[..position - extra_len]
+        // Length of an empty array is encoded as a single byte.
+        &slice[..position - self.trailing_empty_arrays]
     }
 
     /// Sends the message, excluding trailing empty arrays, to the specified client.
@@ -395,7 +384,7 @@ impl InitMessage {
         client.set_change_tick(replicon_tick);
 
         let mut header = [0; mem::size_of::<RepliconTick>()];
-        bincode::serialize_into(&mut header[..], &replicon_tick)?;
+        wire_format::fixint().serialize_into(&mut header[..], &replicon_tick)?;
 
         trace!("sending init message to {:?}", client.id());
         server.send(
@@ -425,7 +414,7 @@ impl Default for InitMessage {
 
 /// A reusable message with replicated component updates.
 ///
-/// Contains change tick, current tick and component updates since the last acknowledged tick for each entity.
+/// Contains change tick, current tick, send time and component updates since the last acknowledged tick for each entity.
 /// Cannot be applied on the client until the init message matching this update message's change tick
 /// has been applied to the client world.
 /// The message will be manually split into packets up to max size, and each packet will be applied
@@ -433,13 +422,17 @@ impl Default for InitMessage {
 /// Message splits only happen per-entity to avoid weird behavior from partial entity updates.
 /// Sent over the [`ReplicationChannel::Update`] channel.
 ///
+/// If data of a single entity doesn't fit into a packet, it will be split into chunks
+/// and sent over the [`ReplicationChannel::UpdateChunks`] channel with a separate update index.
+/// The client will reassemble the data and acknowledge it only after receiving all chunks.
+///
 /// See also [Limits](../index.html#limits)
 pub(super) struct UpdateMessage {
     /// Serialized data.
     cursor: Cursor<Vec<u8>>,
 
-    /// Entities and their sizes in the message with data.
-    entities: Vec<(Entity, usize)>,
+    /// Entities, their sizes in the message with data and whether the client should acknowledge them.
+    entities: Vec<(Entity, usize, bool)>,
 
     /// Entity from last call of [`Self::start_entity_data`].
     data_entity: Entity,
@@ -463,6 +456,13 @@ impl UpdateMessage {
         self.entities.clear();
     }
 
+    /// Returns size in bytes of the current entity data.
+    ///
+    /// See also [`Self::start_entity_data`] and [`Self::end_entity_data`].
+    pub(super) fn entity_data_size(&self) -> u16 {
+        self.entity_data_size
+    }
+
     /// Starts writing entity and its data.
     ///
     /// Data can contain components with their IDs.
@@ -475,38 +475,36 @@ impl UpdateMessage {
         self.entity_data_pos = self.cursor.position();
     }
 
-    /// Writes entity for the current data and remembers the position after it to write length later.
+    /// Writes entity for the current data and remembers the position after it to insert length later.
     ///
     /// Should be called only after first data write.
     fn write_data_entity(&mut self) -> bincode::Result<()> {
         serialize_entity(&mut self.cursor, self.data_entity)?;
         self.entity_data_size_pos = self.cursor.position();
-        self.cursor.set_position(
-            self.entity_data_size_pos + mem::size_of_val(&self.entity_data_size) as u64,
-        );
 
         Ok(())
     }
 
-    /// Ends writing entity data by writing its length into the last remembered position.
+    /// Ends writing entity data by inserting its length as varint into the last remembered position.
     ///
     /// If the entity data is empty, nothing will be written and the cursor will reset.
+    /// If `acknowledge` is `false`, receiving the message won't advance the change limit of the entity.
     /// See also [`Self::start_array`] and [`Self::write_component`].
-    pub(super) fn end_entity_data(&mut self) -> bincode::Result<()> {
+    pub(super) fn end_entity_data(&mut self, acknowledge: bool) -> bincode::Result<()> {
         if self.entity_data_size == 0 {
             self.cursor.set_position(self.entity_data_pos);
             return Ok(());
         }
 
-        let previous_pos = self.cursor.position();
-        self.cursor.set_position(self.entity_data_size_pos);
-
-        bincode::serialize_into(&mut self.cursor, &self.entity_data_size)?;
-
-        self.cursor.set_position(previous_pos);
+        insert_varint(
+            &mut self.cursor,
+            self.entity_data_size_pos,
+            self.entity_data_size,
+        )?;
 
         let data_size = self.cursor.position() - self.entity_data_pos;
-        self.entities.push((self.data_entity, data_size as usize));
+        self.entities
+            .push((self.data_entity, data_size as usize, acknowledge));
 
         self.entity_data_size = 0;
 
@@ -532,7 +530,7 @@ impl UpdateMessage {
         }
 
         let size = write_with(shared_bytes, &mut self.cursor, |cursor| {
-            DefaultOptions::new().serialize_into(&mut *cursor, &fns_id)?;
+            wire_format::varint().serialize_into(&mut *cursor, &fns_id)?;
             // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
             unsafe { component_fns.serialize(ctx, rule_fns, ptr, cursor) }
         })?;
@@ -554,6 +552,9 @@ impl UpdateMessage {
 
     /// Splits message according to entities inside it and sends it to the specified client.
     ///
+    /// `send_time` is the real time elapsed since the server startup, if present it's sent
+    /// in milliseconds to let clients measure latency and jitter.
+    ///
     /// Does nothing if there is no data to send.
     fn send(
         &mut self,
@@ -563,6 +564,7 @@ impl UpdateMessage {
         replicon_tick: RepliconTick,
         tick: Tick,
         timestamp: Duration,
+        send_time: Option<Duration>,
     ) -> bincode::Result<()> {
         debug_assert_eq!(self.entity_data_size, 0);
 
@@ -573,28 +575,77 @@ impl UpdateMessage {
         }
 
         trace!("sending update message(s) to {:?}", client.id());
-        const TICKS_SIZE: usize = 2 * mem::size_of::<RepliconTick>();
-        let mut header = [0; TICKS_SIZE + mem::size_of::<u16>()];
-        bincode::serialize_into(&mut header[..], &(client.change_tick(), replicon_tick))?;
+        let mut header = Vec::with_capacity(MAX_UPDATE_HEADER_SIZE);
+        wire_format::fixint().serialize_into(&mut header, &replicon_tick)?;
+        // The change tick is usually close to the current tick, so the difference is much smaller.
+        // The lowest bit indicates the presence of the send time.
+        let tick_diff = replicon_tick.get().wrapping_sub(client.change_tick().get());
+        header.write_u64_varint(u64::from(tick_diff) << 1 | u64::from(send_time.is_some()))?;
+        if let Some(send_time) = send_time {
+            // Wrapping is expected, clients use only differences between timestamps.
+            let send_time = send_time.as_millis() as u32;
+            wire_format::fixint().serialize_into(&mut header, &send_time)?;
+        }
+        let index_pos = header.len();
 
         let mut message_size = 0;
         let client_id = client.id();
         let (mut update_index, mut entities) =
             client.register_update(client_buffers, tick, timestamp);
-        for &(entity, data_size) in &self.entities {
+        for &(entity, data_size, acknowledge) in &self.entities {
+            if index_pos + MAX_U16_VARINT_SIZE + data_size > MAX_PACKET_SIZE {
+                if message_size != 0 {
+                    let (message, remaining) = slice.split_at(message_size);
+                    slice = remaining;
+                    message_size = 0;
+
+                    header.truncate(index_pos);
+                    header.write_u16_varint(update_index)?;
+
+                    server.send(
+                        client_id,
+                        ReplicationChannel::Update,
+                        Bytes::from([&header, message].concat()),
+                    );
+
+                    (update_index, entities) =
+                        client.register_update(client_buffers, tick, timestamp);
+                }
+
+                if acknowledge {
+                    entities.push(entity);
+                }
+                let (data, remaining) = slice.split_at(data_size);
+                slice = remaining;
+
+                header.truncate(index_pos);
+                header.write_u16_varint(update_index)?;
+                send_chunks(server, client_id, &header, data)?;
+
+                if !slice.is_empty() {
+                    (update_index, entities) =
+                        client.register_update(client_buffers, tick, timestamp);
+                }
+
+                continue;
+            }
+
             // Try to pack back first, then try to pack forward.
             if message_size == 0
-                || can_pack(header.len(), message_size, data_size)
-                || can_pack(header.len(), data_size, message_size)
+                || can_pack(index_pos + MAX_U16_VARINT_SIZE, message_size, data_size)
+                || can_pack(index_pos + MAX_U16_VARINT_SIZE, data_size, message_size)
             {
-                entities.push(entity);
+                if acknowledge {
+                    entities.push(entity);
+                }
                 message_size += data_size;
             } else {
                 let (message, remaining) = slice.split_at(message_size);
                 slice = remaining;
                 message_size = data_size;
 
-                bincode::serialize_into(&mut header[TICKS_SIZE..], &update_index)?;
+                header.truncate(index_pos);
+                header.write_u16_varint(update_index)?;
 
                 server.send(
                     client_id,
@@ -610,7 +661,8 @@ impl UpdateMessage {
         }
 
         if !slice.is_empty() {
-            bincode::serialize_into(&mut header[TICKS_SIZE..], &update_index)?;
+            header.truncate(index_pos);
+            header.write_u16_varint(update_index)?;
 
             server.send(
                 client_id,
@@ -668,9 +720,76 @@ fn write_with<'a>(
     Ok(size)
 }
 
-fn can_pack(header_size: usize, base: usize, add: usize) -> bool {
-    const MAX_PACKET_SIZE: usize = 1200; // TODO: make it configurable by the messaging backend.
+/// Splits entity data into chunks that fit into packets and sends them over [`ReplicationChannel::UpdateChunks`].
+///
+/// Each chunk contains the update message header followed by the chunk index, the number of chunks
+/// as varints and the data.
+fn send_chunks(
+    server: &mut RepliconServer,
+    client_id: ClientId,
+    header: &[u8],
+    data: &[u8],
+) -> bincode::Result<()> {
+    let mut chunk_header = Vec::with_capacity(2 * MAX_U16_VARINT_SIZE);
+    let chunk_size = MAX_PACKET_SIZE - header.len() - 2 * MAX_U16_VARINT_SIZE;
+    let chunks_count: u16 = data
+        .len()
+        .div_ceil(chunk_size)
+        .try_into()
+        .map_err(|_| bincode::ErrorKind::SizeLimit)?;
+
+    for (chunk_index, chunk) in (0..chunks_count).zip(data.chunks(chunk_size)) {
+        chunk_header.clear();
+        chunk_header.write_u16_varint(chunk_index)?;
+        chunk_header.write_u16_varint(chunks_count)?;
+        server.send(
+            client_id,
+            ReplicationChannel::UpdateChunks,
+            Bytes::from([header, &chunk_header, chunk].concat()),
+        );
+    }
+
+    Ok(())
+}
 
+const MAX_PACKET_SIZE: usize = 1200; // TODO: make it configurable by the messaging backend.
+
+/// Maximum size of [`u16`] encoded as LEB128 varint.
+const MAX_U16_VARINT_SIZE: usize = 3;
+
+/// Maximum size of [`u32`] encoded as LEB128 varint.
+const MAX_U32_VARINT_SIZE: usize = 5;
+
+/// Maximum size of the update message header: tick, varint tick difference with the send time flag,
+/// optional send time and varint update index.
+///
+/// The tick difference with the flag takes 33 bits, which still fits into the maximum size of [`u32`] varint.
+const MAX_UPDATE_HEADER_SIZE: usize = mem::size_of::<RepliconTick>()
+    + MAX_U32_VARINT_SIZE
+    + mem::size_of::<u32>()
+    + MAX_U16_VARINT_SIZE;
+
+/// Inserts `value` as LEB128 varint at `pos`, shifting already written data after it.
+///
+/// Used for lengths and sizes that are known only after writing the data they describe.
+fn insert_varint(cursor: &mut Cursor<Vec<u8>>, pos: u64, value: u16) -> bincode::Result<()> {
+    let mut varint = Cursor::new([0; MAX_U16_VARINT_SIZE]);
+    varint.write_u16_varint(value)?;
+    let varint_len = varint.position() as usize;
+
+    let end = cursor.position() as usize;
+    let buffer = cursor.get_mut();
+    buffer.truncate(end); // Remove stale data from previous messages to avoid shifting it too.
+    buffer.splice(
+        pos as usize..pos as usize,
+        varint.get_ref()[..varint_len].iter().copied(),
+    );
+    cursor.set_position((end + varint_len) as u64);
+
+    Ok(())
+}
+
+fn can_pack(header_size: usize, base: usize, add: usize) -> bool {
     let dangling = (base + header_size) % MAX_PACKET_SIZE;
     (dangling > 0) && ((dangling + add) <= MAX_PACKET_SIZE)
 }
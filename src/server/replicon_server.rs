@@ -8,9 +8,10 @@ use crate::core::ClientId;
 /// The messaging backend is responsible for updating this resource:
 /// - When the server is started or stopped, [`Self::set_running`] should be used to reflect this.
 /// - For receiving messages, [`Self::insert_received`] should be used.
-/// A system to forward messages from the backend to Replicon should run in [`ServerSet::ReceivePackets`](super::ServerSet::ReceivePackets).
+///   A system to forward messages from the backend to Replicon should run in [`ServerSet::ReceivePackets`](super::ServerSet::ReceivePackets).
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
-/// A system to forward messages from Replicon to the backend should run in [`ServerSet::SendPackets`](super::ServerSet::SendPackets).
+///   A system to forward messages from Replicon to the backend should run in [`ServerSet::SendPackets`](super::ServerSet::SendPackets).
+/// - For disconnecting clients, [`Self::drain_disconnects`] should be used to drain all disconnect requests.
 #[derive(Resource, Default)]
 pub struct RepliconServer {
     /// Indicates if the server is open for connections.
@@ -26,6 +27,12 @@ pub struct RepliconServer {
 
     /// List of sent messages for each channel since the last tick.
     sent_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// Clients requested to disconnect since the last drain of sent messages.
+    disconnect_requests: Vec<ClientId>,
+
+    /// Clients requested to disconnect before the last drain of sent messages.
+    ready_disconnects: Vec<ClientId>,
 }
 
 impl RepliconServer {
@@ -41,6 +48,8 @@ impl RepliconServer {
         }
         self.sent_messages
             .retain(|&(sender_id, ..)| sender_id != client_id);
+        self.disconnect_requests.retain(|&id| id != client_id);
+        self.ready_disconnects.retain(|&id| id != client_id);
     }
 
     /// Receives all available messages from clients over a channel.
@@ -91,6 +100,8 @@ impl RepliconServer {
                 receive_channel.clear();
             }
             self.sent_messages.clear();
+            self.disconnect_requests.clear();
+            self.ready_disconnects.clear();
         }
 
         self.running = running;
@@ -112,13 +123,56 @@ impl RepliconServer {
         self.sent_messages.retain(f)
     }
 
+    /// Marks disconnect requests of a client as ready after its messages were drained with [`Self::retain_sent`].
+    ///
+    /// Used for testing.
+    pub(crate) fn ready_disconnect(&mut self, client_id: ClientId) {
+        let ready_disconnects = &mut self.ready_disconnects;
+        self.disconnect_requests.retain(|&id| {
+            if id == client_id {
+                ready_disconnects.push(id);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Returns an iterator over mutable sent messages with client ID and channel.
+    pub(crate) fn iter_sent_mut(&mut self) -> impl Iterator<Item = &mut (ClientId, u8, Bytes)> {
+        self.sent_messages.iter_mut()
+    }
+
     /// Removes all sent messages, returning them as an iterator with client ID and channel.
     ///
     /// Should be called only from the messaging backend.
     pub fn drain_sent(&mut self) -> impl Iterator<Item = (ClientId, u8, Bytes)> + '_ {
+        self.ready_disconnects.append(&mut self.disconnect_requests);
         self.sent_messages.drain(..)
     }
 
+    /// Requests the messaging backend to disconnect a client.
+    ///
+    /// Messages sent to the client before the request will be drained first.
+    /// See also [`ServerCommands::disconnect`](super::server_commands::ServerCommands::disconnect).
+    pub fn disconnect(&mut self, client_id: ClientId) {
+        if !self.running {
+            warn!("trying to disconnect `{client_id:?}` when the server is not running");
+            return;
+        }
+
+        debug!("requesting disconnect of `{client_id:?}`");
+        self.disconnect_requests.push(client_id);
+    }
+
+    /// Removes all clients requested to disconnect before the last [`Self::drain_sent`], returning them as an iterator.
+    ///
+    /// Should be called only from the messaging backend after the drained messages are sent,
+    /// for example in [`ServerSet::ReceivePackets`](super::ServerSet::ReceivePackets) on the next frame.
+    pub fn drain_disconnects(&mut self) -> impl Iterator<Item = ClientId> + '_ {
+        self.ready_disconnects.drain(..)
+    }
+
     /// Adds a message from a client to the list of received messages.
     ///
     /// Should be called only from the messaging backend.
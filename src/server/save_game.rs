@@ -0,0 +1,340 @@
+use std::{any::TypeId, io::Cursor};
+
+use bevy::{
+    ecs::{entity::EntityHashMap, system::CommandQueue, world::FromWorld},
+    prelude::*,
+    scene::{DynamicSceneBuilder, SceneFilter, SceneSpawnError},
+    utils::HashSet,
+};
+use bincode::{ErrorKind, Options};
+
+use super::server_tick::ServerTick;
+use crate::{
+    client::server_entity_map::ServerEntityMap,
+    core::{
+        command_markers::EntityMarkers,
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            FnsId, ReplicationFns,
+        },
+        replication_rules::ReplicationRules,
+        wire_format,
+    },
+    scene, Replicated,
+};
+
+/// Saved resources for [`App`].
+pub trait AppSaveExt {
+    /// Includes the resource into saves created with [`capture_save`].
+    ///
+    /// The resource should be registered with [`App::register_type`] and have `#[reflect(Resource)]`.
+    /// [`ServerTick`] is always included.
+    fn save_resource<R: Resource>(&mut self) -> &mut Self;
+}
+
+impl AppSaveExt for App {
+    fn save_resource<R: Resource>(&mut self) -> &mut Self {
+        let mut saved_resources = self
+            .world
+            .get_resource_or_insert_with(SavedResources::default);
+        let type_id = TypeId::of::<R>();
+        if !saved_resources.0.contains(&type_id) {
+            saved_resources.0.push(type_id);
+        }
+
+        self
+    }
+}
+
+/**
+Captures replicated entities and saved resources from a live server into a scene.
+
+Replicated entities are captured with [`scene::replicate_into`], so all replicated components
+should be registered with [`App::register_type`] and have `#[reflect(Component)]`.
+Resources registered with [`AppSaveExt::save_resource`] and [`ServerTick`] are included too.
+
+The scene can be serialized as any other [`DynamicScene`] and later loaded with [`load_save`].
+
+# Panics
+
+Panics if any replicated component or saved resource is not registered using [`App::register_type`].
+
+# Examples
+
+Save the live world to RON and boot a new server from it:
+
+```
+use bevy::{asset::ron, prelude::*, scene::serde::SceneDeserializer};
+use bevy_replicon::{prelude::*, server::save_game};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.register_type::<Score>()
+    .register_type::<Player>()
+    .replicate::<Player>()
+    .save_resource::<Score>()
+    .init_resource::<Score>();
+
+// Saving.
+let scene = save_game::capture_save(&app.world);
+let registry = app.world.resource::<AppTypeRegistry>().clone();
+let ron = scene
+    .serialize_ron(&registry)
+    .expect("save should be serializable");
+
+// Loading, usually on a different run before starting the server.
+let scene_deserializer = SceneDeserializer {
+    type_registry: &registry.read(),
+};
+let mut deserializer = ron::Deserializer::from_str(&ron).expect("save should be valid ron");
+let scene = scene_deserializer
+    .deserialize(&mut deserializer)
+    .expect("ron should be convertible to scene");
+save_game::load_save(&mut app.world, &scene).expect("save should be loadable");
+
+#[derive(Component, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct Player;
+
+#[derive(Default, Reflect, Resource)]
+#[reflect(Resource)]
+struct Score(u32);
+```
+**/
+pub fn capture_save(world: &World) -> DynamicScene {
+    let mut resource_filter = SceneFilter::deny_all().allow::<ServerTick>();
+    if let Some(saved_resources) = world.get_resource::<SavedResources>() {
+        for &type_id in &saved_resources.0 {
+            resource_filter = resource_filter.allow_by_id(type_id);
+        }
+    }
+
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_resource_filter(resource_filter)
+        .extract_resources()
+        .build();
+    scene::replicate_into(&mut scene, world);
+
+    scene
+}
+
+/**
+Replaces replicated entities and resources with the scene created by [`capture_save`].
+
+All replicated entities are despawned and entities from the scene are spawned with [`Replicated`].
+Entity IDs are not preserved, but entities inside components are mapped by the scene
+if the components reflect [`MapEntities`](bevy::ecs::reflect::ReflectMapEntities).
+
+[`ServerTick`] is restored only if the saved tick is newer, since ticks should always increase
+for connected clients. Clients that connect or reconnect after loading receive all entities as new.
+Already connected clients receive despawns of the old entities and spawns of the loaded ones.
+
+Returns the number of loaded entities.
+**/
+pub fn load_save(world: &mut World, scene: &DynamicScene) -> Result<usize, SceneSpawnError> {
+    let server_tick = world.get_resource::<ServerTick>().map(|tick| **tick);
+    let entities = replace_replicated(world, scene)?;
+
+    if let Some(server_tick) = server_tick {
+        let mut loaded_tick = world.resource_mut::<ServerTick>();
+        if **loaded_tick < server_tick {
+            loaded_tick.set(server_tick);
+        }
+    }
+
+    info!("loaded save with {entities} entities");
+
+    Ok(entities)
+}
+
+/**
+Captures replicated components from a live server into bytes using replication functions.
+
+Unlike [`capture_save`], doesn't require reflection. Components are serialized with functions
+registered via [`RuleFns::with_save`](crate::core::replication_fns::rule_fns::RuleFns::with_save)
+or with replication functions if there are none, so components can be quantized on the wire,
+but stored losslessly. Resources are not included, except [`ServerTick`].
+
+The data can be loaded with [`load_binary`] by an app with the same replication rules.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::save_game};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.replicate::<Player>();
+
+let save = save_game::capture_binary(&app.world).expect("save should be serializable");
+save_game::load_binary(&mut app.world, &save).expect("save should be loadable");
+
+#[derive(Component, Deserialize, Serialize)]
+struct Player;
+```
+**/
+pub fn capture_binary(world: &World) -> bincode::Result<Vec<u8>> {
+    let server_tick = world
+        .get_resource::<ServerTick>()
+        .copied()
+        .unwrap_or_default();
+    let replication_fns = world.resource::<ReplicationFns>();
+    let rules = world.resource::<ReplicationRules>();
+    let ctx = SerializeCtx::new(world, *server_tick);
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut entities = Vec::new();
+    let mut saved_ids = HashSet::new();
+    let marker_id = world.components().component_id::<Replicated>();
+    for archetype in world
+        .archetypes()
+        .iter()
+        .filter(|archetype| marker_id.is_some_and(|marker_id| archetype.contains(marker_id)))
+    {
+        // Rules are sorted by priority, so functions of the first matching rule are used for each component.
+        let mut components = Vec::new();
+        saved_ids.clear();
+        for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
+            for fns_info in &rule.components {
+                if saved_ids.insert(fns_info.component_id()) {
+                    components.push((fns_info.component_id(), fns_info.fns_id()));
+                }
+            }
+        }
+
+        for entity in archetype.entities() {
+            entities.push((entity.id(), components.clone()));
+        }
+    }
+
+    wire_format::varint().serialize_into(&mut cursor, &*server_tick)?;
+    wire_format::varint().serialize_into(&mut cursor, &entities.len())?;
+    for (entity, components) in entities {
+        wire_format::varint().serialize_into(&mut cursor, &entity.to_bits())?;
+        wire_format::varint().serialize_into(&mut cursor, &components.len())?;
+        for (component_id, fns_id) in components {
+            let (component_fns, rule_fns) = replication_fns.get(fns_id);
+            let component = world
+                .get_by_id(entity, component_id)
+                .expect("archetype should contain the component");
+            wire_format::varint().serialize_into(&mut cursor, &fns_id)?;
+            // SAFETY: `component_fns`, `component` and `rule_fns` were created for the same component type.
+            unsafe { component_fns.serialize(&ctx, rule_fns.for_save(), component, &mut cursor)? };
+        }
+    }
+
+    Ok(cursor.into_inner())
+}
+
+/**
+Replaces replicated entities with the data created by [`capture_binary`].
+
+All replicated entities are despawned and entities from the data are spawned with [`Replicated`].
+Entity IDs are not preserved, entities inside components are mapped if their deserialization
+functions map entities. [`ServerTick`] is restored the same way as in [`load_save`].
+
+Returns the number of loaded entities.
+**/
+pub fn load_binary(world: &mut World, save: &[u8]) -> bincode::Result<usize> {
+    let server_tick = world.get_resource::<ServerTick>().map(|tick| **tick);
+
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<Replicated>>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        world.despawn(entity);
+    }
+
+    let mut cursor = Cursor::new(save);
+    let saved_tick = wire_format::varint().deserialize_from(&mut cursor)?;
+    let entities_len: usize = wire_format::varint().deserialize_from(&mut cursor)?;
+    world.resource_scope(
+        |world, replication_fns: Mut<ReplicationFns>| -> bincode::Result<()> {
+            let mut queue = CommandQueue::default();
+            let mut entity_map = ServerEntityMap::default();
+            let entity_markers = EntityMarkers::from_world(world);
+            for _ in 0..entities_len {
+                let saved_entity =
+                    Entity::from_bits(wire_format::varint().deserialize_from(&mut cursor)?);
+                let entity = entity_map
+                    .get_by_server_or_insert(saved_entity, || world.spawn(Replicated).id());
+                let components_len: usize = wire_format::varint().deserialize_from(&mut cursor)?;
+                for _ in 0..components_len {
+                    let fns_id: FnsId = wire_format::varint().deserialize_from(&mut cursor)?;
+                    if !replication_fns.contains(fns_id) {
+                        return Err(ErrorKind::Custom(
+                            "save contains unregistered replication functions".into(),
+                        )
+                        .into());
+                    }
+
+                    let (component_fns, rule_fns) = replication_fns.get(fns_id);
+                    let world_cell = world.as_unsafe_world_cell();
+                    // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
+                    let mut entity_mut: EntityMut =
+                        unsafe { world_cell.world_mut().entity_mut(entity).into() };
+                    let mut commands =
+                        Commands::new_from_entities(&mut queue, world_cell.entities());
+                    let mut ctx = WriteCtx::new(&mut commands, &mut entity_map, saved_tick);
+                    // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+                    unsafe {
+                        component_fns.write(
+                            &mut ctx,
+                            rule_fns.for_save(),
+                            &entity_markers,
+                            &mut entity_mut,
+                            &mut cursor,
+                        )?;
+                    }
+                    queue.apply(world);
+                }
+            }
+
+            Ok(())
+        },
+    )?;
+
+    if let Some(mut loaded_tick) = world.get_resource_mut::<ServerTick>() {
+        let newest = server_tick
+            .filter(|&tick| tick > saved_tick)
+            .unwrap_or(saved_tick);
+        loaded_tick.set(newest);
+    }
+
+    info!("loaded binary save with {entities_len} entities");
+
+    Ok(entities_len)
+}
+
+/// Despawns all replicated entities and spawns entities from the scene with [`Replicated`].
+///
+/// Returns the number of spawned entities.
+pub(super) fn replace_replicated(
+    world: &mut World,
+    scene: &DynamicScene,
+) -> Result<usize, SceneSpawnError> {
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<Replicated>>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        world.despawn(entity);
+    }
+
+    let mut entity_map = EntityHashMap::default();
+    scene.write_to_world(world, &mut entity_map)?;
+
+    for &entity in entity_map.values() {
+        world.entity_mut(entity).insert(Replicated);
+    }
+
+    Ok(entity_map.len())
+}
+
+/// Resources registered with [`AppSaveExt::save_resource`].
+#[derive(Default, Resource)]
+struct SavedResources(Vec<TypeId>);
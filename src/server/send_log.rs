@@ -0,0 +1,134 @@
+use std::{collections::VecDeque, ops::RangeBounds};
+
+use bevy::{ecs::component::ComponentId, prelude::*, utils::HashMap};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+Retains which entities and components were included in outgoing replication messages.
+
+Useful for tooling to investigate bandwidth spikes: query the ticks around the spike
+with [`SendLog::query`] or [`SendLog::bytes_by_component`] to see what was sent.
+
+Only component insertions and changes are logged. Mappings, despawns and removals aren't included.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, SendLogPlugin))
+    .add_systems(Update, report_spike.run_if(server_running));
+
+fn report_spike(send_log: Res<SendLog>, server_tick: Res<ServerTick>) {
+    let ticks = (**server_tick - 60)..=**server_tick;
+    for (component_id, bytes) in send_log.bytes_by_component(ticks) {
+        if bytes > 64 * 1024 {
+            warn!("{component_id:?} sent {bytes} bytes over the last 60 ticks");
+        }
+    }
+}
+```
+**/
+pub struct SendLogPlugin;
+
+impl Plugin for SendLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SendLog>();
+    }
+}
+
+/// Log of sent components for the last [`Self::max_ticks`] ticks.
+///
+/// See [`SendLogPlugin`] for details.
+#[derive(Resource)]
+pub struct SendLog {
+    /// Number of ticks for which records are retained.
+    ///
+    /// Older records are discarded when a new tick is sent.
+    pub max_ticks: u32,
+
+    /// Records sorted by tick.
+    records: VecDeque<SentComponent>,
+}
+
+impl SendLog {
+    /// Returns records for ticks within the range.
+    pub fn query(
+        &self,
+        ticks: impl RangeBounds<RepliconTick>,
+    ) -> impl Iterator<Item = &SentComponent> {
+        self.records
+            .iter()
+            .filter(move |record| ticks.contains(&record.tick))
+    }
+
+    /// Returns total sent bytes of each component for ticks within the range.
+    pub fn bytes_by_component(
+        &self,
+        ticks: impl RangeBounds<RepliconTick>,
+    ) -> HashMap<ComponentId, usize> {
+        let mut bytes = HashMap::new();
+        for record in self.query(ticks) {
+            *bytes.entry(record.component_id).or_default() += record.bytes;
+        }
+
+        bytes
+    }
+
+    /// Returns the number of retained records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if there are no retained records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends records of the sent tick and discards records older than [`Self::max_ticks`].
+    pub(super) fn record(
+        &mut self,
+        tick: RepliconTick,
+        records: impl Iterator<Item = SentComponent>,
+    ) {
+        self.records.extend(records);
+        while self
+            .records
+            .front()
+            .is_some_and(|record| tick - record.tick >= self.max_ticks)
+        {
+            self.records.pop_front();
+        }
+    }
+}
+
+impl Default for SendLog {
+    fn default() -> Self {
+        Self {
+            max_ticks: 600,
+            records: Default::default(),
+        }
+    }
+}
+
+/// A component that was included in outgoing messages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SentComponent {
+    /// Tick of the messages.
+    pub tick: RepliconTick,
+
+    pub entity: Entity,
+
+    pub component_id: ComponentId,
+
+    /// Number of clients the component was sent to.
+    pub clients: usize,
+
+    /// Component data bytes sent to all clients.
+    pub bytes: usize,
+}
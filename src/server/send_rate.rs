@@ -0,0 +1,67 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/// Send rates of components for [`App`].
+pub trait AppSendRateExt {
+    /**
+    Sends changes of the component only on every `ticks`-th server tick.
+
+    Useful for components that change every frame but don't need to be sent that often,
+    like cosmetic animation state. Changes made on other ticks are delayed until the next allowed tick,
+    only the latest value is sent. Insertions are always sent immediately.
+
+    While a change is delayed, the entity isn't acknowledged for the client, so other changed components
+    of the entity are sent again until the delayed change goes out.
+    Ignored for components from atomic groups, since they are always sent together.
+
+    Calling it again for the same component replaces the previous rate.
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    Send animation state at most once every 4 ticks:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<AnimationPhase>()
+        .set_send_rate::<AnimationPhase>(4);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct AnimationPhase(f32);
+    ```
+    **/
+    fn set_send_rate<C: Component>(&mut self, ticks: u32) -> &mut Self;
+}
+
+impl AppSendRateExt for App {
+    fn set_send_rate<C: Component>(&mut self, ticks: u32) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        let mut send_rates = self.world.get_resource_or_insert_with(SendRates::default);
+        send_rates.0.retain(|&(id, _)| id != component_id);
+        send_rates.0.push((component_id, ticks.max(1)));
+
+        self
+    }
+}
+
+/// Components with send rates registered by [`AppSendRateExt::set_send_rate`].
+#[derive(Default, Resource)]
+pub(crate) struct SendRates(Vec<(ComponentId, u32)>);
+
+impl SendRates {
+    /// Returns `true` if changes of the component can be sent on this tick.
+    ///
+    /// Components without a send rate can be sent on any tick.
+    pub(crate) fn is_due(&self, component_id: ComponentId, tick: RepliconTick) -> bool {
+        self.0
+            .iter()
+            .find(|&&(id, _)| id == component_id)
+            .is_none_or(|&(_, ticks)| tick.get().is_multiple_of(ticks))
+    }
+}
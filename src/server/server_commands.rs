@@ -0,0 +1,142 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    clone_buffer, pending_from_client::PendingFromClient, replicon_server::RepliconServer,
+};
+use crate::{
+    core::ClientId,
+    network_event::{
+        disconnect_reason::DisconnectReason,
+        server_event::{SendMode, ToClients},
+    },
+};
+
+/// A [`SystemParam`] for server-specific commands.
+#[derive(SystemParam)]
+pub struct ServerCommands<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl ServerCommands<'_, '_> {
+    /**
+    Sends the reason to a client and requests the messaging backend to disconnect it.
+
+    The reason is sent before the disconnect request, so the client can read it as [`DisconnectReason<D>`].
+    The reason should be registered with
+    [`AppDisconnectExt::add_disconnect_reason`](crate::network_event::disconnect_reason::AppDisconnectExt::add_disconnect_reason).
+    See also [`RepliconServer::disconnect`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_disconnect_reason::<KickReason>()
+        .add_systems(Update, reject_new.run_if(server_running));
+
+    fn reject_new(mut server_events: EventReader<ServerEvent>, mut commands: ServerCommands) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                commands.disconnect(client_id, KickReason::ServerFull);
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    enum KickReason {
+        ServerFull,
+    }
+    ```
+    **/
+    pub fn disconnect<D>(&mut self, client_id: ClientId, reason: D)
+    where
+        D: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        self.commands.add(move |world: &mut World| {
+            if client_id == ClientId::SERVER {
+                warn!("ignoring disconnect of the listen server client");
+                return;
+            }
+
+            world.send_event(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: DisconnectReason(reason),
+            });
+            world.resource_mut::<RepliconServer>().disconnect(client_id);
+        });
+    }
+
+    /// Inserts a component value received from a client and marks it with [`PendingFromClient<C>`].
+    ///
+    /// The component should be registered with
+    /// [`AppPendingExt::track_pending_from_client`](super::pending_from_client::AppPendingExt::track_pending_from_client),
+    /// otherwise the marker won't be removed automatically.
+    /// Does nothing if the entity doesn't exist.
+    pub fn apply_from_client<C: Component>(
+        &mut self,
+        entity: Entity,
+        client_id: ClientId,
+        component: C,
+    ) {
+        self.commands.add(move |world: &mut World| {
+            let Some(mut entity) = world.get_entity_mut(entity) else {
+                debug!("ignoring value from `{client_id:?}` for missing `{entity:?}`");
+                return;
+            };
+
+            entity.insert((component, PendingFromClient::<C>::new(client_id)));
+        });
+    }
+
+    /**
+    Spawns `count` copies of a replicated entity and returns them.
+
+    Only [`Replicated`](crate::core::Replicated) and components that match replication rules are copied.
+    Components are serialized once and written into each copy without mapping,
+    so entities inside components keep pointing to the same entities.
+    Copies are sent to clients with the bytes of the template, so replicating them
+    doesn't serialize each copy, unless their components change before sending.
+
+    If the entity doesn't exist or isn't replicated, returned entities are despawned.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Monster>()
+        .add_systems(Update, spawn_horde.run_if(server_running));
+
+    fn spawn_horde(mut commands: ServerCommands, templates: Query<Entity, Added<Monster>>) {
+        for template in &templates {
+            commands.clone_replicated(template, 100);
+        }
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Monster;
+    ```
+    **/
+    pub fn clone_replicated(&mut self, entity: Entity, count: usize) -> Vec<Entity> {
+        let clones: Vec<_> = (0..count)
+            .map(|_| self.commands.spawn_empty().id())
+            .collect();
+        let cloned = clones.clone();
+        self.commands.add(move |world: &mut World| {
+            if let Err(e) = clone_buffer::clone_replicated(world, entity, &cloned) {
+                error!("unable to clone {entity:?}: {e}");
+            }
+        });
+
+        clones
+    }
+}
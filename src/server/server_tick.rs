@@ -16,7 +16,8 @@ use crate::core::replicon_tick::RepliconTick;
 ///
 /// See [`ServerInitTick`](crate::client::ServerInitTick) for tracking the last received
 /// tick on clients.
-#[derive(Clone, Copy, Deref, Debug, Default, Deserialize, Resource, Serialize)]
+#[derive(Clone, Copy, Deref, Debug, Default, Deserialize, Reflect, Resource, Serialize)]
+#[reflect(Resource)]
 pub struct ServerTick(RepliconTick);
 
 impl ServerTick {
@@ -31,4 +32,10 @@ impl ServerTick {
     pub fn increment(&mut self) {
         self.increment_by(1)
     }
+
+    /// Assigns the tick from an external source.
+    #[inline]
+    pub(super) fn set(&mut self, tick: RepliconTick) {
+        self.0 = tick;
+    }
 }
@@ -0,0 +1,175 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*, utils::HashMap};
+
+use super::{save_game, server_tick::ServerTick, ServerSet};
+use crate::{
+    core::{common_conditions::has_authority, replicon_tick::RepliconTick},
+    scene,
+};
+
+/**
+Captures and restores named snapshots of replicated state at runtime.
+
+Send [`SnapshotCommand`] to capture the current state under a name, list captured snapshots,
+restore one or remove it. Useful for undoing griefing on live servers or repeatedly testing
+a scenario from a saved point, for example by forwarding commands from a server console.
+
+Snapshots are built with [`scene::replicate_into`], so all replicated components should be
+registered with [`App::register_type`] and have `#[reflect(Component)]`.
+On restore all replicated entities are despawned and entities from the snapshot are spawned
+with [`Replicated`](crate::Replicated), so clients receive them as new entities. Entity IDs are not preserved.
+
+Commands are applied in [`PreUpdate`] after [`ServerSet::Receive`].
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ServerSnapshotsPlugin))
+    .add_systems(Update, capture_on_start);
+
+fn capture_on_start(mut snapshot_commands: EventWriter<SnapshotCommand>) {
+    snapshot_commands.send(SnapshotCommand::Capture("start".into()));
+}
+```
+**/
+pub struct ServerSnapshotsPlugin;
+
+impl Plugin for ServerSnapshotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerSnapshots>()
+            .add_event::<SnapshotCommand>()
+            .add_systems(
+                PreUpdate,
+                Self::apply_commands
+                    .after(ServerSet::Receive)
+                    .run_if(has_authority),
+            );
+    }
+}
+
+impl ServerSnapshotsPlugin {
+    fn apply_commands(world: &mut World, mut reader: Local<ManualEventReader<SnapshotCommand>>) {
+        let commands: Vec<_> = reader
+            .read(world.resource::<Events<SnapshotCommand>>())
+            .cloned()
+            .collect();
+
+        for command in commands {
+            match command {
+                SnapshotCommand::Capture(name) => capture(world, name),
+                SnapshotCommand::Restore(name) => restore(world, &name),
+                SnapshotCommand::Remove(name) => {
+                    let mut snapshots = world.resource_mut::<ServerSnapshots>();
+                    if snapshots.snapshots.remove(&name).is_some() {
+                        info!("removed snapshot `{name}`");
+                    } else {
+                        warn!("unable to remove missing snapshot `{name}`");
+                    }
+                }
+                SnapshotCommand::List => {
+                    let snapshots = world.resource::<ServerSnapshots>();
+                    info!("{} snapshots captured", snapshots.len());
+                    for (name, info) in snapshots.iter() {
+                        info!(
+                            "`{name}`: {} entities at {:?}",
+                            info.entities, info.server_tick
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn capture(world: &mut World, name: String) {
+    let mut scene = DynamicScene::default();
+    scene::replicate_into(&mut scene, world);
+
+    let info = SnapshotInfo {
+        server_tick: world
+            .get_resource::<ServerTick>()
+            .map(|server_tick| **server_tick)
+            .unwrap_or_default(),
+        entities: scene.entities.len(),
+    };
+    info!("captured snapshot `{name}` with {} entities", info.entities);
+
+    world
+        .resource_mut::<ServerSnapshots>()
+        .snapshots
+        .insert(name, (info, scene));
+}
+
+fn restore(world: &mut World, name: &str) {
+    world.resource_scope(|world, snapshots: Mut<ServerSnapshots>| {
+        let Some((_, scene)) = snapshots.snapshots.get(name) else {
+            warn!("unable to restore missing snapshot `{name}`");
+            return;
+        };
+
+        match save_game::replace_replicated(world, scene) {
+            Ok(entities) => info!("restored snapshot `{name}` with {entities} entities"),
+            Err(e) => error!("unable to restore snapshot `{name}`: {e}"),
+        }
+    });
+}
+
+/// A command for [`ServerSnapshotsPlugin`].
+#[derive(Clone, Debug, Event, PartialEq, Eq)]
+pub enum SnapshotCommand {
+    /// Captures replicated state under the name, replacing the existing snapshot.
+    Capture(String),
+    /// Replaces replicated state with the snapshot.
+    Restore(String),
+    /// Removes the snapshot.
+    Remove(String),
+    /// Logs all captured snapshots.
+    ///
+    /// They can also be obtained from [`ServerSnapshots::iter`].
+    List,
+}
+
+/// Snapshots captured by [`ServerSnapshotsPlugin`].
+#[derive(Default, Resource)]
+pub struct ServerSnapshots {
+    snapshots: HashMap<String, (SnapshotInfo, DynamicScene)>,
+}
+
+impl ServerSnapshots {
+    /// Returns an iterator over snapshot names and their info.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, SnapshotInfo)> {
+        self.snapshots
+            .iter()
+            .map(|(name, &(info, _))| (name.as_str(), info))
+    }
+
+    /// Returns the scene of a snapshot.
+    pub fn get(&self, name: &str) -> Option<&DynamicScene> {
+        self.snapshots.get(name).map(|(_, scene)| scene)
+    }
+
+    /// Returns the number of captured snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if no snapshots are captured.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+/// Information about a snapshot from [`ServerSnapshots`].
+#[derive(Clone, Copy, Debug)]
+pub struct SnapshotInfo {
+    /// Server tick at the moment of capture.
+    pub server_tick: RepliconTick,
+
+    /// Number of captured entities.
+    pub entities: usize,
+}
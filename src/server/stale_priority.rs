@@ -0,0 +1,278 @@
+use std::{cmp::Reverse, mem};
+
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        entity::{EntityHashMap, EntityHashSet},
+    },
+    prelude::*,
+    utils::HashMap,
+};
+
+use super::{
+    connected_clients::ConnectedClients, hibernation::Hibernated, server_tick::ServerTick,
+    ServerPlugin,
+};
+use crate::core::{
+    common_conditions::server_running,
+    replication_rules::ReplicationRules,
+    replication_stages::{AppStageExt, FilterCtx},
+    replicon_tick::RepliconTick,
+    ClientId, Replicated,
+};
+
+/// Replication priorities of components for [`App`].
+pub trait AppPriorityExt {
+    /**
+    Sets priority of the component for [`StalePriorityPlugin`].
+
+    When more entities have changes than [`StalePriority::entities_per_tick`], entities are sent in order
+    of their staleness multiplied by the highest priority among their changed replicated components.
+    So changes of components with higher priority go out before changes of other components
+    that have been waiting for the same time.
+
+    Components without a set priority have priority 1. Components with priority 0 are sent only after
+    reaching [`StalePriority::max_staleness`] or when there is budget left.
+
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    Send position changes before cosmetic ones:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, StalePriorityPlugin::default()))
+        .replicate::<Position>()
+        .replicate::<Tint>()
+        .set_replication_priority::<Position>(10);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Position(Vec2);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Tint(u32);
+    ```
+    **/
+    fn set_replication_priority<C: Component>(&mut self, priority: u32) -> &mut Self;
+}
+
+impl AppPriorityExt for App {
+    fn set_replication_priority<C: Component>(&mut self, priority: u32) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        self.world
+            .get_resource_or_insert_with(ReplicationPriorities::default)
+            .0
+            .insert(component_id, priority);
+
+        self
+    }
+}
+
+/**
+Limits the number of entities with changes sent to each client per tick, prioritizing the most stale ones.
+
+Staleness of an entity for a client is the number of ticks its changes have been waiting to be sent.
+Each tick only [`StalePriority::entities_per_tick`] most stale entities with changes are sent to the client,
+while the rest wait for the next ticks. Staleness can be weighted per component with
+[`AppPriorityExt::set_replication_priority`]. Entities that have been waiting for [`StalePriority::max_staleness`]
+ticks are sent regardless of the limit, which bounds the worst-case staleness under budget pressure.
+
+Only changes from update messages are limited, see [`AppStageExt::add_entity_filter`].
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    StalePriorityPlugin {
+        entities_per_tick: 32,
+        max_staleness: 10,
+    },
+));
+```
+**/
+pub struct StalePriorityPlugin {
+    /// Initial value for [`StalePriority::entities_per_tick`].
+    pub entities_per_tick: usize,
+
+    /// Initial value for [`StalePriority::max_staleness`].
+    pub max_staleness: u32,
+}
+
+impl Default for StalePriorityPlugin {
+    fn default() -> Self {
+        Self {
+            entities_per_tick: 64,
+            max_staleness: 30,
+        }
+    }
+}
+
+impl Plugin for StalePriorityPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StalePriority {
+            entities_per_tick: self.entities_per_tick,
+            max_staleness: self.max_staleness,
+            clients: Default::default(),
+        })
+        .add_entity_filter(filter_prioritized)
+        .add_systems(
+            PostUpdate,
+            prioritize
+                .after(ServerPlugin::increment_tick)
+                .before(ServerPlugin::send_replication)
+                .run_if(server_running)
+                .run_if(resource_changed::<ServerTick>),
+        );
+    }
+}
+
+/// Selects entities with changes that will be sent to each client this tick.
+fn prioritize(
+    world: &mut World,
+    entities: &mut QueryState<EntityRef, (With<Replicated>, Without<Hibernated>)>,
+    mut candidates: Local<Vec<(u32, u32, Entity)>>,
+) {
+    world.resource_scope(|world, mut priority: Mut<StalePriority>| {
+        let server_tick = **world.resource::<ServerTick>();
+        let this_run = world.read_change_tick();
+        let priorities = world.get_resource::<ReplicationPriorities>();
+        let mut components: Vec<_> = world
+            .resource::<ReplicationRules>()
+            .iter()
+            .flat_map(|rule| &rule.components)
+            .map(|fns_info| {
+                let component_id = fns_info.component_id();
+                let priority = priorities
+                    .and_then(|priorities| priorities.0.get(&component_id))
+                    .copied()
+                    .unwrap_or(1);
+                (component_id, priority)
+            })
+            .collect();
+        components.sort_unstable();
+        components.dedup();
+
+        let connected_clients = world.resource::<ConnectedClients>();
+        let priority = &mut *priority;
+        priority
+            .clients
+            .retain(|&client_id, _| connected_clients.get_client(client_id).is_some());
+
+        for client in connected_clients.iter() {
+            let staleness = priority.clients.entry(client.id()).or_default();
+            let pending = mem::take(&mut staleness.pending);
+            staleness.allowed.clear();
+
+            for entity in entities.iter(world) {
+                if !client.visibility().is_visible(entity.id()) {
+                    continue;
+                }
+
+                // Entities without a limit weren't replicated yet and sent with init messages.
+                let Some(limit) = client.get_change_limit(entity.id()) else {
+                    continue;
+                };
+
+                let component_priority = components
+                    .iter()
+                    .filter(|&&(component_id, _)| {
+                        entity
+                            .get_change_ticks_by_id(component_id)
+                            .is_some_and(|ticks| ticks.is_changed(limit, this_run))
+                    })
+                    .map(|&(_, priority)| priority)
+                    .max();
+                if let Some(component_priority) = component_priority {
+                    let since = pending.get(&entity.id()).copied().unwrap_or(server_tick);
+                    staleness.pending.insert(entity.id(), since);
+                    let ticks = server_tick.get().wrapping_sub(since.get());
+                    // Count the current tick too, so fresh changes are ordered by priority.
+                    let weight = ticks.saturating_add(1).saturating_mul(component_priority);
+                    candidates.push((weight, ticks, entity.id()));
+                }
+            }
+
+            candidates.sort_unstable_by_key(|&(weight, ..)| Reverse(weight));
+            for (index, (_, ticks, entity)) in candidates.drain(..).enumerate() {
+                if index < priority.entities_per_tick || ticks >= priority.max_staleness {
+                    // Restart waiting, so resent unacknowledged changes don't take priority over others.
+                    staleness.pending.remove(&entity);
+                    staleness.allowed.insert(entity);
+                }
+            }
+        }
+    });
+}
+
+fn filter_prioritized(ctx: &FilterCtx, entity: Entity) -> bool {
+    ctx.world
+        .resource::<StalePriority>()
+        .is_allowed(ctx.client.id(), entity)
+}
+
+/// Settings and staleness tracking for [`StalePriorityPlugin`].
+#[derive(Resource)]
+pub struct StalePriority {
+    /// Maximum number of entities with changes sent to a client per tick.
+    ///
+    /// By default set to 64.
+    pub entities_per_tick: usize,
+
+    /// Number of ticks after which waiting entities are sent regardless of [`Self::entities_per_tick`].
+    ///
+    /// By default set to 30.
+    pub max_staleness: u32,
+
+    clients: HashMap<ClientId, ClientStaleness>,
+}
+
+impl StalePriority {
+    /// Returns the number of ticks changes of the entity have been waiting to be sent to the client.
+    ///
+    /// Returns [`None`] if the entity has no waiting changes for the client.
+    pub fn staleness(
+        &self,
+        client_id: ClientId,
+        entity: Entity,
+        server_tick: RepliconTick,
+    ) -> Option<u32> {
+        self.clients
+            .get(&client_id)
+            .and_then(|staleness| staleness.pending.get(&entity))
+            .map(|since| server_tick.get().wrapping_sub(since.get()))
+    }
+
+    /// Returns `true` if changes of the entity can be sent to the client this tick.
+    fn is_allowed(&self, client_id: ClientId, entity: Entity) -> bool {
+        self.clients.get(&client_id).is_none_or(|staleness| {
+            staleness.allowed.contains(&entity) || !staleness.pending.contains_key(&entity)
+        })
+    }
+}
+
+/// Staleness of entities for a single client.
+#[derive(Default)]
+struct ClientStaleness {
+    /// Entities with waiting changes and the tick since which they are waiting.
+    pending: EntityHashMap<RepliconTick>,
+
+    /// Entities selected to be sent this tick.
+    allowed: EntityHashSet,
+}
+
+/// Component priorities registered with [`AppPriorityExt::set_replication_priority`].
+#[derive(Default, Resource)]
+struct ReplicationPriorities(HashMap<ComponentId, u32>);
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use super::{server_tick::ServerTick, ServerPlugin, ServerSettings, TickPolicy};
+use crate::core::{common_conditions::server_running, replicon_tick::RepliconTick};
+
+/// A source of the replication tick driven by an external authority.
+///
+/// Useful when the simulation is stepped by something outside of Bevy, like a physics server
+/// or a deterministic host. Implemented for closures that take [`World`] and return [`RepliconTick`].
+///
+/// See [`AppTickSourceExt::set_tick_source`].
+pub trait TickSource: Send + Sync + 'static {
+    /// Returns the current external tick.
+    fn current_tick(&mut self, world: &World) -> RepliconTick;
+}
+
+impl<F> TickSource for F
+where
+    F: FnMut(&World) -> RepliconTick + Send + Sync + 'static,
+{
+    fn current_tick(&mut self, world: &World) -> RepliconTick {
+        (self)(world)
+    }
+}
+
+/// External tick source for [`App`].
+pub trait AppTickSourceExt {
+    /**
+    Drives [`ServerTick`] from the source instead of [`TickPolicy`].
+
+    The source is polled each frame in [`PostUpdate`] before replication is sent.
+    When the returned tick is newer than [`ServerTick`], it's assigned, so replication
+    is sent with the external tick. Older ticks are ignored, since ticks should always increase.
+
+    Should be used with [`TickPolicy::Manual`] to avoid incrementing the tick twice.
+    Needed only on server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replicon_tick::RepliconTick, prelude::*};
+
+    # let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::Manual,
+            ..Default::default()
+        }),
+    ))
+    .init_resource::<PhysicsStep>()
+    .set_tick_source(|world: &World| RepliconTick::new(world.resource::<PhysicsStep>().0));
+
+    #[derive(Default, Resource)]
+    struct PhysicsStep(u32);
+    ```
+    **/
+    fn set_tick_source(&mut self, source: impl TickSource) -> &mut Self;
+}
+
+impl AppTickSourceExt for App {
+    fn set_tick_source(&mut self, source: impl TickSource) -> &mut Self {
+        if let Some(settings) = self.world.get_resource::<ServerSettings>() {
+            if settings.tick_policy != TickPolicy::Manual {
+                warn!(
+                    "external tick source is used with {:?}, consider using `TickPolicy::Manual`",
+                    settings.tick_policy
+                );
+            }
+        }
+
+        let replaced = self.world.contains_resource::<ExternalTickSource>();
+        self.insert_resource(ExternalTickSource(Box::new(source)));
+        if !replaced {
+            self.add_systems(
+                PostUpdate,
+                poll_tick_source
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .run_if(server_running),
+            );
+        }
+
+        self
+    }
+}
+
+/// Assigns the tick from [`ExternalTickSource`] to [`ServerTick`] if it's newer.
+fn poll_tick_source(world: &mut World) {
+    let tick = world
+        .resource_scope(|world, mut source: Mut<ExternalTickSource>| source.0.current_tick(world));
+
+    let server_tick = **world.resource::<ServerTick>();
+    if tick > server_tick {
+        world.resource_mut::<ServerTick>().set(tick);
+        trace!("assigned external {tick:?}");
+    } else if tick < server_tick {
+        debug!("ignoring external {tick:?} older than {server_tick:?}");
+    }
+}
+
+/// Tick source set with [`AppTickSourceExt::set_tick_source`].
+#[derive(Resource)]
+struct ExternalTickSource(Box<dyn TickSource>);
@@ -0,0 +1,158 @@
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashMap},
+    prelude::*,
+};
+
+use super::ServerSet;
+use crate::core::{common_conditions::server_running, Replicated};
+
+/// Value comparison for [`App`].
+pub trait AppComparisonExt {
+    /**
+    Replicates the component only if its value differs from the previously accepted one.
+
+    By default a component is sent when Bevy marks it as changed. Physics or animation systems
+    may write to components every frame even if the values remain numerically identical.
+    With this option each change on server is compared with the last accepted value using [`ApproxEq`]
+    and if they are equal within the `epsilon`, the change tick of the component is restored, so it isn't sent.
+    Small changes accumulate until they exceed the `epsilon`.
+
+    The comparison runs in [`PostUpdate`] before [`ServerSet::Send`], so systems that run
+    after it won't see the restored changes with [`Changed`].
+
+    Should be called after the component is registered for replication.
+    Needed only on server, calling it on client does nothing.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<Transform>()
+        .compare_sent::<Transform>(0.001);
+    ```
+    **/
+    fn compare_sent<C: Component + ApproxEq + Clone>(&mut self, epsilon: f32) -> &mut Self;
+}
+
+impl AppComparisonExt for App {
+    fn compare_sent<C: Component + ApproxEq + Clone>(&mut self, epsilon: f32) -> &mut Self {
+        self.insert_resource(SentValues::<C> {
+            epsilon,
+            values: Default::default(),
+        })
+        .add_systems(
+            PostUpdate,
+            (cleanup_removed::<C>, compare_changed::<C>)
+                .chain()
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        )
+    }
+}
+
+fn cleanup_removed<C: Component + ApproxEq + Clone>(
+    mut removed: RemovedComponents<C>,
+    mut sent_values: ResMut<SentValues<C>>,
+) {
+    for entity in removed.read() {
+        sent_values.values.remove(&entity);
+    }
+}
+
+/// Restores change ticks of components that are equal to their last accepted values.
+fn compare_changed<C: Component + ApproxEq + Clone>(
+    mut sent_values: ResMut<SentValues<C>>,
+    mut components: Query<(Entity, &mut C), (Changed<C>, With<Replicated>)>,
+) {
+    let sent_values = &mut *sent_values;
+    for (entity, mut component) in &mut components {
+        if !component.is_added() {
+            if let Some((value, tick)) = sent_values.values.get(&entity) {
+                if component.approx_eq(value, sent_values.epsilon) {
+                    component.set_last_changed(*tick);
+                    continue;
+                }
+            }
+        }
+
+        sent_values
+            .values
+            .insert(entity, (component.clone(), component.last_changed()));
+    }
+}
+
+/// Last accepted values of components marked with [`AppComparisonExt::compare_sent`].
+#[derive(Resource)]
+struct SentValues<C> {
+    epsilon: f32,
+    values: EntityHashMap<(C, Tick)>,
+}
+
+/// Approximate equality for [`AppComparisonExt::compare_sent`].
+///
+/// Implement it for your components to compare only the replicated fields.
+pub trait ApproxEq {
+    /// Returns `true` if values are equal within the `epsilon`.
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool;
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self - other).abs() <= epsilon as f64
+    }
+}
+
+impl ApproxEq for Vec2 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.abs_diff_eq(*other, epsilon)
+    }
+}
+
+impl ApproxEq for Vec3 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.abs_diff_eq(*other, epsilon)
+    }
+}
+
+impl ApproxEq for Vec4 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.abs_diff_eq(*other, epsilon)
+    }
+}
+
+impl ApproxEq for Quat {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.abs_diff_eq(*other, epsilon)
+    }
+}
+
+impl ApproxEq for Transform {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.translation.approx_eq(&other.translation, epsilon)
+            && self.rotation.approx_eq(&other.rotation, epsilon)
+            && self.scale.approx_eq(&other.scale, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform() {
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+        assert!(transform.approx_eq(&Transform::from_xyz(1.0005, 2.0, 3.0), 0.001));
+        assert!(!transform.approx_eq(&Transform::from_xyz(1.01, 2.0, 3.0), 0.001));
+        assert!(!transform.approx_eq(&transform.with_scale(Vec3::splat(2.0)), 0.001));
+    }
+}
@@ -0,0 +1,192 @@
+use std::{io::Cursor, mem};
+
+use bevy::{
+    ecs::component::{ComponentId, ComponentTicks, Tick},
+    prelude::*,
+    utils::HashMap,
+};
+use bincode::Options;
+
+use super::{
+    connected_clients::ConnectedClients, replicated_archetypes::ReplicatedArchetypes,
+    server_tick::ServerTick, ServerEvent, ServerPlugin,
+};
+use crate::core::{
+    common_conditions::server_running,
+    replication_fns::{ctx::SerializeCtx, ReplicationFns},
+    replication_rules::ReplicationRules,
+    wire_format,
+};
+
+/**
+Pre-serializes the world while no clients are connected to send the initial state to the first joiner immediately.
+
+Without it the first client waits for the next server tick, and the initial state of all entities
+is serialized in that tick. With this plugin, while the server has no clients, components of replicated
+entities are serialized into [`WarmStandby`] on each tick, reusing bytes of components that didn't change.
+When a client connects, replication runs in the same frame regardless of [`TickPolicy`](super::TickPolicy)
+and unchanged components are copied from the cache instead of being serialized.
+The cache is cleared after it's used.
+
+Useful for matches that are prepared before players join, like a map with many entities.
+Cached bytes are serialized with the tick at which they were cached, so custom serialization
+functions that depend on [`SerializeCtx::server_tick`] shouldn't be used with this plugin.
+
+Not added by default. Needed only on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, WarmStandbyPlugin));
+```
+**/
+pub struct WarmStandbyPlugin;
+
+impl Plugin for WarmStandbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WarmStandby>().add_systems(
+            PostUpdate,
+            (
+                (
+                    Self::prepare
+                        .run_if(resource_changed::<ServerTick>)
+                        .run_if(no_clients),
+                    Self::tick_on_connect,
+                )
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication),
+                Self::clear.after(ServerPlugin::send_replication),
+            )
+                .run_if(server_running),
+        );
+    }
+}
+
+impl WarmStandbyPlugin {
+    /// Serializes components that changed since they were cached.
+    fn prepare(world: &mut World, mut replicated_archetypes: Local<ReplicatedArchetypes>) {
+        world.resource_scope(|world, mut standby: Mut<WarmStandby>| {
+            let cache_tick = world.change_tick();
+            let mut old_cache = mem::take(&mut standby.cache);
+            let server_tick = **world.resource::<ServerTick>();
+            let replication_fns = world.resource::<ReplicationFns>();
+            let ctx = SerializeCtx::new(world, server_tick);
+
+            replicated_archetypes.update(world, world.resource::<ReplicationRules>());
+            for replicated_archetype in replicated_archetypes.iter() {
+                let archetype = &world.archetypes()[replicated_archetype.id];
+                let table = &world.storages().tables[archetype.table_id()];
+                for entity in archetype.entities() {
+                    for replicated_component in &replicated_archetype.components {
+                        // SAFETY: component and storage were obtained from this archetype.
+                        let (component, ticks) = unsafe {
+                            super::get_component_unchecked(
+                                table,
+                                &world.storages().sparse_sets,
+                                entity,
+                                replicated_component.storage_type,
+                                replicated_component.component_id,
+                            )
+                        };
+
+                        // Keep only entries of existing components to avoid growing for despawned entities.
+                        let key = (entity.id(), replicated_component.component_id);
+                        if let Some((tick, bytes)) = old_cache.remove(&key) {
+                            if !ticks.is_changed(tick, cache_tick) {
+                                standby.cache.insert(key, (tick, bytes));
+                                continue;
+                            }
+                        }
+
+                        let (component_fns, rule_fns) =
+                            replication_fns.get(replicated_component.fns_id);
+                        let mut cursor = Cursor::new(Vec::new());
+                        let result = wire_format::varint()
+                            .serialize_into(&mut cursor, &replicated_component.fns_id)
+                            // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
+                            .and_then(|_| unsafe {
+                                component_fns.serialize(&ctx, rule_fns, component, &mut cursor)
+                            });
+
+                        match result {
+                            Ok(()) => {
+                                standby.cache.insert(key, (cache_tick, cursor.into_inner()));
+                            }
+                            Err(e) => error!("unable to cache {key:?}: {e}"),
+                        }
+                    }
+                }
+            }
+
+            trace!("cached {} components", standby.cache.len());
+        });
+    }
+
+    /// Increments the tick if a client connected this frame to replicate to it immediately.
+    fn tick_on_connect(
+        mut server_events: EventReader<ServerEvent>,
+        server_tick: ResMut<ServerTick>,
+    ) {
+        let connected = server_events
+            .read()
+            .any(|event| matches!(event, ServerEvent::ClientConnected { .. }));
+        if connected && !server_tick.is_changed() {
+            ServerPlugin::increment_tick(server_tick);
+        }
+    }
+
+    fn clear(mut standby: ResMut<WarmStandby>, connected_clients: Res<ConnectedClients>) {
+        if !connected_clients.is_empty() && !standby.cache.is_empty() {
+            debug!("clearing warm standby cache");
+            standby.cache.clear();
+        }
+    }
+}
+
+fn no_clients(connected_clients: Res<ConnectedClients>) -> bool {
+    connected_clients.is_empty()
+}
+
+/// Serialized components cached by [`WarmStandbyPlugin`].
+#[derive(Default, Resource)]
+pub struct WarmStandby {
+    /// Serialized replication functions IDs and components with the tick of serialization.
+    cache: HashMap<(Entity, ComponentId), (Tick, Vec<u8>)>,
+}
+
+impl WarmStandby {
+    /// Returns the number of cached components.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Removes all cached components.
+    pub(crate) fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Returns cached bytes if the component didn't change since caching.
+    pub(super) fn get(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+        ticks: ComponentTicks,
+        this_run: Tick,
+    ) -> Option<&[u8]> {
+        let (cache_tick, bytes) = self.cache.get(&(entity, component_id))?;
+        if ticks.is_changed(*cache_tick, this_run) {
+            None
+        } else {
+            Some(bytes)
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{archetype::Archetypes, component::ComponentId, entity::Entities},
+    prelude::*,
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    connected_clients::ConnectedClients, server_tick::ServerTick, ServerPlugin, ServerSet,
+};
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_just_stopped, server_running},
+        replicon_channels::ChannelKind,
+        replicon_tick::RepliconTick,
+        Replicated,
+    },
+    network_event::server_event::{SendMode, ServerEventAppExt, ToClients},
+};
+
+/**
+Sends an aggregated overview of the world instead of entities to selected clients.
+
+Useful for companion apps and minimap servers that don't need individual entities.
+Mark such clients with [`ConnectedClient::set_digest_only`](super::connected_clients::ConnectedClient::set_digest_only)
+on connection and they will receive only [`WorldDigest`] at most once per [`Self::interval`].
+
+The digest is generated on server from replicated entities and contains the number of entities for each category
+registered with [`AppDigestExt::add_digest_category`] and the same counts for cells of a grid with [`Self::cell_size`]
+based on [`Transform`]. For entities with [`DigestTeam`] each cell also contains the team with the most entities,
+which can be displayed as a territory.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, WorldDigestPlugin::default()))
+    .add_digest_category::<Player>()
+    .add_systems(PreUpdate, subscribe.after(ServerSet::Receive).run_if(server_running))
+    .add_systems(Update, draw_minimap.run_if(client_connected));
+
+fn subscribe(
+    mut server_events: EventReader<ServerEvent>,
+    mut connected_clients: ResMut<ConnectedClients>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            // Decide based on your authentication data.
+            connected_clients.client_mut(client_id).set_digest_only(true);
+        }
+    }
+}
+
+fn draw_minimap(digest: Res<WorldDigest>) {
+    if digest.is_changed() {
+        for cell in digest.cells() {
+            info!("{} players at ({}, {})", cell.counts[0], cell.x, cell.y);
+        }
+    }
+}
+
+#[derive(Component)]
+struct Player;
+```
+**/
+pub struct WorldDigestPlugin {
+    /// Size of a grid cell in world units.
+    pub cell_size: f32,
+
+    /// Axes of [`Transform::translation`] used for the grid.
+    pub plane: DigestPlane,
+
+    /// Minimum time between digests.
+    pub interval: Duration,
+}
+
+impl Default for WorldDigestPlugin {
+    fn default() -> Self {
+        Self {
+            cell_size: 100.0,
+            plane: DigestPlane::XY,
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for WorldDigestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldDigest>()
+            .init_resource::<DigestCategories>()
+            .insert_resource(DigestSettings {
+                cell_size: self.cell_size,
+                plane: self.plane,
+            })
+            .insert_resource(DigestTimer(Timer::new(self.interval, TimerMode::Repeating)))
+            .add_server_event::<DigestUpdate>(ChannelKind::Unordered)
+            .add_systems(
+                PreUpdate,
+                (
+                    (Self::reset, Self::reset_timer).run_if(server_just_stopped),
+                    Self::reset.in_set(ClientSet::Reset),
+                    Self::receive
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl WorldDigestPlugin {
+    /// Generates the digest after the interval and sends it to digest-only clients.
+    #[allow(clippy::too_many_arguments)]
+    fn send(
+        mut timer: ResMut<DigestTimer>,
+        mut digest: ResMut<WorldDigest>,
+        mut digest_events: EventWriter<ToClients<DigestUpdate>>,
+        time: Res<Time>,
+        server_tick: Res<ServerTick>,
+        settings: Res<DigestSettings>,
+        categories: Res<DigestCategories>,
+        connected_clients: Res<ConnectedClients>,
+        archetypes: &Archetypes,
+        entities: &Entities,
+        replicated: Query<(Entity, Option<&Transform>, Option<&DigestTeam>), With<Replicated>>,
+    ) {
+        timer.tick(time.delta());
+        if !timer.finished() {
+            return;
+        }
+
+        let mut subscribers = connected_clients
+            .iter()
+            .filter(|client| client.is_digest_only())
+            .peekable();
+        if subscribers.peek().is_none() {
+            return;
+        }
+
+        let mut counts = vec![0; categories.0.len()];
+        let mut cells = HashMap::<(i32, i32), CellAccumulator>::new();
+        for (entity, transform, team) in &replicated {
+            let Some(archetype) = entities
+                .get(entity)
+                .and_then(|location| archetypes.get(location.archetype_id))
+            else {
+                continue;
+            };
+
+            let key = transform.map(|transform| {
+                let position = settings.plane.project(transform.translation);
+                let key = (position / settings.cell_size).floor().as_ivec2();
+                (key.x, key.y)
+            });
+            for (index, &component_id) in categories.0.iter().enumerate() {
+                if archetype.contains(component_id) {
+                    counts[index] += 1;
+                    if let Some(key) = key {
+                        CellAccumulator::get_mut(&mut cells, key, counts.len()).counts[index] += 1;
+                    }
+                }
+            }
+
+            if let (Some(key), Some(team)) = (key, team) {
+                let cell = CellAccumulator::get_mut(&mut cells, key, counts.len());
+                *cell.teams.entry(team.0).or_default() += 1;
+            }
+        }
+
+        let mut cells: Vec<_> = cells
+            .into_iter()
+            .map(|((x, y), cell)| DigestCell {
+                x,
+                y,
+                team: cell
+                    .teams
+                    .into_iter()
+                    .max_by(|(a_team, a_count), (b_team, b_count)| {
+                        // Prefer lower team IDs on ties to keep the result deterministic.
+                        a_count.cmp(b_count).then(b_team.cmp(a_team))
+                    })
+                    .map(|(team, _)| team),
+                counts: cell.counts,
+            })
+            .collect();
+        cells.sort_unstable_by_key(|cell| (cell.x, cell.y));
+
+        *digest = WorldDigest {
+            tick: **server_tick,
+            counts,
+            cells,
+        };
+
+        trace!("sending world digest with {} cells", digest.cells.len());
+        for client in subscribers {
+            digest_events.send(ToClients {
+                mode: SendMode::Direct(client.id()),
+                event: DigestUpdate(digest.clone()),
+            });
+        }
+    }
+
+    fn receive(mut digest: ResMut<WorldDigest>, mut digest_events: EventReader<DigestUpdate>) {
+        // Events are unordered, so ignore outdated digests.
+        for DigestUpdate(received) in digest_events.read() {
+            if received.tick >= digest.tick {
+                digest.clone_from(received);
+            }
+        }
+    }
+
+    fn reset(mut digest: ResMut<WorldDigest>) {
+        *digest = Default::default();
+    }
+
+    fn reset_timer(mut timer: ResMut<DigestTimer>) {
+        timer.reset();
+    }
+}
+
+/// Digest configuration for [`App`].
+pub trait AppDigestExt {
+    /// Registers a category of entities that contain the component.
+    ///
+    /// Counts in [`WorldDigest`] are indexed in the order of registration,
+    /// so it should be the same on client and server.
+    ///
+    /// See [`WorldDigestPlugin`] for details.
+    fn add_digest_category<C: Component>(&mut self) -> &mut Self;
+}
+
+impl AppDigestExt for App {
+    fn add_digest_category<C: Component>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<C>();
+        self.world
+            .resource_mut::<DigestCategories>()
+            .0
+            .push(component_id);
+
+        self
+    }
+}
+
+/// Components registered with [`AppDigestExt::add_digest_category`].
+#[derive(Default, Resource)]
+struct DigestCategories(Vec<ComponentId>);
+
+/// Grid configuration from [`WorldDigestPlugin`].
+#[derive(Resource)]
+struct DigestSettings {
+    cell_size: f32,
+    plane: DigestPlane,
+}
+
+/// Interval timer for generating [`WorldDigest`].
+#[derive(Deref, DerefMut, Resource)]
+struct DigestTimer(Timer);
+
+/// Counts for a cell that is being generated.
+struct CellAccumulator {
+    counts: Vec<u32>,
+    teams: HashMap<u8, u32>,
+}
+
+impl CellAccumulator {
+    /// Returns the cell by its key, inserting it if needed.
+    fn get_mut(
+        cells: &mut HashMap<(i32, i32), Self>,
+        key: (i32, i32),
+        categories: usize,
+    ) -> &mut Self {
+        cells.entry(key).or_insert_with(|| Self {
+            counts: vec![0; categories],
+            teams: Default::default(),
+        })
+    }
+}
+
+/// Axes of the digest grid.
+///
+/// See [`WorldDigestPlugin::plane`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigestPlane {
+    /// Uses `x` and `y`, suitable for 2D.
+    #[default]
+    XY,
+    /// Uses `x` and `z`, suitable for 3D with `y` pointing up.
+    XZ,
+}
+
+impl DigestPlane {
+    fn project(self, translation: Vec3) -> Vec2 {
+        match self {
+            DigestPlane::XY => translation.truncate(),
+            DigestPlane::XZ => translation.xz(),
+        }
+    }
+}
+
+/// Team of a server entity for territory in [`WorldDigest`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DigestTeam(pub u8);
+
+/// Aggregated overview of the world.
+///
+/// On server it contains the last generated digest, on clients the last received one.
+/// See [`WorldDigestPlugin`] for details.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+pub struct WorldDigest {
+    tick: RepliconTick,
+    counts: Vec<u32>,
+    cells: Vec<DigestCell>,
+}
+
+impl WorldDigest {
+    /// Returns the server tick at which the digest was generated.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the number of entities for each category in the order of registration.
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// Returns cells with entities sorted by coordinates.
+    pub fn cells(&self) -> &[DigestCell] {
+        &self.cells
+    }
+
+    /// Returns a cell by its coordinates.
+    pub fn cell(&self, x: i32, y: i32) -> Option<&DigestCell> {
+        self.cells
+            .binary_search_by_key(&(x, y), |cell| (cell.x, cell.y))
+            .ok()
+            .map(|index| &self.cells[index])
+    }
+}
+
+/// A cell of the [`WorldDigest`] grid.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DigestCell {
+    /// Cell coordinate along the first axis of [`DigestPlane`].
+    pub x: i32,
+
+    /// Cell coordinate along the second axis of [`DigestPlane`].
+    pub y: i32,
+
+    /// Number of entities in the cell for each category in the order of registration.
+    pub counts: Vec<u32>,
+
+    /// Team with the most entities in the cell.
+    pub team: Option<u8>,
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DigestUpdate(WorldDigest);
@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{
+            client_connected, client_just_connected, server_just_stopped, server_running,
+        },
+        replication_rules::AppRuleExt,
+        replicon_channels::ChannelKind,
+        replicon_tick::RepliconTick,
+        ClientId,
+    },
+    network_event::{
+        client_event::{ClientEventAppExt, FromClient},
+        disconnect_reason::AppDisconnectExt,
+        server_event::{SendMode, ServerEventAppExt, ToClients},
+    },
+    server::{
+        concealed_components::AppConcealExt,
+        connected_clients::{ClientPrivilege, ConnectedClients},
+        server_commands::ServerCommands,
+        server_tick::ServerTick,
+        ServerEvent, ServerPlugin, ServerSet,
+    },
+};
+
+/**
+Lets trusted headless services, like anti-cheat analysis, stats recording or broadcast overlays, connect as clients.
+
+A service inserts [`ServiceToken`] before connecting and sends it automatically after connection.
+Server passes the token to [`Self::approve`]. Approved clients get [`ClientPrivilege::Admin`] to receive
+full replication, including hidden entities and concealed components, are listed in [`ServiceClients`]
+and reported with [`ServiceApproved`]. Denied clients are disconnected with [`ServiceDenied`] as
+[`DisconnectReason`](crate::network_event::disconnect_reason::DisconnectReason).
+
+Services also receive extra metadata:
+- [`EntityOwner`] on entities, concealed from other clients.
+- [`ServiceStats`] about connected clients at most once per [`Self::stats_interval`].
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    ServiceClientPlugin {
+        approve: |_client_id, token| token == "secret-from-config",
+        ..Default::default()
+    },
+));
+
+// On the service before connecting:
+app.insert_resource(ServiceToken("secret-from-config".into()));
+```
+**/
+pub struct ServiceClientPlugin {
+    /// Checks the token of a client that requested the service role.
+    ///
+    /// By default denies all requests.
+    pub approve: ApproveFn,
+
+    /// Minimum time between [`ServiceStats`] updates.
+    pub stats_interval: Duration,
+}
+
+impl Default for ServiceClientPlugin {
+    fn default() -> Self {
+        Self {
+            approve: |_, _| false,
+            stats_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for ServiceClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServiceClients>()
+            .init_resource::<ServiceStats>()
+            .insert_resource(ServiceApproval(self.approve))
+            .insert_resource(StatsTimer(Timer::new(
+                self.stats_interval,
+                TimerMode::Repeating,
+            )))
+            .add_event::<ServiceApproved>()
+            .add_client_event::<ServiceRequest>(ChannelKind::Ordered)
+            .add_server_event::<ServiceStatsUpdate>(ChannelKind::Ordered)
+            .add_disconnect_reason::<ServiceDenied>()
+            .replicate::<EntityOwner>()
+            .conceal_component::<EntityOwner>(ClientPrivilege::Admin)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::send_request
+                        .after(ClientSet::Receive)
+                        .run_if(client_just_connected)
+                        .run_if(resource_exists::<ServiceToken>),
+                    Self::receive_stats
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::reset_stats.in_set(ClientSet::Reset),
+                    Self::approve_requests
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                    (Self::reset_clients, Self::reset_stats).run_if(server_just_stopped),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send_stats
+                    .after(ServerPlugin::increment_tick)
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl ServiceClientPlugin {
+    fn send_request(mut request_events: EventWriter<ServiceRequest>, token: Res<ServiceToken>) {
+        request_events.send(ServiceRequest {
+            token: token.0.clone(),
+        });
+    }
+
+    /// Grants the service role to clients with approved tokens and disconnects the others.
+    fn approve_requests(
+        mut commands: ServerCommands,
+        mut server_events: EventReader<ServerEvent>,
+        mut request_events: EventReader<FromClient<ServiceRequest>>,
+        mut approved_events: EventWriter<ServiceApproved>,
+        mut service_clients: ResMut<ServiceClients>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        approval: Res<ServiceApproval>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+                service_clients.0.remove(client_id);
+            }
+        }
+
+        for FromClient { client_id, event } in request_events.read() {
+            let Some(client) = connected_clients.get_client_mut(*client_id) else {
+                continue;
+            };
+            if service_clients.contains(*client_id) {
+                debug!("ignoring repeated service request from {client_id:?}");
+                continue;
+            }
+
+            if (approval.0)(*client_id, &event.token) {
+                info!("approving {client_id:?} as a service client");
+                client.set_privilege(ClientPrivilege::Admin);
+                service_clients.0.insert(*client_id);
+                approved_events.send(ServiceApproved {
+                    client_id: *client_id,
+                });
+            } else {
+                warn!("disconnecting {client_id:?} with a denied service token");
+                commands.disconnect(*client_id, ServiceDenied);
+            }
+        }
+    }
+
+    /// Sends statistics to service clients after the interval.
+    fn send_stats(
+        mut timer: ResMut<StatsTimer>,
+        mut stats: ResMut<ServiceStats>,
+        mut stats_events: EventWriter<ToClients<ServiceStatsUpdate>>,
+        time: Res<Time>,
+        server_tick: Res<ServerTick>,
+        service_clients: Res<ServiceClients>,
+        connected_clients: Res<ConnectedClients>,
+    ) {
+        timer.tick(time.delta());
+        if !timer.finished() || service_clients.0.is_empty() {
+            return;
+        }
+
+        *stats = ServiceStats {
+            tick: **server_tick,
+            clients: connected_clients
+                .iter()
+                .map(|client| ClientSummary {
+                    client_id: client.id(),
+                    tracked_entities: client.tracked_entities(),
+                    service: service_clients.contains(client.id()),
+                })
+                .collect(),
+        };
+
+        for &client_id in &service_clients.0 {
+            stats_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: ServiceStatsUpdate(stats.clone()),
+            });
+        }
+    }
+
+    fn receive_stats(
+        mut stats: ResMut<ServiceStats>,
+        mut stats_events: EventReader<ServiceStatsUpdate>,
+    ) {
+        if let Some(event) = stats_events.read().last() {
+            stats.clone_from(&event.0);
+        }
+    }
+
+    fn reset_clients(mut service_clients: ResMut<ServiceClients>) {
+        service_clients.0.clear();
+    }
+
+    fn reset_stats(mut stats: ResMut<ServiceStats>) {
+        *stats = Default::default();
+    }
+}
+
+/// Signature of the function that approves service clients by their tokens.
+pub type ApproveFn = fn(ClientId, &str) -> bool;
+
+/// Approval function from [`ServiceClientPlugin`].
+#[derive(Resource)]
+struct ServiceApproval(ApproveFn);
+
+/// Interval timer for sending [`ServiceStats`].
+#[derive(Deref, DerefMut, Resource)]
+struct StatsTimer(Timer);
+
+/// Token that the client sends to request the service role after connection.
+///
+/// Should be inserted only on services. See [`ServiceClientPlugin`] for details.
+#[derive(Clone, Resource)]
+pub struct ServiceToken(pub String);
+
+/// Clients approved as services.
+///
+/// See [`ServiceClientPlugin`] for details.
+#[derive(Default, Resource)]
+pub struct ServiceClients(HashSet<ClientId>);
+
+impl ServiceClients {
+    /// Returns `true` if the client is approved as a service.
+    pub fn contains(&self, client_id: ClientId) -> bool {
+        self.0.contains(&client_id)
+    }
+
+    /// Returns an iterator over approved clients.
+    pub fn iter(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Client that owns an entity.
+///
+/// Replicated only to service clients. See [`ServiceClientPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EntityOwner(pub ClientId);
+
+/// Statistics about connected clients.
+///
+/// On server it contains the last sent statistics, on service clients the last received ones.
+/// See [`ServiceClientPlugin`] for details.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+pub struct ServiceStats {
+    /// Server tick at which the statistics were collected.
+    pub tick: RepliconTick,
+
+    /// Connected clients in no particular order.
+    pub clients: Vec<ClientSummary>,
+}
+
+/// Information about a connected client in [`ServiceStats`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ClientSummary {
+    pub client_id: ClientId,
+
+    /// Number of entities replicated to the client.
+    pub tracked_entities: usize,
+
+    /// Whether the client is approved as a service.
+    pub service: bool,
+}
+
+/// An event emitted on server when a client is approved as a service.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct ServiceApproved {
+    pub client_id: ClientId,
+}
+
+/// A client event that requests the service role.
+///
+/// Sent automatically on connect if [`ServiceToken`] is present.
+#[derive(Clone, Debug, Deserialize, Event, Serialize)]
+pub struct ServiceRequest {
+    pub token: String,
+}
+
+/// Disconnect reason sent to clients with denied service tokens.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ServiceDenied;
+
+#[derive(Deserialize, Event, Serialize)]
+struct ServiceStatsUpdate(ServiceStats);
@@ -1,4 +1,6 @@
-use bevy::prelude::*;
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Instant};
 
 use crate::{
     client::replicon_client::{RepliconClient, RepliconClientStatus},
@@ -79,6 +81,77 @@ pub trait ServerTestAppExt {
     ///
     /// Panics if a client app hasn't been connected before.
     fn exchange_with_client(&mut self, client_app: &mut App);
+
+    /**
+    Measures how long it takes for a client input to be applied back on the client.
+
+    Calls `send_input` on the client app, then runs cycles until `is_applied` returns `true`
+    for the client app. Each cycle exchanges messages, updates [`self`], exchanges messages back
+    and updates the client app. The client app is updated once before the first cycle to send the input.
+
+    Returns [`None`] if the result wasn't applied in `max_cycles`.
+    Useful for automated latency regression tests.
+
+    # Panics
+
+    Panics if a client app hasn't been connected before.
+
+    # Example
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+    use serde::{Deserialize, Serialize};
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<Jump>(ChannelKind::Ordered)
+        .replicate::<Jumped>();
+    }
+    server_app.add_systems(Update, apply_jumps);
+
+    server_app.connect_client(&mut client_app);
+
+    let latency = server_app
+        .measure_input_latency(
+            &mut client_app,
+            10,
+            |client_app| {
+                client_app.world.send_event(Jump);
+            },
+            |client_app| client_app.world.query::<&Jumped>().iter(&client_app.world).len() != 0,
+        )
+        .expect("jump should be applied");
+    assert_eq!(latency.cycles, 1);
+
+    fn apply_jumps(mut commands: Commands, mut jump_events: EventReader<FromClient<Jump>>) {
+        for _ in jump_events.read() {
+            commands.spawn((Replicated, Jumped));
+        }
+    }
+
+    #[derive(Deserialize, Event, Serialize)]
+    struct Jump;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Jumped;
+    ```
+    */
+    fn measure_input_latency(
+        &mut self,
+        client_app: &mut App,
+        max_cycles: usize,
+        send_input: impl FnOnce(&mut App),
+        is_applied: impl FnMut(&mut App) -> bool,
+    ) -> Option<InputLatency>;
 }
 
 impl ServerTestAppExt for App {
@@ -147,6 +220,127 @@ impl ServerTestAppExt for App {
             } else {
                 true
             }
-        })
+        });
+        server.ready_disconnect(client_id);
+    }
+
+    fn measure_input_latency(
+        &mut self,
+        client_app: &mut App,
+        max_cycles: usize,
+        send_input: impl FnOnce(&mut App),
+        mut is_applied: impl FnMut(&mut App) -> bool,
+    ) -> Option<InputLatency> {
+        let start = Instant::now();
+        send_input(client_app);
+        client_app.update();
+
+        for cycle in 1..=max_cycles {
+            self.exchange_with_client(client_app);
+            self.update();
+            self.exchange_with_client(client_app);
+            client_app.update();
+
+            if is_applied(client_app) {
+                return Some(InputLatency {
+                    cycles: cycle,
+                    elapsed: start.elapsed(),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// Result of [`ServerTestAppExt::measure_input_latency`].
+#[derive(Clone, Copy, Debug)]
+pub struct InputLatency {
+    /// Number of exchange cycles until the result was applied.
+    ///
+    /// Deterministic, so it's suitable for assertions.
+    pub cycles: usize,
+
+    /// Wall-clock time since the input was sent.
+    pub elapsed: Duration,
+}
+
+/**
+Extension for [`App`] to run it on a simulated clock.
+
+Replication timers, update timeouts, tick policies and other time-based logic
+use Bevy time resources. After [`Self::use_simulated_time`] they advance only
+by [`Self::advance_time`], so timing behavior can be tested deterministically.
+
+# Example
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::TimeTestAppExt};
+
+let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins.set(ServerPlugin {
+        tick_policy: TickPolicy::MaxTickRate(10),
+        ..Default::default()
+    }),
+))
+.use_simulated_time();
+
+app.world.resource_mut::<RepliconServer>().set_running(true);
+
+app.advance_time(Duration::from_millis(50));
+assert_eq!(app.world.resource::<ServerTick>().get(), 0);
+
+app.advance_time(Duration::from_millis(50));
+assert_eq!(app.world.resource::<ServerTick>().get(), 1);
+```
+**/
+pub trait TimeTestAppExt {
+    /// Replaces the wall clock with a simulated one.
+    ///
+    /// Regular updates after this call don't advance time.
+    fn use_simulated_time(&mut self) -> &mut Self;
+
+    /// Advances the simulated clock by the duration and runs one update.
+    ///
+    /// Virtual time advances by the same duration without clamping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Self::use_simulated_time`] wasn't called.
+    fn advance_time(&mut self, duration: Duration);
+}
+
+impl TimeTestAppExt for App {
+    fn use_simulated_time(&mut self) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+        // The first update only initializes real time, so do it here to make the next update advance it.
+        let mut real_time = self.world.resource_mut::<Time<Real>>();
+        if real_time.last_update().is_none() {
+            real_time.update_with_duration(Duration::ZERO);
+        }
+        self.world
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(Duration::MAX);
+
+        self
+    }
+
+    fn advance_time(&mut self, duration: Duration) {
+        let mut strategy = self.world.resource_mut::<TimeUpdateStrategy>();
+        assert!(
+            matches!(*strategy, TimeUpdateStrategy::ManualDuration(_)),
+            "simulated time should be enabled before advancing"
+        );
+
+        *strategy = TimeUpdateStrategy::ManualDuration(duration);
+        self.update();
+        *self.world.resource_mut::<TimeUpdateStrategy>() =
+            TimeUpdateStrategy::ManualDuration(Duration::ZERO);
     }
 }
@@ -0,0 +1,164 @@
+use bevy::{prelude::*, transform::TransformSystem};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_rules::AppRuleExt,
+    },
+    server::ServerSet,
+};
+
+/**
+Replicates transforms of entities with [`TransformSync`] in parent-relative or world-space mode.
+
+By default the mode is parent-relative: server stores the local [`Transform`] in [`TransformSync`]
+and clients write it into their [`Transform`], letting Bevy recompute [`GlobalTransform`] from the
+replicated hierarchy (see [`ParentSync`](crate::parent_sync::ParentSync)). For deep hierarchies this
+sends only what changed locally, since moving a parent doesn't change local transforms of its children.
+
+Entities with [`WorldSpaceTransform`] are in world-space mode: server stores the transform computed from
+[`GlobalTransform`], so clients can use flat hierarchies without replicating parents. World-space entities
+shouldn't have a parent on client, otherwise the transform will be applied twice.
+
+Don't register [`Transform`] using [`AppRuleExt`] for entities with [`TransformSync`],
+it's replicated only through [`TransformSync`]. Clients insert [`TransformBundle`] if it's missing.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, TransformSyncPlugin));
+
+// Replicated relative to the parent.
+app.world
+    .spawn((Replicated, ParentSync::default(), TransformSync::default()));
+
+// Replicated in world space.
+app.world.spawn((
+    Replicated,
+    TransformSync::default(),
+    WorldSpaceTransform,
+));
+```
+**/
+pub struct TransformSyncPlugin;
+
+impl Plugin for TransformSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformSync>()
+            .replicate::<TransformSync>()
+            .add_systems(
+                PreUpdate,
+                Self::apply
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::store
+                    .after(TransformSystem::TransformPropagate)
+                    .before(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl TransformSyncPlugin {
+    /// Updates [`TransformSync`] from local or world transforms depending on [`WorldSpaceTransform`].
+    ///
+    /// Compared per entity to avoid triggering changes.
+    fn store(
+        mut entities: Query<(
+            &mut TransformSync,
+            &Transform,
+            Option<&GlobalTransform>,
+            Has<WorldSpaceTransform>,
+        )>,
+    ) {
+        for (mut transform_sync, &transform, global_transform, world_space) in &mut entities {
+            let transform = match global_transform {
+                Some(global_transform) if world_space => global_transform.compute_transform(),
+                _ => transform,
+            };
+            transform_sync.set_if_neq(TransformSync(transform));
+        }
+    }
+
+    /// Writes received [`TransformSync`] into [`Transform`].
+    fn apply(
+        mut commands: Commands,
+        mut entities: Query<
+            (Entity, &TransformSync, Option<&mut Transform>),
+            Changed<TransformSync>,
+        >,
+    ) {
+        for (entity, transform_sync, transform) in &mut entities {
+            if let Some(mut transform) = transform {
+                *transform = transform_sync.0;
+            } else {
+                commands
+                    .entity(entity)
+                    .insert(TransformBundle::from_transform(transform_sync.0));
+            }
+        }
+    }
+}
+
+/// Replicated transform of an entity.
+///
+/// Automatically updated on server and applied to [`Transform`] on clients.
+/// Contains the local transform or the world transform if the entity has [`WorldSpaceTransform`].
+#[derive(Component, Clone, Copy, Debug, Default, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(from = "TransformData", into = "TransformData")]
+pub struct TransformSync(Transform);
+
+impl TransformSync {
+    /// Returns the last stored transform.
+    pub fn transform(self) -> Transform {
+        self.0
+    }
+}
+
+/// Switches replication of [`TransformSync`] to world space.
+///
+/// Needed only on server.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct WorldSpaceTransform;
+
+/// Serialized representation of [`TransformSync`].
+///
+/// Bevy math types implement serde traits only with the `serialize` feature.
+#[derive(Deserialize, Serialize)]
+struct TransformData {
+    translation: [f32; 3],
+    rotation: [f32; 4],
+    scale: [f32; 3],
+}
+
+impl From<TransformData> for TransformSync {
+    fn from(value: TransformData) -> Self {
+        Self(Transform {
+            translation: value.translation.into(),
+            rotation: Quat::from_array(value.rotation),
+            scale: value.scale.into(),
+        })
+    }
+}
+
+impl From<TransformSync> for TransformData {
+    fn from(value: TransformSync) -> Self {
+        Self {
+            translation: value.0.translation.into(),
+            rotation: value.0.rotation.into(),
+            scale: value.0.scale.into(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    client::ServerInitTick,
+    gameplay::ability::{
+        Ability, AbilityActivated, AbilityAppExt, AbilityError, AbilityOwner, AbilityPlugin,
+        AbilityRejected, AbilityTarget, ActivateAbility, Cooldowns,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+const DUMMY_ABILITY: u16 = 0;
+
+#[test]
+fn cooldown() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            AbilityPlugin,
+        ))
+        .add_ability(
+            DUMMY_ABILITY,
+            Ability {
+                cooldown: 10,
+                ..Default::default()
+            },
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .spawn((Replicated, AbilityOwner(client_id), Cooldowns::default()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_caster = client_app
+        .world
+        .query_filtered::<Entity, With<Cooldowns>>()
+        .single(&client_app.world);
+    for _ in 0..2 {
+        client_app.world.send_event(ActivateAbility {
+            caster: client_caster,
+            ability: DUMMY_ABILITY,
+            target: AbilityTarget::None,
+        });
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let activated_events = server_app.world.resource::<Events<AbilityActivated>>();
+    assert_eq!(activated_events.len(), 1);
+
+    let mut rejected_events = client_app.world.resource_mut::<Events<AbilityRejected>>();
+    let event = rejected_events
+        .drain()
+        .next()
+        .expect("second activation should be rejected");
+    assert_eq!(event.reason, AbilityError::OnCooldown);
+
+    let cooldowns = client_app.world.get::<Cooldowns>(client_caster).unwrap();
+    let tick = **client_app.world.resource::<ServerInitTick>();
+    assert!(
+        !cooldowns.is_ready(DUMMY_ABILITY, tick),
+        "cooldown should be replicated"
+    );
+}
+
+#[test]
+fn validation() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, AbilityPlugin))
+        .add_ability(
+            DUMMY_ABILITY,
+            Ability {
+                range: Some(1.0),
+                validate: |world, activation| world.get::<Silenced>(activation.caster).is_none(),
+                ..Default::default()
+            },
+        );
+
+    let caster = app
+        .world
+        .spawn((
+            AbilityOwner(ClientId::SERVER),
+            Cooldowns::default(),
+            Transform::default(),
+        ))
+        .id();
+
+    for target in [
+        AbilityTarget::Position(Vec3::X * 2.0),
+        AbilityTarget::Position(Vec3::X * 0.5),
+    ] {
+        app.world.send_event(ActivateAbility {
+            caster,
+            ability: DUMMY_ABILITY,
+            target,
+        });
+    }
+    app.world.send_event(ActivateAbility {
+        caster,
+        ability: DUMMY_ABILITY + 1,
+        target: AbilityTarget::None,
+    });
+
+    app.update(); // Resend local events.
+    app.update();
+
+    assert_eq!(app.world.resource::<Events<AbilityActivated>>().len(), 1);
+
+    app.world.entity_mut(caster).insert(Silenced);
+    app.world.send_event(ActivateAbility {
+        caster,
+        ability: DUMMY_ABILITY,
+        target: AbilityTarget::None,
+    });
+
+    app.update();
+    app.update();
+
+    let mut rejected_events = app.world.resource_mut::<Events<AbilityRejected>>();
+    let reasons: Vec<_> = rejected_events.drain().map(|event| event.reason).collect();
+    assert_eq!(
+        reasons,
+        [
+            AbilityError::OutOfRange,
+            AbilityError::UnknownAbility,
+            AbilityError::Denied
+        ]
+    );
+}
+
+#[derive(Component)]
+struct Silenced;
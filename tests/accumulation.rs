@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn latest_value() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::Manual,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(1))).id();
+
+    server_app.update();
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+    server_app.world.resource_mut::<ServerTick>().increment();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 2, "only the latest value should be sent");
+}
+
+#[test]
+fn accumulation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::Manual,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.accumulate_between_sends::<DummyComponent>(|accumulated, component| {
+        accumulated.0 += component.0
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(1))).id();
+
+    server_app.update();
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+    server_app.world.resource_mut::<ServerTick>().increment();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 3,
+        "changes between ticks should be accumulated"
+    );
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 4;
+    server_app.world.resource_mut::<ServerTick>().increment();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 4,
+        "accumulation should start over after the sent tick"
+    );
+}
+
+#[derive(Clone, Component, Deserialize, Serialize)]
+struct DummyComponent(u32);
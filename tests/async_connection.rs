@@ -0,0 +1,143 @@
+use std::{
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+#[test]
+fn connect_and_disconnect() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins,
+        AsyncConnectionPlugin {
+            connect: start_connecting,
+            disconnect: stop_connection,
+        },
+    ));
+
+    let connection = app.world.resource::<AsyncConnection>().clone();
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut connect = pin!(connection.connect(SERVER_ADDR));
+    assert!(connect.as_mut().poll(&mut cx).is_pending());
+
+    app.update();
+
+    let client = app.world.resource::<RepliconClient>();
+    assert!(client.is_connecting());
+    assert!(connect.as_mut().poll(&mut cx).is_pending());
+
+    let client_id = ClientId::new(1);
+    app.world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Connected {
+            client_id: Some(client_id),
+        });
+
+    app.update();
+
+    assert_eq!(
+        connect.as_mut().poll(&mut cx),
+        Poll::Ready(Ok(Some(client_id)))
+    );
+
+    let mut next_event = pin!(connection.next_event());
+    assert_eq!(
+        next_event.as_mut().poll(&mut cx),
+        Poll::Ready(ConnectionEvent::Connected {
+            client_id: Some(client_id)
+        })
+    );
+
+    let mut disconnect = pin!(connection.disconnect());
+    assert!(disconnect.as_mut().poll(&mut cx).is_pending());
+
+    app.update();
+
+    assert!(app.world.resource::<RepliconClient>().is_disconnected());
+    assert_eq!(disconnect.as_mut().poll(&mut cx), Poll::Ready(()));
+
+    let mut next_event = pin!(connection.next_event());
+    assert_eq!(
+        next_event.as_mut().poll(&mut cx),
+        Poll::Ready(ConnectionEvent::Disconnected)
+    );
+}
+
+#[test]
+fn connection_failure() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins,
+        AsyncConnectionPlugin {
+            connect: start_connecting,
+            disconnect: stop_connection,
+        },
+    ));
+
+    let connection = app.world.resource::<AsyncConnection>().clone();
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut connect = pin!(connection.connect(SERVER_ADDR));
+    assert!(connect.as_mut().poll(&mut cx).is_pending());
+
+    app.update();
+
+    app.world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Disconnected);
+
+    app.update();
+
+    assert_eq!(
+        connect.as_mut().poll(&mut cx),
+        Poll::Ready(Err(ConnectError::Disconnected))
+    );
+}
+
+#[test]
+fn backend_error() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins,
+        AsyncConnectionPlugin {
+            connect: |_, _| Err("no route".to_string()),
+            disconnect: stop_connection,
+        },
+    ));
+
+    let connection = app.world.resource::<AsyncConnection>().clone();
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut connect = pin!(connection.connect(SERVER_ADDR));
+    assert!(connect.as_mut().poll(&mut cx).is_pending());
+
+    app.update();
+
+    assert_eq!(
+        connect.as_mut().poll(&mut cx),
+        Poll::Ready(Err(ConnectError::Backend("no route".to_string())))
+    );
+}
+
+const SERVER_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 5000);
+
+fn start_connecting(world: &mut World, _server_addr: SocketAddr) -> Result<(), String> {
+    world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Connecting);
+    Ok(())
+}
+
+fn stop_connection(world: &mut World) {
+    world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Disconnected);
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{replicon_channels::ReplicationChannel, ClientId},
+    prelude::*,
+    test_app::TimeTestAppExt,
+};
+
+#[test]
+fn limit() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::Manual,
+            ..Default::default()
+        }),
+        BandwidthSimulationPlugin,
+    ))
+    .use_simulated_time();
+
+    const LIMITED_ID: ClientId = ClientId::new(1);
+    const UNLIMITED_ID: ClientId = ClientId::new(2);
+    app.world
+        .resource_mut::<SimulatedBandwidth>()
+        .set_limit(LIMITED_ID, Some(100));
+
+    let mut server = app.world.resource_mut::<RepliconServer>();
+    server.set_running(true);
+    for client_id in [LIMITED_ID, UNLIMITED_ID] {
+        for _ in 0..3 {
+            server.send(client_id, ReplicationChannel::Init, vec![0; 60]);
+        }
+    }
+
+    app.update();
+
+    let sent = drain_sent(&mut app);
+    assert_eq!(sent.iter().filter(|&&id| id == LIMITED_ID).count(), 1);
+    assert_eq!(sent.iter().filter(|&&id| id == UNLIMITED_ID).count(), 3);
+    let bandwidth = app.world.resource::<SimulatedBandwidth>();
+    assert_eq!(bandwidth.held_bytes(LIMITED_ID), 120);
+
+    app.advance_time(Duration::from_millis(500));
+
+    assert_eq!(drain_sent(&mut app), [LIMITED_ID]);
+
+    app.world.resource_mut::<RepliconServer>().send(
+        LIMITED_ID,
+        ReplicationChannel::Update,
+        vec![0; 50],
+    );
+
+    app.advance_time(Duration::from_millis(500));
+
+    assert_eq!(drain_sent(&mut app), [LIMITED_ID]);
+    let bandwidth = app.world.resource::<SimulatedBandwidth>();
+    assert_eq!(bandwidth.held_bytes(LIMITED_ID), 0);
+    assert_eq!(
+        bandwidth.dropped_bytes(LIMITED_ID),
+        50,
+        "unreliable messages that don't fit should be dropped"
+    );
+}
+
+fn drain_sent(app: &mut App) -> Vec<ClientId> {
+    app.world
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .map(|(client_id, ..)| client_id)
+        .collect()
+}
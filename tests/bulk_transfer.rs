@@ -0,0 +1,100 @@
+use bevy::{ecs::event::Events, prelude::*, time::TimePlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            BulkTransferPlugin {
+                max_bytes_per_tick: 20,
+                chunk_size: 10,
+            },
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let data = vec![1; 50];
+    server_app.world.resource_mut::<BulkTransfers>().send(
+        SendMode::Broadcast,
+        "dummy",
+        data.clone(),
+    );
+
+    let mut progress = Vec::new();
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let mut progress_events = client_app.world.resource_mut::<Events<BulkProgress>>();
+        progress.extend(progress_events.drain().map(|event| event.received));
+    }
+
+    assert_eq!(progress, [10, 20, 30, 40, 50]);
+    assert!(server_app.world.resource::<BulkTransfers>().is_empty());
+
+    let mut received_events = client_app.world.resource_mut::<Events<BulkReceived>>();
+    let event = received_events
+        .drain()
+        .next()
+        .expect("client should receive the payload");
+    assert_eq!(event.name, "dummy");
+    assert_eq!(*event.data, data);
+}
+
+#[test]
+fn direct_to_other_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            BulkTransferPlugin::default(),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.resource_mut::<BulkTransfers>().send(
+        SendMode::BroadcastExcept(client_app.world.resource::<RepliconClient>().id().unwrap()),
+        "dummy",
+        vec![1; 5],
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .resource::<Events<BulkReceived>>()
+        .is_empty());
+    assert!(server_app.world.resource::<BulkTransfers>().is_empty());
+}
+
+#[test]
+fn local_resending() {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, RepliconPlugins, BulkTransferPlugin::default()));
+
+    app.world
+        .resource_mut::<BulkTransfers>()
+        .send(SendMode::Broadcast, "dummy", vec![1; 5]);
+
+    app.update();
+
+    let received_events = app.world.resource::<Events<BulkReceived>>();
+    assert_eq!(received_events.len(), 1);
+}
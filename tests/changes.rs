@@ -2,14 +2,18 @@ use std::io::Cursor;
 
 use bevy::{ecs::entity::MapEntities, prelude::*, utils::Duration};
 use bevy_replicon::{
-    client::{confirmed::Confirmed, server_entity_map::ServerEntityMap, ServerInitTick},
+    client::{
+        confirmed::Confirmed, server_entity_map::ServerEntityMap, ServerInitTick, ServerUpdateTick,
+    },
     core::{
         command_markers::MarkerConfig,
         replication_fns::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+        replication_rules::ReplicationRules,
+        replicon_channels::ReplicationChannel,
     },
     prelude::*,
     server::server_tick::ServerTick,
-    test_app::ServerTestAppExt,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
 };
 use serde::{Deserialize, Serialize};
 
@@ -58,6 +62,207 @@ fn small_component() {
     assert!(component.0, "changed value should be updated on client");
 }
 
+#[test]
+fn atomic_group() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_atomic_group::<(BoolComponent, VecComponent)>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Modify the partner on client to detect if it will be sent.
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<VecComponent>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .get_mut::<VecComponent>(client_entity)
+        .unwrap()
+        .0
+        .push(1);
+
+    // Change only one component from the group.
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (bool_component, vec_component) = client_app
+        .world
+        .query::<(&BoolComponent, &VecComponent)>()
+        .single(&client_app.world);
+    assert!(
+        bool_component.0,
+        "changed value should be updated on client"
+    );
+    assert!(
+        vec_component.0.is_empty(),
+        "unchanged partner should be sent together with the changed component"
+    );
+}
+
+#[test]
+fn disabled_component() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<ReplicationRules>()
+        .set_enabled::<BoolComponent>(false);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "disabled component shouldn't be updated");
+
+    server_app
+        .world
+        .resource_mut::<ReplicationRules>()
+        .set_enabled::<BoolComponent>(true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0,
+        "changes made while disabled should be sent after enabling"
+    );
+}
+
+#[test]
+fn enabling_without_change_detection() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app
+        .init_resource::<ChangedCount>()
+        .add_systems(Update, count_changes);
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<ReplicationRules>()
+        .set_enabled::<BoolComponent>(false);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let changed_count = **server_app.world.resource::<ChangedCount>();
+    server_app
+        .world
+        .resource_mut::<ReplicationRules>()
+        .set_enabled::<BoolComponent>(true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(component.0, "current value should be sent after enabling");
+    assert_eq!(
+        **server_app.world.resource::<ChangedCount>(),
+        changed_count,
+        "enabling shouldn't trigger change detection"
+    );
+}
+
 #[test]
 fn package_size_component() {
     let mut server_app = App::new();
@@ -109,6 +314,81 @@ fn package_size_component() {
     assert_eq!(component.0, BIG_DATA);
 }
 
+#[test]
+fn chunked_component() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<VecComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // To exceed multiple packets.
+    const BIG_DATA: &[u8] = &[1; 5000];
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0 = BIG_DATA.to_vec();
+
+    server_app.update();
+
+    // Drop the first chunk.
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let mut dropped = false;
+    let messages: Vec<_> = server.drain_sent().collect();
+    for (client_id, channel_id, message) in messages {
+        if !dropped && channel_id == ReplicationChannel::UpdateChunks.into() {
+            dropped = true;
+        } else {
+            server.send(client_id, channel_id, message);
+        }
+    }
+    assert!(dropped, "component should be sent in chunks");
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0.is_empty(),
+        "incomplete data shouldn't be applied"
+    );
+
+    // Server should resend unacknowledged data.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, BIG_DATA);
+}
+
 #[test]
 fn command_fns() {
     let mut server_app = App::new();
@@ -676,26 +956,30 @@ fn buffering() {
 }
 
 #[test]
-fn old_ignored() {
+fn reorder_window() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
         app.add_plugins((
             MinimalPlugins,
-            RepliconPlugins.set(ServerPlugin {
-                tick_policy: TickPolicy::EveryFrame,
-                ..Default::default()
-            }),
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    reorder_window: Duration::from_millis(100),
+                }),
         ))
-        .replicate_mapped::<MappedComponent>();
+        .replicate::<BoolComponent>();
     }
+    client_app.use_simulated_time();
 
     server_app.connect_client(&mut client_app);
 
-    let server_map_entity = server_app.world.spawn_empty().id();
     let server_entity = server_app
         .world
-        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .spawn((Replicated, BoolComponent(false)))
         .id();
 
     server_app.update();
@@ -703,33 +987,196 @@ fn old_ignored() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    // Change the value, but don't process it on client.
-    let update_entity1 = server_app.world.spawn_empty().id();
     let mut component = server_app
         .world
-        .get_mut::<MappedComponent>(server_entity)
+        .get_mut::<BoolComponent>(server_entity)
         .unwrap();
-    component.0 = update_entity1;
+    component.0 = true;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
+    client_app.update();
 
-    // Change the value again to generate another update.
-    let update_entity2 = server_app.world.spawn_empty().id();
-    let mut component = server_app
+    let component = client_app
         .world
-        .get_mut::<MappedComponent>(server_entity)
-        .unwrap();
-    component.0 = update_entity2;
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "client should hold the update");
 
-    server_app.update();
-    server_app.exchange_with_client(&mut client_app);
-    client_app.update();
+    client_app.advance_time(Duration::from_millis(50));
 
-    let entity_map = client_app.world.resource::<ServerEntityMap>();
-    assert!(
-        !entity_map.to_client().contains_key(&update_entity1),
-        "client should ignore older update"
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        !component.0,
+        "update should be held until the window elapses"
+    );
+
+    client_app.advance_time(Duration::from_millis(50));
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(component.0, "update should be applied after the window");
+}
+
+#[test]
+fn reorder_window_out_of_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    reorder_window: Duration::from_millis(100),
+                }),
+        ))
+        .register_marker::<HistoryMarker>()
+        .set_marker_fns::<HistoryMarker, BoolComponent>(
+            write_history,
+            command_fns::default_remove::<BoolComponent>,
+        )
+        .replicate::<BoolComponent>();
+    }
+    client_app.use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let client_entity = client_app.world.spawn(HistoryMarker).id();
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world.resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value, but hold the update to deliver it after the next one.
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    let delayed: Vec<_> = server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .collect();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = false;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.advance_time(Duration::from_millis(50));
+
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in delayed {
+        client.insert_received(channel_id, message);
+    }
+    client_app.advance_time(Duration::from_millis(50));
+
+    let history = client_app.world.get::<BoolHistory>(client_entity).unwrap();
+    assert_eq!(
+        history.0,
+        [false],
+        "updates should be held until the window elapses for the oldest received"
+    );
+
+    client_app.advance_time(Duration::from_millis(50));
+
+    let client_entity = client_app.world.entity(client_entity);
+    let history = client_entity.get::<BoolHistory>().unwrap();
+    assert_eq!(
+        history.0,
+        [false, true, false],
+        "both updates should be applied in tick order after the window"
+    );
+}
+
+#[test]
+fn old_ignored() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_mapped::<MappedComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_map_entity = server_app.world.spawn_empty().id();
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value, but don't process it on client.
+    let update_entity1 = server_app.world.spawn_empty().id();
+    let mut component = server_app
+        .world
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap();
+    component.0 = update_entity1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value again to generate another update.
+    let update_entity2 = server_app.world.spawn_empty().id();
+    let mut component = server_app
+        .world
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap();
+    component.0 = update_entity2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(
+        !entity_map.to_client().contains_key(&update_entity1),
+        "client should ignore older update"
     );
     assert_eq!(
         client_app.world.entities().len(),
@@ -819,6 +1266,228 @@ fn acknowledgment() {
     );
 }
 
+#[test]
+fn after_unchanged_ticks() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    // Let the archetype become unchanged.
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+    server_app.world.spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world.query::<&BoolComponent>();
+    assert_eq!(components.iter(&client_app.world).len(), 2);
+    assert!(
+        components
+            .iter(&client_app.world)
+            .all(|component| component.0),
+        "changes after unchanged ticks should be sent"
+    );
+}
+
+#[test]
+fn compared_sent() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<FloatComponent>()
+        .compare_sent::<FloatComponent>(0.1);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, FloatComponent(0.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value within the epsilon.
+    let mut component = server_app
+        .world
+        .get_mut::<FloatComponent>(server_entity)
+        .unwrap();
+    component.0 = 0.05;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&FloatComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 0.0,
+        "change within the epsilon shouldn't be sent"
+    );
+
+    // Accumulate the change beyond the epsilon.
+    let mut component = server_app
+        .world
+        .get_mut::<FloatComponent>(server_entity)
+        .unwrap();
+    component.0 = 0.15;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&FloatComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 0.15);
+}
+
+#[test]
+fn once() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_once::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "component should be sent only on insertion");
+}
+
+#[test]
+fn update_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let init_tick = **server_app.world.resource::<ServerTick>();
+    let update_tick = client_app.world.resource::<ServerUpdateTick>();
+    assert_eq!(update_tick.get(), init_tick);
+    assert!(update_tick.frame(init_tick).is_some());
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    assert_eq!(
+        **client_app.world.resource::<ServerInitTick>(),
+        init_tick,
+        "init tick shouldn't change with component changes"
+    );
+
+    let update_tick = client_app.world.resource::<ServerUpdateTick>();
+    assert_eq!(
+        update_tick.get(),
+        tick,
+        "update tick should change with component changes"
+    );
+    let init_frame = update_tick.frame(init_tick).unwrap();
+    let frame = update_tick.frame(tick).unwrap();
+    assert!(frame > init_frame);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
 
@@ -837,9 +1506,28 @@ impl MapEntities for MappedComponent {
     }
 }
 
+#[derive(Clone, Component, Deserialize, Serialize)]
+struct FloatComponent(f32);
+
+impl ApproxEq for FloatComponent {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
+}
+
 #[derive(Component)]
 struct ReplaceMarker;
 
+#[derive(Default, Deref, DerefMut, Resource)]
+struct ChangedCount(usize);
+
+fn count_changes(
+    mut changed_count: ResMut<ChangedCount>,
+    components: Query<(), Changed<BoolComponent>>,
+) {
+    **changed_count += components.iter().count();
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct OriginalComponent(bool);
 
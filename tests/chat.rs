@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    gameplay::chat::{
+        ChatError, ChatMessage, ChatPlugin, ChatReceived, ChatRejected, ChatSender, ChatSettings,
+        ChatTarget,
+    },
+    prelude::*,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
+};
+
+#[test]
+fn global() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin));
+    }
+    server_app.insert_resource(ChatSettings {
+        validate: |_, text| {
+            *text = text.replace("bad", "***");
+            true
+        },
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(ChatMessage {
+        target: ChatTarget::Global,
+        text: "bad word".to_string(),
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut received_events = client_app.world.resource_mut::<Events<ChatReceived>>();
+    let event = received_events
+        .drain()
+        .next()
+        .expect("sender should receive its own message");
+    assert_eq!(event.sender, ChatSender::Client(client_id));
+    assert_eq!(event.text, "*** word");
+}
+
+#[test]
+fn announcement() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app.world.send_event(ChatMessage {
+        target: ChatTarget::Direct(client_id),
+        text: "Welcome".to_string(),
+    });
+    server_app.world.send_event(ChatMessage {
+        target: ChatTarget::Channel(0),
+        text: "Not received".to_string(),
+    });
+
+    server_app.update(); // Resend local events.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut received_events = client_app.world.resource_mut::<Events<ChatReceived>>();
+    let events: Vec<_> = received_events.drain().collect();
+    assert_eq!(events.len(), 1, "client isn't a member of the channel");
+    assert_eq!(events[0].sender, ChatSender::Server);
+}
+
+#[test]
+fn rejection() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin));
+    }
+    server_app.insert_resource(ChatSettings {
+        rate_limit: 2,
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    for target in [
+        ChatTarget::Channel(0),
+        ChatTarget::Direct(ClientId::new(42)),
+        ChatTarget::Global,
+    ] {
+        client_app.world.send_event(ChatMessage {
+            target,
+            text: Default::default(),
+        });
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut rejected_events = client_app.world.resource_mut::<Events<ChatRejected>>();
+    let reasons: Vec<_> = rejected_events.drain().map(|event| event.reason).collect();
+    assert_eq!(
+        reasons,
+        [
+            ChatError::NotInChannel,
+            ChatError::UnknownRecipient,
+            ChatError::RateLimited
+        ]
+    );
+}
+
+#[test]
+fn rate_period() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin))
+            .use_simulated_time();
+    }
+    server_app.insert_resource(ChatSettings {
+        rate_limit: 1,
+        rate_period: Duration::from_secs(5),
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    for elapsed in [
+        Duration::ZERO,
+        Duration::from_secs(4),
+        Duration::from_secs(1),
+    ] {
+        client_app.world.send_event(ChatMessage {
+            target: ChatTarget::Global,
+            text: Default::default(),
+        });
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.advance_time(elapsed);
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    let mut rejected_events = client_app.world.resource_mut::<Events<ChatRejected>>();
+    let reasons: Vec<_> = rejected_events.drain().map(|event| event.reason).collect();
+    assert_eq!(
+        reasons,
+        [ChatError::RateLimited],
+        "only the message within the period should be rejected"
+    );
+}
@@ -0,0 +1,158 @@
+use bevy::{
+    ecs::{entity::MapEntities, system::RunSystemOnce},
+    prelude::*,
+};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn clones() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_mapped::<MappedComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let target = server_app.world.spawn(Replicated).id();
+    let template = server_app
+        .world
+        .spawn((
+            Replicated,
+            BoolComponent(true),
+            MappedComponent(target),
+            NotReplicatedComponent,
+        ))
+        .id();
+
+    let clones = server_app
+        .world
+        .run_system_once(move |mut commands: ServerCommands| {
+            commands.clone_replicated(template, 3)
+        });
+    assert_eq!(clones.len(), 3);
+
+    for &entity in &clones {
+        let entity = server_app.world.entity(entity);
+        assert!(entity.contains::<Replicated>());
+        assert!(entity.get::<BoolComponent>().unwrap().0);
+        assert_eq!(
+            entity.get::<MappedComponent>().unwrap().0,
+            target,
+            "entities inside components shouldn't be mapped"
+        );
+        assert!(
+            !entity.contains::<NotReplicatedComponent>(),
+            "only replicated components should be cloned"
+        );
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_target = client_app
+        .world
+        .query_filtered::<Entity, (With<Replicated>, Without<BoolComponent>)>()
+        .single(&client_app.world);
+    let components: Vec<_> = client_app
+        .world
+        .query::<(&BoolComponent, &MappedComponent)>()
+        .iter(&client_app.world)
+        .map(|(component, mapped)| (component.0, mapped.0))
+        .collect();
+    assert_eq!(components, [(true, client_target); 4]);
+}
+
+#[test]
+fn changed_clone() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let template = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let clones = server_app
+        .world
+        .run_system_once(move |mut commands: ServerCommands| {
+            commands.clone_replicated(template, 2)
+        });
+    let changed = *clones.first().unwrap();
+    server_app
+        .world
+        .run_system_once(move |mut components: Query<&mut BoolComponent>| {
+            components.get_mut(changed).unwrap().0 = true;
+        });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components: Vec<_> = client_app
+        .world
+        .query::<&BoolComponent>()
+        .iter(&client_app.world)
+        .map(|component| component.0)
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        [false, false, true],
+        "changed clone shouldn't be sent with template bytes"
+    );
+}
+
+#[test]
+fn missing_template() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let template = app.world.spawn_empty().id();
+    let clones = app
+        .world
+        .run_system_once(move |mut commands: ServerCommands| {
+            commands.clone_replicated(template, 2)
+        });
+
+    for entity in clones {
+        assert!(app.world.get_entity(entity).is_none());
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Component)]
+struct NotReplicatedComponent;
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn decryption() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ComponentEncryptionPlugin,
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<SecretComponent>()
+        .encrypt_component::<SecretComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ServerEncryptionKeys>()
+        .insert(client_id, KEY);
+    **client_app.world.resource_mut::<ClientEncryptionKey>() = Some(KEY);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DummyComponent, SecretComponent(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let secret = client_app
+        .world
+        .query_filtered::<&SecretComponent, With<DummyComponent>>()
+        .single(&client_app.world);
+    assert_eq!(secret.0, 1);
+
+    server_app
+        .world
+        .get_mut::<SecretComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let secret = client_app
+        .world
+        .query::<&SecretComponent>()
+        .single(&client_app.world);
+    assert_eq!(secret.0, 2);
+}
+
+#[test]
+fn without_key() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ComponentEncryptionPlugin,
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<SecretComponent>()
+        .encrypt_component::<SecretComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, SecretComponent(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<DummyComponent>, Without<SecretComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn wrong_key() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ComponentEncryptionPlugin,
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<SecretComponent>()
+        .encrypt_component::<SecretComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ServerEncryptionKeys>()
+        .insert(client_id, KEY);
+    **client_app.world.resource_mut::<ClientEncryptionKey>() = Some([1; 32]);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, SecretComponent(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<DummyComponent>, Without<SecretComponent>)>()
+        .single(&client_app.world);
+}
+
+const KEY: [u8; 32] = [0; 32];
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct SecretComponent(u32);
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+
+#[test]
+fn recording() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .replicate_with_history::<Transform>(3);
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let entity = app.world.spawn((Replicated, Transform::default())).id();
+
+    for x in 0..4 {
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x = x as f32;
+        app.update();
+    }
+
+    let tick = **app.world.resource::<ServerTick>();
+    let history = app
+        .world
+        .get::<ComponentHistory<Transform>>(entity)
+        .unwrap();
+    assert_eq!(history.len(), 3, "oldest values should be removed");
+    assert_eq!(history.last().unwrap().0, tick);
+    assert_eq!(history.get(tick).unwrap().translation.x, 3.0);
+    assert_eq!(history.get(tick - 2).unwrap().translation.x, 1.0);
+    assert_eq!(history.ago(1).unwrap().translation.x, 2.0);
+    assert!(history.get(tick - 3).is_none());
+
+    app.world.entity_mut(entity).remove::<Transform>();
+    app.update();
+
+    assert!(!app
+        .world
+        .entity(entity)
+        .contains::<ComponentHistory<Transform>>());
+}
+
+#[test]
+fn client_without_history() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_history::<Transform>(8);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, Transform::default()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<Transform>)>()
+        .single(&client_app.world);
+    assert!(
+        client_app
+            .world
+            .query::<&ComponentHistory<Transform>>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "history should be recorded only on server"
+    );
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn denied() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+    }
+    server_app.add_plugins(ConnectionFilterPlugin);
+
+    let client_id = ClientId::new(1);
+    server_app
+        .world
+        .resource_mut::<ConnectionFilter>()
+        .deny(client_id, None);
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut denied_events = server_app.world.resource_mut::<Events<ConnectionDenied>>();
+    assert_eq!(denied_events.drain().count(), 1);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [client_id]);
+}
+
+#[test]
+fn expired() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+    }
+    server_app.add_plugins(ConnectionFilterPlugin);
+
+    let mut filter = server_app.world.resource_mut::<ConnectionFilter>();
+    filter.deny(ClientId::new(1), Some(Duration::ZERO));
+    assert!(filter.is_allowed(ClientId::new(1)));
+
+    server_app.connect_client(&mut client_app);
+
+    let denied_events = server_app.world.resource::<Events<ConnectionDenied>>();
+    assert!(denied_events.is_empty());
+
+    let filter = server_app.world.resource::<ConnectionFilter>();
+    assert_eq!(
+        filter.iter_denied().count(),
+        0,
+        "expired entries should be removed"
+    );
+}
+
+#[test]
+fn allowlist_only() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+    }
+    server_app.add_plugins(ConnectionFilterPlugin);
+
+    let mut filter = server_app.world.resource_mut::<ConnectionFilter>();
+    filter.allowlist_only = true;
+    filter.allow(ClientId::new(2), None);
+    assert!(!filter.is_allowed(ClientId::new(1)));
+    assert!(filter.is_allowed(ClientId::new(2)));
+
+    filter.deny(ClientId::new(2), None);
+    assert!(!filter.is_allowed(ClientId::new(2)));
+}
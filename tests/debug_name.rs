@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn labels() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DebugNamePlugin,
+        ));
+    }
+    server_app.label_by_type::<Player>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, Player, Name::new("Alice")));
+    server_app.world.spawn((Replicated, Player));
+    server_app
+        .world
+        .spawn((Replicated, Name::new("a".repeat(40))));
+    server_app.world.spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut labels: Vec<_> = client_app
+        .world
+        .query::<&ReplicatedDebugName>()
+        .iter(&client_app.world)
+        .map(|debug_name| debug_name.as_str().to_string())
+        .collect();
+    labels.sort();
+    assert_eq!(
+        labels,
+        [
+            "Alice".to_string(),
+            "Player".to_string(),
+            "a".repeat(ReplicatedDebugName::MAX_LEN)
+        ]
+    );
+}
+
+#[test]
+fn rename() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DebugNamePlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Name::new("Alice")))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<Name>(server_entity)
+        .unwrap()
+        .set("Bob");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let debug_name = client_app
+        .world
+        .query::<&ReplicatedDebugName>()
+        .single(&client_app.world);
+    assert_eq!(debug_name.as_str(), "Bob");
+}
+
+#[derive(Component)]
+struct Player;
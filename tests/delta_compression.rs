@@ -0,0 +1,284 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    core::{replication_stages::ComponentStages, replicon_channels::ReplicationChannel},
+    field_delta,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn changed_field() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_delta::<DeltaComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((
+            Replicated,
+            DeltaComponent {
+                items: vec![Item(1), Item(2)],
+                gold: 10,
+                weight: 1.5,
+            },
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&DeltaComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.items, [Item(1), Item(2)]);
+    assert_eq!(component.gold, 10);
+
+    let mut component = server_app
+        .world
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.gold = 20;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DeltaComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.items, [Item(1), Item(2)]);
+    assert_eq!(component.gold, 20);
+    assert_eq!(component.weight, 1.5);
+}
+
+#[test]
+fn unacknowledged_changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_delta::<DeltaComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DeltaComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.gold = 5;
+
+    // Lose the message with the first change.
+    server_app.update();
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let messages: Vec<_> = server.drain_sent().collect();
+    for (client_id, channel_id, message) in messages {
+        if channel_id != ReplicationChannel::Update.into() {
+            server.send(client_id, channel_id, message);
+        }
+    }
+
+    let mut component = server_app
+        .world
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.weight = 2.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DeltaComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.gold, 5,
+        "fields changed after the acknowledged value should be resent"
+    );
+    assert_eq!(component.weight, 2.0);
+}
+
+#[test]
+fn disabled_compression() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_delta::<DeltaComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DeltaComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<ServerSettings>()
+        .delta_compression = false;
+    let mut component = server_app
+        .world
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.gold = 5;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let stages = server_app.world.resource::<ComponentStages>();
+    assert_eq!(stages.stored_values(), 0);
+
+    let component = client_app
+        .world
+        .query::<&DeltaComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.gold, 5);
+
+    server_app
+        .world
+        .resource_mut::<ServerSettings>()
+        .delta_compression = true;
+    let mut component = server_app
+        .world
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.gold = 0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DeltaComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.gold, 0,
+        "value sent in full shouldn't be used as a baseline"
+    );
+}
+
+#[test]
+fn baseline_expiry() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                update_timeout: Duration::from_millis(100),
+                ..Default::default()
+            }),
+        ))
+        .replicate_delta::<DeltaComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Trigger the cleanup every frame.
+    server_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DeltaComponent::default()))
+        .id();
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let stages = server_app.world.resource::<ComponentStages>();
+    assert_eq!(
+        stages.stored_values(),
+        1,
+        "baseline should be kept while the entity is visible"
+    );
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, false);
+
+    server_app.update();
+    server_app.update();
+
+    let stages = server_app.world.resource::<ComponentStages>();
+    assert_eq!(stages.stored_values(), 0);
+}
+
+#[derive(Component, Clone, Default)]
+struct DeltaComponent {
+    items: Vec<Item>,
+    gold: u32,
+    weight: f32,
+}
+
+field_delta!(DeltaComponent {
+    items,
+    gold,
+    weight
+});
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct Item(u16);
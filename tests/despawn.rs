@@ -1,6 +1,12 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_replicon::{
-    client::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+    client::server_entity_map::ServerEntityMap,
+    core::replication_fns::ctx::DespawnCtx,
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
 };
 use serde::{Deserialize, Serialize};
 
@@ -88,6 +94,58 @@ fn with_heirarchy() {
     assert!(client_app.world.entities().is_empty());
 }
 
+#[test]
+fn hierarchy_with_client_children() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ParentSyncPlugin { automatic: true }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+    server_app.world.spawn(Replicated).set_parent(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_child_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Parent>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .spawn_empty()
+        .set_parent(client_child_entity);
+
+    // Despawn only the parent, so the child despawn won't be sent.
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world.entities().is_empty());
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(
+        entity_map.to_client().is_empty(),
+        "mappings of despawned children should be removed"
+    );
+    assert!(entity_map.to_server().is_empty());
+}
+
 #[test]
 fn after_spawn() {
     let mut server_app = App::new();
@@ -118,5 +176,149 @@ fn after_spawn() {
     assert!(client_app.world.entities().is_empty());
 }
 
+#[test]
+fn grace_period() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app
+        .add_plugins(DespawnGracePlugin {
+            grace_period: Duration::from_secs(1),
+        })
+        .use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (client_entity, despawned) = client_app
+        .world
+        .query_filtered::<(Entity, &Despawned), With<DummyComponent>>()
+        .single(&client_app.world);
+    assert_eq!(despawned.tick, **server_app.world.resource::<ServerTick>());
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+
+    client_app.advance_time(Duration::from_millis(500));
+    assert!(client_app.world.get_entity(client_entity).is_some());
+
+    client_app.advance_time(Duration::from_millis(500));
+    assert!(client_app.world.get_entity(client_entity).is_none());
+}
+
+#[test]
+fn custom_despawn_fn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .set_despawn_fn(mark_dying);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), With<Dying>>()
+        .single(&client_app.world);
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+}
+
+#[test]
+fn grace_period_with_custom_despawn_fn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app
+        .add_plugins(DespawnGracePlugin {
+            grace_period: Duration::from_secs(1),
+        })
+        .set_despawn_fn(mark_dying)
+        .use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, (With<Despawned>, Without<Dying>)>()
+        .single(&client_app.world);
+
+    client_app.advance_time(Duration::from_secs(1));
+    assert!(
+        client_app.world.entity(client_entity).contains::<Dying>(),
+        "custom function should be called after the grace period"
+    );
+}
+
+fn mark_dying(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
+    entity.insert(Dying);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component)]
+struct Dying;
@@ -0,0 +1,49 @@
+use bevy::{ecs::event::Events, ecs::system::RunSystemOnce, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn reason_before_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_disconnect_reason::<DummyReason>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .run_system_once(move |mut commands: ServerCommands| {
+            commands.disconnect(client_id, DummyReason::ServerFull)
+        });
+
+    server_app.update();
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_disconnects().count(),
+        0,
+        "disconnect should be requested only after the reason is drained"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut reason_events = client_app
+        .world
+        .resource_mut::<Events<DisconnectReason<DummyReason>>>();
+    let reasons: Vec<_> = reason_events.drain().map(|event| event.0).collect();
+    assert_eq!(reasons, [DummyReason::ServerFull]);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [client_id]);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum DummyReason {
+    ServerFull,
+}
@@ -0,0 +1,71 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn allocation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_mapped::<MappedComponent>();
+    }
+
+    let pooled_entity = client_app.world.spawn(Pooled).id();
+    let mut pool = vec![pooled_entity];
+    client_app.set_entity_allocator(move |commands: &mut Commands| {
+        pool.pop().unwrap_or_else(|| commands.spawn(Pooled).id())
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+    server_app
+        .world
+        .spawn((Replicated, MappedComponent(server_entity)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let replicated_count = client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<Pooled>)>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(
+        replicated_count, 2,
+        "all entities should be allocated with the allocator"
+    );
+    assert!(client_app
+        .world
+        .entity(pooled_entity)
+        .contains::<Replicated>());
+
+    let mapped_component = client_app
+        .world
+        .query::<&MappedComponent>()
+        .single(&client_app.world);
+    assert!(client_app
+        .world
+        .entity(mapped_component.0)
+        .contains::<Pooled>());
+}
+
+#[derive(Component)]
+struct Pooled;
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    network_event::server_event::ServerEventChannel, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn queueing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.set_server_event_budget::<DummyEvent>(2, 10, OverflowPolicy::DropNewest);
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    for index in 0..3 {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent(index),
+        });
+    }
+
+    let mut received = Vec::new();
+    for expected_queued in [1, 0] {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let budget = server_app.world.resource::<ServerEventBudget<DummyEvent>>();
+        assert_eq!(budget.queued(client_id), expected_queued);
+
+        let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+        received.extend(dummy_events.drain().map(|event| event.0));
+    }
+
+    assert_eq!(received, [0, 1, 2], "events should keep their order");
+    assert!(server_app
+        .world
+        .resource::<Events<EventSaturated<DummyEvent>>>()
+        .is_empty());
+}
+
+#[test]
+fn drop_oldest() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.set_server_event_budget::<DummyEvent>(1, 2, OverflowPolicy::DropOldest);
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    for index in 0..4 {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Direct(client_id),
+            event: DummyEvent(index),
+        });
+    }
+
+    let mut received = Vec::new();
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+        received.extend(dummy_events.drain().map(|event| event.0));
+    }
+
+    assert_eq!(received, [2, 3]);
+
+    let mut saturated_events = server_app
+        .world
+        .resource_mut::<Events<EventSaturated<DummyEvent>>>();
+    let event = saturated_events
+        .drain()
+        .next()
+        .expect("saturation should be reported");
+    assert_eq!(event.client_id, client_id);
+    assert_eq!(event.dropped, 2);
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.set_server_event_budget::<DummyEvent>(1, 1, OverflowPolicy::Disconnect);
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    for index in 0..2 {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent(index),
+        });
+    }
+
+    server_app.update();
+
+    let budget = server_app.world.resource::<ServerEventBudget<DummyEvent>>();
+    assert_eq!(budget.queued(client_id), 0);
+
+    let channel_id = u8::from(
+        *server_app
+            .world
+            .resource::<ServerEventChannel<DummyEvent>>(),
+    );
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    assert!(
+        server
+            .drain_sent()
+            .all(|(_, message_channel, _)| message_channel != channel_id),
+        "queued messages should be discarded"
+    );
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [client_id]);
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent(usize);
@@ -2,12 +2,13 @@ use std::io::Cursor;
 
 use bevy::prelude::*;
 use bevy_replicon::{
+    bincode::{self, DefaultOptions, Options},
     core::{
         command_markers::MarkerConfig,
         replication_fns::{
             command_fns,
-            ctx::{DespawnCtx, WriteCtx},
-            rule_fns::RuleFns,
+            ctx::{DespawnCtx, SerializeCtx, WriteCtx},
+            rule_fns::{Codec, CompactTag, RuleFns},
             test_fns::TestFnsEntityExt,
             ReplicationFns,
         },
@@ -15,7 +16,7 @@ use bevy_replicon::{
     prelude::*,
     server::server_tick::ServerTick,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
 
 #[test]
 #[should_panic]
@@ -69,6 +70,62 @@ fn remove() {
     assert!(!entity.contains::<OriginalComponent>());
 }
 
+#[test]
+fn write_marker() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = **app.world.resource::<ServerTick>();
+    let (marker_info, variant_info) =
+        app.world
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                (
+                    replication_fns
+                        .register_rule_fns(world, RuleFns::<OriginalComponent>::default()),
+                    replication_fns
+                        .register_rule_fns(world, RuleFns::<SingleVariantComponent>::default()),
+                )
+            });
+
+    let mut entity = app
+        .world
+        .spawn((OriginalComponent, SingleVariantComponent::Variant));
+    let marker_data = entity.serialize(marker_info);
+    let variant_data = entity.serialize(variant_info);
+    assert!(marker_data.is_empty());
+    assert!(!variant_data.is_empty(), "enum index should be written");
+
+    entity.remove::<(OriginalComponent, SingleVariantComponent)>();
+    entity.apply_write(&marker_data, marker_info, tick);
+    entity.apply_write(&variant_data, variant_info, tick);
+    assert!(entity.contains::<OriginalComponent>());
+    assert!(entity.contains::<SingleVariantComponent>());
+}
+
+#[test]
+fn write_compact() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(world, RuleFns::<CompactComponent>::compact())
+        });
+
+    let mut entity = app.world.spawn(CompactComponent::Second);
+    let data = entity.serialize(fns_info);
+    assert_eq!(data, [CompactComponent::Second.to_tag()]);
+
+    entity.insert(CompactComponent::First);
+    entity.apply_write(&data, fns_info, tick);
+    assert_eq!(
+        *entity.get::<CompactComponent>().unwrap(),
+        CompactComponent::Second
+    );
+}
+
 #[test]
 fn write_with_command() {
     let mut app = App::new();
@@ -342,12 +399,140 @@ fn despawn() {
     assert!(app.world.get::<Despawned>(id).is_some());
 }
 
+#[test]
+fn write_with_codec() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns
+                .register_rule_fns(world, RuleFns::<IntComponent>::with_codec::<FixintCodec>())
+        });
+
+    let mut entity = app.world.spawn(IntComponent(1));
+    let data = entity.serialize(fns_info);
+    assert_eq!(
+        data.len(),
+        4,
+        "integer should be serialized with a fixed size"
+    );
+
+    entity.remove::<IntComponent>();
+    entity.apply_write(&data, fns_info, tick);
+    assert_eq!(*entity.get::<IntComponent>().unwrap(), IntComponent(1));
+}
+
+#[test]
+fn write_with_default_codec() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .set_default_codec::<FixintCodec>();
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            let rule_fns = replication_fns.default_codec.rule_fns::<IntComponent>();
+            replication_fns.register_rule_fns(world, rule_fns)
+        });
+
+    let mut entity = app.world.spawn(IntComponent(1));
+    let data = entity.serialize(fns_info);
+    assert_eq!(
+        data.len(),
+        4,
+        "integer should be serialized with the default codec"
+    );
+
+    entity.remove::<IntComponent>();
+    entity.apply_write(&data, fns_info, tick);
+    assert_eq!(*entity.get::<IntComponent>().unwrap(), IntComponent(1));
+}
+
+#[test]
+fn write_with_closures() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let offset = 10;
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(
+                world,
+                RuleFns::new(
+                    move |_ctx: &SerializeCtx,
+                          component: &IntComponent,
+                          cursor: &mut Cursor<Vec<u8>>| {
+                        DefaultOptions::new().serialize_into(cursor, &(component.0 - offset))
+                    },
+                    move |_ctx: &mut WriteCtx, cursor: &mut Cursor<&[u8]>| {
+                        let value: u32 = DefaultOptions::new().deserialize_from(cursor)?;
+                        Ok(IntComponent(value + offset))
+                    },
+                ),
+            )
+        });
+
+    let mut entity = app.world.spawn(IntComponent(15));
+    let data = entity.serialize(fns_info);
+    assert_eq!(
+        DefaultOptions::new().deserialize::<u32>(&data).unwrap(),
+        5,
+        "captured state should be used for serialization"
+    );
+
+    entity.remove::<IntComponent>();
+    entity.apply_write(&data, fns_info, tick);
+    assert_eq!(*entity.get::<IntComponent>().unwrap(), IntComponent(15));
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct OriginalComponent;
 
 #[derive(Component, Deserialize, Serialize)]
 struct ReplacedComponent;
 
+#[derive(Component, Deserialize, Serialize)]
+enum SingleVariantComponent {
+    Variant,
+}
+
+#[derive(Component, Debug, PartialEq)]
+enum CompactComponent {
+    First,
+    Second,
+}
+
+bevy_replicon::compact_tag!(CompactComponent { First, Second });
+
+#[derive(Component, Debug, Deserialize, PartialEq, Serialize)]
+struct IntComponent(u32);
+
+/// Bincode with fixed-size integers.
+struct FixintCodec;
+
+impl Codec for FixintCodec {
+    fn serialize<T: Serialize>(cursor: &mut Cursor<Vec<u8>>, value: &T) -> bincode::Result<()> {
+        DefaultOptions::new()
+            .with_fixint_encoding()
+            .serialize_into(cursor, value)
+    }
+
+    fn deserialize_seed<'de, T: DeserializeSeed<'de>>(
+        cursor: &mut Cursor<&[u8]>,
+        seed: T,
+    ) -> bincode::Result<T::Value> {
+        DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_from_seed(seed, cursor)
+    }
+}
+
 #[derive(Component)]
 struct Despawned;
 
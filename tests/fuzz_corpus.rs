@@ -0,0 +1,72 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_replicon::{client::fuzz_corpus, prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn record_and_replay() {
+    let directory = std::env::temp_dir().join(format!("replicon_corpus_{}", std::process::id()));
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app.add_plugins(FuzzCorpusPlugin {
+        directory: directory.clone(),
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(1))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entries: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(entries.len(), 1, "single session should be recorded");
+    let data = fs::read(&entries[0]).unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+
+    let mut replay_app = App::new();
+    replay_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<DummyComponent>();
+
+    fuzz_corpus::replay(&mut replay_app, &data).unwrap();
+
+    let component = replay_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&replay_app.world);
+    assert_eq!(component.0, 2);
+
+    let result = fuzz_corpus::replay(&mut replay_app, &[1, 0, 255]);
+    assert!(result.is_err(), "malformed data should return an error");
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
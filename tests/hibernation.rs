@@ -0,0 +1,255 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn hibernate_and_wake() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HibernationPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, HibernationRegion(0), DummyComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.resource_mut::<Hibernation>().hibernate(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(server_app.world.get::<Hibernated>(server_entity).is_some());
+    let digest = client_app
+        .world
+        .resource::<RegionDigests>()
+        .get(&0)
+        .cloned()
+        .expect("client should receive digest");
+    assert_eq!(digest.entities_count, 1);
+    assert!(digest.bounds.is_none());
+    assert!(digest.summary.is_empty());
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world.query::<&DummyComponent>();
+    let component = components.single(&client_app.world);
+    assert_eq!(component.0, 0, "hibernated entity shouldn't be updated");
+
+    server_app.world.resource_mut::<Hibernation>().wake(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world.get::<Hibernated>(server_entity).is_none());
+    assert!(client_app.world.resource::<RegionDigests>().is_empty());
+
+    let component = components.single(&client_app.world);
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn connect_during_hibernation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HibernationPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app
+        .world
+        .spawn((Replicated, HibernationRegion(0), DummyComponent(0)));
+    server_app.world.resource_mut::<Hibernation>().hibernate(0);
+
+    server_app.update();
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app
+        .world
+        .resource::<RegionDigests>()
+        .contains_key(&0));
+    let mut components = client_app.world.query::<&DummyComponent>();
+    assert_eq!(components.iter(&client_app.world).count(), 0);
+
+    server_app.world.resource_mut::<Hibernation>().wake(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(components.iter(&client_app.world).count(), 1);
+}
+
+#[test]
+fn spawn_in_hibernated_region() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HibernationPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.resource_mut::<Hibernation>().hibernate(0);
+
+    server_app.update();
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, HibernationRegion(0), DummyComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world.get::<Hibernated>(server_entity).is_some());
+    let mut components = client_app.world.query::<&DummyComponent>();
+    assert_eq!(components.iter(&client_app.world).count(), 0);
+}
+
+#[test]
+fn summary() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HibernationPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn_batch([
+        (
+            Replicated,
+            HibernationRegion(0),
+            Transform::from_xyz(-1.0, 0.0, 2.0),
+        ),
+        (
+            Replicated,
+            HibernationRegion(0),
+            Transform::from_xyz(1.0, 0.0, -2.0),
+        ),
+    ]);
+    server_app
+        .world
+        .resource_mut::<Hibernation>()
+        .hibernate_with_summary(0, vec![1, 2]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let digest = &client_app.world.resource::<RegionDigests>()[&0];
+    assert_eq!(digest.entities_count, 2);
+    assert_eq!(
+        digest.bounds,
+        Some((Vec3::new(-1.0, 0.0, -2.0), Vec3::new(1.0, 0.0, 2.0)))
+    );
+    assert_eq!(digest.summary, [1, 2]);
+}
+
+#[test]
+fn wake_by_anchor() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HibernationPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, HibernationRegion(0), DummyComponent(0)))
+        .id();
+    server_app.world.resource_mut::<Hibernation>().hibernate(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(server_app.world.get::<Hibernated>(server_entity).is_some());
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app.world.spawn((
+        Replicated,
+        HibernationRegion(0),
+        InterestAnchor { client_id },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world.get::<Hibernated>(server_entity).is_none());
+    assert!(!server_app.world.resource::<Hibernation>().is_hibernated(0));
+    assert!(client_app.world.resource::<RegionDigests>().is_empty());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(usize);
@@ -0,0 +1,80 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn dont_replicate() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<OtherComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), OtherComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .run_system_once(move |mut commands: Commands| {
+            commands
+                .entity(server_entity)
+                .dont_replicate::<BoolComponent>();
+        });
+
+    let mut server_entity_mut = server_app.world.entity_mut(server_entity);
+    server_entity_mut.get_mut::<BoolComponent>().unwrap().0 = true;
+    server_entity_mut.get_mut::<OtherComponent>().unwrap().0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (component, other) = client_app
+        .world
+        .query::<(&BoolComponent, &OtherComponent)>()
+        .single(&client_app.world);
+    assert!(!component.0, "ignored component shouldn't be replicated");
+    assert!(other.0, "other components should continue replicating");
+
+    server_app
+        .world
+        .run_system_once(move |mut commands: Commands| {
+            commands
+                .entity(server_entity)
+                .resume_replication::<BoolComponent>();
+        });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(component.0, "current value should be sent after resume");
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct OtherComponent(bool);
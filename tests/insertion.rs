@@ -3,7 +3,11 @@ use std::io::Cursor;
 use bevy::{ecs::entity::MapEntities, prelude::*};
 use bevy_replicon::{
     client::server_entity_map::ServerEntityMap,
-    core::replication_fns::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+    core::replication_fns::{
+        command_fns,
+        ctx::{SerializeCtx, WriteCtx},
+        rule_fns::{self, RuleFns},
+    },
     prelude::*,
     test_app::ServerTestAppExt,
 };
@@ -398,6 +402,150 @@ fn after_removal() {
         .single(&client_app.world);
 }
 
+#[test]
+fn converted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_into::<OriginalComponent, ReplacedComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(OriginalComponent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<ReplacedComponent>, Without<OriginalComponent>)>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<OriginalComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let replaced_components = client_app
+        .world
+        .query_filtered::<(), With<ReplacedComponent>>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(replaced_components, 0);
+}
+
+#[test]
+fn marker_rule() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_with_marker::<RuleMarker, BoolComponent>(RuleFns::new(
+            serialize_inverted,
+            rule_fns::default_deserialize,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), RuleMarker));
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components: Vec<_> = client_app
+        .world
+        .query::<&BoolComponent>()
+        .iter(&client_app.world)
+        .map(|component| component.0)
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        [false, true],
+        "only entity with the marker should use the marker rule"
+    );
+    assert!(client_app
+        .world
+        .query_filtered::<(), With<RuleMarker>>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn per_client() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(
+            RuleFns::new(serialize_inverted_for_client, rule_fns::default_deserialize).per_client(),
+        );
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client_id = client_app1.world.resource::<RepliconClient>().id().unwrap();
+    server_app.insert_resource(InvertedClient(client_id));
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    for (client_app, inverted) in [(&mut client_app1, true), (&mut client_app2, false)] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+
+        let component = client_app
+            .world
+            .query::<&BoolComponent>()
+            .single(&client_app.world);
+        assert_eq!(component.0, inverted);
+    }
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct MappedComponent(Entity);
 
@@ -426,12 +574,27 @@ struct NotReplicatedComponent;
 #[derive(Component)]
 struct ReplaceMarker;
 
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component)]
+struct RuleMarker;
+
+#[derive(Resource)]
+struct InvertedClient(ClientId);
+
 #[derive(Component, Deserialize, Serialize)]
 struct OriginalComponent;
 
 #[derive(Component, Deserialize, Serialize)]
 struct ReplacedComponent;
 
+impl From<OriginalComponent> for ReplacedComponent {
+    fn from(_value: OriginalComponent) -> Self {
+        Self
+    }
+}
+
 /// Deserializes [`OriginalComponent`], but ignores it and inserts [`ReplacedComponent`].
 fn replace(
     ctx: &mut WriteCtx,
@@ -444,3 +607,23 @@ fn replace(
 
     Ok(())
 }
+
+/// Serializes [`BoolComponent`] with the inverted value.
+fn serialize_inverted(
+    _ctx: &SerializeCtx,
+    component: &BoolComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    bincode::serialize_into(cursor, &BoolComponent(!component.0))
+}
+
+/// Serializes [`BoolComponent`] with the inverted value for the client from [`InvertedClient`].
+fn serialize_inverted_for_client(
+    ctx: &SerializeCtx,
+    component: &BoolComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let inverted_client = ctx.get_resource::<InvertedClient>().unwrap();
+    let inverted = ctx.client_id == Some(inverted_client.0);
+    bincode::serialize_into(cursor, &BoolComponent(component.0 != inverted))
+}
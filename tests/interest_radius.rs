@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn expansion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app
+        .add_plugins(InterestRadiusPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+    let mut interest = server_app.world.resource_mut::<InterestRadius>();
+    interest.radius = 40.0;
+    interest.initial_radius = 10.0;
+    interest.expansion_speed = 20.0;
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app.world.spawn((
+        Replicated,
+        Transform::default(),
+        InterestAnchor { client_id },
+    ));
+    server_app.world.spawn((
+        Replicated,
+        DummyComponent,
+        Transform::from_xyz(5.0, 0.0, 0.0),
+    ));
+    server_app.world.spawn((
+        Replicated,
+        DummyComponent,
+        Transform::from_xyz(30.0, 0.0, 0.0),
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let radius = server_app
+        .world
+        .resource::<InterestRadius>()
+        .client_radius(client_id);
+    assert!(radius < 30.0, "radius should expand progressively");
+    let components = client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(components, 1, "only nearby entity should be visible");
+
+    for _ in 0..15 {
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let radius = server_app
+        .world
+        .resource::<InterestRadius>()
+        .client_radius(client_id);
+    assert_eq!(radius, 40.0, "radius should be limited by the maximum");
+    let components = client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(components, 2);
+}
+
+#[test]
+fn no_anchors() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_plugins(InterestRadiusPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, Transform::default()));
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<DummyComponent>, Without<Transform>)>()
+        .single(&client_app.world);
+    let components = client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(
+        components, 1,
+        "client without anchors should see only entities without transform"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
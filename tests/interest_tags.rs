@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn subscription() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_plugins(InterestTagsPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, InterestTags::tag(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app
+            .world
+            .query::<&DummyComponent>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "client without subscription shouldn't see tagged entities"
+    );
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut subscriptions = server_app.world.resource_mut::<InterestSubscriptions>();
+    subscriptions.subscribe(client_id, InterestTags::tag(0) | InterestTags::tag(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+
+    let mut subscriptions = server_app.world.resource_mut::<InterestSubscriptions>();
+    subscriptions.unsubscribe(client_id, InterestTags::tag(0));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn tags_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_plugins(InterestTagsPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut subscriptions = server_app.world.resource_mut::<InterestSubscriptions>();
+    subscriptions.set(client_id, InterestTags::tag(1));
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DummyComponent, InterestTags::tag(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+
+    server_app
+        .world
+        .get_mut::<InterestTags>(server_entity)
+        .unwrap()
+        .insert(InterestTags::tag(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn fast_forward() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.add_plugins(InterpolationDelayPlugin {
+        spike_recovery: SpikeRecovery::FastForward {
+            threshold: Duration::from_millis(250),
+            blend: Duration::from_millis(100),
+        },
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+    client_app.update();
+
+    let mut fast_forwarded = client_app.world.resource_mut::<Events<FastForwarded>>();
+    let event = fast_forwarded
+        .drain()
+        .next()
+        .expect("stall should be detected");
+    assert_eq!(event.stall, Duration::from_secs(1));
+    let delay = client_app.world.resource::<InterpolationDelay>();
+    assert_eq!(delay.fast_forward_blend(), Some(0.0));
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )));
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .resource::<Events<FastForwarded>>()
+        .is_empty());
+    let delay = client_app.world.resource::<InterpolationDelay>();
+    assert_eq!(delay.fast_forward_blend(), Some(0.5));
+
+    client_app.update();
+
+    let delay = client_app.world.resource::<InterpolationDelay>();
+    assert_eq!(
+        delay.fast_forward_blend(),
+        None,
+        "blending should finish after its duration"
+    );
+}
+
+#[test]
+fn replay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.add_plugins(InterpolationDelayPlugin::default());
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .resource::<Events<FastForwarded>>()
+        .is_empty());
+    let delay = client_app.world.resource::<InterpolationDelay>();
+    assert_eq!(delay.fast_forward_blend(), None);
+}
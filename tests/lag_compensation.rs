@@ -0,0 +1,117 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+
+#[test]
+fn rewind() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        LagCompensationPlugin,
+    ))
+    .replicate_with_history::<Transform>(31);
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let target = app
+        .world
+        .spawn((
+            Replicated,
+            HitCollider { radius: 1.0 },
+            Transform::from_xyz(10.0, 0.0, 0.0),
+        ))
+        .id();
+
+    app.update();
+    let shot_tick = **app.world.resource::<ServerTick>();
+    app.update();
+
+    // Target moves away after the projectile should reach it.
+    app.world
+        .get_mut::<Transform>(target)
+        .unwrap()
+        .translation
+        .y = 100.0;
+    app.update();
+    app.update();
+
+    for tick in [shot_tick, **app.world.resource::<ServerTick>()] {
+        app.world.send_event(LaunchProjectile {
+            shooter: None,
+            origin: Vec3::ZERO,
+            velocity: Vec3::X * 10.0,
+            radius: 0.1,
+            lifetime: 10,
+            tick,
+        });
+    }
+    app.update();
+
+    let hits: Vec<_> = app
+        .world
+        .resource_mut::<Events<ProjectileHit>>()
+        .drain()
+        .collect();
+    assert_eq!(hits.len(), 1, "only the rewound projectile should hit");
+    assert_eq!(hits[0].target, target);
+    assert_eq!(hits[0].position, Vec3::new(10.0, 0.0, 0.0));
+
+    let mut projectiles = app.world.query::<&Projectile>();
+    assert_eq!(projectiles.iter(&app.world).count(), 1);
+}
+
+#[test]
+fn advance() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        LagCompensationPlugin,
+    ))
+    .replicate_with_history::<Transform>(31);
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let target = app
+        .world
+        .spawn((
+            Replicated,
+            HitCollider { radius: 1.0 },
+            Transform::from_xyz(25.0, 0.0, 0.0),
+        ))
+        .id();
+
+    app.update();
+
+    let tick = **app.world.resource::<ServerTick>();
+    app.world.send_event(LaunchProjectile {
+        shooter: None,
+        origin: Vec3::ZERO,
+        velocity: Vec3::X * 10.0,
+        radius: 0.1,
+        lifetime: 10,
+        tick,
+    });
+
+    app.update();
+    app.update();
+    app.update();
+
+    let mut hit_events = app.world.resource_mut::<Events<ProjectileHit>>();
+    let hit = hit_events
+        .drain()
+        .next()
+        .expect("projectile should reach the target");
+    assert_eq!(hit.target, target);
+
+    let mut projectiles = app.world.query::<&Projectile>();
+    assert_eq!(
+        projectiles.iter(&app.world).count(),
+        0,
+        "projectile should be despawned after the hit"
+    );
+}
@@ -0,0 +1,166 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn get_and_set() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            LiveTweakPlugin,
+        ))
+        .register_type::<DummyComponent>()
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<TweakPermissions>()
+        .insert(client_id);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<DummyComponent>>()
+        .single(&client_app.world);
+
+    let registry = client_app.world.resource::<AppTypeRegistry>().clone();
+    client_app.world.send_event(TweakRequest::set(
+        0,
+        client_entity,
+        &DummyComponent(1),
+        &registry.read(),
+    ));
+    client_app
+        .world
+        .send_event(TweakRequest::get::<DummyComponent>(1, client_entity));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_component = server_app
+        .world
+        .get::<DummyComponent>(server_entity)
+        .unwrap();
+    assert_eq!(server_component.0, 1);
+
+    let client_component = client_app
+        .world
+        .get::<DummyComponent>(client_entity)
+        .unwrap();
+    assert_eq!(client_component.0, 1, "change should be replicated");
+
+    let mut responses = client_app.world.resource_mut::<Events<TweakResponse>>();
+    let responses: Vec<_> = responses.drain().collect();
+    assert_eq!(responses.len(), 2);
+    for (id, response) in responses.iter().enumerate() {
+        assert_eq!(response.id as usize, id);
+        let component = response
+            .component::<DummyComponent>(&registry.read())
+            .expect("response should contain the component");
+        assert_eq!(component.0, 1);
+    }
+}
+
+#[test]
+fn without_permission() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            LiveTweakPlugin,
+        ))
+        .register_type::<DummyComponent>()
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<DummyComponent>>()
+        .single(&client_app.world);
+
+    let registry = client_app.world.resource::<AppTypeRegistry>().clone();
+    client_app.world.send_event(TweakRequest::set(
+        0,
+        client_entity,
+        &DummyComponent(1),
+        &registry.read(),
+    ));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_component = server_app
+        .world
+        .get::<DummyComponent>(server_entity)
+        .unwrap();
+    assert_eq!(server_component.0, 0);
+
+    let mut responses = client_app.world.resource_mut::<Events<TweakResponse>>();
+    let response = responses
+        .drain()
+        .next()
+        .expect("client should receive a response");
+    assert_eq!(response.result, Err(TweakError::PermissionDenied));
+}
+
+#[test]
+fn not_replicated_component() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, LiveTweakPlugin))
+        .register_type::<DummyComponent>();
+
+    let entity = app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    app.world
+        .send_event(TweakRequest::get::<DummyComponent>(0, entity));
+
+    app.update();
+    app.update();
+
+    let mut responses = app.world.resource_mut::<Events<TweakResponse>>();
+    let response = responses
+        .drain()
+        .next()
+        .expect("local request should be answered");
+    assert_eq!(response.result, Err(TweakError::NotReplicated));
+}
+
+#[derive(Clone, Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct DummyComponent(usize);
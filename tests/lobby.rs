@@ -0,0 +1,136 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    gameplay::lobby::{
+        Lobby, LobbyEvent, LobbyPlugin, LobbyRequest, LobbySlot, SlotReady, SlotTeam,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn join_and_leave() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            LobbyPlugin {
+                max_slots: 2,
+                teams: 2,
+            },
+        ));
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+    }
+
+    let lobby = server_app.world.resource::<Lobby>();
+    assert!(lobby.is_full());
+
+    let mut teams: Vec<_> = client_app1
+        .world
+        .query::<(&LobbySlot, &SlotTeam)>()
+        .iter(&client_app1.world)
+        .map(|(slot, team)| (slot.index(), **team))
+        .collect();
+    teams.sort_unstable();
+    assert_eq!(teams, [(0, 0), (1, 1)], "clients should be balanced");
+
+    server_app.disconnect_client(&mut client_app1);
+
+    let lobby = server_app.world.resource::<Lobby>();
+    assert!(!lobby.is_full());
+    assert!(lobby.slot_entity(0).is_none());
+
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<LobbyEvent>>()
+        .drain()
+        .collect();
+    assert!(matches!(
+        events.last(),
+        Some(LobbyEvent::Left { slot: 0, .. })
+    ));
+}
+
+#[test]
+fn rejection() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            LobbyPlugin {
+                max_slots: 1,
+                teams: 1,
+            },
+        ));
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client_id = client_app2.world.resource::<RepliconClient>().id().unwrap();
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<LobbyEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events.last(), Some(&LobbyEvent::Rejected { client_id }));
+}
+
+#[test]
+fn requests() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            LobbyPlugin::default(),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(LobbyRequest::Team(1));
+    client_app.world.send_event(LobbyRequest::Team(2)); // Invalid.
+    client_app.world.send_event(LobbyRequest::Ready(true));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (team, ready) = client_app
+        .world
+        .query::<(&SlotTeam, &SlotReady)>()
+        .single(&client_app.world);
+    assert_eq!(**team, 1);
+    assert!(**ready);
+
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<LobbyEvent>>()
+        .drain()
+        .skip(1) // Join.
+        .collect();
+    assert_eq!(events.len(), 2);
+}
@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*, server::connected_clients::ConnectedClients, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn replication() {
+    let mut host_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut host_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MatchHostPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    let mut match_app = App::new();
+    match_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MatchHostPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    match_app.world.spawn((Replicated, DummyComponent));
+
+    let mut host = host_app.world.resource_mut::<MatchHost>();
+    let match_id = host.add_match(match_app);
+
+    host_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    host_app
+        .world
+        .resource_mut::<MatchHost>()
+        .move_client(client_id, Some(match_id));
+
+    move_client(&mut host_app, &mut client_app);
+
+    let host = host_app.world.resource::<MatchHost>();
+    assert_eq!(
+        host.client_match(client_id),
+        None,
+        "client should be assigned only after reset"
+    );
+
+    host_app.update();
+    host_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+
+    let host = host_app.world.resource::<MatchHost>();
+    assert_eq!(host.client_match(client_id), Some(match_id));
+    let match_world = host.world(match_id).unwrap();
+    assert!(match_world
+        .resource::<ConnectedClients>()
+        .get_client(client_id)
+        .is_some());
+    assert!(
+        host_app
+            .world
+            .resource::<ConnectedClients>()
+            .get_client(client_id)
+            .is_some(),
+        "host should still track the connection"
+    );
+
+    let mut moved_events = host_app.world.resource_mut::<Events<MatchMoved>>();
+    assert_eq!(
+        moved_events.drain().collect::<Vec<_>>(),
+        [MatchMoved {
+            client_id,
+            from: None,
+            to: Some(match_id),
+        }]
+    );
+}
+
+#[test]
+fn moving() {
+    let mut host_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut host_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MatchHostPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    let mut host = host_app.world.resource_mut::<MatchHost>();
+    let [first_id, second_id] = [(); 2].map(|_| {
+        let mut match_app = App::new();
+        match_app
+            .add_plugins((
+                MinimalPlugins,
+                RepliconPlugins.set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }),
+                MatchHostPlugin,
+            ))
+            .replicate::<DummyComponent>();
+        match_app.world.spawn((Replicated, DummyComponent));
+        host.add_match(match_app)
+    });
+
+    host_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    let mut host = host_app.world.resource_mut::<MatchHost>();
+    host.move_client(client_id, Some(first_id));
+
+    move_client(&mut host_app, &mut client_app);
+    host_app.update();
+    host_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut host = host_app.world.resource_mut::<MatchHost>();
+    host.move_client(client_id, Some(second_id));
+
+    move_client(&mut host_app, &mut client_app);
+    assert!(
+        client_app
+            .world
+            .query::<&DummyComponent>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "entities of the previous match should be despawned"
+    );
+
+    host_app.update();
+    host_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+
+    let mut moved_events = host_app.world.resource_mut::<Events<MatchMoved>>();
+    assert_eq!(
+        moved_events.drain().last(),
+        Some(MatchMoved {
+            client_id,
+            from: Some(first_id),
+            to: Some(second_id),
+        })
+    );
+
+    let host = host_app.world.resource::<MatchHost>();
+    assert_eq!(host.client_match(client_id), Some(second_id));
+    for (match_id, connected) in [(first_id, false), (second_id, true)] {
+        let match_world = host.world(match_id).unwrap();
+        assert_eq!(
+            match_world
+                .resource::<ConnectedClients>()
+                .get_client(client_id)
+                .is_some(),
+            connected
+        );
+    }
+
+    host_app.disconnect_client(&mut client_app);
+
+    let host = host_app.world.resource::<MatchHost>();
+    assert_eq!(host.client_match(client_id), None);
+    let match_world = host.world(second_id).unwrap();
+    assert!(match_world
+        .resource::<ConnectedClients>()
+        .get_client(client_id)
+        .is_none());
+}
+
+/// Applies a requested move and exchanges the reset with the client.
+fn move_client(host_app: &mut App, client_app: &mut App) {
+    host_app.update();
+    host_app.exchange_with_client(client_app);
+    client_app.update();
+    host_app.exchange_with_client(client_app);
+    host_app.update();
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
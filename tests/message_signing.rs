@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn signed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MessageSigningPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ServerSigningKeys>()
+        .insert(client_id, KEY);
+    **client_app.world.resource_mut::<ClientSigningKey>() = Some(KEY);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn invalid_signature() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MessageSigningPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ServerSigningKeys>()
+        .insert(client_id, [0; 32]);
+    **client_app.world.resource_mut::<ClientSigningKey>() = Some(KEY);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn unsigned() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MessageSigningPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    **client_app.world.resource_mut::<ClientSigningKey>() = Some(KEY);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn key_removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, MessageSigningPlugin));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ServerSigningKeys>()
+        .insert(client_id, KEY);
+    **client_app.world.resource_mut::<ClientSigningKey>() = Some(KEY);
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert!(server_app.world.resource::<ServerSigningKeys>().is_empty());
+    assert!(client_app.world.resource::<ClientSigningKey>().is_none());
+}
+
+#[test]
+fn derived() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MessageSigningPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    **server_app.world.resource_mut::<ServerSigningSecret>() = Some(KEY);
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    let client_id = ClientId::new(1);
+    **client_app.world.resource_mut::<ClientSigningSecret>() = Some(client_secret(&KEY, client_id));
+
+    server_app.connect_client(&mut client_app);
+    assert_eq!(
+        client_app.world.resource::<RepliconClient>().id(),
+        Some(client_id)
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_key = server_app.world.resource::<ServerSigningKeys>()[&client_id];
+    assert_eq!(
+        **client_app.world.resource::<ClientSigningKey>(),
+        Some(server_key)
+    );
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn wrong_secret() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MessageSigningPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    **server_app.world.resource_mut::<ServerSigningSecret>() = Some(KEY);
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    **client_app.world.resource_mut::<ClientSigningSecret>() = Some([0; 32]);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .query::<&DummyComponent>()
+        .iter(&client_app.world)
+        .next()
+        .is_none());
+}
+
+const KEY: [u8; 32] = [1; 32];
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
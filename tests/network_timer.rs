@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::confirmed::Confirmed, prelude::*, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn countdown() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyTimer>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    server_app
+        .world
+        .spawn((Replicated, DummyTimer(NetworkTimer::new(tick, 10))));
+
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    let server_timer = server_app
+        .world
+        .query::<&DummyTimer>()
+        .single(&server_app.world);
+    let (client_timer, confirmed) = client_app
+        .world
+        .query::<(&DummyTimer, &Confirmed)>()
+        .single(&client_app.world);
+
+    assert_eq!(
+        confirmed.last_tick(),
+        tick + 1,
+        "timer shouldn't be sent again"
+    );
+    assert_eq!(server_timer.remaining(server_tick), 7);
+    assert_eq!(
+        client_timer.remaining(server_tick),
+        server_timer.remaining(server_tick)
+    );
+}
+
+#[derive(Component, Deref, Deserialize, Serialize)]
+struct DummyTimer(NetworkTimer);
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_replicon::{
-    core::replicon_channels::ReplicationChannel, prelude::*, server::server_tick::ServerTick,
-    test_app::ServerTestAppExt,
+    core::replicon_channels::ReplicationChannel,
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
 };
 use serde::{Deserialize, Serialize};
 
@@ -204,6 +208,122 @@ fn server_inactive() {
     assert_eq!(app.world.resource::<ServerTick>().get(), 0);
 }
 
+#[test]
+fn runtime_settings() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    app.update();
+    assert_eq!(app.world.resource::<ServerTick>().get(), 1);
+
+    app.world.resource_mut::<ServerSettings>().tick_policy = TickPolicy::Manual;
+
+    app.update();
+    assert_eq!(
+        app.world.resource::<ServerTick>().get(),
+        1,
+        "tick shouldn't be incremented after switching to the manual policy"
+    );
+
+    app.world.resource_mut::<ServerSettings>().tick_policy = TickPolicy::EveryFrame;
+
+    app.update();
+    assert_eq!(app.world.resource::<ServerTick>().get(), 2);
+}
+
+#[test]
+fn stall_policy() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::MaxTickRate(10),
+            ..Default::default()
+        }),
+    ))
+    .use_simulated_time();
+
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let stall = Duration::from_secs(1);
+    app.advance_time(stall);
+    assert_eq!(
+        app.world.resource::<ServerTick>().get(),
+        1,
+        "missed ticks should be dropped by default"
+    );
+
+    app.world.resource_mut::<ServerSettings>().stall_policy = StallPolicy::CatchUp { max_ticks: 4 };
+
+    app.advance_time(stall);
+    assert_eq!(app.world.resource::<ServerTick>().get(), 5);
+
+    app.advance_time(Duration::ZERO);
+    assert_eq!(app.world.resource::<ServerTick>().get(), 9);
+
+    app.advance_time(Duration::ZERO);
+    assert_eq!(app.world.resource::<ServerTick>().get(), 11);
+
+    app.advance_time(Duration::ZERO);
+    assert_eq!(
+        app.world.resource::<ServerTick>().get(),
+        11,
+        "backlog should be exhausted"
+    );
+
+    app.world.resource_mut::<ServerSettings>().stall_policy = StallPolicy::Jump;
+
+    app.advance_time(stall);
+    assert_eq!(app.world.resource::<ServerTick>().get(), 21);
+
+    let mut jumped_events = app.world.resource_mut::<Events<TickJumped>>();
+    let event = jumped_events
+        .drain()
+        .next()
+        .expect("jump should emit an event");
+    assert_eq!(event.from.get(), 11);
+    assert_eq!(event.to.get(), 21);
+}
+
+#[test]
+fn tick_jump_notification() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::MaxTickRate(10),
+                stall_policy: StallPolicy::Jump,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.advance_time(Duration::from_secs(1));
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut jumped_events = client_app.world.resource_mut::<Events<TickJumped>>();
+    let event = jumped_events
+        .drain()
+        .next()
+        .expect("client should receive the jump");
+    assert_eq!(event.from.get(), 0);
+    assert_eq!(event.to.get(), 10);
+}
+
 #[test]
 fn diagnostics() {
     let mut server_app = App::new();
@@ -213,6 +333,7 @@ fn diagnostics() {
             MinimalPlugins,
             RepliconPlugins.set(ServerPlugin {
                 tick_policy: TickPolicy::EveryFrame,
+                send_time: true,
                 ..Default::default()
             }),
         ))
@@ -260,8 +381,175 @@ fn diagnostics() {
     assert_eq!(stats.mappings, 1);
     assert_eq!(stats.despawns, 1);
     assert_eq!(stats.packets, 2);
-    assert_eq!(stats.bytes, 33);
+    assert_eq!(stats.bytes, 27);
+
+    let latency = client_app.world.resource::<ClientLatency>();
+    assert_eq!(latency.samples(), 1);
+}
+
+#[test]
+fn drop_metrics() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app
+        .add_plugins(ClientDiagnosticsPlugin)
+        .use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Generate two updates and deliver them in one frame, the older one will be outdated.
+    for _ in 0..2 {
+        server_app
+            .world
+            .get_mut::<DummyComponent>(server_entity)
+            .unwrap()
+            .set_changed();
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let stats = client_app.world.resource::<ClientStats>();
+    assert_eq!(stats.entity_updates, 2);
+    assert_eq!(stats.outdated_updates, 1);
+    assert_eq!(stats.duplicate_updates, 0);
+
+    // Deliver the same update twice.
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+    server_app.update();
+
+    let messages: Vec<_> = server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .collect();
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in messages {
+        client.insert_received(channel_id, message.clone());
+        client.insert_received(channel_id, message);
+    }
+    client_app.update();
+
+    let stats = client_app.world.resource::<ClientStats>();
+    assert_eq!(stats.entity_updates, 4);
+    assert_eq!(stats.duplicate_updates, 1);
+    assert_eq!(stats.drop_rate(), 0.5);
+
+    **client_app.world.resource_mut::<DropRateThreshold>() = 0.4;
+    client_app.advance_time(Duration::from_secs(1));
+
+    let mut drop_events = client_app.world.resource_mut::<Events<DropRateExceeded>>();
+    assert_eq!(
+        drop_events.drain().collect::<Vec<_>>(),
+        [DropRateExceeded {
+            rate: 0.5,
+            outdated: 1,
+            duplicates: 1,
+        }]
+    );
+}
+
+#[test]
+fn server_diagnostics() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_plugins(ServerDiagnosticsPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+
+    let component_id = server_app.world.component_id::<DummyComponent>().unwrap();
+    let stats = server_app.world.resource::<ComponentSizeStats>();
+    let histogram = stats.get(&component_id).unwrap();
+    assert_eq!(histogram.count(), 1);
+    assert_eq!(histogram.min(), 1);
+    assert_eq!(histogram.p99(), 1);
+}
+
+#[test]
+fn input_latency() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_systems(Update, spawn_on_event);
+
+    server_app.connect_client(&mut client_app);
+
+    let latency = server_app
+        .measure_input_latency(
+            &mut client_app,
+            5,
+            |client_app| {
+                client_app.world.send_event(DummyEvent);
+            },
+            |client_app| {
+                client_app
+                    .world
+                    .query::<&DummyComponent>()
+                    .iter(&client_app.world)
+                    .len()
+                    != 0
+            },
+        )
+        .expect("spawn should be replicated");
+    assert_eq!(latency.cycles, 1);
+
+    let latency = server_app.measure_input_latency(&mut client_app, 5, |_| (), |_| false);
+    assert!(latency.is_none(), "unapplied input shouldn't be measured");
+}
+
+fn spawn_on_event(mut commands: Commands, mut dummy_events: EventReader<FromClient<DummyEvent>>) {
+    for _ in dummy_events.read() {
+        commands.spawn((Replicated, DummyComponent));
+    }
 }
 
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn insertion_and_removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            PackedFlagsPlugin,
+        ))
+        .replicate_flag::<FlagA>()
+        .replicate_flag::<FlagB>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, FlagA, FlagB)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let flags = server_app.world.get::<PackedFlags>(server_entity).unwrap();
+    assert_eq!(flags.bits(), 0b11);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, (With<FlagA>, With<FlagB>)>()
+        .single(&client_app.world);
+
+    server_app.world.entity_mut(server_entity).remove::<FlagA>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world.entity(client_entity);
+    assert!(!client_entity.contains::<FlagA>());
+    assert!(client_entity.contains::<FlagB>());
+    assert_eq!(client_entity.get::<PackedFlags>().unwrap().bits(), 0b10);
+}
+
+#[test]
+fn no_flags() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        PackedFlagsPlugin,
+    ))
+    .replicate_flag::<FlagA>();
+
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let entity = app.world.spawn(Replicated).id();
+
+    app.update();
+
+    assert!(!app.world.entity(entity).contains::<PackedFlags>());
+}
+
+#[test]
+#[should_panic]
+fn duplicate_flag() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, PackedFlagsPlugin))
+        .replicate_flag::<FlagA>()
+        .replicate_flag::<FlagA>();
+}
+
+#[derive(Component, Default)]
+struct FlagA;
+
+#[derive(Component, Default)]
+struct FlagB;
@@ -0,0 +1,62 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_replicon::{core::ClientId, prelude::*};
+
+#[test]
+fn cleared_after_update() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .init_resource::<PendingClients>()
+        .track_pending_from_client::<DummyComponent>()
+        .add_systems(Update, record_pending);
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = server_app.world.spawn(DummyComponent(0)).id();
+    let client_id = ClientId::new(1);
+    server_app
+        .world
+        .run_system_once(move |mut commands: ServerCommands| {
+            commands.apply_from_client(entity, client_id, DummyComponent(1));
+        });
+
+    server_app.update();
+
+    assert_eq!(server_app.world.get::<DummyComponent>(entity).unwrap().0, 1);
+    assert_eq!(
+        server_app.world.resource::<PendingClients>().0,
+        [client_id],
+        "value should be pending during the update"
+    );
+    assert!(
+        !server_app
+            .world
+            .entity(entity)
+            .contains::<PendingFromClient<DummyComponent>>(),
+        "marker should be removed after the update"
+    );
+}
+
+fn record_pending(
+    mut pending_clients: ResMut<PendingClients>,
+    pending: Query<&PendingFromClient<DummyComponent>>,
+) {
+    pending_clients
+        .0
+        .extend(pending.iter().map(|pending| pending.client_id()));
+}
+
+#[derive(Default, Resource)]
+struct PendingClients(Vec<ClientId>);
+
+#[derive(Component)]
+struct DummyComponent(u32);
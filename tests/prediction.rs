@@ -0,0 +1,204 @@
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn correct_prediction() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app
+        .add_plugins(PredictionPlugin)
+        .predict::<DummyComponent>()
+        .add_systems(PreUpdate, mark_predicted.after(ClientSet::Receive))
+        .add_systems(PredictionSchedule, increment);
+
+    server_app.connect_client(&mut client_app);
+
+    let timestep = client_app.world.resource::<Time<Fixed>>().timestep();
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component, history) = client_app
+        .world
+        .query::<(&DummyComponent, &PredictionHistory<DummyComponent>)>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 2);
+    assert_eq!(history.iter().count(), 2);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 3, "prediction should be kept");
+    assert!(client_app
+        .world
+        .resource::<Events<Mispredicted>>()
+        .is_empty());
+}
+
+#[test]
+fn misprediction() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app
+        .add_plugins(PredictionPlugin)
+        .predict::<DummyComponent>()
+        .add_systems(PreUpdate, mark_predicted.after(ClientSet::Receive))
+        .add_systems(PredictionSchedule, increment);
+
+    server_app.connect_client(&mut client_app);
+
+    let timestep = client_app.world.resource::<Time<Fixed>>().timestep();
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component, history) = client_app
+        .world
+        .query::<(&DummyComponent, &PredictionHistory<DummyComponent>)>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 12, "state should be re-simulated");
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    assert_eq!(history.get(tick).map(|component| component.0), Some(10));
+
+    let mut mispredicted = client_app.world.resource_mut::<Events<Mispredicted>>();
+    let event = mispredicted
+        .drain()
+        .next()
+        .expect("misprediction should be detected");
+    assert_eq!(event.tick, tick);
+}
+
+#[test]
+fn out_of_rollback_scope() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app
+        .add_plugins((PredictionPlugin, RollbackScopePlugin))
+        .predict::<DummyComponent>()
+        .add_systems(PreUpdate, mark_predicted.after(ClientSet::Receive))
+        .add_systems(PredictionSchedule, increment);
+
+    server_app.connect_client(&mut client_app);
+
+    let timestep = client_app.world.resource::<Time<Fixed>>().timestep();
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component, history) = client_app
+        .world
+        .query::<(&DummyComponent, &PredictionHistory<DummyComponent>)>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 11,
+        "received value should be written without re-simulation"
+    );
+    assert_eq!(
+        history.iter().count(),
+        1,
+        "only the received value should be stored"
+    );
+    assert!(client_app
+        .world
+        .resource::<Events<Mispredicted>>()
+        .is_empty());
+}
+
+fn mark_predicted(
+    mut commands: Commands,
+    components: Query<Entity, (Added<DummyComponent>, Without<Predicted>)>,
+) {
+    for entity in &components {
+        commands.entity(entity).insert(Predicted);
+    }
+}
+
+fn increment(mut components: Query<&mut DummyComponent, With<Predicted>>) {
+    for mut component in &mut components {
+        component.0 += 1;
+    }
+}
+
+#[derive(Clone, Component, Deserialize, PartialEq, Serialize)]
+struct DummyComponent(usize);
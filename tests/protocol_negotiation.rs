@@ -0,0 +1,236 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn same_registrations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolNegotiationPlugin,
+        ))
+        .replicate::<ComponentA>()
+        .replicate::<ComponentB>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    assert!(server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id)
+        .is_replication_paused());
+
+    server_app.world.spawn((Replicated, ComponentA, ComponentB));
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id)
+        .is_replication_paused());
+    assert!(server_app
+        .world
+        .resource::<Events<ProtocolDowngraded>>()
+        .is_empty());
+
+    client_app
+        .world
+        .query_filtered::<(), (With<ComponentA>, With<ComponentB>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn missing_registration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolNegotiationPlugin,
+        ));
+    }
+
+    server_app
+        .replicate::<ComponentA>()
+        .replicate::<BoolComponent>();
+    client_app.replicate::<BoolComponent>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, ComponentA, BoolComponent(false)))
+        .id();
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut downgraded_events = server_app
+        .world
+        .resource_mut::<Events<ProtocolDowngraded>>();
+    let event = downgraded_events
+        .drain()
+        .next()
+        .expect("server should emit downgrade for the client");
+    assert_eq!(event.missing, [std::any::type_name::<ComponentA>()]);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .get::<BoolComponent>(client_entity)
+        .unwrap();
+    assert!(component.0, "mapped component should be updated");
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<BoolComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!client_app
+        .world
+        .entity(client_entity)
+        .contains::<BoolComponent>());
+}
+
+#[test]
+fn mismatch_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolNegotiationPlugin,
+        ))
+        .insert_resource(MismatchPolicy::Disconnect);
+    }
+
+    server_app
+        .replicate::<ComponentA>()
+        .replicate::<BoolComponent>();
+    client_app
+        .replicate::<BoolComponent>()
+        .replicate::<ComponentB>();
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut reason_events = client_app
+        .world
+        .resource_mut::<Events<DisconnectReason<ProtocolMismatch>>>();
+    let DisconnectReason(mismatch) = reason_events
+        .drain()
+        .next()
+        .expect("client should receive mismatch reason");
+    assert_eq!(mismatch.missing, [std::any::type_name::<ComponentA>()]);
+    assert_eq!(mismatch.unknown, [std::any::type_name::<ComponentB>()]);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [client_id]);
+}
+
+#[test]
+fn wire_names() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolNegotiationPlugin,
+        ))
+        .insert_resource(MismatchPolicy::Disconnect);
+    }
+
+    server_app
+        .replicate_as::<GenericComponent<ComponentA>>("generic_a")
+        .replicate_as::<GenericComponent<ComponentB>>("generic_b");
+    client_app
+        .replicate_as::<GenericComponent<ComponentB>>("generic_b")
+        .replicate_as::<GenericComponent<ComponentA>>("generic_a");
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((
+        Replicated,
+        GenericComponent(ComponentA, 1),
+        GenericComponent(ComponentB, 2),
+    ));
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app
+        .world
+        .resource::<Events<DisconnectReason<ProtocolMismatch>>>()
+        .is_empty());
+
+    let (component_a, component_b) = client_app
+        .world
+        .query::<(&GenericComponent<ComponentA>, &GenericComponent<ComponentB>)>()
+        .single(&client_app.world);
+    assert_eq!(component_a.1, 1);
+    assert_eq!(component_b.1, 2);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentA;
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentB;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct GenericComponent<T>(T, u8);
@@ -0,0 +1,175 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn reload() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolReloadPlugin,
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(&client_app.world);
+
+    // Register a component that the client doesn't have before the existing one.
+    server_app.reload_protocol(|app| {
+        app.replicate::<DummyComponent>()
+            .replicate::<BoolComponent>();
+    });
+    server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world.get_entity(client_entity).is_none());
+    let reloaded_events = client_app.world.resource::<Events<ProtocolReloaded>>();
+    assert_eq!(reloaded_events.len(), 1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client = client_app.world.resource::<RepliconClient>();
+    assert!(client.is_connected());
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(component.0, "entity should be received again");
+}
+
+#[test]
+fn removed_rule() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolReloadPlugin,
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.reload_protocol(|_| ());
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    client_app
+        .world
+        .query::<&Replicated>()
+        .single(&client_app.world);
+    assert!(
+        client_app
+            .world
+            .query::<&BoolComponent>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "component without a rule shouldn't be replicated"
+    );
+}
+
+#[test]
+fn consecutive_reloads() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ProtocolReloadPlugin,
+        ));
+        register_history(app);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.reload_protocol(register_history);
+    server_app.reload_protocol(register_history);
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let history = server_app
+        .world
+        .get::<ComponentHistory<BoolComponent>>(server_entity)
+        .unwrap();
+    assert_eq!(history.len(), 2, "systems should be added only once");
+
+    let reloaded_events = client_app.world.resource::<Events<ProtocolReloaded>>();
+    assert_eq!(reloaded_events.len(), 1);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<BoolComponent>)>()
+        .single(&client_app.world);
+}
+
+fn register_history(app: &mut App) {
+    app.replicate_with_history::<BoolComponent>(8);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Clone, Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
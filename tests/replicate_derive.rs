@@ -0,0 +1,292 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::{
+    compact_tag, prelude::*, register_replicated_components, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn registration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+        register_replicated_components!(app, DummyComponent, MappedComponent, CompactComponent);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mapped_entity = server_app.world.spawn(Replicated).id();
+    server_app.world.spawn((
+        Replicated,
+        DummyComponent,
+        MappedComponent(mapped_entity),
+        CompactComponent::Second,
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (mapped_component, &compact_component) = client_app
+        .world
+        .query_filtered::<(&MappedComponent, &CompactComponent), With<DummyComponent>>()
+        .single(&client_app.world);
+    assert_eq!(compact_component, CompactComponent::Second);
+
+    assert!(client_app
+        .world
+        .entity(mapped_component.0)
+        .contains::<Replicated>());
+}
+
+#[test]
+fn once() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+        register_replicated_components!(app, OnceComponent);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, OnceComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<OnceComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&OnceComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 0, "changes shouldn't be sent");
+}
+
+#[test]
+fn field_attributes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+        register_replicated_components!(app, PartialComponent, MappedPartialComponent);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mapped_entity = server_app.world.spawn(Replicated).id();
+    let server_entity = server_app
+        .world
+        .spawn((
+            Replicated,
+            PartialComponent {
+                regular: 1,
+                once: 1,
+                skipped: 1,
+            },
+            MappedPartialComponent(mapped_entity, 1),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (&component, mapped_component) = client_app
+        .world
+        .query::<(&PartialComponent, &MappedPartialComponent)>()
+        .single(&client_app.world);
+    assert_eq!(
+        component,
+        PartialComponent {
+            regular: 1,
+            once: 1,
+            skipped: 0,
+        }
+    );
+    assert_eq!(mapped_component.1, 0);
+    assert!(client_app
+        .world
+        .entity(mapped_component.0)
+        .contains::<Replicated>());
+
+    *server_app
+        .world
+        .get_mut::<PartialComponent>(server_entity)
+        .unwrap() = PartialComponent {
+        regular: 2,
+        once: 2,
+        skipped: 2,
+    };
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let &component = client_app
+        .world
+        .query::<&PartialComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component,
+        PartialComponent {
+            regular: 2,
+            once: 1,
+            skipped: 0,
+        },
+        "only regular fields should be updated"
+    );
+}
+
+#[test]
+fn once_field_size() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+        register_replicated_components!(app, NamedComponent);
+    }
+    server_app.add_plugins(SendLogPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((
+            Replicated,
+            NamedComponent {
+                value: 0,
+                name: NAME.into(),
+            },
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let insert_tick = **server_app.world.resource::<ServerTick>();
+
+    server_app
+        .world
+        .get_mut::<NamedComponent>(server_entity)
+        .unwrap()
+        .value = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let change_tick = **server_app.world.resource::<ServerTick>();
+    let send_log = server_app.world.resource::<SendLog>();
+    let insert_bytes = send_log
+        .query(insert_tick..=insert_tick)
+        .next()
+        .unwrap()
+        .bytes;
+    let change_bytes = send_log.query(change_tick..).next().unwrap().bytes;
+    assert!(
+        change_bytes + NAME.len() <= insert_bytes,
+        "once field should be written only on insertion"
+    );
+
+    let component = client_app
+        .world
+        .query::<&NamedComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.value, 1);
+    assert_eq!(component.name, NAME);
+}
+
+const NAME: &str = "Long player name";
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(priority = 5, name = "dummy")]
+struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(mapped)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Clone, Component, Copy, Debug, PartialEq, Replicate)]
+#[replicate(compact)]
+enum CompactComponent {
+    First,
+    Second,
+}
+
+compact_tag!(CompactComponent { First, Second });
+
+#[derive(Component, Deserialize, Serialize, Replicate)]
+#[replicate(once)]
+struct OnceComponent(u8);
+
+#[derive(Clone, Component, Copy, Debug, PartialEq, Replicate)]
+struct PartialComponent {
+    regular: u8,
+    #[replicate(once)]
+    once: u8,
+    #[replicate(skip)]
+    skipped: u8,
+}
+
+#[derive(Component, Replicate)]
+struct NamedComponent {
+    value: u8,
+    #[replicate(once)]
+    name: String,
+}
+
+#[derive(Component, Replicate)]
+#[replicate(mapped)]
+struct MappedPartialComponent(Entity, #[replicate(skip)] u8);
+
+impl MapEntities for MappedPartialComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
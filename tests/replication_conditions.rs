@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn skipped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Health>()
+        .replicate_if::<Health>(|health, _world| health.0 < MAX_HEALTH);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Health(MAX_HEALTH)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut healths = client_app.world.query::<&Health>();
+    assert_eq!(
+        healths.iter(&client_app.world).len(),
+        0,
+        "component shouldn't be sent while the condition fails"
+    );
+    assert_eq!(client_app.world.entities().len(), 1);
+
+    server_app.world.get_mut::<Health>(server_entity).unwrap().0 = 50;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let health = healths.single(&client_app.world);
+    assert_eq!(health.0, 50);
+}
+
+const MAX_HEALTH: u32 = 100;
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
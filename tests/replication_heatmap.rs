@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sampling() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app
+        .add_plugins(ReplicationHeatmapPlugin)
+        .use_simulated_time();
+
+    server_app.connect_client(&mut client_app);
+
+    let busy_entity = server_app
+        .world
+        .spawn((
+            Replicated,
+            DummyComponent(0),
+            GlobalTransform::from_xyz(1.0, 2.0, 3.0),
+        ))
+        .id();
+    let idle_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app
+        .world
+        .resource_mut::<ReplicationHeatmap>()
+        .start(Duration::from_secs(1));
+
+    for _ in 0..3 {
+        server_app
+            .world
+            .get_mut::<DummyComponent>(busy_entity)
+            .unwrap()
+            .0 += 1;
+        server_app.advance_time(Duration::from_millis(100));
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let heatmap = server_app.world.resource::<ReplicationHeatmap>();
+    assert!(heatmap.is_sampling());
+    assert!(!heatmap.is_finished());
+    assert!(heatmap.samples().is_empty());
+
+    server_app.advance_time(Duration::from_secs(1));
+
+    let heatmap = server_app.world.resource::<ReplicationHeatmap>();
+    assert!(heatmap.is_finished());
+    let [busy_sample, idle_sample] = heatmap.samples() else {
+        panic!("both entities should be sampled");
+    };
+    assert_eq!(busy_sample.entity, busy_entity);
+    assert_eq!(busy_sample.position, Some(Vec3::new(1.0, 2.0, 3.0)));
+    assert_eq!(idle_sample.entity, idle_entity);
+    assert_eq!(idle_sample.position, None);
+    assert!(busy_sample.bytes > idle_sample.bytes);
+
+    let mut csv = Vec::new();
+    heatmap.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("entity,x,y,z,bytes"));
+    assert_eq!(
+        lines.next(),
+        Some(&*format!(
+            "{},1,2,3,{}",
+            busy_entity.to_bits(),
+            busy_sample.bytes
+        ))
+    );
+    assert_eq!(
+        lines.next(),
+        Some(&*format!(
+            "{},,,,{}",
+            idle_entity.to_bits(),
+            idle_sample.bytes
+        ))
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u32);
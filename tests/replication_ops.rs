@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplicationOpsPlugin,
+        ))
+        .replicate::<ComponentA>()
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, ComponentA, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    let a_id = client_app.world.component_id::<ComponentA>().unwrap();
+    let bool_id = client_app.world.component_id::<BoolComponent>().unwrap();
+
+    let kinds = drain_kinds(&mut client_app);
+    assert_eq!(
+        kinds,
+        [
+            ReplicationOpKind::Spawn {
+                entity: client_entity
+            },
+            ReplicationOpKind::Insert {
+                entity: client_entity,
+                component_id: a_id,
+            },
+            ReplicationOpKind::Insert {
+                entity: client_entity,
+                component_id: bool_id,
+            },
+        ]
+    );
+
+    server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let kinds = drain_kinds(&mut client_app);
+    assert_eq!(
+        kinds,
+        [ReplicationOpKind::Change {
+            entity: client_entity,
+            component_id: bool_id,
+        }]
+    );
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<ComponentA>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let kinds = drain_kinds(&mut client_app);
+    assert_eq!(
+        kinds,
+        [
+            ReplicationOpKind::Remove {
+                entity: client_entity,
+                component_id: a_id,
+            },
+            ReplicationOpKind::Despawn {
+                entity: client_entity
+            },
+        ]
+    );
+}
+
+fn drain_kinds(app: &mut App) -> Vec<ReplicationOpKind> {
+    app.world
+        .resource_mut::<Events<ReplicationOp>>()
+        .drain()
+        .map(|op| op.kind)
+        .collect()
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentA;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
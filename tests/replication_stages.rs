@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{replication_stages::FilterCtx, replicon_channels::ReplicationChannel},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn entity_filter() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app
+        .insert_resource(AllowUpdates(false))
+        .add_entity_filter(|ctx: &FilterCtx, _| ctx.world.resource::<AllowUpdates>().0);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "filtered change shouldn't be sent");
+
+    server_app.world.resource_mut::<AllowUpdates>().0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0,
+        "skipped change should be sent after the filter passes"
+    );
+}
+
+#[test]
+fn send_receive_stages() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .add_send_stage(|_, _, message| *message = reverse(message))
+        .add_receive_stage(|_, message| {
+            *message = reverse(message);
+            true
+        });
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(component.0);
+}
+
+#[test]
+fn discarding_receive_stage() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.add_receive_stage(|channel, _| channel != ReplicationChannel::Init);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world.entities().is_empty());
+}
+
+fn reverse(message: &Bytes) -> Bytes {
+    message.iter().rev().copied().collect()
+}
+
+#[derive(Resource)]
+struct AllowUpdates(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
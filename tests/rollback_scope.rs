@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+#[test]
+fn radius() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, RollbackScopePlugin))
+        .insert_resource(RollbackScope::new(Some(5.0)));
+
+    app.world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Connected { client_id: None });
+
+    let anchor = app
+        .world
+        .spawn((Replicated, RollbackAnchor, Transform::default()))
+        .id();
+    let nearby = app
+        .world
+        .spawn((Replicated, Transform::from_xyz(3.0, 0.0, 0.0)))
+        .id();
+    let distant = app
+        .world
+        .spawn((Replicated, Transform::from_xyz(10.0, 0.0, 0.0)))
+        .id();
+
+    app.update();
+
+    assert!(app.world.entity(anchor).contains::<InRollbackScope>());
+    assert!(app.world.entity(nearby).contains::<InRollbackScope>());
+    assert!(!app.world.entity(distant).contains::<InRollbackScope>());
+
+    app.world
+        .get_mut::<Transform>(nearby)
+        .unwrap()
+        .translation
+        .x = 6.0;
+
+    app.update();
+
+    assert!(app.world.entity(anchor).contains::<InRollbackScope>());
+    assert!(!app.world.entity(nearby).contains::<InRollbackScope>());
+}
+
+#[test]
+fn anchors_only() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, RollbackScopePlugin));
+
+    app.world
+        .resource_mut::<RepliconClient>()
+        .set_status(RepliconClientStatus::Connected { client_id: None });
+
+    let anchor = app.world.spawn((Replicated, RollbackAnchor)).id();
+    let nearby = app.world.spawn((Replicated, Transform::default())).id();
+
+    app.update();
+
+    assert!(app.world.entity(anchor).contains::<InRollbackScope>());
+    assert!(!app.world.entity(nearby).contains::<InRollbackScope>());
+}
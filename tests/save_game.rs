@@ -0,0 +1,166 @@
+use std::io::Cursor;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication_fns::{
+        ctx::{SerializeCtx, WriteCtx},
+        rule_fns::{self, RuleFns},
+    },
+    prelude::*,
+    server::{save_game, server_tick::ServerTick},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn round_trip() {
+    let mut old_server_app = App::new();
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut old_server_app, &mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_type::<DummyComponent>()
+        .register_type::<DummyResource>()
+        .replicate::<DummyComponent>()
+        .save_resource::<DummyResource>();
+    }
+
+    old_server_app.insert_resource(DummyResource(1));
+    old_server_app.world.spawn((Replicated, DummyComponent(1)));
+    old_server_app.world.spawn(DummyComponent(2));
+    for _ in 0..3 {
+        old_server_app.update();
+    }
+
+    let scene = save_game::capture_save(&old_server_app.world);
+    let saved_tick = **old_server_app.world.resource::<ServerTick>();
+
+    let entities = save_game::load_save(&mut server_app.world, &scene).unwrap();
+    assert_eq!(entities, 1, "only replicated entities should be saved");
+    assert_eq!(server_app.world.resource::<DummyResource>().0, 1);
+    assert_eq!(**server_app.world.resource::<ServerTick>(), saved_tick);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn older_tick() {
+    let mut server_app = App::new();
+    server_app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    let scene = save_game::capture_save(&server_app.world);
+
+    server_app.world.resource_mut::<ServerTick>().increment();
+    let server_tick = **server_app.world.resource::<ServerTick>();
+
+    save_game::load_save(&mut server_app.world, &scene).unwrap();
+    assert_eq!(
+        **server_app.world.resource::<ServerTick>(),
+        server_tick,
+        "tick shouldn't decrease after loading"
+    );
+}
+
+#[test]
+fn binary_save_fns() {
+    let mut old_server_app = App::new();
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut old_server_app, &mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(
+            RuleFns::new(serialize_rounded, deserialize_rounded).with_save(
+                rule_fns::default_serialize::<FloatComponent>,
+                rule_fns::default_deserialize::<FloatComponent>,
+            ),
+        );
+    }
+
+    old_server_app
+        .world
+        .spawn((Replicated, FloatComponent(1.25)));
+    old_server_app.update();
+
+    let save = save_game::capture_binary(&old_server_app.world).unwrap();
+    let saved_tick = **old_server_app.world.resource::<ServerTick>();
+
+    let entities = save_game::load_binary(&mut server_app.world, &save).unwrap();
+    assert_eq!(entities, 1);
+    assert_eq!(**server_app.world.resource::<ServerTick>(), saved_tick);
+
+    let component = server_app
+        .world
+        .query_filtered::<&FloatComponent, With<Replicated>>()
+        .single(&server_app.world);
+    assert_eq!(component.0, 1.25, "save should use lossless functions");
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&FloatComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 1.0,
+        "replication should use rounding functions"
+    );
+}
+
+fn serialize_rounded(
+    _ctx: &SerializeCtx,
+    component: &FloatComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    bincode::serialize_into(cursor, &(component.0.round() as i32))
+}
+
+fn deserialize_rounded(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<FloatComponent> {
+    let value: i32 = bincode::deserialize_from(cursor)?;
+    Ok(FloatComponent(value as f32))
+}
+
+#[derive(Component, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct DummyComponent(u32);
+
+#[derive(Default, Reflect, Resource)]
+#[reflect(Resource)]
+struct DummyResource(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct FloatComponent(f32);
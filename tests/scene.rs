@@ -1,5 +1,13 @@
-use bevy::prelude::*;
-use bevy_replicon::{prelude::*, scene};
+use bevy::{
+    asset::AssetPlugin,
+    prelude::*,
+    scene::{DynamicEntity, ScenePlugin},
+};
+use bevy_replicon::{
+    prelude::*,
+    scene::{self, ReplicatedScene, SceneReplicationPlugin},
+    test_app::ServerTestAppExt,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -87,10 +95,85 @@ fn entity_update() {
     assert_eq!(dyn_entity.components.len(), 2);
 }
 
+#[test]
+fn hot_reload() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<ValueComponent>();
+    }
+    server_app
+        .add_plugins((AssetPlugin::default(), ScenePlugin, SceneReplicationPlugin))
+        .register_type::<ValueComponent>();
+
+    server_app.connect_client(&mut client_app);
+
+    let scene = DynamicScene {
+        resources: Vec::new(),
+        entities: vec![DynamicEntity {
+            entity: Entity::PLACEHOLDER,
+            components: vec![ValueComponent(0).clone_value()],
+        }],
+    };
+    let handle = server_app
+        .world
+        .resource_mut::<Assets<DynamicScene>>()
+        .add(scene);
+    server_app.world.spawn((
+        DynamicSceneBundle {
+            scene: handle.clone(),
+            ..Default::default()
+        },
+        ReplicatedScene,
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world.query::<&ValueComponent>();
+    let component = components.single(&client_app.world);
+    assert_eq!(component.0, 0);
+
+    // Simulate hot reload.
+    let mut scenes = server_app.world.resource_mut::<Assets<DynamicScene>>();
+    let scene = scenes.get_mut(&handle).unwrap();
+    scene.entities[0].components = vec![ValueComponent(1).clone_value()];
+    scene.entities.push(DynamicEntity {
+        entity: Entity::from_raw(1),
+        components: vec![ValueComponent(2).clone_value()],
+    });
+
+    // Modification events are emitted in `PostUpdate`, so the scene will be updated on the next frame.
+    server_app.update();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut values: Vec<_> = components
+        .iter(&client_app.world)
+        .map(|component| component.0)
+        .collect();
+    values.sort_unstable();
+    assert_eq!(values, [1, 2]);
+}
+
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 struct DummyComponent;
 
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct ValueComponent(usize);
+
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 struct OtherReflectedComponent;
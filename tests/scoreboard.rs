@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    gameplay::scoreboard::{Scoreboard, ScoreboardPlugin},
+    prelude::*,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
+};
+
+#[test]
+fn interval() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            ScoreboardPlugin {
+                columns: 2,
+                interval: Duration::from_secs(1),
+            },
+        ))
+        .use_simulated_time();
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let scoreboard = client_app.world.resource::<Scoreboard>();
+    assert_eq!(
+        scoreboard.get(client_id),
+        Some(&[0, 0][..]),
+        "new client should receive the table immediately"
+    );
+
+    let mut scoreboard = server_app.world.resource_mut::<Scoreboard>();
+    scoreboard.add(client_id, 1, 5);
+    scoreboard.set(ClientId::SERVER, 0, 3);
+
+    server_app.advance_time(Duration::from_millis(500));
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let scoreboard = client_app.world.resource::<Scoreboard>();
+    assert_eq!(
+        scoreboard.len(),
+        1,
+        "table shouldn't be sent before the interval"
+    );
+
+    server_app.advance_time(Duration::from_millis(500));
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let scoreboard = client_app.world.resource::<Scoreboard>();
+    assert_eq!(scoreboard.get(client_id), Some(&[0, 5][..]));
+    assert_eq!(scoreboard.get(ClientId::SERVER), Some(&[3, 0][..]));
+    let order: Vec<_> = scoreboard
+        .sorted_by(0)
+        .into_iter()
+        .map(|(client_id, _)| client_id)
+        .collect();
+    assert_eq!(order, [ClientId::SERVER, client_id]);
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ScoreboardPlugin::default()));
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(client_app.world.resource::<Scoreboard>().len(), 1);
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert!(server_app.world.resource::<Scoreboard>().is_empty());
+    assert!(client_app.world.resource::<Scoreboard>().is_empty());
+}
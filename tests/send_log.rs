@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn records() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.add_plugins(SendLogPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let insert_tick = **server_app.world.resource::<ServerTick>();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+
+    let change_tick = **server_app.world.resource::<ServerTick>();
+    let component_id = server_app.world.component_id::<BoolComponent>().unwrap();
+    let send_log = server_app.world.resource::<SendLog>();
+    let records: Vec<_> = send_log.query(..).copied().collect();
+    assert_eq!(records.len(), 2);
+    for (record, tick) in records.iter().zip([insert_tick, change_tick]) {
+        assert_eq!(record.tick, tick);
+        assert_eq!(record.entity, server_entity);
+        assert_eq!(record.component_id, component_id);
+        assert_eq!(record.clients, 1);
+        assert_ne!(record.bytes, 0);
+    }
+
+    assert_eq!(send_log.query(change_tick..).count(), 1);
+    assert_eq!(
+        send_log.bytes_by_component(insert_tick..=change_tick)[&component_id],
+        records[0].bytes + records[1].bytes
+    );
+}
+
+#[test]
+fn retention() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.add_plugins(SendLogPlugin);
+    server_app.world.resource_mut::<SendLog>().max_ticks = 1;
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    let send_log = server_app.world.resource::<SendLog>();
+    assert_eq!(send_log.len(), 1, "old records should be discarded");
+    assert_eq!(send_log.query(..).next().unwrap().tick, tick);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
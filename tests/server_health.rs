@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn sample() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ServerHealthPlugin {
+                interval: Duration::ZERO,
+            },
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_health = *server_app.world.resource::<ServerHealth>();
+    let client_health = *client_app.world.resource::<ServerHealth>();
+    assert_eq!(server_health.players(), 1);
+    assert_eq!(client_health, server_health);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client_health = client_app.world.resource::<ServerHealth>();
+    assert_eq!(*client_health, ServerHealth::default());
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    network_event::disconnect_reason::DisconnectReason, prelude::*, test_app::ServerTestAppExt,
+};
+
+#[test]
+fn approval() {
+    let mut server_app = App::new();
+    let mut service_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut service_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ServiceClientPlugin {
+                approve: |_, token| token == "secret",
+                stats_interval: Duration::ZERO,
+            },
+        ));
+    }
+    service_app.insert_resource(ServiceToken("secret".into()));
+
+    server_app.connect_client(&mut service_app);
+    server_app.connect_client(&mut client_app);
+    let service_id = service_app.world.resource::<RepliconClient>().id().unwrap();
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    server_app.exchange_with_client(&mut service_app);
+    server_app.update();
+
+    let service_clients = server_app.world.resource::<ServiceClients>();
+    assert!(service_clients.contains(service_id));
+    assert!(!service_clients.contains(client_id));
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.client(service_id).privilege(),
+        ClientPrivilege::Admin
+    );
+
+    server_app.world.spawn((Replicated, EntityOwner(client_id)));
+
+    server_app.update();
+    for app in [&mut service_app, &mut client_app] {
+        server_app.exchange_with_client(app);
+        app.update();
+    }
+
+    let owner = service_app
+        .world
+        .query::<&EntityOwner>()
+        .single(&service_app.world);
+    assert_eq!(owner.0, client_id);
+
+    let stats = service_app.world.resource::<ServiceStats>();
+    assert_eq!(stats.clients.len(), 2);
+    let summary = stats
+        .clients
+        .iter()
+        .find(|summary| summary.client_id == service_id)
+        .expect("service should be included");
+    assert!(summary.service);
+
+    client_app
+        .world
+        .query::<&Replicated>()
+        .single(&client_app.world);
+    assert!(
+        client_app
+            .world
+            .query::<&EntityOwner>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "owners should be replicated only to services"
+    );
+    assert_eq!(*client_app.world.resource::<ServiceStats>(), default());
+}
+
+#[test]
+fn denial() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ServiceClientPlugin::default(),
+        ));
+    }
+    client_app.insert_resource(ServiceToken("secret".into()));
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!server_app
+        .world
+        .resource::<ServiceClients>()
+        .contains(client_id));
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [client_id]);
+
+    let reasons = client_app
+        .world
+        .resource::<Events<DisconnectReason<ServiceDenied>>>();
+    assert_eq!(reasons.len(), 1);
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_replicon::{core::replicon_tick::RepliconTick, prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            SharedRandomPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.insert_resource(SharedSeed::new(42));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_seed = *client_app.world.resource::<SharedSeed>();
+    assert_eq!(client_seed.get(), 42);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client_seed = client_app.world.resource::<SharedSeed>();
+    assert_eq!(*client_seed, SharedSeed::default());
+}
+
+#[test]
+fn new_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            SharedRandomPlugin,
+        ));
+    }
+
+    server_app.insert_resource(SharedSeed::new(42));
+
+    server_app.update();
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_seed = client_app.world.resource::<SharedSeed>();
+    assert_eq!(client_seed.get(), 42);
+}
+
+#[test]
+fn streams() {
+    let seed = SharedSeed::new(42);
+    let tick = RepliconTick::new(1);
+
+    let values = |tick, stream| -> Vec<_> {
+        let mut rng = seed.rng(tick, stream);
+        (0..4).map(|_| rng.next_u64()).collect()
+    };
+
+    assert_eq!(values(tick, 0), values(tick, 0));
+    assert_ne!(values(tick, 0), values(tick, 1));
+    assert_ne!(values(tick, 0), values(tick + 1, 0));
+
+    let mut rng = seed.rng(tick, 0);
+    for _ in 0..100 {
+        assert!((3..8).contains(&rng.range(3..8)));
+        assert!((0.0..1.0).contains(&rng.next_f32()));
+    }
+}
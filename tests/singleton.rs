@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn single_replicated() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_singleton::<DummyState>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyState)).id();
+    let extra_entity = server_app.world.spawn((Replicated, DummyState)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let server_singleton = server_app.world.resource::<Singleton<DummyState>>();
+    assert_eq!(server_singleton.entity(), Some(server_entity));
+    assert!(!server_app
+        .world
+        .entity(extra_entity)
+        .contains::<Replicated>());
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<DummyState>>()
+        .single(&client_app.world);
+    let client_singleton = client_app.world.resource::<Singleton<DummyState>>();
+    assert_eq!(client_singleton.entity(), Some(client_entity));
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_singleton = client_app.world.resource::<Singleton<DummyState>>();
+    assert_eq!(client_singleton.entity(), None);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyState;
@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn interpolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Transform>();
+    }
+    client_app
+        .add_plugins(SnapshotInterpolationPlugin {
+            delay: Duration::from_millis(75),
+            tick_period: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .interpolate::<Transform>();
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )));
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Transform::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Transform>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .entity_mut(client_entity)
+        .insert(Interpolated);
+    client_app.update();
+
+    server_app
+        .world
+        .get_mut::<Transform>(server_entity)
+        .unwrap()
+        .translation
+        .x = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for expected in [5.0, 10.0, 10.0] {
+        client_app.update();
+        let transform = client_app.world.get::<Transform>(client_entity).unwrap();
+        assert!(
+            (transform.translation.x - expected).abs() < 0.001,
+            "expected {expected}, got {}",
+            transform.translation.x
+        );
+    }
+
+    let buffer = client_app
+        .world
+        .get::<SnapshotBuffer<Transform>>(client_entity)
+        .unwrap();
+    assert_eq!(
+        buffer.len(),
+        2,
+        "only the last two rendered snapshots should be kept"
+    );
+}
+
+#[test]
+fn extrapolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Transform>();
+    }
+    client_app
+        .add_plugins(SnapshotInterpolationPlugin {
+            delay: Duration::from_millis(75),
+            max_extrapolation: Duration::from_millis(100),
+            tick_period: Duration::from_millis(50),
+        })
+        .interpolate::<Transform>()
+        .set_extrapolation::<Transform>(extrapolate_linear);
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )));
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Transform::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Transform>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .entity_mut(client_entity)
+        .insert(Interpolated);
+    client_app.update();
+
+    server_app
+        .world
+        .get_mut::<Transform>(server_entity)
+        .unwrap()
+        .translation
+        .x = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for expected in [5.0, 15.0, 25.0, 30.0, 30.0] {
+        client_app.update();
+        let transform = client_app.world.get::<Transform>(client_entity).unwrap();
+        assert!(
+            (transform.translation.x - expected).abs() < 0.001,
+            "expected {expected}, got {}",
+            transform.translation.x
+        );
+    }
+}
+
+#[test]
+fn fast_forward() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Transform>();
+    }
+    client_app
+        .add_plugins((
+            InterpolationDelayPlugin {
+                mode: InterpolationDelayMode::Fixed(Duration::from_millis(75)),
+                spike_recovery: SpikeRecovery::FastForward {
+                    threshold: Duration::from_millis(100),
+                    blend: Duration::from_millis(100),
+                },
+            },
+            SnapshotInterpolationPlugin {
+                tick_period: Duration::from_millis(50),
+                ..Default::default()
+            },
+        ))
+        .interpolate::<Transform>();
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )));
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Transform::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Transform>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .entity_mut(client_entity)
+        .insert(Interpolated);
+    client_app.update();
+
+    for x in [10.0, 20.0, 30.0] {
+        server_app
+            .world
+            .get_mut::<Transform>(server_entity)
+            .unwrap()
+            .translation
+            .x = x;
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate a stall.
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        200,
+    )));
+    client_app.update();
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )));
+
+    let transform = client_app.world.get::<Transform>(client_entity).unwrap();
+    assert_eq!(
+        transform.translation.x, 0.0,
+        "blending should start from the displayed value"
+    );
+
+    for expected in [12.5, 30.0] {
+        client_app.update();
+        let transform = client_app.world.get::<Transform>(client_entity).unwrap();
+        assert!(
+            (transform.translation.x - expected).abs() < 0.001,
+            "expected {expected}, got {}",
+            transform.translation.x
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn capture_and_restore() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_type::<DummyComponent>()
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_plugins(ServerSnapshotsPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(1))).id();
+
+    server_app
+        .world
+        .send_event(SnapshotCommand::Capture("start".into()));
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let snapshots = server_app.world.resource::<ServerSnapshots>();
+    let [(name, info)] = snapshots.iter().collect::<Vec<_>>()[..] else {
+        panic!("snapshot should be captured");
+    };
+    assert_eq!(name, "start");
+    assert_eq!(info.entities, 1);
+
+    server_app.world.despawn(server_entity);
+    server_app.world.spawn((Replicated, DummyComponent(2)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 2);
+
+    server_app
+        .world
+        .send_event(SnapshotCommand::Restore("start".into()));
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = server_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&server_app.world);
+    assert_eq!(component.0, 1);
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 1);
+
+    server_app
+        .world
+        .send_event(SnapshotCommand::Remove("start".into()));
+    server_app.update();
+
+    assert!(server_app.world.resource::<ServerSnapshots>().is_empty());
+}
+
+#[derive(Clone, Component, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct DummyComponent(u32);
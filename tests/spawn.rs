@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy_replicon::{
-    client::{confirmed::Confirmed, server_entity_map::ServerEntityMap},
+    client::{
+        confirmed::Confirmed,
+        deferred_spawn::{DeferredSpawn, ReplicationPending},
+        server_entity_map::ServerEntityMap,
+    },
     prelude::*,
     test_app::ServerTestAppExt,
 };
@@ -253,5 +257,117 @@ fn after_despawn() {
         .single(&client_app.world);
 }
 
+#[test]
+fn deferred() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<VecComponent>();
+    }
+    client_app.insert_resource(DeferredSpawn {
+        size_threshold: 100,
+        max_bytes_per_frame: 1,
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, VecComponent(vec![0; 100])));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    let entity = client_app.world.entity(client_entity);
+    assert!(entity.contains::<ReplicationPending>());
+    assert!(!entity.contains::<DummyComponent>());
+    assert!(!entity.contains::<VecComponent>());
+
+    client_app.update();
+
+    let entity = client_app.world.entity(client_entity);
+    assert!(entity.contains::<ReplicationPending>());
+    assert!(
+        entity.contains::<DummyComponent>() ^ entity.contains::<VecComponent>(),
+        "only a single component should be inserted per frame with such budget"
+    );
+
+    client_app.update();
+
+    let entity = client_app.world.entity(client_entity);
+    assert!(!entity.contains::<ReplicationPending>());
+    assert!(entity.contains::<DummyComponent>());
+    assert!(entity.contains::<VecComponent>());
+}
+
+#[test]
+fn deferred_with_update() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<VecComponent>();
+    }
+    client_app.insert_resource(DeferredSpawn {
+        size_threshold: 100,
+        max_bytes_per_frame: 0, // Apply only on flush.
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent(vec![0; 100])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(DummyComponent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity = client_app
+        .world
+        .query_filtered::<EntityRef, With<Replicated>>()
+        .single(&client_app.world);
+    assert!(
+        !entity.contains::<ReplicationPending>(),
+        "pending data should be applied before the next message for the entity"
+    );
+    assert!(entity.contains::<DummyComponent>());
+    assert!(entity.contains::<VecComponent>());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct VecComponent(Vec<u8>);
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn limited() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.add_plugins(StalePriorityPlugin {
+        entities_per_tick: 1,
+        max_staleness: u32::MAX,
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn_batch([(Replicated, BoolComponent(false)); 2]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = server_app.world.query::<&mut BoolComponent>();
+    for mut component in components.iter_mut(&mut server_app.world) {
+        component.0 = true;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world.query::<&BoolComponent>();
+    let changed = components
+        .iter(&client_app.world)
+        .filter(|component| component.0)
+        .count();
+    assert_eq!(changed, 1, "only one entity should be sent per tick");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        components
+            .iter(&client_app.world)
+            .all(|component| component.0),
+        "waiting entity should be sent on the next tick"
+    );
+}
+
+#[test]
+fn max_staleness() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.add_plugins(StalePriorityPlugin {
+        entities_per_tick: 0,
+        max_staleness: 1,
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .query::<&mut BoolComponent>()
+        .single_mut(&mut server_app.world);
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "entity shouldn't be sent without budget");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0,
+        "entity should be sent after reaching max staleness"
+    );
+}
+
+#[test]
+fn component_priority() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<PriorityComponent>();
+    }
+    server_app
+        .add_plugins(StalePriorityPlugin {
+            entities_per_tick: 1,
+            max_staleness: u32::MAX,
+        })
+        .set_replication_priority::<PriorityComponent>(2);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+    server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), PriorityComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = server_app
+        .world
+        .query::<(&mut BoolComponent, Option<&mut PriorityComponent>)>();
+    for (mut component, priority_component) in components.iter_mut(&mut server_app.world) {
+        component.0 = true;
+        if let Some(mut priority_component) = priority_component {
+            priority_component.set_changed();
+        }
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app
+        .world
+        .query::<(&BoolComponent, Has<PriorityComponent>)>();
+    for (component, has_priority) in components.iter(&client_app.world) {
+        assert_eq!(
+            component.0, has_priority,
+            "only entity with higher priority should be sent"
+        );
+    }
+}
+
+#[derive(Clone, Copy, Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct PriorityComponent;
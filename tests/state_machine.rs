@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    compact_tag,
+    gameplay::state_machine::{AppStateMachineExt, MachineTransition, StateMachine},
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn transitions() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_state_machine::<TestState>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    let mut machine = StateMachine::new(TestState::Root, tick);
+    machine.push(TestState::First, tick);
+    let server_entity = server_app.world.spawn((Replicated, machine)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (client_entity, client_machine) = client_app
+        .world
+        .query::<(Entity, &StateMachine<TestState>)>()
+        .single(&client_app.world);
+    assert_eq!(client_machine.entered_tick(0), Some(tick));
+    assert_eq!(client_machine.entered_tick(1), Some(tick));
+
+    let mut transitions = client_app
+        .world
+        .resource_mut::<Events<MachineTransition<TestState>>>();
+    let transition = transitions.drain().next().unwrap();
+    assert_eq!(transition.entity, client_entity);
+    assert!(transition.from.is_empty());
+    assert_eq!(transition.to, [TestState::Root, TestState::First]);
+
+    server_app.update();
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    let mut machine = server_app
+        .world
+        .get_mut::<StateMachine<TestState>>(server_entity)
+        .unwrap();
+    machine.transition(1, TestState::Second, tick);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_machine = client_app
+        .world
+        .get::<StateMachine<TestState>>(client_entity)
+        .unwrap();
+    assert_eq!(client_machine.current(), &TestState::Second);
+    assert_eq!(
+        client_machine.entered_tick(1),
+        Some(tick),
+        "entered tick should be restored from the message tick"
+    );
+
+    let mut transitions = client_app
+        .world
+        .resource_mut::<Events<MachineTransition<TestState>>>();
+    let transition = transitions.drain().next().unwrap();
+    assert_eq!(transition.from, [TestState::Root, TestState::First]);
+    assert_eq!(transition.to, [TestState::Root, TestState::Second]);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TestState {
+    Root,
+    First,
+    Second,
+}
+
+compact_tag!(TestState {
+    Root,
+    First,
+    Second
+});
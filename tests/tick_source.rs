@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::ServerInitTick, core::replicon_tick::RepliconTick, prelude::*,
+    server::server_tick::ServerTick, test_app::ServerTestAppExt,
+};
+
+#[test]
+fn external_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::Manual,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app
+        .init_resource::<ExternalStep>()
+        .set_tick_source(|world: &World| RepliconTick::new(world.resource::<ExternalStep>().0));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.resource_mut::<ExternalStep>().0 = 10;
+    server_app.world.spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        **server_app.world.resource::<ServerTick>(),
+        RepliconTick::new(10)
+    );
+    assert_eq!(
+        **client_app.world.resource::<ServerInitTick>(),
+        RepliconTick::new(10),
+        "messages should be labeled with the external tick"
+    );
+
+    server_app.world.resource_mut::<ExternalStep>().0 = 5;
+
+    server_app.update();
+
+    assert_eq!(
+        **server_app.world.resource::<ServerTick>(),
+        RepliconTick::new(10),
+        "older ticks should be ignored"
+    );
+}
+
+#[derive(Default, Resource)]
+struct ExternalStep(u32);
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+
+#[test]
+fn changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TimeScalePlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.resource_mut::<ServerTimeScale>().set(0.0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_time_scale = *server_app.world.resource::<ServerTimeScale>();
+    let client_time_scale = *client_app.world.resource::<ServerTimeScale>();
+    assert!(client_time_scale.is_paused());
+    assert_eq!(client_time_scale, server_time_scale);
+    assert_eq!(
+        client_time_scale.tick(),
+        **server_app.world.resource::<ServerTick>()
+    );
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client_time_scale = client_app.world.resource::<ServerTimeScale>();
+    assert_eq!(*client_time_scale, ServerTimeScale::default());
+}
+
+#[test]
+fn new_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TimeScalePlugin,
+        ));
+    }
+
+    server_app.world.resource_mut::<ServerTimeScale>().set(0.5);
+
+    server_app.update();
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_time_scale = client_app.world.resource::<ServerTimeScale>();
+    assert_eq!(client_time_scale.scale(), 0.5);
+}
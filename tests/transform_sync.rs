@@ -0,0 +1,103 @@
+use bevy::{prelude::*, transform::TransformPlugin};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn parent_relative() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TransformSyncPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_parent = server_app
+        .world
+        .spawn((
+            Replicated,
+            TransformBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+        ))
+        .id();
+    let server_child = server_app
+        .world
+        .spawn((
+            Replicated,
+            ParentSync::default(),
+            TransformSync::default(),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 2.0, 0.0)),
+        ))
+        .set_parent(server_parent)
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let transform_sync = server_app.world.get::<TransformSync>(server_child).unwrap();
+    assert_eq!(
+        transform_sync.transform(),
+        Transform::from_xyz(0.0, 2.0, 0.0),
+        "only local transform should be stored"
+    );
+
+    let client_transform = client_app
+        .world
+        .query_filtered::<&Transform, With<TransformSync>>()
+        .single(&client_app.world);
+    assert_eq!(*client_transform, Transform::from_xyz(0.0, 2.0, 0.0));
+}
+
+#[test]
+fn world_space() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TransformSyncPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_parent = server_app
+        .world
+        .spawn(TransformBundle::from_transform(Transform::from_xyz(
+            1.0, 0.0, 0.0,
+        )))
+        .id();
+    server_app
+        .world
+        .spawn((
+            Replicated,
+            TransformSync::default(),
+            WorldSpaceTransform,
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 2.0, 0.0)),
+        ))
+        .set_parent(server_parent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let transform = client_app
+        .world
+        .query_filtered::<&Transform, With<TransformSync>>()
+        .single(&client_app.world);
+    assert_eq!(*transform, Transform::from_xyz(1.0, 2.0, 0.0));
+}
@@ -287,5 +287,160 @@ fn whitelist_despawn() {
     assert!(!visibility.is_visible(server_entity));
 }
 
+#[test]
+fn tracked_entities() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, true);
+
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.client(client_id).tracked_entities(),
+        1,
+        "only visible entities should be tracked"
+    );
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, false);
+
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(connected_clients.client(client_id).tracked_entities(), 0);
+}
+
+#[test]
+fn admin_privilege() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world.entities().is_empty());
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let client = connected_clients.client_mut(client_id);
+    client.set_privilege(ClientPrivilege::Admin);
+    assert!(client.visibility().is_visible(server_entity));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let client = connected_clients.client_mut(client_id);
+    client.set_privilege(ClientPrivilege::Regular);
+    assert!(!client.visibility().is_visible(server_entity));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world.entities().is_empty());
+}
+
+#[test]
+fn concealed_component() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<ConcealedComponent>()
+        .conceal_component::<ConcealedComponent>(ClientPrivilege::Moderator);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, ConcealedComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<DummyComponent>, Without<ConcealedComponent>)>()
+        .single(&client_app.world);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    connected_clients
+        .client_mut(client_id)
+        .set_privilege(ClientPrivilege::Moderator);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<DummyComponent>, With<ConcealedComponent>)>()
+        .single(&client_app.world);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct ConcealedComponent;
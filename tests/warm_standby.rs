@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::{ServerTestAppExt, TimeTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn first_joiner() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::MaxTickRate(10),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app
+        .add_plugins(WarmStandbyPlugin)
+        .use_simulated_time();
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(1))).id();
+
+    server_app.advance_time(Duration::from_millis(100));
+    assert_eq!(server_app.world.resource::<WarmStandby>().len(), 1);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 2;
+
+    server_app.advance_time(Duration::from_millis(100));
+    assert_eq!(
+        server_app.world.resource::<WarmStandby>().len(),
+        1,
+        "changed component should replace its cache entry"
+    );
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, 2,
+        "client should receive the state without waiting for the tick"
+    );
+    assert!(server_app.world.resource::<WarmStandby>().is_empty());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u32);
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn digest_only() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            WorldDigestPlugin {
+                cell_size: 10.0,
+                interval: Duration::ZERO,
+                ..Default::default()
+            },
+        ))
+        .add_digest_category::<Player>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ConnectedClients>()
+        .client_mut(client_id)
+        .set_digest_only(true);
+
+    server_app.world.spawn((
+        Replicated,
+        Player,
+        Transform::from_xyz(5.0, 5.0, 0.0),
+        DigestTeam(1),
+    ));
+    server_app.world.spawn((
+        Replicated,
+        Player,
+        Transform::from_xyz(15.0, 5.0, 0.0),
+        DigestTeam(2),
+    ));
+    server_app.world.spawn((
+        Replicated,
+        Transform::from_xyz(15.0, 5.0, 0.0),
+        DigestTeam(2),
+    ));
+    server_app.world.spawn((Replicated, Player));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app
+            .world
+            .query::<&Replicated>()
+            .iter(&client_app.world)
+            .next()
+            .is_none(),
+        "entities shouldn't be replicated"
+    );
+
+    let digest = client_app.world.resource::<WorldDigest>();
+    assert_eq!(digest.counts(), [3]);
+    assert_eq!(digest.cells().len(), 2);
+
+    let cell = digest.cell(0, 0).unwrap();
+    assert_eq!(cell.counts, [1]);
+    assert_eq!(cell.team, Some(1));
+
+    let cell = digest.cell(1, 0).unwrap();
+    assert_eq!(cell.counts, [1]);
+    assert_eq!(cell.team, Some(2));
+}
+
+#[test]
+fn regular_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            WorldDigestPlugin {
+                interval: Duration::ZERO,
+                ..Default::default()
+            },
+        ))
+        .add_digest_category::<Player>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, Player, Transform::default()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query::<&Replicated>()
+        .single(&client_app.world);
+
+    let digest = client_app.world.resource::<WorldDigest>();
+    assert!(digest.cells().is_empty(), "digest shouldn't be sent");
+}
+
+#[derive(Component)]
+struct Player;